@@ -1,4 +1,5 @@
 const logger = require('../utils/logger');
+const codes = require('../../crates/codes/codes.json');
 
 /**
  * Classe para erros customizados da API
@@ -41,6 +42,14 @@ class ValidationError extends ApiError {
   }
 }
 
+/**
+ * Extrai o código de erro do contrato (`Error(Contract, #N)`) e o traduz pelo crate strgrid-codes
+ */
+const contractErrorName = (message) => {
+  const match = /Error\(Contract, #(\d+)\)/.exec(message || '');
+  return match ? codes.errors[match[1]] || null : null;
+};
+
 /**
  * Middleware principal de tratamento de erros
  */
@@ -90,7 +99,11 @@ const errorHandler = (error, req, res, next) => {
   }
 
   // Erros específicos do Soroban
-  if (error.message && error.message.includes('contract')) {
+  const contractError = contractErrorName(error.message);
+  if (contractError) {
+    err = new ContractError(`Erro do contrato: ${contractError}`, error);
+    err.code = contractError;
+  } else if (error.message && error.message.includes('contract')) {
     if (error.message.includes('not found')) {
       err = new ContractError('Contrato não encontrado ou não inicializado');
     } else if (error.message.includes('insufficient')) {
@@ -123,6 +136,7 @@ const errorHandler = (error, req, res, next) => {
       success: false,
       message: err.message,
       type: err.type || 'API_ERROR',
+      ...(err.code && { code: err.code }),
       ...(err.errors && { errors: err.errors }),
       ...(process.env.NODE_ENV === 'development' && { stack: err.stack })
     });
@@ -197,6 +211,7 @@ module.exports = {
   errorHandler,
   asyncHandler,
   notFoundHandler,
+  contractErrorName,
   initializeErrorHandlers,
   ApiError,
   StellarError,
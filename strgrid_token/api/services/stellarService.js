@@ -10,6 +10,16 @@ const {
 } = require('@stellar/stellar-sdk');
 const logger = require('../utils/logger');
 
+// Taxas de inclusão (stroops) por classe de prioridade do contrato
+const BASE_FEE = process.env.BASE_FEE || '100';
+const GRID_CRITICAL_FEE = process.env.GRID_CRITICAL_FEE || '10000';
+
+// Operações tratadas como críticas para a rede mesmo antes de consultar o contrato
+const GRID_CRITICAL_OPERATIONS = (process.env.GRID_CRITICAL_OPERATIONS || 'set_generator_status')
+  .split(',')
+  .map((name) => name.trim())
+  .filter(Boolean);
+
 class StellarService {
   constructor() {
     this.contractId = process.env.CONTRACT_ID || 'CBEXN3HDQZQL4QDUDUPXKUIDL7IQP57IFIXCW5T5TSLSTMXJNP5SMNRC';
@@ -20,6 +30,36 @@ class StellarService {
     this.contract = new Contract(this.contractId);
   }
 
+  /**
+   * Consulta a classificação de prioridade de uma operação no contrato
+   */
+  async getOperationPriority(method) {
+    if (GRID_CRITICAL_OPERATIONS.includes(method)) {
+      return 'GridCritical';
+    }
+
+    try {
+      const operation = this.contract.call(
+        'operation_priority',
+        nativeToScVal(method, { type: 'symbol' })
+      );
+      const result = await this.simulateTransaction(operation);
+      const [priority] = scValToNative(result.result.retval);
+      return priority;
+    } catch (error) {
+      logger.warn(`Prioridade de ${method} indisponível, usando Normal:`, error);
+      return 'Normal';
+    }
+  }
+
+  /**
+   * Taxa de inclusão para uma operação conforme sua prioridade
+   */
+  async feeFor(method) {
+    const priority = await this.getOperationPriority(method);
+    return priority === 'GridCritical' ? GRID_CRITICAL_FEE : BASE_FEE;
+  }
+
   /**
    * Simula uma transação no contrato
    */
@@ -51,14 +91,15 @@ class StellarService {
   }
 
   /**
-   * Submete uma transação assinada
+   * Submete uma transação assinada; `method` define a taxa pela prioridade
    */
-  async submitTransaction(operation, sourceKeypair) {
+  async submitTransaction(operation, sourceKeypair, method = null) {
     try {
       const sourceAccount = await this.server.getAccount(sourceKeypair.publicKey());
+      const fee = method ? await this.feeFor(method) : BASE_FEE;
 
       let transaction = new TransactionBuilder(sourceAccount, {
-        fee: '100',
+        fee,
         networkPassphrase: this.networkPassphrase,
       })
         .addOperation(operation)
@@ -135,7 +176,7 @@ class StellarService {
         nativeToScVal(capacityKw, { type: 'u64' })
       );
 
-      const result = await this.submitTransaction(operation, keypair);
+      const result = await this.submitTransaction(operation, keypair, 'register_generator');
       
       return {
         transactionHash: result.hash,
@@ -149,6 +190,35 @@ class StellarService {
     }
   }
 
+  /**
+   * Ativar/desativar um gerador (operação crítica para a rede)
+   */
+  async setGeneratorStatus(generatorAddress, isActive, sourceKeypair) {
+    try {
+      const keypair = typeof sourceKeypair === 'string' 
+        ? Keypair.fromSecret(sourceKeypair)
+        : sourceKeypair;
+
+      const operation = this.contract.call(
+        'set_generator_status',
+        new Address(generatorAddress).toScVal(),
+        nativeToScVal(isActive, { type: 'bool' })
+      );
+
+      const result = await this.submitTransaction(operation, keypair, 'set_generator_status');
+      
+      return {
+        transactionHash: result.hash,
+        generatorAddress,
+        isActive,
+        status: 'updated'
+      };
+    } catch (error) {
+      logger.error('Erro ao atualizar status do gerador:', error);
+      throw error;
+    }
+  }
+
   /**
    * Obter dados de um gerador
    */
@@ -191,7 +261,7 @@ class StellarService {
       const operation = this.contract.call(
         'mint_energy_tokens',
         new Address(generatorAddress).toScVal(),
-        nativeToScVal(amountKwh, { type: 'u64' }),
+        nativeToScVal(amountKwh, { type: 'i128' }),
         nativeToScVal(energyType, { type: 'string' }),
         new Address(recipientAddress).toScVal()
       );
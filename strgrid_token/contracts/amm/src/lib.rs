@@ -0,0 +1,383 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const QUOTE_TOKEN: Symbol = symbol_short!("QUOTE");
+const FEE_BPS: Symbol = symbol_short!("FEE_BPS");
+const RESERVES: Symbol = symbol_short!("RESERVES");
+const TOTAL_SHARES: Symbol = symbol_short!("TSHARES");
+const SHARES: Symbol = symbol_short!("SHARES");
+const OBSERVATIONS: Symbol = symbol_short!("OBSERV");
+
+// Tópicos dos eventos publicados pelo AMM
+const DEPOSITED: Symbol = symbol_short!("deposit");
+const WITHDRAWN: Symbol = symbol_short!("withdraw");
+const SWAPPED: Symbol = symbol_short!("swap");
+
+/// Escala do preço (ativo de cotação por kWh)
+pub const PRICE_PRECISION: i128 = 10_000_000;
+/// Taxa máxima de swap (10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+/// Quantidade de observações de preço mantidas para o TWAP
+pub const MAX_OBSERVATIONS: u32 = 64;
+
+/// Preço acumulado (preço × segundos) registrado a cada mudança das reservas
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub cumulative: i128,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AmmError {
+    NotInitialized = 1,
+    InvalidFee = 3,
+    InvalidAmount = 4,
+    InsufficientLiquidity = 5,
+    InsufficientShares = 6,
+    SlippageExceeded = 7,
+    NotEnoughHistory = 8,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, AmmError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn quote_token(env: &Env) -> token::Client<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&QUOTE_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, AmmError::NotInitialized));
+    token::Client::new(env, &address)
+}
+
+/// Transfere kWh de `from` para o pool e devolve o que de fato entrou, já descontadas a taxa de
+/// transferência e as perdas cobradas pelo token
+fn deposit_energy(env: &Env, from: &Address, amount_kwh: u64) -> u64 {
+    let energy = energy_token(env);
+    let pool = env.current_contract_address();
+    let before = energy.balance_of(&pool);
+    energy.transfer(from, &pool, &i128::from(amount_kwh));
+    let received = (energy.balance_of(&pool) - before) as u64;
+    if received == 0 {
+        panic_with_error!(env, AmmError::InvalidAmount);
+    }
+    received
+}
+
+/// Reservas de STRGRID e do ativo de cotação
+fn reserves(env: &Env) -> (u64, i128) {
+    env.storage().instance().get(&RESERVES).unwrap_or((0, 0))
+}
+
+fn total_shares(env: &Env) -> i128 {
+    env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
+}
+
+fn shares(env: &Env, provider: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(SHARES, provider.clone()))
+        .unwrap_or(0)
+}
+
+fn set_shares(env: &Env, provider: &Address, provider_shares: i128, total: i128) {
+    env.storage()
+        .persistent()
+        .set(&(SHARES, provider.clone()), &provider_shares);
+    env.storage().instance().set(&TOTAL_SHARES, &total);
+}
+
+fn observations(env: &Env) -> Vec<Observation> {
+    env.storage()
+        .instance()
+        .get(&OBSERVATIONS)
+        .unwrap_or(Vec::new(env))
+}
+
+fn spot(energy_reserve: u64, quote_reserve: i128) -> i128 {
+    if energy_reserve == 0 {
+        return 0;
+    }
+    quote_reserve * PRICE_PRECISION / energy_reserve as i128
+}
+
+/// Preço acumulado até `now`, estendendo a última observação com o preço à vista
+fn cumulative_at(env: &Env, now: u64) -> i128 {
+    match observations(env).last() {
+        Some(last) => {
+            let (energy_reserve, quote_reserve) = reserves(env);
+            last.cumulative + spot(energy_reserve, quote_reserve) * (now - last.timestamp) as i128
+        }
+        None => 0,
+    }
+}
+
+/// Registra o preço acumulado com as reservas atuais, antes de elas mudarem
+fn observe(env: &Env) {
+    let now = env.ledger().timestamp();
+    let mut history = observations(env);
+    if let Some(last) = history.last() {
+        if last.timestamp == now {
+            return;
+        }
+    }
+    history.push_back(Observation {
+        timestamp: now,
+        cumulative: cumulative_at(env, now),
+    });
+    if history.len() > MAX_OBSERVATIONS {
+        history.pop_front();
+    }
+    env.storage().instance().set(&OBSERVATIONS, &history);
+}
+
+fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[contract]
+pub struct AmmContract;
+
+#[contractimpl]
+impl AmmContract {
+    /// Configura no deploy o par STRGRID/ativo de cotação e a taxa de swap dos provedores
+    pub fn __constructor(env: Env, energy_token: Address, quote_token: Address, fee_bps: u32) {
+        if fee_bps > MAX_FEE_BPS {
+            panic_with_error!(&env, AmmError::InvalidFee);
+        }
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&QUOTE_TOKEN, &quote_token);
+        env.storage().instance().set(&FEE_BPS, &fee_bps);
+    }
+
+    /// Deposita liquidez na proporção das reservas e recebe cotas do pool
+    ///
+    /// Usa todo o `energy_amount` se o `max_quote` bastar; caso contrário, limita pelo `max_quote`
+    pub fn deposit(
+        env: Env,
+        provider: Address,
+        energy_amount: u64,
+        max_quote: i128,
+        min_shares: i128,
+    ) -> i128 {
+        provider.require_auth();
+        if energy_amount == 0 || max_quote <= 0 {
+            panic_with_error!(&env, AmmError::InvalidAmount);
+        }
+        observe(&env);
+
+        let (energy_reserve, quote_reserve) = reserves(&env);
+        let total = total_shares(&env);
+        // Cotação proporcional às reservas para `energy` kWh, arredondada para cima
+        let quote_for = |energy: u64| {
+            (energy as i128 * quote_reserve + energy_reserve as i128 - 1) / energy_reserve as i128
+        };
+        let energy_sent = if total == 0 || quote_for(energy_amount) <= max_quote {
+            energy_amount
+        } else {
+            (max_quote * energy_reserve as i128 / quote_reserve) as u64
+        };
+        if energy_sent == 0 {
+            panic_with_error!(&env, AmmError::InvalidAmount);
+        }
+
+        // Cotas e cotação saem do que chegou ao pool, já descontada a taxa de transferência do
+        // STRGRID; como isso nunca passa de `energy_sent`, a cotação fica dentro de `max_quote`
+        let energy_in = deposit_energy(&env, &provider, energy_sent);
+        let quote_in = if total == 0 { max_quote } else { quote_for(energy_in) };
+        let minted = if total == 0 {
+            isqrt(energy_in as u128 * quote_in as u128) as i128
+        } else {
+            (energy_in as i128 * total / energy_reserve as i128)
+                .min(quote_in * total / quote_reserve)
+        };
+        if minted <= 0 {
+            panic_with_error!(&env, AmmError::InvalidAmount);
+        }
+        if minted < min_shares {
+            panic_with_error!(&env, AmmError::SlippageExceeded);
+        }
+        quote_token(&env).transfer(&provider, &env.current_contract_address(), &quote_in);
+        env.storage().instance().set(
+            &RESERVES,
+            &(energy_reserve + energy_in, quote_reserve + quote_in),
+        );
+        set_shares(&env, &provider, shares(&env, &provider) + minted, total + minted);
+
+        env.events()
+            .publish((DEPOSITED, provider), (energy_in, quote_in, minted));
+        minted
+    }
+
+    /// Resgata cotas pela parte proporcional das duas reservas
+    pub fn withdraw(
+        env: Env,
+        provider: Address,
+        share_amount: i128,
+        min_energy: u64,
+        min_quote: i128,
+    ) -> (u64, i128) {
+        provider.require_auth();
+        let provider_shares = shares(&env, &provider);
+        if share_amount <= 0 || share_amount > provider_shares {
+            panic_with_error!(&env, AmmError::InsufficientShares);
+        }
+        observe(&env);
+
+        let (energy_reserve, quote_reserve) = reserves(&env);
+        let total = total_shares(&env);
+        let energy_out = (energy_reserve as i128 * share_amount / total) as u64;
+        let quote_out = quote_reserve * share_amount / total;
+        if energy_out < min_energy || quote_out < min_quote {
+            panic_with_error!(&env, AmmError::SlippageExceeded);
+        }
+
+        env.storage().instance().set(
+            &RESERVES,
+            &(energy_reserve - energy_out, quote_reserve - quote_out),
+        );
+        set_shares(&env, &provider, provider_shares - share_amount, total - share_amount);
+        let pool = env.current_contract_address();
+        if energy_out > 0 {
+            energy_token(&env).transfer(&pool, &provider, &i128::from(energy_out));
+        }
+        if quote_out > 0 {
+            quote_token(&env).transfer(&pool, &provider, &quote_out);
+        }
+
+        env.events()
+            .publish((WITHDRAWN, provider), (share_amount, energy_out, quote_out));
+        (energy_out, quote_out)
+    }
+
+    /// Troca pelo produto constante; `buy_energy` paga em cotação e recebe STRGRID
+    ///
+    /// A taxa é cobrada sobre a entrada e permanece no pool, remunerando os provedores.
+    pub fn swap(
+        env: Env,
+        trader: Address,
+        buy_energy: bool,
+        amount_in: i128,
+        min_out: i128,
+    ) -> i128 {
+        trader.require_auth();
+        if amount_in <= 0 || (!buy_energy && amount_in > u64::MAX as i128) {
+            panic_with_error!(&env, AmmError::InvalidAmount);
+        }
+        let (energy_reserve, quote_reserve) = reserves(&env);
+        if energy_reserve == 0 || quote_reserve == 0 {
+            panic_with_error!(&env, AmmError::InsufficientLiquidity);
+        }
+        observe(&env);
+
+        // Vendendo STRGRID, a troca é cotada pelo que chegou ao pool, já descontada a taxa de
+        // transferência do token
+        let amount_in = if buy_energy {
+            amount_in
+        } else {
+            deposit_energy(&env, &trader, amount_in as u64) as i128
+        };
+        let fee_bps: u32 = env.storage().instance().get(&FEE_BPS).unwrap_or(0);
+        let net_in = amount_in * (10_000 - fee_bps as i128) / 10_000;
+        let (reserve_in, reserve_out) = if buy_energy {
+            (quote_reserve, energy_reserve as i128)
+        } else {
+            (energy_reserve as i128, quote_reserve)
+        };
+        let amount_out = net_in * reserve_out / (reserve_in + net_in);
+        if amount_out <= 0 {
+            panic_with_error!(&env, AmmError::InsufficientLiquidity);
+        }
+        if amount_out < min_out {
+            panic_with_error!(&env, AmmError::SlippageExceeded);
+        }
+
+        let pool = env.current_contract_address();
+        if buy_energy {
+            quote_token(&env).transfer(&trader, &pool, &amount_in);
+            energy_token(&env).transfer(&pool, &trader, &amount_out);
+            env.storage().instance().set(
+                &RESERVES,
+                &(energy_reserve - amount_out as u64, quote_reserve + amount_in),
+            );
+        } else {
+            quote_token(&env).transfer(&pool, &trader, &amount_out);
+            env.storage().instance().set(
+                &RESERVES,
+                &(energy_reserve + amount_in as u64, quote_reserve - amount_out),
+            );
+        }
+
+        env.events()
+            .publish((SWAPPED, trader), (buy_energy, amount_in, amount_out));
+        amount_out
+    }
+
+    /// Preço médio ponderado no tempo nos últimos `window_seconds`, em `PRICE_PRECISION`
+    pub fn twap(env: Env, window_seconds: u64) -> i128 {
+        let now = env.ledger().timestamp();
+        if window_seconds == 0 || window_seconds > now {
+            panic_with_error!(&env, AmmError::NotEnoughHistory);
+        }
+        let target = now - window_seconds;
+        let history = observations(&env);
+        let mut start = None;
+        for i in (0..history.len()).rev() {
+            let observation = history.get_unchecked(i);
+            if observation.timestamp <= target {
+                start = Some(observation);
+                break;
+            }
+        }
+        let start = start.unwrap_or_else(|| panic_with_error!(&env, AmmError::NotEnoughHistory));
+        (cumulative_at(&env, now) - start.cumulative) / (now - start.timestamp) as i128
+    }
+
+    /// Preço à vista (cotação por kWh), em `PRICE_PRECISION`
+    pub fn spot_price(env: Env) -> i128 {
+        let (energy_reserve, quote_reserve) = reserves(&env);
+        spot(energy_reserve, quote_reserve)
+    }
+
+    /// Reservas de STRGRID e do ativo de cotação
+    pub fn get_reserves(env: Env) -> (u64, i128) {
+        reserves(&env)
+    }
+
+    /// Cotas do provedor de liquidez
+    pub fn shares_of(env: Env, provider: Address) -> i128 {
+        shares(&env, &provider)
+    }
+
+    /// Total de cotas emitidas
+    pub fn total_shares(env: Env) -> i128 {
+        total_shares(&env)
+    }
+}
+
+mod test;
@@ -0,0 +1,141 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_liquidity_swaps_and_twap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let trader = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &5000u64);
+    energy.mint_energy_tokens(&generator, &2000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &1000i128);
+    energy.transfer(&generator, &bob, &100i128);
+    energy.transfer(&generator, &trader, &100i128);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let usdc_admin = token::StellarAssetClient::new(&env, &usdc);
+    usdc_admin.mint(&alice, &2000i128);
+    usdc_admin.mint(&bob, &1000i128);
+    usdc_admin.mint(&trader, &1000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let amm_id = env.register(AmmContract, (energy_id.clone(), usdc.clone(), 30u32));
+    let amm = AmmContractClient::new(&env, &amm_id);
+    assert_eq!(
+        amm.try_swap(&trader, &true, &10i128, &0i128),
+        Err(Ok(AmmError::InsufficientLiquidity.into()))
+    );
+
+    // First deposit sets the price at 2 USDC per kWh
+    assert_eq!(amm.deposit(&alice, &1000u64, &2000i128, &0i128), 1414);
+    assert_eq!(amm.spot_price(), 2 * PRICE_PRECISION);
+    assert_eq!(amm.try_twap(&100u64), Err(Ok(AmmError::NotEnoughHistory.into())));
+
+    // Buying energy: 0.3% fee stays in the pool
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        amm.try_swap(&trader, &true, &200i128, &91i128),
+        Err(Ok(AmmError::SlippageExceeded.into()))
+    );
+    assert_eq!(amm.swap(&trader, &true, &200i128, &90i128), 90);
+    assert_eq!(amm.get_reserves(), (910u64, 2200i128));
+    assert_eq!(energy.balance_of(&trader), 190);
+    assert_eq!(usdc_client.balance(&trader), 800);
+
+    // TWAP weights 100s at 2.0 and 100s at the post-swap price
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(amm.twap(&200u64), (20_000_000 + 24_175_824) / 2);
+    assert_eq!(amm.twap(&100u64), 24_175_824);
+
+    // Second deposit only pulls the quote matching the current ratio
+    assert_eq!(amm.deposit(&bob, &91u64, &1000i128, &0i128), 141);
+    assert_eq!(usdc_client.balance(&bob), 780);
+    assert_eq!(amm.total_shares(), 1555);
+
+    // Selling energy back
+    assert_eq!(amm.swap(&trader, &false, &100i128, &0i128), 217);
+    assert_eq!(usdc_client.balance(&trader), 1017);
+
+    assert_eq!(
+        amm.try_withdraw(&bob, &142i128, &0u64, &0i128),
+        Err(Ok(AmmError::InsufficientShares.into()))
+    );
+    let (energy_out, quote_out) = amm.withdraw(&alice, &1414i128, &0u64, &0i128);
+    assert_eq!(energy.balance_of(&alice), i128::from(energy_out));
+    assert_eq!(usdc_client.balance(&alice), quote_out);
+    assert_eq!(amm.shares_of(&alice), 0);
+    assert_eq!(amm.total_shares(), 141);
+}
+
+#[test]
+fn test_pool_reserves_track_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let trader = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &5000u64);
+    energy.mint_energy_tokens(&generator, &2000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &1000i128);
+    energy.transfer(&generator, &trader, &100i128);
+    energy.transfer(&generator, &bob, &100i128);
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let usdc_admin = token::StellarAssetClient::new(&env, &usdc);
+    usdc_admin.mint(&alice, &2000i128);
+    usdc_admin.mint(&bob, &200i128);
+
+    let amm_id = env.register(AmmContract, (energy_id.clone(), usdc.clone(), 0u32));
+    let amm = AmmContractClient::new(&env, &amm_id);
+
+    // A 1% fee leaves 990 of the 1000 kWh in the pool, and shares are minted on that
+    assert_eq!(amm.deposit(&alice, &1000u64, &990i128, &0i128), 990);
+    assert_eq!(amm.get_reserves(), (990, 990));
+    assert_eq!(energy.balance_of(&amm_id), 990);
+
+    // Selling 100 kWh is priced on the 99 that arrive: 99 * 990 / (990 + 99)
+    assert_eq!(amm.swap(&trader, &false, &100i128, &0i128), 90);
+    assert_eq!(amm.get_reserves(), (1089, 900));
+    assert_eq!(energy.balance_of(&amm_id), 1089);
+
+    // The quote side is priced on the 99 kWh that arrive: ceil(99 * 900 / 1089), not 83
+    assert_eq!(amm.deposit(&bob, &100u64, &200i128, &0i128), 90);
+    assert_eq!(amm.get_reserves(), (1188, 982));
+    assert_eq!(token::Client::new(&env, &usdc).balance(&bob), 118);
+
+    // Withdrawing every share empties the pool without touching anyone else's kWh
+    amm.withdraw(&alice, &990i128, &0u64, &0i128);
+    amm.withdraw(&bob, &90i128, &0u64, &0i128);
+    assert_eq!(energy.balance_of(&amm_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn test_constructor_rejects_fee_above_maximum() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    env.register(AmmContract, (energy_id, usdc, MAX_FEE_BPS + 1));
+}
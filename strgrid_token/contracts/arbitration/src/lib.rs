@@ -0,0 +1,431 @@
+#![no_std]
+//! Arbitragem por painel de árbitros com stake
+//!
+//! Disputas sobre negociações do escrow ou mints contestados são sorteadas para um painel de
+//! árbitros cadastrados, que votam dentro do prazo. Quem abre o caso paga a taxa de arbitragem,
+//! dividida entre os árbitros que votaram; quem foi sorteado e não votou perde parte do stake.
+//! Para executar as decisões, este contrato deve ser o árbitro do escrow e ter o papel `Auditor`
+//! no token.
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, BytesN, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const ESCROW: Symbol = symbol_short!("ESCROW");
+const CONFIG: Symbol = symbol_short!("CONFIG");
+const ARBITERS: Symbol = symbol_short!("ARBITERS");
+const ARBITER: Symbol = symbol_short!("ARBITER");
+const CASE: Symbol = symbol_short!("CASE");
+const CASE_SEQ: Symbol = symbol_short!("CASESEQ");
+const VOTE: Symbol = symbol_short!("VOTE");
+
+// Tópicos dos eventos publicados pela arbitragem
+const STAKED: Symbol = symbol_short!("staked");
+const UNSTAKED: Symbol = symbol_short!("unstaked");
+const OPENED: Symbol = symbol_short!("opened");
+const VOTED: Symbol = symbol_short!("voted");
+const DECIDED: Symbol = symbol_short!("decided");
+const SLASHED: Symbol = symbol_short!("slashed");
+
+/// Denominador dos basis points (100% = 10.000 bps)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Interface do escrow usada para executar as decisões sobre negociações
+#[contractclient(name = "EscrowClient")]
+pub trait Escrow {
+    fn resolve(env: Env, trade_id: u64, release_to_buyer: bool);
+}
+
+/// Parâmetros da arbitragem; stake e taxa são no mesmo ativo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbitrationConfig {
+    pub stake_token: Address,
+    pub min_stake: i128,
+    pub panel_size: u32,
+    pub voting_period: u64,
+    /// Taxa paga por quem abre o caso
+    pub fee: i128,
+    /// Parte do stake perdida por árbitro sorteado que não votou, em basis points
+    pub slash_bps: u32,
+}
+
+/// Objeto da disputa
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Subject {
+    /// Negociação do escrow; votar a favor libera ao comprador, contra estorna as partes
+    EscrowTrade(u64),
+    /// Mint contestado com o hash da evidência; votar a favor anula o lote
+    Mint(u64, BytesN<32>),
+}
+
+/// Situação de um caso
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaseStatus {
+    Voting,
+    Upheld,
+    Dismissed,
+}
+
+/// Caso em arbitragem e seu painel sorteado
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Case {
+    pub id: u64,
+    pub claimant: Address,
+    pub subject: Subject,
+    pub panel: Vec<Address>,
+    pub fee: i128,
+    pub voting_ends_at: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub status: CaseStatus,
+}
+
+/// Stake de um árbitro e quantos casos abertos o ocupam
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Arbiter {
+    pub stake: i128,
+    pub open_cases: u32,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ArbitrationError {
+    NotInitialized = 1,
+    InvalidConfig = 3,
+    InsufficientStake = 4,
+    NotArbiter = 5,
+    ArbiterBusy = 6,
+    NotEnoughArbiters = 7,
+    CaseNotFound = 8,
+    CaseClosed = 9,
+    NotOnPanel = 10,
+    AlreadyVoted = 11,
+    VotingOpen = 12,
+    VotingClosed = 13,
+}
+
+fn config(env: &Env) -> ArbitrationConfig {
+    env.storage()
+        .instance()
+        .get(&CONFIG)
+        .unwrap_or_else(|| panic_with_error!(env, ArbitrationError::NotInitialized))
+}
+
+fn arbiters(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&ARBITERS)
+        .unwrap_or(Vec::new(env))
+}
+
+fn load_arbiter(env: &Env, arbiter: &Address) -> Arbiter {
+    env.storage()
+        .persistent()
+        .get(&(ARBITER, arbiter.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, ArbitrationError::NotArbiter))
+}
+
+fn save_arbiter(env: &Env, arbiter: &Address, data: &Arbiter) {
+    env.storage()
+        .persistent()
+        .set(&(ARBITER, arbiter.clone()), data);
+}
+
+/// Tira o árbitro do sorteio de novos painéis
+fn delist(env: &Env, arbiter: &Address) {
+    let mut listed = arbiters(env);
+    if let Some(index) = listed.first_index_of(arbiter) {
+        listed.remove(index);
+        env.storage().persistent().set(&ARBITERS, &listed);
+    }
+}
+
+fn load(env: &Env, case_id: u64) -> Case {
+    env.storage()
+        .persistent()
+        .get(&(CASE, case_id))
+        .unwrap_or_else(|| panic_with_error!(env, ArbitrationError::CaseNotFound))
+}
+
+fn save(env: &Env, case: &Case) {
+    env.storage().persistent().set(&(CASE, case.id), case);
+}
+
+/// Executa a decisão do painel no escrow ou no token
+fn enforce(env: &Env, subject: &Subject, upheld: bool) {
+    match subject {
+        Subject::EscrowTrade(trade_id) => {
+            let escrow: Address = env.storage().instance().get(&ESCROW).unwrap();
+            EscrowClient::new(env, &escrow).resolve(trade_id, &upheld);
+        }
+        Subject::Mint(token_id, evidence_hash) if upheld => {
+            let energy: Address = env.storage().instance().get(&ENERGY_TOKEN).unwrap();
+            STRGRIDClient::new(env, &energy).challenge_mint(
+                &env.current_contract_address(),
+                token_id,
+                evidence_hash,
+            );
+        }
+        Subject::Mint(..) => {}
+    }
+}
+
+#[contract]
+pub struct ArbitrationContract;
+
+#[contractimpl]
+impl ArbitrationContract {
+    /// Configura no deploy a arbitragem com o token de energia, o escrow arbitrado e os parâmetros
+    pub fn __constructor(
+        env: Env,
+        energy_token: Address,
+        escrow: Address,
+        config: ArbitrationConfig,
+    ) {
+        if config.min_stake <= 0
+            || config.fee < 0
+            || config.panel_size == 0
+            || config.voting_period == 0
+            || config.slash_bps > BPS_DENOMINATOR
+        {
+            panic_with_error!(&env, ArbitrationError::InvalidConfig);
+        }
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&ESCROW, &escrow);
+        env.storage().instance().set(&CONFIG, &config);
+    }
+
+    /// Parâmetros vigentes
+    pub fn config(env: Env) -> ArbitrationConfig {
+        config(&env)
+    }
+
+    /// Deposita stake; com o mínimo atingido, o árbitro entra no sorteio dos painéis
+    pub fn stake(env: Env, arbiter: Address, amount: i128) -> i128 {
+        arbiter.require_auth();
+        let config = config(&env);
+        if amount <= 0 {
+            panic_with_error!(&env, ArbitrationError::InsufficientStake);
+        }
+        token::Client::new(&env, &config.stake_token).transfer(
+            &arbiter,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut data = env
+            .storage()
+            .persistent()
+            .get(&(ARBITER, arbiter.clone()))
+            .unwrap_or(Arbiter {
+                stake: 0,
+                open_cases: 0,
+            });
+        data.stake += amount;
+        if data.stake < config.min_stake {
+            panic_with_error!(&env, ArbitrationError::InsufficientStake);
+        }
+        save_arbiter(&env, &arbiter, &data);
+        let mut listed = arbiters(&env);
+        if !listed.contains(&arbiter) {
+            listed.push_back(arbiter.clone());
+            env.storage().persistent().set(&ARBITERS, &listed);
+        }
+
+        env.events().publish((STAKED, arbiter), (amount, data.stake));
+        data.stake
+    }
+
+    /// Retira todo o stake e sai do cadastro; não é possível com casos abertos no painel
+    pub fn unstake(env: Env, arbiter: Address) -> i128 {
+        arbiter.require_auth();
+        let data = load_arbiter(&env, &arbiter);
+        if data.open_cases > 0 {
+            panic_with_error!(&env, ArbitrationError::ArbiterBusy);
+        }
+        env.storage()
+            .persistent()
+            .remove(&(ARBITER, arbiter.clone()));
+        delist(&env, &arbiter);
+        token::Client::new(&env, &config(&env).stake_token).transfer(
+            &env.current_contract_address(),
+            &arbiter,
+            &data.stake,
+        );
+
+        env.events().publish((UNSTAKED, arbiter), data.stake);
+        data.stake
+    }
+
+    /// Abre um caso pagando a taxa; o painel é sorteado entre os árbitros cadastrados
+    ///
+    /// Como o escrow só aceita decisões após o prazo da negociação e o token só anula mints na
+    /// janela de contestação, o caso deve ser aberto a tempo de o painel decidir.
+    pub fn open_case(env: Env, claimant: Address, subject: Subject) -> u64 {
+        claimant.require_auth();
+        let config = config(&env);
+        let mut candidates = arbiters(&env);
+        if let Some(index) = candidates.first_index_of(&claimant) {
+            candidates.remove(index);
+        }
+        if candidates.len() < config.panel_size {
+            panic_with_error!(&env, ArbitrationError::NotEnoughArbiters);
+        }
+        if config.fee > 0 {
+            token::Client::new(&env, &config.stake_token).transfer(
+                &claimant,
+                &env.current_contract_address(),
+                &config.fee,
+            );
+        }
+
+        candidates.shuffle();
+        let panel = candidates.slice(0..config.panel_size);
+        for arbiter in panel.iter() {
+            let mut data = load_arbiter(&env, &arbiter);
+            data.open_cases += 1;
+            save_arbiter(&env, &arbiter, &data);
+        }
+
+        let id: u64 = env.storage().instance().get(&CASE_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&CASE_SEQ, &id);
+        let case = Case {
+            id,
+            claimant: claimant.clone(),
+            subject: subject.clone(),
+            panel: panel.clone(),
+            fee: config.fee,
+            voting_ends_at: env.ledger().timestamp() + config.voting_period,
+            votes_for: 0,
+            votes_against: 0,
+            status: CaseStatus::Voting,
+        };
+        save(&env, &case);
+
+        env.events().publish((OPENED, id, claimant), (subject, panel));
+        id
+    }
+
+    /// Vota a favor ou contra o pedido do caso dentro do prazo (apenas árbitros do painel)
+    pub fn vote(env: Env, arbiter: Address, case_id: u64, uphold: bool) {
+        arbiter.require_auth();
+        let mut case = load(&env, case_id);
+        if case.status != CaseStatus::Voting {
+            panic_with_error!(&env, ArbitrationError::CaseClosed);
+        }
+        if env.ledger().timestamp() >= case.voting_ends_at {
+            panic_with_error!(&env, ArbitrationError::VotingClosed);
+        }
+        if !case.panel.contains(&arbiter) {
+            panic_with_error!(&env, ArbitrationError::NotOnPanel);
+        }
+        let vote_key = (VOTE, case_id, arbiter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            panic_with_error!(&env, ArbitrationError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&vote_key, &uphold);
+
+        if uphold {
+            case.votes_for += 1;
+        } else {
+            case.votes_against += 1;
+        }
+        save(&env, &case);
+        env.events().publish((VOTED, case_id, arbiter), uphold);
+    }
+
+    /// Encerra o caso após o prazo (ou com o painel inteiro votado) e executa a decisão
+    ///
+    /// A maioria simples dos votos decide; empate ou nenhum voto rejeita o pedido. A taxa e o
+    /// stake cortado dos ausentes são divididos entre os árbitros que votaram; sem votantes,
+    /// voltam para quem abriu o caso. Qualquer um pode chamar.
+    pub fn finalize(env: Env, case_id: u64) -> CaseStatus {
+        let mut case = load(&env, case_id);
+        if case.status != CaseStatus::Voting {
+            panic_with_error!(&env, ArbitrationError::CaseClosed);
+        }
+        let voters = case.votes_for + case.votes_against;
+        if env.ledger().timestamp() < case.voting_ends_at && voters < case.panel.len() {
+            panic_with_error!(&env, ArbitrationError::VotingOpen);
+        }
+        let config = config(&env);
+        let upheld = case.votes_for > case.votes_against;
+        case.status = if upheld {
+            CaseStatus::Upheld
+        } else {
+            CaseStatus::Dismissed
+        };
+        save(&env, &case);
+
+        // Libera o painel e corta o stake de quem não votou
+        let mut pot = case.fee;
+        let mut voted = Vec::new(&env);
+        for arbiter in case.panel.iter() {
+            let mut data = load_arbiter(&env, &arbiter);
+            data.open_cases -= 1;
+            if env.storage().persistent().has(&(VOTE, case_id, arbiter.clone())) {
+                voted.push_back(arbiter.clone());
+            } else {
+                let slashed = data.stake * config.slash_bps as i128 / BPS_DENOMINATOR as i128;
+                data.stake -= slashed;
+                pot += slashed;
+                if data.stake < config.min_stake {
+                    delist(&env, &arbiter);
+                }
+                env.events().publish((SLASHED, case_id, arbiter.clone()), slashed);
+            }
+            save_arbiter(&env, &arbiter, &data);
+        }
+
+        let payment = token::Client::new(&env, &config.stake_token);
+        let pool = env.current_contract_address();
+        if voted.is_empty() {
+            if pot > 0 {
+                payment.transfer(&pool, &case.claimant, &pot);
+            }
+        } else {
+            // O resto da divisão fica com o primeiro votante
+            let share = pot / voted.len() as i128;
+            let remainder = pot - share * voted.len() as i128;
+            for (index, arbiter) in voted.iter().enumerate() {
+                let amount = if index == 0 { share + remainder } else { share };
+                if amount > 0 {
+                    payment.transfer(&pool, &arbiter, &amount);
+                }
+            }
+        }
+
+        enforce(&env, &case.subject, upheld);
+        env.events()
+            .publish((DECIDED, case_id), (case.status, case.votes_for, case.votes_against));
+        case.status
+    }
+
+    /// Consulta um caso
+    pub fn get_case(env: Env, case_id: u64) -> Case {
+        load(&env, case_id)
+    }
+
+    /// Stake e casos abertos de um árbitro
+    pub fn get_arbiter(env: Env, arbiter: Address) -> Arbiter {
+        load_arbiter(&env, &arbiter)
+    }
+
+    /// Árbitros no sorteio dos painéis
+    pub fn arbiters(env: Env) -> Vec<Address> {
+        arbiters(&env)
+    }
+}
+
+mod test;
@@ -0,0 +1,157 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env,
+};
+use strgrid_escrow::{EscrowContract, EscrowContractClient, TradeStatus};
+use strgrid_token::{testutils, Role, STRGRIDContractClient};
+
+struct Setup<'a> {
+    env: Env,
+    energy: STRGRIDContractClient<'a>,
+    escrow: EscrowContractClient<'a>,
+    arbitration: ArbitrationContractClient<'a>,
+    usdc: token::Client<'a>,
+    generator: Address,
+    arbiters: [Address; 4],
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+
+    let usdc_id = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let usdc = token::Client::new(&env, &usdc_id);
+    let arbiters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    // Each contract needs the other's address at deploy, so the panel's is fixed up front
+    let arbitration_id = Address::generate(&env);
+    let escrow_id = env.register(EscrowContract, (energy_id.clone(), arbitration_id.clone()));
+    let escrow = EscrowContractClient::new(&env, &escrow_id);
+    env.register_at(
+        &arbitration_id,
+        ArbitrationContract,
+        (
+            energy_id.clone(),
+            escrow_id.clone(),
+            ArbitrationConfig {
+                stake_token: usdc_id.clone(),
+                min_stake: 500,
+                panel_size: 3,
+                voting_period: 3_600,
+                fee: 90,
+                slash_bps: 1_000,
+            },
+        ),
+    );
+    let arbitration = ArbitrationContractClient::new(&env, &arbitration_id);
+    energy.grant_role(&Role::Auditor, &arbitration_id);
+    for arbiter in arbiters.iter() {
+        token::StellarAssetClient::new(&env, &usdc_id).mint(arbiter, &1_000i128);
+        arbitration.stake(arbiter, &500i128);
+    }
+
+    Setup {
+        env,
+        energy,
+        escrow,
+        arbitration,
+        usdc,
+        generator,
+        arbiters,
+    }
+}
+
+#[test]
+fn test_panel_voids_challenged_mint_and_slashes_absentee() {
+    let s = setup();
+    let claimant = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.usdc.address).mint(&claimant, &100i128);
+    s.energy.set_dispute_window(&86_400u64);
+    let lot = s.energy.mint_energy_tokens(&s.generator, &500i128, &24u64, &None);
+
+    let evidence = BytesN::from_array(&s.env, &[7u8; 32]);
+    let case_id = s.arbitration.open_case(&claimant, &Subject::Mint(lot, evidence));
+    let case = s.arbitration.get_case(&case_id);
+    assert_eq!(case.panel.len(), 3);
+    assert_eq!(s.usdc.balance(&claimant), 10);
+    let outsider = s
+        .arbiters
+        .iter()
+        .find(|arbiter| !case.panel.contains(*arbiter))
+        .unwrap();
+    assert_eq!(
+        s.arbitration.try_vote(outsider, &case_id, &true),
+        Err(Ok(ArbitrationError::NotOnPanel.into()))
+    );
+
+    // Two panelists uphold the challenge and the third never shows up
+    let (first, second, absent) = (
+        case.panel.get_unchecked(0),
+        case.panel.get_unchecked(1),
+        case.panel.get_unchecked(2),
+    );
+    s.arbitration.vote(&first, &case_id, &true);
+    s.arbitration.vote(&second, &case_id, &true);
+    assert_eq!(
+        s.arbitration.try_vote(&first, &case_id, &false),
+        Err(Ok(ArbitrationError::AlreadyVoted.into()))
+    );
+    assert_eq!(
+        s.arbitration.try_finalize(&case_id),
+        Err(Ok(ArbitrationError::VotingOpen.into()))
+    );
+    assert_eq!(
+        s.arbitration.try_unstake(&absent),
+        Err(Ok(ArbitrationError::ArbiterBusy.into()))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 3_600);
+    assert_eq!(s.arbitration.finalize(&case_id), CaseStatus::Upheld);
+    assert!(s.energy.get_energy_token(&lot).is_voided);
+
+    // The fee plus the absentee's 10% slash is shared by the two voters
+    assert_eq!(s.usdc.balance(&first), 500 + 70);
+    assert_eq!(s.usdc.balance(&second), 500 + 70);
+    assert_eq!(s.arbitration.get_arbiter(&absent).stake, 450);
+    assert!(!s.arbitration.arbiters().contains(&absent));
+    assert_eq!(s.arbitration.unstake(&absent), 450);
+}
+
+#[test]
+fn test_panel_refunds_escrow_trade() {
+    let s = setup();
+    let buyer = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.usdc.address).mint(&buyer, &1_100i128);
+    s.energy.mint_energy_tokens(&s.generator, &500i128, &24u64, &None);
+    let trade_id = s.escrow.open(&s.generator, &buyer, &100u64, &s.usdc.address, &1_000i128, &600u64);
+
+    let case_id = s.arbitration.open_case(&buyer, &Subject::EscrowTrade(trade_id));
+    s.env.ledger().with_mut(|li| li.timestamp = 600);
+    for arbiter in s.arbitration.get_case(&case_id).panel.iter() {
+        s.arbitration.vote(&arbiter, &case_id, &false);
+    }
+
+    // With the whole panel in, the case closes before the voting deadline
+    assert_eq!(s.arbitration.finalize(&case_id), CaseStatus::Dismissed);
+    assert_eq!(s.escrow.get_trade(&trade_id).status, TradeStatus::Refunded);
+    assert_eq!(s.usdc.balance(&buyer), 1_100 - 90);
+    assert_eq!(s.energy.balance_of(&s.generator), 500);
+    assert_eq!(
+        s.arbitration.try_finalize(&case_id),
+        Err(Ok(ArbitrationError::CaseClosed.into()))
+    );
+}
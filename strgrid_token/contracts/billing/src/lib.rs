@@ -0,0 +1,255 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const LATE_FEE: Symbol = symbol_short!("LATEFEE");
+const INVOICE: Symbol = symbol_short!("INVOICE");
+const INVOICE_SEQ: Symbol = symbol_short!("INVSEQ");
+const CONSUMER_INVOICES: Symbol = symbol_short!("CONS_INV");
+
+// Tópicos dos eventos publicados pelo contrato de faturamento
+const ISSUED: Symbol = symbol_short!("issued");
+const PAID: Symbol = symbol_short!("paid");
+const PAID_ENERGY: Symbol = symbol_short!("paid_kwh");
+const LATE_FEE_SET: Symbol = symbol_short!("late_fee");
+
+/// Multa por atraso: `fee_bps` sobre o valor original a cada `period_secs` (ou fração) após o vencimento
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateFee {
+    pub fee_bps: u32,
+    pub period_secs: u64,
+}
+
+/// Fatura emitida por um varejista ao consumidor pelos kWh fornecidos
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    pub id: u64,
+    pub retailer: Address,
+    pub consumer: Address,
+    pub payment_token: Address,
+    pub kwh: u64,
+    pub price: i128,
+    pub due_date: u64,
+    pub issued_at: u64,
+    pub is_paid: bool,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BillingError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    InvalidDueDate = 4,
+    InvalidLateFee = 5,
+    SelfInvoice = 6,
+    InvoiceNotFound = 7,
+    AlreadyPaid = 8,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, BillingError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ADMIN)
+        .unwrap_or_else(|| panic_with_error!(env, BillingError::NotInitialized))
+}
+
+fn late_fee(env: &Env) -> LateFee {
+    env.storage().instance().get(&LATE_FEE).unwrap_or(LateFee {
+        fee_bps: 0,
+        period_secs: 1,
+    })
+}
+
+/// Períodos de atraso iniciados desde o vencimento
+fn overdue_periods(env: &Env, invoice: &Invoice, fee: &LateFee) -> u64 {
+    let now = env.ledger().timestamp();
+    if now <= invoice.due_date {
+        return 0;
+    }
+    (now - invoice.due_date).div_ceil(fee.period_secs)
+}
+
+/// Acrescenta a multa acumulada a um valor original da fatura
+fn with_late_fee(env: &Env, invoice: &Invoice, base: i128) -> i128 {
+    let fee = late_fee(env);
+    let periods = overdue_periods(env, invoice, &fee) as i128;
+    base + base * fee.fee_bps as i128 * periods / 10_000
+}
+
+fn load_open(env: &Env, invoice_id: u64) -> Invoice {
+    let invoice = BillingContract::get_invoice(env.clone(), invoice_id);
+    if invoice.is_paid {
+        panic_with_error!(env, BillingError::AlreadyPaid);
+    }
+    invoice
+}
+
+fn save_invoice(env: &Env, invoice: &Invoice) {
+    env.storage().persistent().set(&(INVOICE, invoice.id), invoice);
+}
+
+fn consumer_invoice_ids(env: &Env, consumer: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(CONSUMER_INVOICES, consumer.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+#[contract]
+pub struct BillingContract;
+
+#[contractimpl]
+impl BillingContract {
+    /// Configura no deploy o faturamento com o token de energia e o admin da multa por atraso
+    pub fn __constructor(env: Env, energy_token: Address, admin: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&ADMIN, &admin);
+    }
+
+    /// Define a multa por atraso aplicada às faturas vencidas (apenas admin)
+    pub fn set_late_fee(env: Env, fee_bps: u32, period_secs: u64) {
+        admin(&env).require_auth();
+        if fee_bps > 10_000 || period_secs == 0 {
+            panic_with_error!(&env, BillingError::InvalidLateFee);
+        }
+        env.storage().instance().set(&LATE_FEE, &LateFee { fee_bps, period_secs });
+        env.events().publish((LATE_FEE_SET,), (fee_bps, period_secs));
+    }
+
+    /// Emite uma fatura ao consumidor pelos kWh fornecidos, com preço no ativo de pagamento
+    pub fn issue_invoice(
+        env: Env,
+        retailer: Address,
+        consumer: Address,
+        payment_token: Address,
+        kwh: u64,
+        price: i128,
+        due_date: u64,
+    ) -> u64 {
+        if retailer == consumer {
+            panic_with_error!(&env, BillingError::SelfInvoice);
+        }
+        retailer.require_auth();
+        if kwh == 0 || price <= 0 {
+            panic_with_error!(&env, BillingError::InvalidAmount);
+        }
+        if due_date <= env.ledger().timestamp() {
+            panic_with_error!(&env, BillingError::InvalidDueDate);
+        }
+
+        let id: u64 = env.storage().instance().get(&INVOICE_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&INVOICE_SEQ, &id);
+        let invoice = Invoice {
+            id,
+            retailer: retailer.clone(),
+            consumer: consumer.clone(),
+            payment_token,
+            kwh,
+            price,
+            due_date,
+            issued_at: env.ledger().timestamp(),
+            is_paid: false,
+        };
+        save_invoice(&env, &invoice);
+
+        let mut ids = consumer_invoice_ids(&env, &consumer);
+        ids.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&(CONSUMER_INVOICES, consumer.clone()), &ids);
+
+        env.events()
+            .publish((ISSUED, id, retailer, consumer), (kwh, price, due_date));
+        id
+    }
+
+    /// Paga a fatura no ativo de pagamento, incluindo a multa acumulada, direto ao varejista
+    pub fn pay(env: Env, invoice_id: u64) -> i128 {
+        let mut invoice = load_open(&env, invoice_id);
+        invoice.consumer.require_auth();
+
+        let amount = with_late_fee(&env, &invoice, invoice.price);
+        invoice.is_paid = true;
+        save_invoice(&env, &invoice);
+
+        token::Client::new(&env, &invoice.payment_token).transfer(
+            &invoice.consumer,
+            &invoice.retailer,
+            &amount,
+        );
+        env.events().publish((PAID, invoice_id), amount);
+        amount
+    }
+
+    /// Quita a fatura queimando STRGRID do consumidor: os kWh faturados mais a multa acumulada
+    ///
+    /// O consumidor deve aprovar este contrato como spender dos kWh a queimar.
+    pub fn pay_with_energy(env: Env, invoice_id: u64) -> u64 {
+        let mut invoice = load_open(&env, invoice_id);
+        invoice.consumer.require_auth();
+
+        let kwh = with_late_fee(&env, &invoice, invoice.kwh as i128) as u64;
+        invoice.is_paid = true;
+        save_invoice(&env, &invoice);
+
+        energy_token(&env).burn_from(
+            &env.current_contract_address(),
+            &invoice.consumer,
+            &i128::from(kwh),
+        );
+        env.events().publish((PAID_ENERGY, invoice_id), kwh);
+        kwh
+    }
+
+    /// Valor devido hoje no ativo de pagamento, já com a multa por atraso
+    pub fn amount_due(env: Env, invoice_id: u64) -> i128 {
+        let invoice = load_open(&env, invoice_id);
+        with_late_fee(&env, &invoice, invoice.price)
+    }
+
+    /// Consulta uma fatura
+    pub fn get_invoice(env: Env, invoice_id: u64) -> Invoice {
+        env.storage()
+            .persistent()
+            .get(&(INVOICE, invoice_id))
+            .unwrap_or_else(|| panic_with_error!(&env, BillingError::InvoiceNotFound))
+    }
+
+    /// Lista as faturas do consumidor, na ordem de emissão
+    pub fn get_consumer_invoices(env: Env, consumer: Address, unpaid_only: bool) -> Vec<Invoice> {
+        let mut invoices = Vec::new(&env);
+        for id in consumer_invoice_ids(&env, &consumer).iter() {
+            let invoice = Self::get_invoice(env.clone(), id);
+            if !(unpaid_only && invoice.is_paid) {
+                invoices.push_back(invoice);
+            }
+        }
+        invoices
+    }
+
+    /// Consulta a multa por atraso vigente
+    pub fn get_late_fee(env: Env) -> LateFee {
+        late_fee(&env)
+    }
+}
+
+mod test;
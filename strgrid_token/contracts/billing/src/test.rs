@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_invoices_late_fees_and_payments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let retailer = Address::generate(&env);
+    let household = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    energy.transfer(&generator, &household, &300i128);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&household, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let billing_id = env.register(BillingContract, (energy_id.clone(), admin.clone()));
+    let billing = BillingContractClient::new(&env, &billing_id);
+
+    // 2% per started day overdue
+    let day = 24 * 3600u64;
+    billing.set_late_fee(&200u32, &day);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(
+        billing.try_issue_invoice(&retailer, &household, &usdc, &100u64, &1_000i128, &1_000u64),
+        Err(Ok(BillingError::InvalidDueDate.into()))
+    );
+    let due = 1_000 + 30 * day;
+    let first = billing.issue_invoice(&retailer, &household, &usdc, &100u64, &1_000i128, &due);
+    let second = billing.issue_invoice(&retailer, &household, &usdc, &200u64, &2_000i128, &due);
+
+    // Paid on time in the payment asset
+    assert_eq!(billing.amount_due(&first), 1_000);
+    assert_eq!(billing.pay(&first), 1_000);
+    assert_eq!(usdc_client.balance(&retailer), 1_000);
+    assert_eq!(
+        billing.try_pay(&first),
+        Err(Ok(BillingError::AlreadyPaid.into()))
+    );
+
+    // A day and a half late accrues two periods of fees; paying in STRGRID burns kWh
+    env.ledger().with_mut(|li| li.timestamp = due + day + day / 2);
+    assert_eq!(billing.amount_due(&second), 2_080);
+    energy.approve(&household, &billing_id, &208i128);
+    assert_eq!(billing.pay_with_energy(&second), 208);
+    assert_eq!(energy.balance_of(&household), 92);
+    assert_eq!(energy.total_supply(), 292);
+
+    let invoices = billing.get_consumer_invoices(&household, &false);
+    assert_eq!(invoices.len(), 2);
+    assert!(invoices.iter().all(|invoice| invoice.is_paid));
+    assert_eq!(billing.get_consumer_invoices(&household, &true).len(), 0);
+}
@@ -0,0 +1,233 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
+    Address, Env, Map, Symbol, Vec,
+};
+
+// Símbolos para armazenamento de dados
+const SHARES: Symbol = symbol_short!("SHARES");
+const ACCRUED: Symbol = symbol_short!("ACCRUED");
+const TOTAL_DUE: Symbol = symbol_short!("TOTALDUE");
+const PROPOSAL: Symbol = symbol_short!("PROPOSAL");
+const PROPOSAL_SEQ: Symbol = symbol_short!("PROPSEQ");
+
+// Tópicos dos eventos publicados pela cooperativa
+const DISTRIBUTED: Symbol = symbol_short!("distrib");
+const WITHDRAWN: Symbol = symbol_short!("withdrawn");
+const PROPOSED: Symbol = symbol_short!("proposed");
+const APPROVED: Symbol = symbol_short!("approved");
+const SHARES_CHANGED: Symbol = symbol_short!("shares");
+
+/// Proposta de nova tabela de cotas; executa quando aprovada por mais da metade das cotas vigentes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShareProposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub shares: Map<Address, u32>,
+    pub approvals: Vec<Address>,
+    pub executed: bool,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum CoopError {
+    NotInitialized = 1,
+    InvalidShares = 3,
+    NotMember = 4,
+    ProposalNotFound = 5,
+    ProposalClosed = 6,
+    AlreadyApproved = 7,
+    NothingToDistribute = 8,
+    NothingToWithdraw = 9,
+}
+
+fn shares(env: &Env) -> Map<Address, u32> {
+    env.storage()
+        .instance()
+        .get(&SHARES)
+        .unwrap_or_else(|| panic_with_error!(env, CoopError::NotInitialized))
+}
+
+fn total_shares(shares: &Map<Address, u32>) -> u64 {
+    shares.values().iter().map(|s| s as u64).sum()
+}
+
+/// Tabela válida: ao menos um membro e nenhuma cota zerada
+fn check_shares(env: &Env, shares: &Map<Address, u32>) {
+    if shares.is_empty() || shares.values().iter().any(|s| s == 0) {
+        panic_with_error!(env, CoopError::InvalidShares);
+    }
+}
+
+fn require_member(env: &Env, member: &Address) -> u32 {
+    member.require_auth();
+    shares(env)
+        .get(member.clone())
+        .unwrap_or_else(|| panic_with_error!(env, CoopError::NotMember))
+}
+
+fn accrued(env: &Env, member: &Address, payment_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ACCRUED, member.clone(), payment_token.clone()))
+        .unwrap_or(0)
+}
+
+fn total_due(env: &Env, payment_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(TOTAL_DUE, payment_token.clone()))
+        .unwrap_or(0)
+}
+
+fn load_proposal(env: &Env, proposal_id: u64) -> ShareProposal {
+    env.storage()
+        .persistent()
+        .get(&(PROPOSAL, proposal_id))
+        .unwrap_or_else(|| panic_with_error!(env, CoopError::ProposalNotFound))
+}
+
+/// Registra o voto e executa a proposta se as aprovações somarem mais da metade das cotas vigentes
+fn record_approval(env: &Env, mut proposal: ShareProposal, member: Address) -> bool {
+    if proposal.executed {
+        panic_with_error!(env, CoopError::ProposalClosed);
+    }
+    if proposal.approvals.contains(&member) {
+        panic_with_error!(env, CoopError::AlreadyApproved);
+    }
+    proposal.approvals.push_back(member.clone());
+    env.events().publish((APPROVED, proposal.id), member);
+
+    let current = shares(env);
+    let approved: u64 = proposal
+        .approvals
+        .iter()
+        .map(|m| current.get(m).unwrap_or(0) as u64)
+        .sum();
+    if approved * 2 > total_shares(&current) {
+        proposal.executed = true;
+        env.storage().instance().set(&SHARES, &proposal.shares);
+        env.events()
+            .publish((SHARES_CHANGED, proposal.id), proposal.shares.clone());
+    }
+    env.storage().persistent().set(&(PROPOSAL, proposal.id), &proposal);
+    proposal.executed
+}
+
+#[contract]
+pub struct CoopContract;
+
+#[contractimpl]
+impl CoopContract {
+    /// Configura no deploy a cooperativa com os geradores membros e suas cotas
+    ///
+    /// O endereço do contrato é a tesouraria comum: os membros o configuram como destino de
+    /// vendas e royalties no marketplace.
+    pub fn __constructor(env: Env, shares: Map<Address, u32>) {
+        check_shares(&env, &shares);
+        env.storage().instance().set(&SHARES, &shares);
+    }
+
+    /// Cotas vigentes dos membros
+    pub fn shares(env: Env) -> Map<Address, u32> {
+        shares(&env)
+    }
+
+    /// Rateia entre os membros, pelas cotas vigentes, o saldo da tesouraria ainda não rateado
+    pub fn distribute(env: Env, payment_token: Address) -> i128 {
+        let shares = shares(&env);
+        let due = total_due(&env, &payment_token);
+        let balance = token::Client::new(&env, &payment_token).balance(&env.current_contract_address());
+        let available = balance - due;
+        if available <= 0 {
+            panic_with_error!(&env, CoopError::NothingToDistribute);
+        }
+
+        let total = total_shares(&shares) as i128;
+        let mut distributed = 0i128;
+        for (member, share) in shares.iter() {
+            let amount = available * share as i128 / total;
+            if amount > 0 {
+                let key = (ACCRUED, member.clone(), payment_token.clone());
+                env.storage()
+                    .persistent()
+                    .set(&key, &(accrued(&env, &member, &payment_token) + amount));
+                distributed += amount;
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&(TOTAL_DUE, payment_token.clone()), &(due + distributed));
+
+        env.events().publish((DISTRIBUTED, payment_token), distributed);
+        distributed
+    }
+
+    /// Valor já rateado e ainda não sacado por um membro
+    pub fn accrued_of(env: Env, member: Address, payment_token: Address) -> i128 {
+        accrued(&env, &member, &payment_token)
+    }
+
+    /// Saca a parte rateada do membro
+    pub fn withdraw(env: Env, member: Address, payment_token: Address) -> i128 {
+        member.require_auth();
+        let amount = accrued(&env, &member, &payment_token);
+        if amount == 0 {
+            panic_with_error!(&env, CoopError::NothingToWithdraw);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&(ACCRUED, member.clone(), payment_token.clone()));
+        env.storage().persistent().set(
+            &(TOTAL_DUE, payment_token.clone()),
+            &(total_due(&env, &payment_token) - amount),
+        );
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &member,
+            &amount,
+        );
+
+        env.events().publish((WITHDRAWN, member, payment_token), amount);
+        amount
+    }
+
+    /// Propõe uma nova tabela de cotas (apenas membros); a proposta já conta o voto do proponente
+    pub fn propose_shares(env: Env, proposer: Address, new_shares: Map<Address, u32>) -> u64 {
+        require_member(&env, &proposer);
+        check_shares(&env, &new_shares);
+
+        let id: u64 = env.storage().instance().get(&PROPOSAL_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&PROPOSAL_SEQ, &id);
+        let proposal = ShareProposal {
+            id,
+            proposer: proposer.clone(),
+            shares: new_shares,
+            approvals: Vec::new(&env),
+            executed: false,
+        };
+        env.events().publish((PROPOSED, id), proposer.clone());
+
+        record_approval(&env, proposal, proposer);
+        id
+    }
+
+    /// Aprova uma proposta; com mais da metade das cotas vigentes a nova tabela entra em vigor
+    ///
+    /// Saldos já rateados não mudam; a nova tabela vale para os próximos rateios.
+    pub fn approve(env: Env, member: Address, proposal_id: u64) -> bool {
+        require_member(&env, &member);
+        record_approval(&env, load_proposal(&env, proposal_id), member)
+    }
+
+    /// Consulta uma proposta de cotas
+    pub fn get_proposal(env: Env, proposal_id: u64) -> ShareProposal {
+        load_proposal(&env, proposal_id)
+    }
+}
+
+mod test;
@@ -0,0 +1,62 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{map, testutils::Address as _, token, Address, Env};
+
+#[test]
+fn test_coop_distribution_and_share_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let shares = map![&env, (alice.clone(), 50u32), (bob.clone(), 30u32), (carol.clone(), 20u32)];
+    let coop_id = env.register(CoopContract, (shares,));
+    let coop = CoopContractClient::new(&env, &coop_id);
+
+    // Sales proceeds land in the shared treasury
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    usdc_client.transfer(&buyer, &coop_id, &1_000i128);
+
+    assert_eq!(coop.distribute(&usdc), 1_000);
+    assert_eq!(coop.accrued_of(&alice, &usdc), 500);
+    assert_eq!(coop.try_distribute(&usdc), Err(Ok(CoopError::NothingToDistribute.into())));
+    assert_eq!(coop.withdraw(&bob, &usdc), 300);
+    assert_eq!(usdc_client.balance(&bob), 300);
+    assert_eq!(coop.try_withdraw(&bob, &usdc), Err(Ok(CoopError::NothingToWithdraw.into())));
+
+    // Share changes need a majority of current shares
+    let equal = map![&env, (alice.clone(), 1u32), (bob.clone(), 1u32), (carol.clone(), 1u32)];
+    assert_eq!(
+        coop.try_propose_shares(&buyer, &equal),
+        Err(Ok(CoopError::NotMember.into()))
+    );
+    let proposal_id = coop.propose_shares(&bob, &equal);
+    assert!(!coop.get_proposal(&proposal_id).executed);
+    assert_eq!(
+        coop.try_approve(&bob, &proposal_id),
+        Err(Ok(CoopError::AlreadyApproved.into()))
+    );
+    // 30 + 20 of 100 is not a majority; Alice's 50 tips it
+    assert!(!coop.approve(&carol, &proposal_id));
+    assert!(coop.approve(&alice, &proposal_id));
+    assert_eq!(coop.shares(), equal);
+    assert_eq!(
+        coop.try_approve(&alice, &proposal_id),
+        Err(Ok(CoopError::ProposalClosed.into()))
+    );
+
+    // New shares apply to later distributions; earlier accruals are kept
+    usdc_client.transfer(&buyer, &coop_id, &300i128);
+    assert_eq!(coop.distribute(&usdc), 300);
+    assert_eq!(coop.withdraw(&alice, &usdc), 600);
+    assert_eq!(coop.withdraw(&carol, &usdc), 300);
+    assert_eq!(coop.withdraw(&bob, &usdc), 100);
+    assert_eq!(usdc_client.balance(&coop_id), 0);
+}
@@ -0,0 +1,247 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, Symbol,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const ARBITER: Symbol = symbol_short!("ARBITER");
+const TRADE: Symbol = symbol_short!("TRADE");
+const TRADE_SEQ: Symbol = symbol_short!("TRADESEQ");
+
+// Tópicos dos eventos publicados pelo escrow
+const OPENED: Symbol = symbol_short!("opened");
+const CONFIRMED: Symbol = symbol_short!("confirmed");
+const RELEASED: Symbol = symbol_short!("released");
+const REFUNDED: Symbol = symbol_short!("refunded");
+
+/// Situação de uma negociação em custódia
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TradeStatus {
+    Open,
+    Released,
+    Refunded,
+}
+
+/// Negociação bilateral: kWh do vendedor e pagamento do comprador ficam custodiados até a entrega
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trade {
+    pub id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub amount_kwh: u64,
+    pub payment_token: Address,
+    pub payment_amount: i128,
+    pub deadline: u64,
+    pub seller_confirmed: bool,
+    pub buyer_confirmed: bool,
+    pub status: TradeStatus,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    InvalidTimeout = 4,
+    SelfTrade = 5,
+    TradeNotFound = 6,
+    TradeClosed = 7,
+    NotParty = 8,
+    TimeoutNotReached = 9,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, EscrowError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+/// Transfere kWh de `from` para a custódia e devolve o que de fato entrou, já descontadas a
+/// taxa de transferência e as perdas cobradas pelo token
+fn deposit_energy(env: &Env, from: &Address, amount_kwh: u64) -> u64 {
+    let energy = energy_token(env);
+    let escrow = env.current_contract_address();
+    let before = energy.balance_of(&escrow);
+    energy.transfer(from, &escrow, &i128::from(amount_kwh));
+    let received = (energy.balance_of(&escrow) - before) as u64;
+    if received == 0 {
+        panic_with_error!(env, EscrowError::InvalidAmount);
+    }
+    received
+}
+
+fn arbiter(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ARBITER)
+        .unwrap_or_else(|| panic_with_error!(env, EscrowError::NotInitialized))
+}
+
+fn load_open(env: &Env, trade_id: u64) -> Trade {
+    let trade = EscrowContract::get_trade(env.clone(), trade_id);
+    if trade.status != TradeStatus::Open {
+        panic_with_error!(env, EscrowError::TradeClosed);
+    }
+    trade
+}
+
+fn save_trade(env: &Env, trade: &Trade) {
+    env.storage().persistent().set(&(TRADE, trade.id), trade);
+}
+
+/// Entrega os kWh ao comprador e o pagamento ao vendedor
+fn release(env: &Env, mut trade: Trade) {
+    trade.status = TradeStatus::Released;
+    save_trade(env, &trade);
+
+    let escrow = env.current_contract_address();
+    energy_token(env).transfer(&escrow, &trade.buyer, &i128::from(trade.amount_kwh));
+    token::Client::new(env, &trade.payment_token).transfer(
+        &escrow,
+        &trade.seller,
+        &trade.payment_amount,
+    );
+    env.events().publish((RELEASED, trade.id), trade.amount_kwh);
+}
+
+/// Devolve a cada parte o que ela depositou
+fn refund(env: &Env, mut trade: Trade) {
+    trade.status = TradeStatus::Refunded;
+    save_trade(env, &trade);
+
+    let escrow = env.current_contract_address();
+    energy_token(env).transfer(&escrow, &trade.seller, &i128::from(trade.amount_kwh));
+    token::Client::new(env, &trade.payment_token).transfer(
+        &escrow,
+        &trade.buyer,
+        &trade.payment_amount,
+    );
+    env.events().publish((REFUNDED, trade.id), trade.amount_kwh);
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Configura no deploy o escrow com o token de energia e o árbitro das disputas
+    pub fn __constructor(env: Env, energy_token: Address, arbiter: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&ARBITER, &arbiter);
+    }
+
+    /// Consulta o árbitro das disputas
+    pub fn arbiter(env: Env) -> Address {
+        arbiter(&env)
+    }
+
+    /// Abre uma negociação acordada fora da cadeia; ambas as partes assinam e depositam
+    pub fn open(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        amount_kwh: u64,
+        payment_token: Address,
+        payment_amount: i128,
+        timeout_secs: u64,
+    ) -> u64 {
+        if seller == buyer {
+            panic_with_error!(&env, EscrowError::SelfTrade);
+        }
+        seller.require_auth();
+        buyer.require_auth();
+        if amount_kwh == 0 || payment_amount <= 0 {
+            panic_with_error!(&env, EscrowError::InvalidAmount);
+        }
+        if timeout_secs == 0 {
+            panic_with_error!(&env, EscrowError::InvalidTimeout);
+        }
+        let deadline = env
+            .ledger()
+            .timestamp()
+            .checked_add(timeout_secs)
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::InvalidTimeout));
+
+        // A negociação entrega só o que chegou à custódia, já descontada a taxa do token
+        let amount_kwh = deposit_energy(&env, &seller, amount_kwh);
+        token::Client::new(&env, &payment_token).transfer(
+            &buyer,
+            &env.current_contract_address(),
+            &payment_amount,
+        );
+
+        let id: u64 = env.storage().instance().get(&TRADE_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&TRADE_SEQ, &id);
+        let trade = Trade {
+            id,
+            seller: seller.clone(),
+            buyer: buyer.clone(),
+            amount_kwh,
+            payment_token,
+            payment_amount,
+            deadline,
+            seller_confirmed: false,
+            buyer_confirmed: false,
+            status: TradeStatus::Open,
+        };
+        save_trade(&env, &trade);
+
+        env.events()
+            .publish((OPENED, id, seller, buyer), (amount_kwh, payment_amount));
+        id
+    }
+
+    /// Confirma a entrega por uma das partes; com as duas confirmações a custódia é liberada
+    pub fn confirm(env: Env, trade_id: u64, party: Address) {
+        party.require_auth();
+        let mut trade = load_open(&env, trade_id);
+        if party == trade.seller {
+            trade.seller_confirmed = true;
+        } else if party == trade.buyer {
+            trade.buyer_confirmed = true;
+        } else {
+            panic_with_error!(&env, EscrowError::NotParty);
+        }
+        env.events().publish((CONFIRMED, trade_id), party);
+
+        if trade.seller_confirmed && trade.buyer_confirmed {
+            release(&env, trade);
+        } else {
+            save_trade(&env, &trade);
+        }
+    }
+
+    /// Decide uma negociação sem as duas confirmações após o prazo: libera ou estorna (apenas árbitro)
+    pub fn resolve(env: Env, trade_id: u64, release_to_buyer: bool) {
+        arbiter(&env).require_auth();
+        let trade = load_open(&env, trade_id);
+        if env.ledger().timestamp() < trade.deadline {
+            panic_with_error!(&env, EscrowError::TimeoutNotReached);
+        }
+        if release_to_buyer {
+            release(&env, trade);
+        } else {
+            refund(&env, trade);
+        }
+    }
+
+    /// Consulta uma negociação
+    pub fn get_trade(env: Env, trade_id: u64) -> Trade {
+        env.storage()
+            .persistent()
+            .get(&(TRADE, trade_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::TradeNotFound))
+    }
+}
+
+mod test;
@@ -0,0 +1,135 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_escrow_release_on_both_confirmations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let escrow_id = env.register(EscrowContract, (energy_id.clone(), arbiter.clone()));
+    let escrow = EscrowContractClient::new(&env, &escrow_id);
+    assert_eq!(
+        escrow.try_open(&buyer, &buyer, &100u64, &usdc, &1_000i128, &3600u64),
+        Err(Ok(EscrowError::SelfTrade.into()))
+    );
+
+    // Both sides are held until delivery is confirmed
+    let trade_id = escrow.open(&generator, &buyer, &200u64, &usdc, &3_000i128, &3600u64);
+    assert_eq!(energy.balance_of(&escrow_id), 200);
+    assert_eq!(usdc_client.balance(&escrow_id), 3_000);
+
+    assert_eq!(
+        escrow.try_confirm(&trade_id, &stranger),
+        Err(Ok(EscrowError::NotParty.into()))
+    );
+    escrow.confirm(&trade_id, &buyer);
+    assert_eq!(escrow.get_trade(&trade_id).status, TradeStatus::Open);
+    escrow.confirm(&trade_id, &generator);
+
+    assert_eq!(escrow.get_trade(&trade_id).status, TradeStatus::Released);
+    assert_eq!(energy.balance_of(&buyer), 200);
+    assert_eq!(usdc_client.balance(&generator), 3_000);
+    assert_eq!(usdc_client.balance(&escrow_id), 0);
+    assert_eq!(
+        escrow.try_confirm(&trade_id, &buyer),
+        Err(Ok(EscrowError::TradeClosed.into()))
+    );
+}
+
+#[test]
+fn test_escrow_arbiter_refund_after_timeout() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let escrow_id = env.register(EscrowContract, (energy_id.clone(), arbiter.clone()));
+    let escrow = EscrowContractClient::new(&env, &escrow_id);
+
+    let trade_id = escrow.open(&generator, &buyer, &200u64, &usdc, &3_000i128, &3600u64);
+    escrow.confirm(&trade_id, &generator);
+
+    // The arbiter can only step in once the timeout has passed
+    assert_eq!(
+        escrow.try_resolve(&trade_id, &false),
+        Err(Ok(EscrowError::TimeoutNotReached.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    escrow.resolve(&trade_id, &false);
+
+    assert_eq!(escrow.get_trade(&trade_id).status, TradeStatus::Refunded);
+    assert_eq!(energy.balance_of(&generator), 500);
+    assert_eq!(usdc_client.balance(&buyer), 10_000);
+    assert_eq!(
+        escrow.try_resolve(&trade_id, &true),
+        Err(Ok(EscrowError::TradeClosed.into()))
+    );
+}
+
+#[test]
+fn test_trade_records_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+
+    let escrow_id = env.register(EscrowContract, (energy_id.clone(), Address::generate(&env)));
+    let escrow = EscrowContractClient::new(&env, &escrow_id);
+
+    // Two trades: releasing the first must not dip into the second one's custody
+    let first = escrow.open(&generator, &buyer, &200u64, &usdc, &1_000i128, &3600u64);
+    let second = escrow.open(&generator, &buyer, &100u64, &usdc, &1_000i128, &3600u64);
+    assert_eq!(escrow.get_trade(&first).amount_kwh, 198);
+    assert_eq!(energy.balance_of(&escrow_id), 198 + 99);
+
+    escrow.confirm(&first, &generator);
+    escrow.confirm(&first, &buyer);
+    assert_eq!(energy.balance_of(&escrow_id), 99);
+    escrow.confirm(&second, &generator);
+    escrow.confirm(&second, &buyer);
+    assert_eq!(energy.balance_of(&escrow_id), 0);
+}
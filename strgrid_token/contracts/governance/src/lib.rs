@@ -0,0 +1,231 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, Address, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const CONFIG: Symbol = symbol_short!("CONFIG");
+const PROPOSAL: Symbol = symbol_short!("PROPOSAL");
+const PROPOSAL_SEQ: Symbol = symbol_short!("PROPSEQ");
+const VOTED: Symbol = symbol_short!("VOTED");
+
+// Tópicos dos eventos publicados pela governança
+const PROPOSED: Symbol = symbol_short!("proposed");
+const VOTED_TOPIC: Symbol = symbol_short!("voted");
+const EXECUTED: Symbol = symbol_short!("executed");
+
+/// Denominador dos basis points (100% = 10.000 bps)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+// Tipos do token de energia usados nas alterações de parâmetro
+pub use strgrid_interface::{MintCaps, SourceType};
+
+/// Alteração de parâmetro do protocolo aplicada pelos setters de admin do token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParamChange {
+    TransferFee(u32, Address),
+    MintCaps(MintCaps),
+    DisputeWindow(u64),
+    EmissionFactor(SourceType, u64),
+}
+
+/// Conselho e prazos da governança
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    pub council: Vec<Address>,
+    pub voting_period: u64,
+    pub timelock: u64,
+    /// Participação mínima do conselho, em basis points
+    pub quorum_bps: u32,
+}
+
+/// Proposta de alteração; executável após a votação e o timelock se aprovada com quórum
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Address,
+    pub change: ParamChange,
+    pub voting_ends_at: u64,
+    pub executable_at: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub executed: bool,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    NotInitialized = 1,
+    InvalidConfig = 3,
+    NotCouncilMember = 4,
+    ProposalNotFound = 5,
+    VotingClosed = 6,
+    VotingOpen = 7,
+    AlreadyVoted = 8,
+    ProposalRejected = 9,
+    TimelockActive = 10,
+    AlreadyExecuted = 11,
+}
+
+fn config(env: &Env) -> GovernanceConfig {
+    env.storage()
+        .instance()
+        .get(&CONFIG)
+        .unwrap_or_else(|| panic_with_error!(env, GovernanceError::NotInitialized))
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, GovernanceError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn require_council(env: &Env, member: &Address) {
+    member.require_auth();
+    if !config(env).council.contains(member) {
+        panic_with_error!(env, GovernanceError::NotCouncilMember);
+    }
+}
+
+fn load(env: &Env, proposal_id: u64) -> Proposal {
+    env.storage()
+        .persistent()
+        .get(&(PROPOSAL, proposal_id))
+        .unwrap_or_else(|| panic_with_error!(env, GovernanceError::ProposalNotFound))
+}
+
+fn save(env: &Env, proposal: &Proposal) {
+    env.storage().persistent().set(&(PROPOSAL, proposal.id), proposal);
+}
+
+/// Aprovada: maioria dos votos e participação mínima do conselho
+fn is_approved(config: &GovernanceConfig, proposal: &Proposal) -> bool {
+    let turnout = (proposal.votes_for + proposal.votes_against) as u64;
+    proposal.votes_for > proposal.votes_against
+        && turnout * BPS_DENOMINATOR as u64 >= config.quorum_bps as u64 * config.council.len() as u64
+}
+
+#[contract]
+pub struct GovernanceContract;
+
+#[contractimpl]
+impl GovernanceContract {
+    /// Configura no deploy a governança; o contrato deve ser o admin do token de energia
+    pub fn __constructor(env: Env, energy_token: Address, config: GovernanceConfig) {
+        if config.council.is_empty()
+            || config.voting_period == 0
+            || config.quorum_bps == 0
+            || config.quorum_bps > BPS_DENOMINATOR
+        {
+            panic_with_error!(&env, GovernanceError::InvalidConfig);
+        }
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&CONFIG, &config);
+    }
+
+    /// Conselho e prazos vigentes
+    pub fn config(env: Env) -> GovernanceConfig {
+        config(&env)
+    }
+
+    /// Propõe uma alteração de parâmetro (apenas conselho)
+    pub fn propose(env: Env, proposer: Address, change: ParamChange) -> u64 {
+        require_council(&env, &proposer);
+        let config = config(&env);
+
+        let id: u64 = env.storage().instance().get(&PROPOSAL_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&PROPOSAL_SEQ, &id);
+        let voting_ends_at = env.ledger().timestamp() + config.voting_period;
+        let proposal = Proposal {
+            id,
+            proposer: proposer.clone(),
+            change: change.clone(),
+            voting_ends_at,
+            executable_at: voting_ends_at + config.timelock,
+            votes_for: 0,
+            votes_against: 0,
+            executed: false,
+        };
+        save(&env, &proposal);
+
+        env.events().publish((PROPOSED, id, proposer), change);
+        id
+    }
+
+    /// Vota a favor ou contra uma proposta durante o período de votação (apenas conselho)
+    pub fn vote(env: Env, member: Address, proposal_id: u64, support: bool) {
+        require_council(&env, &member);
+        let mut proposal = load(&env, proposal_id);
+        if env.ledger().timestamp() >= proposal.voting_ends_at {
+            panic_with_error!(&env, GovernanceError::VotingClosed);
+        }
+        let voted_key = (VOTED, proposal_id, member.clone());
+        if env.storage().persistent().has(&voted_key) {
+            panic_with_error!(&env, GovernanceError::AlreadyVoted);
+        }
+        env.storage().persistent().set(&voted_key, &support);
+
+        if support {
+            proposal.votes_for += 1;
+        } else {
+            proposal.votes_against += 1;
+        }
+        save(&env, &proposal);
+        env.events().publish((VOTED_TOPIC, proposal_id, member), support);
+    }
+
+    /// Executa uma proposta aprovada depois do timelock, chamando o setter de admin do token
+    pub fn execute(env: Env, proposal_id: u64) {
+        let mut proposal = load(&env, proposal_id);
+        if proposal.executed {
+            panic_with_error!(&env, GovernanceError::AlreadyExecuted);
+        }
+        let now = env.ledger().timestamp();
+        if now < proposal.voting_ends_at {
+            panic_with_error!(&env, GovernanceError::VotingOpen);
+        }
+        if !is_approved(&config(&env), &proposal) {
+            panic_with_error!(&env, GovernanceError::ProposalRejected);
+        }
+        if now < proposal.executable_at {
+            panic_with_error!(&env, GovernanceError::TimelockActive);
+        }
+        proposal.executed = true;
+        save(&env, &proposal);
+
+        let token = energy_token(&env);
+        match proposal.change.clone() {
+            ParamChange::TransferFee(fee_bps, treasury) => token.set_transfer_fee(&fee_bps, &treasury),
+            ParamChange::MintCaps(caps) => token.set_mint_caps(&caps),
+            ParamChange::DisputeWindow(seconds) => token.set_dispute_window(&seconds),
+            ParamChange::EmissionFactor(source_type, grams_per_kwh) => {
+                token.set_emission_factor(&source_type, &grams_per_kwh)
+            }
+        }
+
+        env.events().publish((EXECUTED, proposal_id), proposal.change);
+    }
+
+    /// Consulta uma proposta
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        load(&env, proposal_id)
+    }
+
+    /// Consulta se a proposta está aprovada pelas regras de maioria e quórum
+    pub fn is_approved(env: Env, proposal_id: u64) -> bool {
+        is_approved(&config(&env), &load(&env, proposal_id))
+    }
+}
+
+mod test;
@@ -0,0 +1,84 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_council_votes_and_executes_after_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    // The governance contract is the token's admin
+    let gov_id = Address::generate(&env);
+    let energy_id = testutils::deploy(&env, &gov_id, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    env.register_at(
+        &gov_id,
+        GovernanceContract,
+        (
+            energy_id.clone(),
+            GovernanceConfig {
+                council: vec![&env, alice.clone(), bob.clone(), carol.clone()],
+                voting_period: 100,
+                timelock: 50,
+                quorum_bps: 6_000,
+            },
+        ),
+    );
+    let gov = GovernanceContractClient::new(&env, &gov_id);
+
+    assert_eq!(
+        gov.try_propose(&outsider, &ParamChange::DisputeWindow(3_600)),
+        Err(Ok(GovernanceError::NotCouncilMember.into()))
+    );
+    let proposal_id = gov.propose(&alice, &ParamChange::DisputeWindow(3_600));
+    gov.vote(&alice, &proposal_id, &true);
+    assert_eq!(
+        gov.try_vote(&alice, &proposal_id, &true),
+        Err(Ok(GovernanceError::AlreadyVoted.into()))
+    );
+    assert_eq!(
+        gov.try_execute(&proposal_id),
+        Err(Ok(GovernanceError::VotingOpen.into()))
+    );
+
+    // One vote of three misses the 60% quorum
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        gov.try_vote(&bob, &proposal_id, &true),
+        Err(Ok(GovernanceError::VotingClosed.into()))
+    );
+    assert_eq!(
+        gov.try_execute(&proposal_id),
+        Err(Ok(GovernanceError::ProposalRejected.into()))
+    );
+
+    // Two of three in favour passes, then waits for the timelock
+    let proposal_id = gov.propose(&bob, &ParamChange::EmissionFactor(SourceType::Solar, 45));
+    gov.vote(&alice, &proposal_id, &true);
+    gov.vote(&bob, &proposal_id, &true);
+    assert!(gov.is_approved(&proposal_id));
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(
+        gov.try_execute(&proposal_id),
+        Err(Ok(GovernanceError::TimelockActive.into()))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 250);
+    gov.execute(&proposal_id);
+    assert_eq!(energy.get_emission_factor(&strgrid_token::SourceType::Solar), 45);
+    assert!(gov.get_proposal(&proposal_id).executed);
+    assert_eq!(
+        gov.try_execute(&proposal_id),
+        Err(Ok(GovernanceError::AlreadyExecuted.into()))
+    );
+}
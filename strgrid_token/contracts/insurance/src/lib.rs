@@ -0,0 +1,203 @@
+#![no_std]
+//! Pool de seguro contra inadimplência de entrega
+//!
+//! O token repassa ao pool uma fatia dos pagamentos de contratos a termo e compromissos
+//! day-ahead liquidados (`set_insurance`). Compradores lesados por uma entrega que não veio
+//! abrem um pedido de indenização, valorada ao PLD do intervalo publicado pelo oracle no token,
+//! e um auditor (papel `Auditor` do token) aprova ou rejeita o pedido.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short, token,
+    Address, Env, Symbol,
+};
+use strgrid_interface::{ForwardStatus, Role, STRGRIDClient, SETTLEMENT_INTERVAL_SECONDS};
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const CLAIM: Symbol = symbol_short!("CLAIM");
+const CLAIM_SEQ: Symbol = symbol_short!("CLAIMSEQ");
+const CLAIMED: Symbol = symbol_short!("CLAIMED");
+
+// Tópicos dos eventos publicados pelo pool
+const FILED: Symbol = symbol_short!("filed");
+const PAID: Symbol = symbol_short!("paid");
+const REJECTED: Symbol = symbol_short!("rejected");
+
+/// Contrato do token cuja inadimplência é reclamada
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Coverage {
+    Forward(u64),
+    DayAhead(u64),
+}
+
+/// Situação de um pedido de indenização
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClaimStatus {
+    Pending,
+    Paid,
+    Rejected,
+}
+
+/// Pedido de indenização: kWh não entregues valorados ao PLD do intervalo da entrega
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub id: u64,
+    pub claimant: Address,
+    pub coverage: Coverage,
+    pub shortfall_kwh: u64,
+    pub payment_token: Address,
+    pub amount: i128,
+    pub status: ClaimStatus,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InsuranceError {
+    NotInitialized = 1,
+    NotAuditor = 3,
+    NotDefaulted = 4,
+    NotClaimant = 5,
+    AlreadyClaimed = 6,
+    ClaimNotFound = 7,
+    ClaimClosed = 8,
+    InsufficientReserve = 9,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, InsuranceError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn require_auditor(env: &Env, auditor: &Address) {
+    auditor.require_auth();
+    if !energy_token(env).has_role(&Role::Auditor, auditor) {
+        panic_with_error!(env, InsuranceError::NotAuditor);
+    }
+}
+
+/// Credor, kWh não entregues e intervalo de liquidação de um contrato inadimplente
+fn shortfall(env: &Env, coverage: Coverage) -> (Address, u64, u64) {
+    let energy = energy_token(env);
+    match coverage {
+        Coverage::Forward(forward_id) => {
+            let forward = energy.get_forward(&forward_id);
+            if forward.status != ForwardStatus::Defaulted {
+                panic_with_error!(env, InsuranceError::NotDefaulted);
+            }
+            // A entrega era devida até o último intervalo da janela
+            let interval = (forward.window_end - 1) / SETTLEMENT_INTERVAL_SECONDS;
+            (forward.buyer, forward.amount_kwh, interval)
+        }
+        Coverage::DayAhead(commitment_id) => {
+            let commitment = energy.get_day_ahead_commitment(&commitment_id);
+            if !commitment.settled || commitment.delivered_kwh >= commitment.amount_kwh {
+                panic_with_error!(env, InsuranceError::NotDefaulted);
+            }
+            let missing = commitment.amount_kwh - commitment.delivered_kwh;
+            (commitment.buyer, missing, commitment.interval)
+        }
+    }
+}
+
+fn load_pending(env: &Env, claim_id: u64) -> Claim {
+    let claim = InsuranceContract::get_claim(env.clone(), claim_id);
+    if claim.status != ClaimStatus::Pending {
+        panic_with_error!(env, InsuranceError::ClaimClosed);
+    }
+    claim
+}
+
+fn save_claim(env: &Env, claim: &Claim) {
+    env.storage().persistent().set(&(CLAIM, claim.id), claim);
+}
+
+#[contract]
+pub struct InsuranceContract;
+
+#[contractimpl]
+impl InsuranceContract {
+    /// Configura no deploy o pool com o token de energia que consulta contratos, PLD e auditores
+    pub fn __constructor(env: Env, energy_token: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+    }
+
+    /// Pede indenização por um contrato inadimplente; cada contrato só pode ser reclamado uma vez
+    pub fn file_claim(env: Env, claimant: Address, coverage: Coverage) -> u64 {
+        claimant.require_auth();
+        if env.storage().persistent().has(&(CLAIMED, coverage)) {
+            panic_with_error!(&env, InsuranceError::AlreadyClaimed);
+        }
+        let (creditor, shortfall_kwh, interval) = shortfall(&env, coverage);
+        if claimant != creditor {
+            panic_with_error!(&env, InsuranceError::NotClaimant);
+        }
+        let pld = energy_token(&env).get_pld(&interval);
+
+        let id: u64 = env.storage().instance().get(&CLAIM_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&CLAIM_SEQ, &id);
+        let claim = Claim {
+            id,
+            claimant: claimant.clone(),
+            coverage,
+            shortfall_kwh,
+            payment_token: pld.payment_token,
+            amount: pld.price_per_kwh * shortfall_kwh as i128,
+            status: ClaimStatus::Pending,
+        };
+        save_claim(&env, &claim);
+        env.storage().persistent().set(&(CLAIMED, coverage), &id);
+
+        env.events()
+            .publish((FILED, id, claimant), (shortfall_kwh, claim.amount));
+        id
+    }
+
+    /// Aprova o pedido e paga a indenização com a reserva do pool (apenas auditor)
+    pub fn approve_claim(env: Env, auditor: Address, claim_id: u64) {
+        require_auditor(&env, &auditor);
+        let mut claim = load_pending(&env, claim_id);
+        let payment = token::Client::new(&env, &claim.payment_token);
+        let pool = env.current_contract_address();
+        if payment.balance(&pool) < claim.amount {
+            panic_with_error!(&env, InsuranceError::InsufficientReserve);
+        }
+
+        claim.status = ClaimStatus::Paid;
+        save_claim(&env, &claim);
+        payment.transfer(&pool, &claim.claimant, &claim.amount);
+        env.events().publish((PAID, claim_id, auditor), claim.amount);
+    }
+
+    /// Rejeita o pedido; o contrato não pode ser reclamado de novo (apenas auditor)
+    pub fn reject_claim(env: Env, auditor: Address, claim_id: u64) {
+        require_auditor(&env, &auditor);
+        let mut claim = load_pending(&env, claim_id);
+        claim.status = ClaimStatus::Rejected;
+        save_claim(&env, &claim);
+        env.events().publish((REJECTED, claim_id, auditor), claim.amount);
+    }
+
+    /// Reserva do pool em um ativo de pagamento
+    pub fn reserve(env: Env, payment_token: Address) -> i128 {
+        token::Client::new(&env, &payment_token).balance(&env.current_contract_address())
+    }
+
+    /// Consulta um pedido de indenização
+    pub fn get_claim(env: Env, claim_id: u64) -> Claim {
+        env.storage()
+            .persistent()
+            .get(&(CLAIM, claim_id))
+            .unwrap_or_else(|| panic_with_error!(&env, InsuranceError::ClaimNotFound))
+    }
+}
+
+mod test;
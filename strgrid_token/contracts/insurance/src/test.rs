@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use strgrid_token::{testutils, InsuranceConfig, Role as TokenRole, STRGRIDContractClient};
+
+#[test]
+fn test_default_claims_paid_from_premiums() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.grant_role(&TokenRole::Oracle, &oracle);
+    energy.grant_role(&TokenRole::Auditor, &auditor);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&generator, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let pool_id = env.register(InsuranceContract, (energy_id.clone(),));
+    let pool = InsuranceContractClient::new(&env, &pool_id);
+    energy.set_insurance(&Some(InsuranceConfig {
+        pool: pool_id.clone(),
+        premium_bps: 1_000,
+    }));
+
+    // One forward is delivered, funding the pool; the other defaults
+    let delivered = energy.sell_forward(
+        &generator, &buyer, &100u64, &10u64, &3_600u64, &usdc, &1_000i128, &100i128,
+    );
+    let defaulted = energy.sell_forward(
+        &generator, &buyer, &40u64, &10u64, &3_600u64, &usdc, &400i128, &50i128,
+    );
+    assert_eq!(
+        pool.try_file_claim(&buyer, &Coverage::Forward(defaulted)),
+        Err(Ok(InsuranceError::NotDefaulted.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    energy.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    energy.deliver_forward(&delivered);
+    assert_eq!(pool.reserve(&usdc), 100);
+    assert_eq!(usdc_client.balance(&generator), 10_000 - 150 + 900 + 100);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    energy.claim_forward_default(&defaulted);
+    energy.publish_pld(&oracle, &0u64, &usdc, &2i128);
+
+    assert_eq!(
+        pool.try_file_claim(&generator, &Coverage::Forward(defaulted)),
+        Err(Ok(InsuranceError::NotClaimant.into()))
+    );
+    let claim_id = pool.file_claim(&buyer, &Coverage::Forward(defaulted));
+    let claim = pool.get_claim(&claim_id);
+    assert_eq!((claim.shortfall_kwh, claim.amount), (40, 80));
+    assert_eq!(
+        pool.try_file_claim(&buyer, &Coverage::Forward(defaulted)),
+        Err(Ok(InsuranceError::AlreadyClaimed.into()))
+    );
+
+    // Only the token's auditors adjudicate
+    assert_eq!(
+        pool.try_approve_claim(&buyer, &claim_id),
+        Err(Ok(InsuranceError::NotAuditor.into()))
+    );
+    let before = usdc_client.balance(&buyer);
+    pool.approve_claim(&auditor, &claim_id);
+    assert_eq!(usdc_client.balance(&buyer), before + 80);
+    assert_eq!(pool.reserve(&usdc), 20);
+    assert_eq!(pool.get_claim(&claim_id).status, ClaimStatus::Paid);
+    assert_eq!(
+        pool.try_reject_claim(&auditor, &claim_id),
+        Err(Ok(InsuranceError::ClaimClosed.into()))
+    );
+}
+
+#[test]
+fn test_day_ahead_shortfall_claim_rejected_or_underfunded() {
+    let env = Env::default();
+    let fixture = strgrid_token::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let energy = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let buyer = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    energy.grant_role(&TokenRole::Auditor, &auditor);
+    energy.grant_role(&TokenRole::Oracle, &oracle);
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &1_000i128);
+
+    let pool_id = env.register(InsuranceContract, (fixture.contract_id.clone(),));
+    let pool = InsuranceContractClient::new(&env, &pool_id);
+
+    // Commit 50 kWh for tomorrow's first interval and deliver nothing
+    let day = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+    let offer = energy.offer_day_ahead(&generator, &day, &50u64, &usdc, &3i128);
+    let first = energy.accept_day_ahead(&buyer, &offer, &20u64);
+    let second = energy.accept_day_ahead(&buyer, &offer, &30u64);
+    env.ledger().with_mut(|li| li.timestamp = (day + 1) * SETTLEMENT_INTERVAL_SECONDS);
+    energy.settle_day_ahead(&first);
+    energy.settle_day_ahead(&second);
+    energy.publish_pld(&oracle, &day, &usdc, &5i128);
+
+    let rejected = pool.file_claim(&buyer, &Coverage::DayAhead(first));
+    assert_eq!(pool.get_claim(&rejected).amount, 100);
+    pool.reject_claim(&auditor, &rejected);
+    assert_eq!(pool.get_claim(&rejected).status, ClaimStatus::Rejected);
+    assert_eq!(
+        pool.try_file_claim(&buyer, &Coverage::DayAhead(first)),
+        Err(Ok(InsuranceError::AlreadyClaimed.into()))
+    );
+
+    // Nothing was ever delivered, so the pool holds no premiums to pay with
+    let unfunded = pool.file_claim(&buyer, &Coverage::DayAhead(second));
+    assert_eq!(
+        pool.try_approve_claim(&auditor, &unfunded),
+        Err(Ok(InsuranceError::InsufficientReserve.into()))
+    );
+}
@@ -0,0 +1,456 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+mod orderbook;
+
+pub use orderbook::{Order, Side};
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const LISTING: Symbol = symbol_short!("LISTING");
+const LISTING_SEQ: Symbol = symbol_short!("LISTSEQ");
+const ACTIVE_LISTINGS: Symbol = symbol_short!("ACTIVE");
+const ROYALTY: Symbol = symbol_short!("ROYALTY");
+const ROYALTY_DUE: Symbol = symbol_short!("ROYDUE");
+const PAYOUT: Symbol = symbol_short!("PAYOUT");
+const ACQUIRED: Symbol = symbol_short!("ACQUIRED");
+const ACQUIRED_TOTAL: Symbol = symbol_short!("ACQTOTAL");
+
+// Tópicos dos eventos publicados pelo marketplace
+const LISTED: Symbol = symbol_short!("listed");
+const CANCELLED: Symbol = symbol_short!("cancelled");
+const PURCHASED: Symbol = symbol_short!("purchased");
+const ROYALTY_SET: Symbol = symbol_short!("royalty");
+const ROYALTY_CLAIMED: Symbol = symbol_short!("roy_claim");
+const PAYOUT_SET: Symbol = symbol_short!("payout");
+
+/// Tamanho máximo de página retornado por `browse`
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Royalty máximo que um gerador pode cobrar, em basis points
+pub const MAX_ROYALTY_BPS: u32 = 2_000;
+
+/// Denominador dos basis points (100% = 10.000 bps)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Oferta de kWh em custódia do marketplace, vendida a um preço por kWh no ativo de pagamento
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub id: u64,
+    pub seller: Address,
+    /// Gerador que emitiu o lote revendido
+    pub origin: Address,
+    pub payment_token: Address,
+    pub price_per_kwh: i128,
+    pub amount_kwh: u64,
+    pub remaining_kwh: u64,
+    pub is_active: bool,
+    pub created_at: u64,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MarketError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    InvalidPrice = 4,
+    ListingNotFound = 5,
+    ListingInactive = 6,
+    InsufficientListing = 7,
+    SelfPurchase = 8,
+    OrderNotFound = 9,
+    OrderClosed = 10,
+    InvalidRoyalty = 11,
+    NothingToClaim = 12,
+    ConsumerNotRegistered = 13,
+    UnknownProvenance = 14,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, MarketError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+/// Transfere kWh de `from` para a custódia do marketplace e devolve o que de fato entrou,
+/// já descontadas a taxa de transferência e as perdas cobradas pelo token
+pub(crate) fn deposit_energy(env: &Env, from: &Address, amount_kwh: u64) -> u64 {
+    let energy = energy_token(env);
+    let custody = env.current_contract_address();
+    let before = energy.balance_of(&custody);
+    energy.transfer(from, &custody, &i128::from(amount_kwh));
+    let received = (energy.balance_of(&custody) - before) as u64;
+    if received == 0 {
+        panic_with_error!(env, MarketError::InvalidAmount);
+    }
+    received
+}
+
+/// Transfere kWh da custódia para `to` e devolve o que ele de fato recebeu
+fn deliver_energy(env: &Env, to: &Address, amount_kwh: u64) -> u64 {
+    let energy = energy_token(env);
+    let before = energy.balance_of(to);
+    energy.transfer(&env.current_contract_address(), to, &i128::from(amount_kwh));
+    (energy.balance_of(to) - before) as u64
+}
+
+/// kWh que `holder` comprou aqui de ofertas com origem em `origin` e ainda não revendeu
+fn acquired(env: &Env, holder: &Address, origin: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(ACQUIRED, holder.clone(), origin.clone()))
+        .unwrap_or(0)
+}
+
+/// Total comprado aqui por `holder`, de qualquer origem, ainda não revendido
+fn acquired_total(env: &Env, holder: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(ACQUIRED_TOTAL, holder.clone()))
+        .unwrap_or(0)
+}
+
+fn write_acquired(env: &Env, holder: &Address, origin: &Address, amount: u64, total: u64) {
+    env.storage()
+        .persistent()
+        .set(&(ACQUIRED, holder.clone(), origin.clone()), &amount);
+    env.storage()
+        .persistent()
+        .set(&(ACQUIRED_TOTAL, holder.clone()), &total);
+}
+
+/// Registra a origem de kWh recebidos de uma oferta
+fn credit_acquired(env: &Env, holder: &Address, origin: &Address, amount: u64) {
+    write_acquired(
+        env,
+        holder,
+        origin,
+        acquired(env, holder, origin) + amount,
+        acquired_total(env, holder) + amount,
+    );
+}
+
+/// Confere que o vendedor tem `amount` kWh vindos de `origin`, para que o royalty vá ao gerador
+/// real; revendas consomem o que ele comprou aqui dessa origem
+///
+/// O saldo é fungível, então a origem de uma revenda é a registrada nas compras feitas aqui; o
+/// próprio gerador só vende como primária o saldo que não comprou no marketplace.
+fn take_provenance(env: &Env, seller: &Address, origin: &Address, amount: u64) {
+    let total = acquired_total(env, seller);
+    if seller == origin {
+        let balance = energy_token(env).balance_of(seller);
+        if balance - (total as i128) < amount as i128 {
+            panic_with_error!(env, MarketError::UnknownProvenance);
+        }
+        return;
+    }
+    let from_origin = acquired(env, seller, origin);
+    if from_origin < amount {
+        panic_with_error!(env, MarketError::UnknownProvenance);
+    }
+    write_acquired(env, seller, origin, from_origin - amount, total - amount);
+}
+
+/// Recusa compradores não cadastrados quando o token de energia exige cadastro de consumidor
+fn require_eligible_buyer(env: &Env, buyer: &Address) {
+    if !energy_token(env).is_eligible_consumer(buyer) {
+        panic_with_error!(env, MarketError::ConsumerNotRegistered);
+    }
+}
+
+/// Preço por kWh ajustado pelo multiplicador de congestionamento vigente na zona
+fn congested_price(env: &Env, zone: u32, price: i128) -> i128 {
+    let multiplier = energy_token(env).congestion_multiplier(&zone);
+    price
+        .checked_mul(multiplier as i128)
+        .unwrap_or_else(|| panic_with_error!(env, MarketError::InvalidPrice))
+        / BPS_DENOMINATOR as i128
+}
+
+/// IDs das ofertas ativas, em ordem crescente (de criação)
+fn active_listings(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&ACTIVE_LISTINGS)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Grava a oferta e mantém o índice de ativas em dia com `is_active`
+fn save_listing(env: &Env, listing: &Listing) {
+    env.storage().persistent().set(&(LISTING, listing.id), listing);
+
+    let mut ids = active_listings(env);
+    match (ids.binary_search(listing.id), listing.is_active) {
+        (Err(position), true) => ids.insert(position, listing.id),
+        (Ok(position), false) => {
+            ids.remove(position);
+        }
+        _ => return,
+    }
+    env.storage().persistent().set(&ACTIVE_LISTINGS, &ids);
+}
+
+fn royalty_bps(env: &Env, generator: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(ROYALTY, generator.clone()))
+        .unwrap_or(0)
+}
+
+fn royalties_due(env: &Env, generator: &Address, payment_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ROYALTY_DUE, generator.clone(), payment_token.clone()))
+        .unwrap_or(0)
+}
+
+/// Endereço que recebe os recebimentos de uma conta: ela própria ou o destino que configurou
+fn payout_of(env: &Env, account: &Address) -> Address {
+    env.storage()
+        .persistent()
+        .get(&(PAYOUT, account.clone()))
+        .unwrap_or(account.clone())
+}
+
+/// Royalty devido ao gerador de origem; a venda primária pelo próprio gerador não paga royalty
+fn royalty_for(env: &Env, listing: &Listing, payment: i128) -> i128 {
+    if listing.seller == listing.origin {
+        return 0;
+    }
+    payment
+        .checked_mul(royalty_bps(env, &listing.origin) as i128)
+        .unwrap_or_else(|| panic_with_error!(env, MarketError::InvalidPrice))
+        / BPS_DENOMINATOR as i128
+}
+
+fn accrue_royalty(env: &Env, generator: &Address, payment_token: &Address, amount: i128) {
+    let due = royalties_due(env, generator, payment_token) + amount;
+    env.storage()
+        .persistent()
+        .set(&(ROYALTY_DUE, generator.clone(), payment_token.clone()), &due);
+}
+
+#[contract]
+pub struct MarketplaceContract;
+
+#[contractimpl]
+impl MarketplaceContract {
+    /// Configura no deploy o marketplace com o contrato de token de energia negociado
+    pub fn __constructor(env: Env, energy_token: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+    }
+
+    /// Consulta o contrato de token de energia negociado
+    pub fn energy_token(env: Env) -> Address {
+        energy_token(&env).address
+    }
+
+    /// Cria uma oferta de kWh do lote `token_id`, transferindo-os para custódia do marketplace
+    pub fn list(
+        env: Env,
+        seller: Address,
+        token_id: u64,
+        amount_kwh: u64,
+        payment_token: Address,
+        price_per_kwh: i128,
+    ) -> u64 {
+        seller.require_auth();
+        if amount_kwh == 0 {
+            panic_with_error!(&env, MarketError::InvalidAmount);
+        }
+        if price_per_kwh <= 0 {
+            panic_with_error!(&env, MarketError::InvalidPrice);
+        }
+
+        let origin = energy_token(&env).generator_of(&token_id);
+        take_provenance(&env, &seller, &origin, amount_kwh);
+        // A oferta vale só o que chegou à custódia; com taxa, isso é menos que `amount_kwh`
+        let amount_kwh = deposit_energy(&env, &seller, amount_kwh);
+
+        let id: u64 = env.storage().instance().get(&LISTING_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&LISTING_SEQ, &id);
+        let listing = Listing {
+            id,
+            seller: seller.clone(),
+            origin,
+            payment_token,
+            price_per_kwh,
+            amount_kwh,
+            remaining_kwh: amount_kwh,
+            is_active: true,
+            created_at: env.ledger().timestamp(),
+        };
+        save_listing(&env, &listing);
+
+        env.events()
+            .publish((LISTED, id, seller), (amount_kwh, price_per_kwh));
+        id
+    }
+
+    /// Cancela uma oferta, devolvendo ao vendedor os kWh não vendidos
+    pub fn cancel(env: Env, listing_id: u64) {
+        let mut listing = Self::get_listing(env.clone(), listing_id);
+        listing.seller.require_auth();
+        if !listing.is_active {
+            panic_with_error!(&env, MarketError::ListingInactive);
+        }
+
+        let remaining = listing.remaining_kwh;
+        listing.remaining_kwh = 0;
+        listing.is_active = false;
+        save_listing(&env, &listing);
+
+        if remaining > 0 {
+            let returned = deliver_energy(&env, &listing.seller, remaining);
+            if listing.seller != listing.origin {
+                credit_acquired(&env, &listing.seller, &listing.origin, returned);
+            }
+        }
+        env.events().publish((CANCELLED, listing_id), remaining);
+    }
+
+    /// Compra `amount_kwh` de uma oferta: pagamento e entrega na mesma invocação
+    ///
+    /// Se a zona do gerador de origem estiver congestionada, o preço da oferta é multiplicado
+    /// pelo fator vigente.
+    pub fn buy(env: Env, buyer: Address, listing_id: u64, amount_kwh: u64) -> i128 {
+        buyer.require_auth();
+        if amount_kwh == 0 {
+            panic_with_error!(&env, MarketError::InvalidAmount);
+        }
+        let mut listing = Self::get_listing(env.clone(), listing_id);
+        if !listing.is_active {
+            panic_with_error!(&env, MarketError::ListingInactive);
+        }
+        if buyer == listing.seller {
+            panic_with_error!(&env, MarketError::SelfPurchase);
+        }
+        if amount_kwh > listing.remaining_kwh {
+            panic_with_error!(&env, MarketError::InsufficientListing);
+        }
+        require_eligible_buyer(&env, &buyer);
+        let price_per_kwh = match energy_token(&env).zone_of(&listing.origin) {
+            Some(zone) => congested_price(&env, zone, listing.price_per_kwh),
+            None => listing.price_per_kwh,
+        };
+        let payment = price_per_kwh
+            .checked_mul(amount_kwh as i128)
+            .unwrap_or_else(|| panic_with_error!(&env, MarketError::InvalidPrice));
+
+        listing.remaining_kwh -= amount_kwh;
+        listing.is_active = listing.remaining_kwh > 0;
+        save_listing(&env, &listing);
+
+        let payment_client = token::Client::new(&env, &listing.payment_token);
+        let royalty = royalty_for(&env, &listing, payment);
+        if royalty > 0 {
+            payment_client.transfer(&buyer, &env.current_contract_address(), &royalty);
+            accrue_royalty(&env, &listing.origin, &listing.payment_token, royalty);
+        }
+        payment_client.transfer(&buyer, &payout_of(&env, &listing.seller), &(payment - royalty));
+        let delivered = deliver_energy(&env, &buyer, amount_kwh);
+        credit_acquired(&env, &buyer, &listing.origin, delivered);
+
+        env.events()
+            .publish((PURCHASED, listing_id, buyer), (amount_kwh, payment));
+        payment
+    }
+
+    /// Define o royalty do gerador sobre revendas de sua energia, em basis points
+    pub fn set_royalty(env: Env, generator: Address, royalty_bps: u32) {
+        generator.require_auth();
+        if royalty_bps > MAX_ROYALTY_BPS {
+            panic_with_error!(&env, MarketError::InvalidRoyalty);
+        }
+        env.storage()
+            .persistent()
+            .set(&(ROYALTY, generator.clone()), &royalty_bps);
+        env.events().publish((ROYALTY_SET, generator), royalty_bps);
+    }
+
+    /// Consulta o royalty de um gerador, em basis points
+    pub fn royalty_of(env: Env, generator: Address) -> u32 {
+        royalty_bps(&env, &generator)
+    }
+
+    /// Royalties acumulados por um gerador em um ativo de pagamento
+    pub fn royalties_due(env: Env, generator: Address, payment_token: Address) -> i128 {
+        royalties_due(&env, &generator, &payment_token)
+    }
+
+    /// Saca os royalties acumulados em um ativo de pagamento
+    pub fn claim_royalties(env: Env, generator: Address, payment_token: Address) -> i128 {
+        generator.require_auth();
+        let due = royalties_due(&env, &generator, &payment_token);
+        if due == 0 {
+            panic_with_error!(&env, MarketError::NothingToClaim);
+        }
+        env.storage()
+            .persistent()
+            .remove(&(ROYALTY_DUE, generator.clone(), payment_token.clone()));
+        token::Client::new(&env, &payment_token).transfer(
+            &env.current_contract_address(),
+            &payout_of(&env, &generator),
+            &due,
+        );
+        env.events()
+            .publish((ROYALTY_CLAIMED, generator, payment_token), due);
+        due
+    }
+
+    /// Direciona vendas e royalties da conta para outro endereço, como a tesouraria de uma cooperativa
+    pub fn set_payout(env: Env, account: Address, recipient: Address) {
+        account.require_auth();
+        if recipient == account {
+            env.storage().persistent().remove(&(PAYOUT, account.clone()));
+        } else {
+            env.storage()
+                .persistent()
+                .set(&(PAYOUT, account.clone()), &recipient);
+        }
+        env.events().publish((PAYOUT_SET, account), recipient);
+    }
+
+    /// Endereço que recebe vendas e royalties de uma conta
+    pub fn payout_of(env: Env, account: Address) -> Address {
+        payout_of(&env, &account)
+    }
+
+    /// Consulta uma oferta
+    pub fn get_listing(env: Env, listing_id: u64) -> Listing {
+        env.storage()
+            .persistent()
+            .get(&(LISTING, listing_id))
+            .unwrap_or_else(|| panic_with_error!(&env, MarketError::ListingNotFound))
+    }
+
+    /// Lista ofertas ativas em ordem de criação, paginado por offset/limit
+    ///
+    /// Percorre só o índice de ofertas ativas, então o custo não cresce com o histórico.
+    pub fn browse(env: Env, offset: u32, limit: u32) -> Vec<Listing> {
+        let ids = active_listings(&env);
+        let end = ids.len().min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut result = Vec::new(&env);
+        for position in offset..end {
+            result.push_back(Self::get_listing(env.clone(), ids.get_unchecked(position)));
+        }
+        result
+    }
+
+    /// Número de ofertas ativas
+    pub fn active_listing_count(env: Env) -> u32 {
+        active_listings(&env).len()
+    }
+}
+
+mod test;
@@ -0,0 +1,291 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, symbol_short, token, Address, Env, Symbol, Vec,
+};
+
+use crate::{
+    congested_price, deposit_energy, energy_token, payout_of, require_eligible_buyer, MarketError,
+    MarketplaceContract, MarketplaceContractArgs, MarketplaceContractClient,
+};
+
+const ORDER: Symbol = symbol_short!("ORDER");
+const ORDER_SEQ: Symbol = symbol_short!("ORDSEQ");
+const LEVELS: Symbol = symbol_short!("LEVELS");
+const QUEUE: Symbol = symbol_short!("QUEUE");
+
+const ORDER_PLACED: Symbol = symbol_short!("order");
+const ORDER_FILLED: Symbol = symbol_short!("fill");
+const ORDER_CANCELLED: Symbol = symbol_short!("ord_cncl");
+
+/// Lado de uma ordem limitada
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Bid,
+    Ask,
+}
+
+/// Ordem limitada; bids custodiam pagamento e asks custodiam kWh
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Order {
+    pub id: u64,
+    pub owner: Address,
+    pub side: Side,
+    pub zone: u32,
+    pub payment_token: Address,
+    pub price: i128,
+    pub amount_kwh: u64,
+    pub remaining_kwh: u64,
+    pub is_open: bool,
+}
+
+fn load(env: &Env, order_id: u64) -> Order {
+    env.storage()
+        .persistent()
+        .get(&(ORDER, order_id))
+        .unwrap_or_else(|| panic_with_error!(env, MarketError::OrderNotFound))
+}
+
+fn save(env: &Env, order: &Order) {
+    env.storage().persistent().set(&(ORDER, order.id), order);
+}
+
+/// Níveis de preço com ordens abertas, em ordem crescente
+fn levels(env: &Env, side: Side, zone: u32, payment_token: &Address) -> Vec<i128> {
+    env.storage()
+        .persistent()
+        .get(&(LEVELS, side, zone, payment_token.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Fila FIFO de ordens em um nível de preço
+fn queue(env: &Env, side: Side, zone: u32, payment_token: &Address, price: i128) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(QUEUE, side, zone, payment_token.clone(), price))
+        .unwrap_or(Vec::new(env))
+}
+
+fn enqueue(env: &Env, order: &Order) {
+    let levels_key = (LEVELS, order.side, order.zone, order.payment_token.clone());
+    let mut prices = levels(env, order.side, order.zone, &order.payment_token);
+    if let Err(position) = prices.binary_search(order.price) {
+        prices.insert(position, order.price);
+        env.storage().persistent().set(&levels_key, &prices);
+    }
+
+    let queue_key = (QUEUE, order.side, order.zone, order.payment_token.clone(), order.price);
+    let mut ids = queue(env, order.side, order.zone, &order.payment_token, order.price);
+    ids.push_back(order.id);
+    env.storage().persistent().set(&queue_key, &ids);
+}
+
+/// Retira a ordem de sua fila, removendo o nível de preço se ficar vazio
+fn dequeue(env: &Env, order: &Order) {
+    let queue_key = (QUEUE, order.side, order.zone, order.payment_token.clone(), order.price);
+    let mut ids = queue(env, order.side, order.zone, &order.payment_token, order.price);
+    if let Some(position) = ids.first_index_of(order.id) {
+        ids.remove(position);
+    }
+    if !ids.is_empty() {
+        env.storage().persistent().set(&queue_key, &ids);
+        return;
+    }
+    env.storage().persistent().remove(&queue_key);
+
+    let levels_key = (LEVELS, order.side, order.zone, order.payment_token.clone());
+    let mut prices = levels(env, order.side, order.zone, &order.payment_token);
+    if let Ok(position) = prices.binary_search(order.price) {
+        prices.remove(position);
+    }
+    env.storage().persistent().set(&levels_key, &prices);
+}
+
+/// Melhor ordem do lado: maior bid ou menor ask, por ordem de chegada no nível
+fn best(env: &Env, side: Side, zone: u32, payment_token: &Address) -> Option<Order> {
+    let prices = levels(env, side, zone, payment_token);
+    let price = match side {
+        Side::Bid => prices.last()?,
+        Side::Ask => prices.first()?,
+    };
+    let id = queue(env, side, zone, payment_token, price).first()?;
+    Some(load(env, id))
+}
+
+fn cost(env: &Env, price: i128, amount_kwh: u64) -> i128 {
+    price
+        .checked_mul(amount_kwh as i128)
+        .unwrap_or_else(|| panic_with_error!(env, MarketError::InvalidPrice))
+}
+
+fn place(
+    env: &Env,
+    owner: Address,
+    side: Side,
+    zone: u32,
+    payment_token: Address,
+    price: i128,
+    amount_kwh: u64,
+) -> u64 {
+    owner.require_auth();
+    if amount_kwh == 0 {
+        panic_with_error!(env, MarketError::InvalidAmount);
+    }
+    if price <= 0 {
+        panic_with_error!(env, MarketError::InvalidPrice);
+    }
+
+    if side == Side::Bid {
+        require_eligible_buyer(env, &owner);
+    }
+
+    // Asks ficam com o que chegou à custódia, já descontada a taxa do token
+    let amount_kwh = match side {
+        Side::Bid => {
+            token::Client::new(env, &payment_token).transfer(
+                &owner,
+                &env.current_contract_address(),
+                &cost(env, price, amount_kwh),
+            );
+            amount_kwh
+        }
+        Side::Ask => deposit_energy(env, &owner, amount_kwh),
+    };
+
+    let id: u64 = env.storage().instance().get(&ORDER_SEQ).unwrap_or(0) + 1;
+    env.storage().instance().set(&ORDER_SEQ, &id);
+    let order = Order {
+        id,
+        owner: owner.clone(),
+        side,
+        zone,
+        payment_token,
+        price,
+        amount_kwh,
+        remaining_kwh: amount_kwh,
+        is_open: true,
+    };
+    save(env, &order);
+    enqueue(env, &order);
+
+    env.events()
+        .publish((ORDER_PLACED, id, owner), (side, zone, price, amount_kwh));
+    id
+}
+
+#[contractimpl]
+impl MarketplaceContract {
+    /// Ordem de compra limitada: custodia `price * amount_kwh` do ativo de pagamento
+    pub fn place_bid(
+        env: Env,
+        buyer: Address,
+        zone: u32,
+        payment_token: Address,
+        price: i128,
+        amount_kwh: u64,
+    ) -> u64 {
+        place(&env, buyer, Side::Bid, zone, payment_token, price, amount_kwh)
+    }
+
+    /// Ordem de venda limitada: custodia `amount_kwh` do token de energia
+    pub fn place_ask(
+        env: Env,
+        seller: Address,
+        zone: u32,
+        payment_token: Address,
+        price: i128,
+        amount_kwh: u64,
+    ) -> u64 {
+        place(&env, seller, Side::Ask, zone, payment_token, price, amount_kwh)
+    }
+
+    /// Cancela uma ordem aberta, devolvendo a custódia restante
+    pub fn cancel_order(env: Env, order_id: u64) {
+        let mut order = load(&env, order_id);
+        order.owner.require_auth();
+        if !order.is_open {
+            panic_with_error!(&env, MarketError::OrderClosed);
+        }
+
+        dequeue(&env, &order);
+        let remaining = order.remaining_kwh;
+        order.remaining_kwh = 0;
+        order.is_open = false;
+        save(&env, &order);
+
+        if remaining > 0 {
+            match order.side {
+                Side::Bid => token::Client::new(&env, &order.payment_token).transfer(
+                    &env.current_contract_address(),
+                    &order.owner,
+                    &cost(&env, order.price, remaining),
+                ),
+                Side::Ask => energy_token(&env).transfer(
+                    &env.current_contract_address(),
+                    &order.owner,
+                    &i128::from(remaining),
+                ),
+            }
+        }
+        env.events().publish((ORDER_CANCELLED, order_id), remaining);
+    }
+
+    /// Cruza bids e asks de uma zona enquanto houver preço compatível, até `max_fills` execuções
+    ///
+    /// Cada execução usa o preço da ordem mais antiga (a que estava no livro), multiplicado pelo
+    /// fator de congestionamento da zona e limitado ao preço do bid; a diferença custodiada pelo
+    /// bid é devolvida ao comprador.
+    pub fn match_orders(env: Env, zone: u32, payment_token: Address, max_fills: u32) -> u32 {
+        let payment = token::Client::new(&env, &payment_token);
+        let energy = energy_token(&env);
+        let market = env.current_contract_address();
+
+        let mut fills = 0;
+        while fills < max_fills {
+            let (Some(mut bid), Some(mut ask)) = (
+                best(&env, Side::Bid, zone, &payment_token),
+                best(&env, Side::Ask, zone, &payment_token),
+            ) else {
+                break;
+            };
+            if bid.price < ask.price {
+                break;
+            }
+
+            let quantity = bid.remaining_kwh.min(ask.remaining_kwh);
+            let price = if bid.id < ask.id { bid.price } else { ask.price };
+            let price = congested_price(&env, zone, price).min(bid.price);
+
+            energy.transfer(&market, &bid.owner, &i128::from(quantity));
+            payment.transfer(&market, &payout_of(&env, &ask.owner), &cost(&env, price, quantity));
+            let refund = cost(&env, bid.price - price, quantity);
+            if refund > 0 {
+                payment.transfer(&market, &bid.owner, &refund);
+            }
+
+            for order in [&mut bid, &mut ask] {
+                order.remaining_kwh -= quantity;
+                if order.remaining_kwh == 0 {
+                    order.is_open = false;
+                    dequeue(&env, order);
+                }
+                save(&env, order);
+            }
+
+            env.events()
+                .publish((ORDER_FILLED, bid.id, ask.id), (quantity, price));
+            fills += 1;
+        }
+        fills
+    }
+
+    /// Consulta uma ordem
+    pub fn get_order(env: Env, order_id: u64) -> Order {
+        load(&env, order_id)
+    }
+
+    /// Níveis de preço com ordens abertas de um lado do livro, em ordem crescente
+    pub fn price_levels(env: Env, side: Side, zone: u32, payment_token: Address) -> Vec<i128> {
+        levels(&env, side, zone, &payment_token)
+    }
+}
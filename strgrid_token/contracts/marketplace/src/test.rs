@@ -0,0 +1,446 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, Env,
+};
+use strgrid_token::{
+    testutils, ConsumerClass, ConsumerProfile, Role, STRGRIDContractClient, SourceType,
+};
+
+#[test]
+fn test_list_buy_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    // Energy token with a funded generator
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    // Payment asset (USDC SAC) funded for the buyer
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    // Listing escrows the kWh
+    let listing_id = market.list(&generator, &token_id, &300u64, &usdc, &20i128);
+    assert_eq!(energy.balance_of(&generator), 200);
+    assert_eq!(energy.balance_of(&market_id), 300);
+    assert_eq!(market.browse(&0u32, &10u32).len(), 1);
+    
+    // Partial fill: payment and delivery together
+    let paid = market.buy(&buyer, &listing_id, &100u64);
+    assert_eq!(paid, 2_000);
+    assert_eq!(usdc_client.balance(&generator), 2_000);
+    assert_eq!(usdc_client.balance(&buyer), 8_000);
+    assert_eq!(energy.balance_of(&buyer), 100);
+    assert_eq!(market.get_listing(&listing_id).remaining_kwh, 200);
+    assert_eq!(
+        market.try_buy(&buyer, &listing_id, &201u64),
+        Err(Ok(MarketError::InsufficientListing.into()))
+    );
+    assert_eq!(
+        market.try_buy(&generator, &listing_id, &1u64),
+        Err(Ok(MarketError::SelfPurchase.into()))
+    );
+    
+    // Cancel returns the unsold kWh
+    market.cancel(&listing_id);
+    assert_eq!(energy.balance_of(&generator), 400);
+    assert_eq!(energy.balance_of(&market_id), 0);
+    assert!(!market.get_listing(&listing_id).is_active);
+    assert_eq!(market.browse(&0u32, &10u32).len(), 0);
+    assert_eq!(
+        market.try_buy(&buyer, &listing_id, &1u64),
+        Err(Ok(MarketError::ListingInactive.into()))
+    );
+}
+
+#[test]
+fn test_browse_paginates_active_listings() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    let first = market.list(&generator, &token_id, &100u64, &usdc, &10i128);
+    let second = market.list(&generator, &token_id, &100u64, &usdc, &11i128);
+    let third = market.list(&generator, &token_id, &100u64, &usdc, &12i128);
+    market.cancel(&second);
+    assert_eq!(market.active_listing_count(), 2);
+    
+    let page = market.browse(&0u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, first);
+    let page = market.browse(&1u32, &10u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, third);
+    assert_eq!(market.browse(&5u32, &10u32).len(), 0);
+    
+    // A fully filled listing leaves the index; a partial fill keeps it
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    market.buy(&buyer, &first, &100u64);
+    market.buy(&buyer, &third, &50u64);
+    assert_eq!(market.active_listing_count(), 1);
+    let page = market.browse(&0u32, &10u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, third);
+}
+
+#[test]
+fn test_order_book_matching() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let late_buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&late_buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    // Asks at two price levels in zone 1; a bid in another zone never matches
+    let cheap_ask = market.place_ask(&generator, &1u32, &usdc, &10i128, &100u64);
+    let dear_ask = market.place_ask(&generator, &1u32, &usdc, &12i128, &100u64);
+    let other_zone = market.place_bid(&buyer, &2u32, &usdc, &20i128, &50u64);
+    assert_eq!(market.price_levels(&Side::Ask, &1u32, &usdc), vec![&env, 10i128, 12i128]);
+    assert_eq!(market.match_orders(&1u32, &usdc, &10u32), 0);
+    
+    // Bid at 11 crosses only the cheapest ask, at the resting ask price
+    let bid = market.place_bid(&late_buyer, &1u32, &usdc, &11i128, &150u64);
+    assert_eq!(market.match_orders(&1u32, &usdc, &10u32), 1);
+    assert_eq!(energy.balance_of(&late_buyer), 100);
+    assert_eq!(usdc_client.balance(&generator), 1_000);
+    // 150 * 11 escrowed, 100 * 10 paid, 100 * 1 refunded
+    assert_eq!(usdc_client.balance(&late_buyer), 10_000 - 1_650 + 100);
+    assert!(!market.get_order(&cheap_ask).is_open);
+    assert_eq!(market.get_order(&bid).remaining_kwh, 50);
+    assert_eq!(market.price_levels(&Side::Ask, &1u32, &usdc), vec![&env, 12i128]);
+    
+    // Cancelling returns the remaining escrow
+    market.cancel_order(&bid);
+    assert_eq!(usdc_client.balance(&late_buyer), 10_000 - 1_000);
+    market.cancel_order(&dear_ask);
+    market.cancel_order(&other_zone);
+    assert_eq!(energy.balance_of(&generator), 400);
+    assert_eq!(usdc_client.balance(&buyer), 10_000);
+    assert_eq!(
+        market.try_cancel_order(&bid),
+        Err(Ok(MarketError::OrderClosed.into()))
+    );
+}
+
+#[test]
+fn test_resale_royalty() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let reseller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&reseller, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    assert_eq!(
+        market.try_set_royalty(&generator, &(MAX_ROYALTY_BPS + 1)),
+        Err(Ok(MarketError::InvalidRoyalty.into()))
+    );
+    market.set_royalty(&generator, &500u32);
+    assert_eq!(market.royalty_of(&generator), 500);
+    
+    // Primary sale by the generator pays no royalty
+    let primary = market.list(&generator, &token_id, &200u64, &usdc, &10i128);
+    market.buy(&reseller, &primary, &200u64);
+    assert_eq!(usdc_client.balance(&generator), 2_000);
+    assert_eq!(market.royalties_due(&generator, &usdc), 0);
+    
+    // Resale pays 5% of the price back to the originating generator
+    let resale = market.list(&reseller, &token_id, &200u64, &usdc, &20i128);
+    assert_eq!(market.get_listing(&resale).origin, generator);
+    market.buy(&buyer, &resale, &100u64);
+    assert_eq!(usdc_client.balance(&reseller), 10_000 - 2_000 + 1_900);
+    assert_eq!(market.royalties_due(&generator, &usdc), 100);
+    
+    // Royalties follow the generator's payout address, e.g. a co-op treasury
+    let coop = Address::generate(&env);
+    market.set_payout(&generator, &coop);
+    assert_eq!(market.payout_of(&generator), coop);
+    assert_eq!(market.claim_royalties(&generator, &usdc), 100);
+    assert_eq!(usdc_client.balance(&coop), 100);
+    assert_eq!(usdc_client.balance(&market_id), 0);
+    
+    // Sale proceeds too
+    let second = market.list(&generator, &token_id, &100u64, &usdc, &10i128);
+    market.buy(&buyer, &second, &100u64);
+    assert_eq!(usdc_client.balance(&coop), 1_100);
+    assert_eq!(usdc_client.balance(&generator), 2_000);
+    assert_eq!(
+        market.try_claim_royalties(&generator, &usdc),
+        Err(Ok(MarketError::NothingToClaim.into()))
+    );
+}
+
+#[test]
+fn test_purchases_require_registered_consumer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    energy.set_registration_required(&true);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    let listing_id = market.list(&generator, &token_id, &100u64, &usdc, &10i128);
+    
+    assert_eq!(
+        market.try_buy(&buyer, &listing_id, &10u64),
+        Err(Ok(MarketError::ConsumerNotRegistered.into()))
+    );
+    assert_eq!(
+        market.try_place_bid(&buyer, &1u32, &usdc, &10i128, &10u64),
+        Err(Ok(MarketError::ConsumerNotRegistered.into()))
+    );
+    
+    energy.register_consumer(&buyer, &ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: 1,
+        expected_monthly_kwh: 250,
+    });
+    market.buy(&buyer, &listing_id, &10u64);
+    assert_eq!(energy.balance_of(&buyer), 10);
+}
+
+#[test]
+fn test_congestion_multiplier_raises_clearing_prices() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.grant_role(&Role::GridOperator, &operator);
+    energy.register_generator(&generator, &1000u64);
+    energy.set_generator_profile(&generator, &SourceType::Solar, &1u32);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    // Zone 1 is congested at 1.5x until t = 1_000
+    energy.set_congestion(&operator, &1u32, &15_000u32, &1_000u64);
+    assert_eq!(energy.congestion_multiplier(&1u32), 15_000);
+    assert_eq!(energy.congestion_multiplier(&2u32), 10_000);
+    
+    // Listings from a congested zone clear at the multiplied price
+    let listing_id = market.list(&generator, &token_id, &100u64, &usdc, &10i128);
+    assert_eq!(market.buy(&buyer, &listing_id, &10u64), 150);
+    assert_eq!(usdc_client.balance(&generator), 150);
+    
+    // Order book fills are multiplied but never exceed the bid's limit
+    market.place_ask(&generator, &1u32, &usdc, &10i128, &20u64);
+    market.place_bid(&buyer, &1u32, &usdc, &12i128, &10u64);
+    assert_eq!(market.match_orders(&1u32, &usdc, &10u32), 1);
+    assert_eq!(usdc_client.balance(&generator), 150 + 120);
+    market.place_bid(&buyer, &1u32, &usdc, &20i128, &10u64);
+    assert_eq!(market.match_orders(&1u32, &usdc, &10u32), 1);
+    assert_eq!(usdc_client.balance(&generator), 150 + 120 + 150);
+    assert_eq!(usdc_client.balance(&buyer), 10_000 - 150 - 120 - 150);
+    
+    // Once the window ends prices are back to normal
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    assert_eq!(energy.congestion_multiplier(&1u32), 10_000);
+    assert_eq!(market.buy(&buyer, &listing_id, &10u64), 100);
+}
+
+#[test]
+fn test_listing_records_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let other_seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&seller, &1000u64);
+    energy.register_generator(&other_seller, &1000u64);
+    let token_id = energy.mint_energy_tokens(&seller, &500i128, &24u64, &None);
+    let other_id = energy.mint_energy_tokens(&other_seller, &500i128, &24u64, &None);
+    energy.set_transfer_fee(&100u32, &treasury);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &100_000i128);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    // A 1% fee leaves 297 of the 300 kWh in custody, and that is what the listing offers
+    let listing_id = market.list(&seller, &token_id, &300u64, &usdc, &20i128);
+    let other_listing = market.list(&other_seller, &other_id, &300u64, &usdc, &20i128);
+    let listing = market.get_listing(&listing_id);
+    assert_eq!((listing.amount_kwh, listing.remaining_kwh), (297, 297));
+    assert_eq!(energy.balance_of(&market_id), 594);
+    
+    // Taking the whole listing is paid only out of its own escrow
+    assert_eq!(
+        market.try_buy(&buyer, &listing_id, &300u64),
+        Err(Ok(MarketError::InsufficientListing.into()))
+    );
+    market.buy(&buyer, &listing_id, &297u64);
+    assert_eq!(energy.balance_of(&market_id), 297);
+    assert_eq!(market.get_listing(&other_listing).remaining_kwh, 297);
+}
+
+#[test]
+fn test_ask_records_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    
+    // The ask rests with the 99 kWh left after the 1% fee
+    let ask = market.place_ask(&generator, &1u32, &usdc, &10i128, &100u64);
+    assert_eq!(market.get_order(&ask).remaining_kwh, 99);
+    
+    // A bid for more fills only what is in custody
+    let bid = market.place_bid(&buyer, &1u32, &usdc, &10i128, &100u64);
+    assert_eq!(market.match_orders(&1u32, &usdc, &10u32), 1);
+    assert!(!market.get_order(&ask).is_open);
+    assert_eq!(market.get_order(&bid).remaining_kwh, 1);
+    assert_eq!(energy.balance_of(&market_id), 0);
+}
+
+#[test]
+fn test_resale_must_name_the_lot_it_was_bought_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let free_generator = Address::generate(&env);
+    let reseller = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.register_generator(&free_generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    let free_id = energy.mint_energy_tokens(&free_generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&reseller, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&free_generator, &10_000i128);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    market.set_royalty(&generator, &500u32);
+    
+    let primary = market.list(&generator, &token_id, &300u64, &usdc, &10i128);
+    market.buy(&reseller, &primary, &100u64);
+    
+    // Naming a royalty-free lot does not dodge the royalty
+    assert_eq!(
+        market.try_list(&reseller, &free_id, &100u64, &usdc, &20i128),
+        Err(Ok(MarketError::UnknownProvenance.into()))
+    );
+    // Nor can a reseller list more of the lot than it bought here
+    assert_eq!(
+        market.try_list(&reseller, &token_id, &101u64, &usdc, &20i128),
+        Err(Ok(MarketError::UnknownProvenance.into()))
+    );
+    
+    // A generator cannot pass bought kWh off as its own primary sale
+    market.buy(&free_generator, &primary, &200u64);
+    assert_eq!(
+        market.try_list(&free_generator, &free_id, &600u64, &usdc, &20i128),
+        Err(Ok(MarketError::UnknownProvenance.into()))
+    );
+    let own = market.list(&free_generator, &free_id, &500u64, &usdc, &20i128);
+    assert_eq!(market.get_listing(&own).origin, free_generator);
+    
+    // Cancelling a resale gives its provenance back
+    let resale = market.list(&reseller, &token_id, &100u64, &usdc, &20i128);
+    assert_eq!(market.get_listing(&resale).origin, generator);
+    market.cancel(&resale);
+    let resale = market.list(&reseller, &token_id, &100u64, &usdc, &20i128);
+    assert_eq!(market.get_listing(&resale).remaining_kwh, 100);
+}
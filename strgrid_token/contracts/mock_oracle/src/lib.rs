@@ -0,0 +1,81 @@
+#![no_std]
+//! Oracle simulado para testes de integração; não deve ser implantado em rede
+//!
+//! Devolve os preços configurados no formato `lastprice` da SEP-40 e "assina" leituras de
+//! medição arbitrárias: cada leitura recebe uma prova única que `verify_reading` reconhece,
+//! a mesma interface que o token consulta no mint quando um oracle está configurado.
+//! Nenhuma função exige autorização.
+
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol,
+};
+
+// Símbolos para armazenamento de dados
+const PRICE: Symbol = symbol_short!("PRICE");
+const READING: Symbol = symbol_short!("READING");
+const READING_SEQ: Symbol = symbol_short!("READSEQ");
+
+/// Preço de um ativo e o instante em que foi publicado
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Leitura de medição assinada pelo oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reading {
+    pub generator: Address,
+    pub amount_kwh: u64,
+}
+
+#[contract]
+pub struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    /// Define o preço corrente do ativo, com o timestamp do ledger atual
+    pub fn set_price(env: Env, asset: Address, price: i128) {
+        let data = PriceData {
+            price,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&(PRICE, asset), &data);
+    }
+
+    /// Último preço configurado para o ativo
+    pub fn lastprice(env: Env, asset: Address) -> Option<PriceData> {
+        env.storage().persistent().get(&(PRICE, asset))
+    }
+
+    /// Assina a leitura e devolve a prova a ser apresentada no mint
+    ///
+    /// Leituras iguais recebem provas diferentes, como medições sucessivas de um mesmo medidor.
+    pub fn sign_reading(env: Env, generator: Address, amount_kwh: u64) -> BytesN<32> {
+        let seq: u64 = env.storage().instance().get(&READING_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&READING_SEQ, &seq);
+
+        let reading = Reading {
+            generator,
+            amount_kwh,
+        };
+        let payload = (env.current_contract_address(), reading.clone(), seq).to_xdr(&env);
+        let proof: BytesN<32> = env.crypto().sha256(&payload).into();
+        env.storage().persistent().set(&(READING, proof.clone()), &reading);
+        proof
+    }
+
+    /// Confirma que `proof` foi emitida por `sign_reading` para esta leitura
+    pub fn verify_reading(env: Env, generator: Address, amount_kwh: u64, proof: BytesN<32>) -> bool {
+        let reading: Option<Reading> = env.storage().persistent().get(&(READING, proof));
+        reading
+            == Some(Reading {
+                generator,
+                amount_kwh,
+            })
+    }
+}
+
+mod test;
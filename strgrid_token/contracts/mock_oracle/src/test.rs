@@ -0,0 +1,27 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::{Address as _, Ledger}, Address, Env};
+
+#[test]
+fn test_prices_and_signed_readings() {
+    let env = Env::default();
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    let asset = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Prices are whatever the test configured, stamped with the ledger time
+    assert_eq!(oracle.lastprice(&asset), None);
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_000);
+    oracle.set_price(&asset, &25i128);
+    assert_eq!(oracle.lastprice(&asset), Some(PriceData { price: 25, timestamp: 1_000 }));
+    
+    // Each signature is unique and only vouches for the exact reading
+    let proof = oracle.sign_reading(&generator, &500u64);
+    assert_ne!(oracle.sign_reading(&generator, &500u64), proof);
+    assert!(oracle.verify_reading(&generator, &500u64, &proof));
+    assert!(!oracle.verify_reading(&generator, &501u64, &proof));
+    assert!(!oracle.verify_reading(&Address::generate(&env), &500u64, &proof));
+    assert!(!oracle.verify_reading(&generator, &500u64, &BytesN::from_array(&env, &[7u8; 32])));
+}
@@ -0,0 +1,66 @@
+#![no_std]
+//! Roteador de chamadas atômicas
+//!
+//! Executa em sequência várias operações do STRGRID (ou de contratos vizinhos, como o
+//! marketplace) na mesma transação: se qualquer chamada falhar, todas são revertidas. O chamador
+//! autoriza uma única vez a árvore de invocações que parte de `multicall`, p.ex. aprovar, comprar
+//! no marketplace e queimar os kWh comprados.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, symbol_short,
+    Address, Env, Symbol, Val, Vec,
+};
+
+// Tópicos dos eventos publicados pelo roteador
+const EXECUTED: Symbol = symbol_short!("multicall");
+
+/// Máximo de chamadas em um `multicall`
+pub const MAX_CALLS: u32 = 20;
+
+/// Invocação de `function` no contrato `contract` com os argumentos já codificados
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RouterError {
+    NoCalls = 1,
+    TooManyCalls = 2,
+}
+
+#[contract]
+pub struct RouterContract;
+
+#[contractimpl]
+impl RouterContract {
+    /// Executa as chamadas em ordem, atomicamente, e retorna o resultado de cada uma
+    ///
+    /// As autorizações exigidas pelas chamadas internas entram na árvore autorizada pelo
+    /// `caller`; o roteador não guarda estado nem custodia fundos.
+    pub fn multicall(env: Env, caller: Address, calls: Vec<ContractCall>) -> Vec<Val> {
+        caller.require_auth();
+        if calls.is_empty() {
+            panic_with_error!(&env, RouterError::NoCalls);
+        }
+        if calls.len() > MAX_CALLS {
+            panic_with_error!(&env, RouterError::TooManyCalls);
+        }
+
+        let mut results = Vec::new(&env);
+        for call in calls.iter() {
+            results.push_back(env.invoke_contract::<Val>(&call.contract, &call.function, call.args));
+        }
+
+        env.events().publish((EXECUTED, caller), calls.len());
+        results
+    }
+}
+
+mod test;
@@ -0,0 +1,88 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token, vec, Address, Env, IntoVal};
+use strgrid_marketplace::{MarketplaceContract, MarketplaceContractClient};
+use strgrid_token::{testutils, STRGRIDContractClient, STRGRIDError};
+
+#[test]
+fn test_buy_burn_and_approve_in_one_multicall() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let retailer = Address::generate(&env);
+    
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    let token_id = energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    let market_id = env.register(MarketplaceContract, (energy_id.clone(),));
+    let market = MarketplaceContractClient::new(&env, &market_id);
+    let listing_id = market.list(&generator, &token_id, &300u64, &usdc, &20i128);
+    
+    let router_id = env.register(RouterContract, ());
+    let router = RouterContractClient::new(&env, &router_id);
+    assert_eq!(
+        router.try_multicall(&buyer, &Vec::new(&env)),
+        Err(Ok(RouterError::NoCalls.into()))
+    );
+    
+    // Buy 100 kWh, consume 60 of them and let the retailer settle the rest
+    let calls = vec![
+        &env,
+        ContractCall {
+            contract: market_id.clone(),
+            function: Symbol::new(&env, "buy"),
+            args: (buyer.clone(), listing_id, 100u64).into_val(&env),
+        },
+        ContractCall {
+            contract: energy_id.clone(),
+            function: Symbol::new(&env, "burn_energy_tokens"),
+            args: (buyer.clone(), token_id, 60i128).into_val(&env),
+        },
+        ContractCall {
+            contract: energy_id.clone(),
+            function: Symbol::new(&env, "approve"),
+            args: (buyer.clone(), retailer.clone(), 40i128).into_val(&env),
+        },
+    ];
+    let results = router.multicall(&buyer, &calls);
+    assert_eq!(results.len(), 3);
+    let paid: i128 = results.get_unchecked(0).into_val(&env);
+    assert_eq!(paid, 2_000);
+    assert_eq!(usdc_client.balance(&buyer), 8_000);
+    assert_eq!(energy.balance_of(&buyer), 40);
+    assert_eq!(energy.allowance(&buyer, &retailer), 40);
+    
+    // A failing call reverts the ones before it
+    let token_id = energy.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    let listing_id = market.list(&generator, &token_id, &100u64, &usdc, &20i128);
+    let calls = vec![
+        &env,
+        ContractCall {
+            contract: market_id.clone(),
+            function: Symbol::new(&env, "buy"),
+            args: (buyer.clone(), listing_id, 50u64).into_val(&env),
+        },
+        ContractCall {
+            contract: energy_id.clone(),
+            function: Symbol::new(&env, "transfer"),
+            args: (buyer.clone(), retailer.clone(), 500i128).into_val(&env),
+        },
+    ];
+    assert_eq!(
+        router.try_multicall(&buyer, &calls),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    assert_eq!(usdc_client.balance(&buyer), 8_000);
+    assert_eq!(energy.balance_of(&buyer), 40);
+    assert_eq!(market.get_listing(&listing_id).remaining_kwh, 100);
+}
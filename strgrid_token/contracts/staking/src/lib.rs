@@ -0,0 +1,325 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, panic_with_error, symbol_short, token,
+    Address, Env, Symbol, Vec,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const POOL: Symbol = symbol_short!("POOL");
+const TOTAL_SHARES: Symbol = symbol_short!("TSHARES");
+const SHARES: Symbol = symbol_short!("SHARES");
+const REWARD_TOKENS: Symbol = symbol_short!("RWDTOKENS");
+const REWARD_INDEX: Symbol = symbol_short!("RWDINDEX");
+const PAID_INDEX: Symbol = symbol_short!("PAIDINDEX");
+const ACCRUED: Symbol = symbol_short!("ACCRUED");
+
+// Tópicos dos eventos publicados pelo staking
+const STAKED: Symbol = symbol_short!("staked");
+const UNSTAKED: Symbol = symbol_short!("unstaked");
+const REWARDED: Symbol = symbol_short!("rewarded");
+const CLAIMED: Symbol = symbol_short!("claimed");
+const BACKSTOP: Symbol = symbol_short!("backstop");
+
+/// Escala do índice de recompensa por cota
+pub const INDEX_PRECISION: i128 = 1_000_000_000_000;
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum StakingError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    InsufficientShares = 4,
+    NoStakers = 5,
+    NothingToClaim = 6,
+    InsufficientBackstop = 7,
+    PoolDrained = 8,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, StakingError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+/// Saldo do contrato em um ativo de recompensa (o STRGRID não expõe `balance` da SEP-41)
+fn held(env: &Env, reward_token: &Address) -> i128 {
+    let contract = env.current_contract_address();
+    let energy = energy_token(env);
+    if *reward_token == energy.address {
+        energy.balance_of(&contract)
+    } else {
+        token::Client::new(env, reward_token).balance(&contract)
+    }
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ADMIN)
+        .unwrap_or_else(|| panic_with_error!(env, StakingError::NotInitialized))
+}
+
+/// STRGRID custodiado, já descontadas as coberturas pagas pelo backstop
+fn pool(env: &Env) -> u64 {
+    env.storage().instance().get(&POOL).unwrap_or(0)
+}
+
+fn total_shares(env: &Env) -> u64 {
+    env.storage().instance().get(&TOTAL_SHARES).unwrap_or(0)
+}
+
+fn shares(env: &Env, staker: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(SHARES, staker.clone()))
+        .unwrap_or(0)
+}
+
+fn reward_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&REWARD_TOKENS)
+        .unwrap_or(Vec::new(env))
+}
+
+fn reward_index(env: &Env, reward_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(REWARD_INDEX, reward_token.clone()))
+        .unwrap_or(0)
+}
+
+fn paid_index(env: &Env, staker: &Address, reward_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(PAID_INDEX, staker.clone(), reward_token.clone()))
+        .unwrap_or(0)
+}
+
+fn accrued(env: &Env, staker: &Address, reward_token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ACCRUED, staker.clone(), reward_token.clone()))
+        .unwrap_or(0)
+}
+
+/// Recompensa de `reward_token` devida ao staker, incluindo a ainda não consolidada
+fn pending(env: &Env, staker: &Address, reward_token: &Address) -> i128 {
+    let delta = reward_index(env, reward_token) - paid_index(env, staker, reward_token);
+    accrued(env, staker, reward_token) + shares(env, staker) as i128 * delta / INDEX_PRECISION
+}
+
+/// Consolida as recompensas do staker antes de suas cotas mudarem
+fn checkpoint(env: &Env, staker: &Address) {
+    for reward_token in reward_tokens(env).iter() {
+        let owed = pending(env, staker, &reward_token);
+        env.storage()
+            .persistent()
+            .set(&(ACCRUED, staker.clone(), reward_token.clone()), &owed);
+        env.storage().persistent().set(
+            &(PAID_INDEX, staker.clone(), reward_token.clone()),
+            &reward_index(env, &reward_token),
+        );
+    }
+}
+
+fn set_shares(env: &Env, staker: &Address, staker_shares: u64, total: u64, pool_amount: u64) {
+    env.storage()
+        .persistent()
+        .set(&(SHARES, staker.clone()), &staker_shares);
+    env.storage().instance().set(&TOTAL_SHARES, &total);
+    env.storage().instance().set(&POOL, &pool_amount);
+}
+
+#[contract]
+pub struct StakingContract;
+
+#[contractimpl]
+impl StakingContract {
+    /// Configura no deploy o staking com o token de energia e o admin que aciona o backstop
+    pub fn __constructor(env: Env, energy_token: Address, admin: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&ADMIN, &admin);
+    }
+
+    /// Deposita STRGRID no pool e recebe cotas proporcionais ao valor atual do pool
+    pub fn stake(env: Env, staker: Address, amount: u64) -> u64 {
+        staker.require_auth();
+        if amount == 0 {
+            panic_with_error!(&env, StakingError::InvalidAmount);
+        }
+        checkpoint(&env, &staker);
+
+        // As cotas saem do que chegou ao pool, já descontada a taxa de transferência
+        let energy = energy_token(&env);
+        let contract = env.current_contract_address();
+        let before = energy.balance_of(&contract);
+        energy.transfer(&staker, &contract, &i128::from(amount));
+        let amount = (energy.balance_of(&contract) - before) as u64;
+
+        // Com o pool zerado por coberturas e cotas em aberto, cotas 1:1 diluiriam os antigos
+        let (pool_amount, total) = (pool(&env), total_shares(&env));
+        let minted = if total == 0 {
+            amount
+        } else if pool_amount == 0 {
+            panic_with_error!(&env, StakingError::PoolDrained)
+        } else {
+            (amount as u128 * total as u128 / pool_amount as u128) as u64
+        };
+        if minted == 0 {
+            panic_with_error!(&env, StakingError::InvalidAmount);
+        }
+        set_shares(
+            &env,
+            &staker,
+            shares(&env, &staker) + minted,
+            total + minted,
+            pool_amount + amount,
+        );
+
+        env.events().publish((STAKED, staker), (amount, minted));
+        minted
+    }
+
+    /// Resgata cotas pelo valor atual do pool, já descontadas eventuais coberturas
+    pub fn unstake(env: Env, staker: Address, share_amount: u64) -> u64 {
+        staker.require_auth();
+        let staker_shares = shares(&env, &staker);
+        if share_amount == 0 || share_amount > staker_shares {
+            panic_with_error!(&env, StakingError::InsufficientShares);
+        }
+        checkpoint(&env, &staker);
+
+        let (pool_amount, total) = (pool(&env), total_shares(&env));
+        let amount = (share_amount as u128 * pool_amount as u128 / total as u128) as u64;
+        set_shares(
+            &env,
+            &staker,
+            staker_shares - share_amount,
+            total - share_amount,
+            pool_amount - amount,
+        );
+        if amount > 0 {
+            energy_token(&env).transfer(
+                &env.current_contract_address(),
+                &staker,
+                &i128::from(amount),
+            );
+        }
+
+        env.events().publish((UNSTAKED, staker), (share_amount, amount));
+        amount
+    }
+
+    /// Repassa taxas (do marketplace ou da tesouraria do token) aos stakers, pro-rata às cotas
+    pub fn notify_reward(env: Env, funder: Address, reward_token: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, StakingError::InvalidAmount);
+        }
+        let total = total_shares(&env);
+        if total == 0 {
+            panic_with_error!(&env, StakingError::NoStakers);
+        }
+        // O índice sobe pelo que chegou ao contrato, já descontada a taxa do ativo
+        let before = held(&env, &reward_token);
+        token::Client::new(&env, &reward_token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let amount = held(&env, &reward_token) - before;
+        if amount <= 0 {
+            panic_with_error!(&env, StakingError::InvalidAmount);
+        }
+
+        let mut tokens = reward_tokens(&env);
+        if !tokens.contains(&reward_token) {
+            tokens.push_back(reward_token.clone());
+            env.storage().instance().set(&REWARD_TOKENS, &tokens);
+        }
+        let index = reward_index(&env, &reward_token) + amount * INDEX_PRECISION / total as i128;
+        env.storage()
+            .persistent()
+            .set(&(REWARD_INDEX, reward_token.clone()), &index);
+
+        env.events().publish((REWARDED, reward_token), amount);
+    }
+
+    /// Saca as recompensas acumuladas em um ativo
+    pub fn claim(env: Env, staker: Address, reward_token: Address) -> i128 {
+        staker.require_auth();
+        checkpoint(&env, &staker);
+        let amount = accrued(&env, &staker, &reward_token);
+        if amount == 0 {
+            panic_with_error!(&env, StakingError::NothingToClaim);
+        }
+        env.storage()
+            .persistent()
+            .remove(&(ACCRUED, staker.clone(), reward_token.clone()));
+        token::Client::new(&env, &reward_token).transfer(
+            &env.current_contract_address(),
+            &staker,
+            &amount,
+        );
+
+        env.events().publish((CLAIMED, staker, reward_token), amount);
+        amount
+    }
+
+    /// Cobre o inadimplemento de um gerador com o STRGRID do pool (apenas admin)
+    ///
+    /// A perda é absorvida por todos os stakers na proporção de suas cotas.
+    pub fn cover_default(env: Env, recipient: Address, amount: u64, reference: u64) {
+        admin(&env).require_auth();
+        let pool_amount = pool(&env);
+        if amount == 0 {
+            panic_with_error!(&env, StakingError::InvalidAmount);
+        }
+        if amount > pool_amount {
+            panic_with_error!(&env, StakingError::InsufficientBackstop);
+        }
+        env.storage().instance().set(&POOL, &(pool_amount - amount));
+        energy_token(&env).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &i128::from(amount),
+        );
+
+        env.events().publish((BACKSTOP, reference, recipient), amount);
+    }
+
+    /// Cotas do staker
+    pub fn shares_of(env: Env, staker: Address) -> u64 {
+        shares(&env, &staker)
+    }
+
+    /// STRGRID resgatável pelas cotas do staker
+    pub fn stake_of(env: Env, staker: Address) -> u64 {
+        let total = total_shares(&env);
+        if total == 0 {
+            return 0;
+        }
+        (shares(&env, &staker) as u128 * pool(&env) as u128 / total as u128) as u64
+    }
+
+    /// STRGRID disponível no pool de backstop
+    pub fn total_staked(env: Env) -> u64 {
+        pool(&env)
+    }
+
+    /// Recompensas a sacar pelo staker em um ativo
+    pub fn pending_rewards(env: Env, staker: Address, reward_token: Address) -> i128 {
+        pending(&env, &staker, &reward_token)
+    }
+}
+
+mod test;
@@ -0,0 +1,182 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_staking_rewards_and_backstop() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let market = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &300i128);
+    energy.transfer(&generator, &bob, &100i128);
+
+    let staking_id = env.register(StakingContract, (energy_id.clone(), admin.clone()));
+    let staking = StakingContractClient::new(&env, &staking_id);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&market, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    assert_eq!(
+        staking.try_notify_reward(&market, &usdc, &100i128),
+        Err(Ok(StakingError::NoStakers.into()))
+    );
+
+    staking.stake(&alice, &300u64);
+    staking.stake(&bob, &100u64);
+    assert_eq!(staking.total_staked(), 400);
+
+    // Fees are split 3:1 by shares
+    staking.notify_reward(&market, &usdc, &400i128);
+    assert_eq!(staking.pending_rewards(&alice, &usdc), 300);
+    assert_eq!(staking.claim(&bob, &usdc), 100);
+    assert_eq!(usdc_client.balance(&bob), 100);
+    assert_eq!(
+        staking.try_claim(&bob, &usdc),
+        Err(Ok(StakingError::NothingToClaim.into()))
+    );
+
+    // A generator default is covered from the pool; both stakers absorb 25%
+    assert_eq!(
+        staking.try_cover_default(&buyer, &500u64, &1u64),
+        Err(Ok(StakingError::InsufficientBackstop.into()))
+    );
+    staking.cover_default(&buyer, &100u64, &1u64);
+    assert_eq!(energy.balance_of(&buyer), 100);
+    assert_eq!(staking.stake_of(&alice), 225);
+    assert_eq!(staking.stake_of(&bob), 75);
+
+    // Unstaking returns the post-loss value; earlier rewards stay claimable
+    assert_eq!(
+        staking.try_unstake(&alice, &301u64),
+        Err(Ok(StakingError::InsufficientShares.into()))
+    );
+    assert_eq!(staking.unstake(&alice, &300u64), 225);
+    assert_eq!(energy.balance_of(&alice), 225);
+    assert_eq!(staking.claim(&alice, &usdc), 300);
+
+    // Later fees go entirely to the remaining staker
+    staking.notify_reward(&market, &usdc, &50i128);
+    assert_eq!(staking.pending_rewards(&alice, &usdc), 0);
+    assert_eq!(staking.pending_rewards(&bob, &usdc), 50);
+}
+
+#[test]
+fn test_stake_counts_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &300i128);
+    energy.transfer(&generator, &bob, &300i128);
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+
+    let staking_id = env.register(StakingContract, (energy_id.clone(), admin.clone()));
+    let staking = StakingContractClient::new(&env, &staking_id);
+
+    // A 1% fee leaves 198 of the 200 kWh in the pool
+    assert_eq!(staking.stake(&alice, &200u64), 198);
+    assert_eq!(staking.total_staked(), 198);
+    assert_eq!(energy.balance_of(&staking_id), 198);
+
+    // Later stakers do not dilute earlier ones by the fee either
+    staking.stake(&bob, &100u64);
+    assert_eq!(staking.stake_of(&alice), 198);
+    assert_eq!(staking.total_staked(), energy.balance_of(&staking_id) as u64);
+
+    // The whole pool can still be paid out
+    staking.unstake(&alice, &198u64);
+    staking.unstake(&bob, &staking.shares_of(&bob));
+    assert_eq!(energy.balance_of(&staking_id), 0);
+}
+
+#[test]
+fn test_stake_rejected_while_pool_is_drained() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &100i128);
+    energy.transfer(&generator, &bob, &100i128);
+
+    let staking_id = env.register(StakingContract, (energy_id.clone(), admin.clone()));
+    let staking = StakingContractClient::new(&env, &staking_id);
+
+    // A default wipes out the pool while alice's shares are still outstanding
+    staking.stake(&alice, &100u64);
+    staking.cover_default(&buyer, &100u64, &1u64);
+    assert_eq!(staking.total_staked(), 0);
+
+    // Minting bob 1:1 would hand him alice's claim on future deposits
+    assert_eq!(
+        staking.try_stake(&bob, &100u64),
+        Err(Ok(StakingError::PoolDrained.into()))
+    );
+    assert_eq!(energy.balance_of(&bob), 100);
+    assert_eq!(staking.shares_of(&alice), 100);
+}
+
+#[test]
+fn test_reward_index_counts_amount_received_after_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let funder = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &alice, &100i128);
+    energy.transfer(&generator, &bob, &100i128);
+    energy.transfer(&generator, &funder, &400i128);
+
+    let staking_id = env.register(StakingContract, (energy_id.clone(), admin.clone()));
+    let staking = StakingContractClient::new(&env, &staking_id);
+    staking.stake(&alice, &100u64);
+    staking.stake(&bob, &100u64);
+
+    // A 1% fee leaves 198 of the 200 kWh reward for the stakers
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+    staking.notify_reward(&funder, &energy_id, &200i128);
+    assert_eq!(staking.pending_rewards(&alice, &energy_id), 99);
+    assert_eq!(staking.pending_rewards(&bob, &energy_id), 99);
+
+    // Both claims are covered by what the contract actually holds
+    assert_eq!(staking.claim(&alice, &energy_id), 99);
+    assert_eq!(staking.claim(&bob, &energy_id), 99);
+    assert_eq!(energy.balance_of(&staking_id) as u64, staking.total_staked());
+}
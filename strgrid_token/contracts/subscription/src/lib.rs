@@ -0,0 +1,224 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, Symbol,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const SUBSCRIPTION: Symbol = symbol_short!("SUB");
+const SUBSCRIPTION_SEQ: Symbol = symbol_short!("SUBSEQ");
+
+// Tópicos dos eventos publicados pelo contrato de assinaturas
+const SUBSCRIBED: Symbol = symbol_short!("subscribe");
+const DELIVERED: Symbol = symbol_short!("delivered");
+const TOPPED_UP: Symbol = symbol_short!("top_up");
+const CANCELLED: Symbol = symbol_short!("cancelled");
+
+/// Compra recorrente: a cada ciclo o gerador entrega `kwh_per_cycle` e recebe `price_per_cycle`
+/// do saldo pré-pago pelo consumidor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub id: u64,
+    pub consumer: Address,
+    pub generator: Address,
+    pub payment_token: Address,
+    pub kwh_per_cycle: u64,
+    pub price_per_cycle: i128,
+    pub cycle_secs: u64,
+    pub next_cycle_at: u64,
+    pub balance: i128,
+    pub is_active: bool,
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum SubscriptionError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    InvalidCycle = 4,
+    SelfSubscription = 5,
+    SubscriptionNotFound = 6,
+    SubscriptionInactive = 7,
+    CycleNotDue = 8,
+    InsufficientFunds = 9,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, SubscriptionError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn load_active(env: &Env, subscription_id: u64) -> Subscription {
+    let subscription = SubscriptionContract::get_subscription(env.clone(), subscription_id);
+    if !subscription.is_active {
+        panic_with_error!(env, SubscriptionError::SubscriptionInactive);
+    }
+    subscription
+}
+
+fn save_subscription(env: &Env, subscription: &Subscription) {
+    env.storage()
+        .persistent()
+        .set(&(SUBSCRIPTION, subscription.id), subscription);
+}
+
+#[contract]
+pub struct SubscriptionContract;
+
+#[contractimpl]
+impl SubscriptionContract {
+    /// Configura no deploy o contrato com o token de energia entregue nas assinaturas
+    pub fn __constructor(env: Env, energy_token: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+    }
+
+    /// Cria uma assinatura acordada entre consumidor e gerador, com depósito inicial do consumidor
+    ///
+    /// O gerador deve aprovar este contrato como spender dos kWh a entregar; o primeiro ciclo
+    /// vence imediatamente.
+    #[allow(clippy::too_many_arguments)]
+    pub fn subscribe(
+        env: Env,
+        consumer: Address,
+        generator: Address,
+        payment_token: Address,
+        kwh_per_cycle: u64,
+        price_per_cycle: i128,
+        cycle_secs: u64,
+        deposit: i128,
+    ) -> u64 {
+        if consumer == generator {
+            panic_with_error!(&env, SubscriptionError::SelfSubscription);
+        }
+        consumer.require_auth();
+        generator.require_auth();
+        if kwh_per_cycle == 0 || price_per_cycle <= 0 || deposit < 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidAmount);
+        }
+        if cycle_secs == 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidCycle);
+        }
+
+        if deposit > 0 {
+            token::Client::new(&env, &payment_token).transfer(
+                &consumer,
+                &env.current_contract_address(),
+                &deposit,
+            );
+        }
+
+        let id: u64 = env.storage().instance().get(&SUBSCRIPTION_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&SUBSCRIPTION_SEQ, &id);
+        let subscription = Subscription {
+            id,
+            consumer: consumer.clone(),
+            generator: generator.clone(),
+            payment_token,
+            kwh_per_cycle,
+            price_per_cycle,
+            cycle_secs,
+            next_cycle_at: env.ledger().timestamp(),
+            balance: deposit,
+            is_active: true,
+        };
+        save_subscription(&env, &subscription);
+
+        env.events().publish(
+            (SUBSCRIBED, id, consumer, generator),
+            (kwh_per_cycle, price_per_cycle, cycle_secs),
+        );
+        id
+    }
+
+    /// Processa o ciclo vencido: entrega os kWh ao consumidor e paga o gerador (qualquer um pode chamar)
+    pub fn process_cycle(env: Env, subscription_id: u64) {
+        let mut subscription = load_active(&env, subscription_id);
+        if env.ledger().timestamp() < subscription.next_cycle_at {
+            panic_with_error!(&env, SubscriptionError::CycleNotDue);
+        }
+        if subscription.balance < subscription.price_per_cycle {
+            panic_with_error!(&env, SubscriptionError::InsufficientFunds);
+        }
+
+        subscription.balance -= subscription.price_per_cycle;
+        subscription.next_cycle_at += subscription.cycle_secs;
+        save_subscription(&env, &subscription);
+
+        energy_token(&env).transfer_from(
+            &env.current_contract_address(),
+            &subscription.generator,
+            &subscription.consumer,
+            &i128::from(subscription.kwh_per_cycle),
+        );
+        token::Client::new(&env, &subscription.payment_token).transfer(
+            &env.current_contract_address(),
+            &subscription.generator,
+            &subscription.price_per_cycle,
+        );
+
+        env.events().publish(
+            (DELIVERED, subscription_id),
+            (subscription.kwh_per_cycle, subscription.price_per_cycle),
+        );
+    }
+
+    /// Adiciona saldo pré-pago a uma assinatura ativa
+    pub fn top_up(env: Env, subscription_id: u64, amount: i128) {
+        let mut subscription = load_active(&env, subscription_id);
+        subscription.consumer.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, SubscriptionError::InvalidAmount);
+        }
+
+        token::Client::new(&env, &subscription.payment_token).transfer(
+            &subscription.consumer,
+            &env.current_contract_address(),
+            &amount,
+        );
+        subscription.balance += amount;
+        save_subscription(&env, &subscription);
+
+        env.events()
+            .publish((TOPPED_UP, subscription_id), (amount, subscription.balance));
+    }
+
+    /// Cancela a assinatura, devolvendo ao consumidor o saldo não utilizado
+    pub fn cancel(env: Env, subscription_id: u64) -> i128 {
+        let mut subscription = load_active(&env, subscription_id);
+        subscription.consumer.require_auth();
+
+        let refund = subscription.balance;
+        subscription.balance = 0;
+        subscription.is_active = false;
+        save_subscription(&env, &subscription);
+
+        if refund > 0 {
+            token::Client::new(&env, &subscription.payment_token).transfer(
+                &env.current_contract_address(),
+                &subscription.consumer,
+                &refund,
+            );
+        }
+        env.events().publish((CANCELLED, subscription_id), refund);
+        refund
+    }
+
+    /// Consulta uma assinatura
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Subscription {
+        env.storage()
+            .persistent()
+            .get(&(SUBSCRIPTION, subscription_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SubscriptionError::SubscriptionNotFound))
+    }
+}
+
+mod test;
@@ -0,0 +1,65 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_subscription_cycles_top_up_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let household = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&household, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let subs_id = env.register(SubscriptionContract, (energy_id.clone(),));
+    let subs = SubscriptionContractClient::new(&env, &subs_id);
+
+    // 100 kWh per 30-day cycle for 1,000, prefunded for one cycle
+    let cycle = 30 * 24 * 3600u64;
+    energy.approve(&generator, &subs_id, &300i128);
+    let sub_id = subs.subscribe(&household, &generator, &usdc, &100u64, &1_000i128, &cycle, &1_000i128);
+    assert_eq!(usdc_client.balance(&subs_id), 1_000);
+
+    subs.process_cycle(&sub_id);
+    assert_eq!(energy.balance_of(&household), 100);
+    assert_eq!(usdc_client.balance(&generator), 1_000);
+    assert_eq!(
+        subs.try_process_cycle(&sub_id),
+        Err(Ok(SubscriptionError::CycleNotDue.into()))
+    );
+
+    // Next cycle is due but unfunded until the household tops up
+    env.ledger().with_mut(|li| li.timestamp += cycle);
+    assert_eq!(
+        subs.try_process_cycle(&sub_id),
+        Err(Ok(SubscriptionError::InsufficientFunds.into()))
+    );
+    subs.top_up(&sub_id, &1_500i128);
+    subs.process_cycle(&sub_id);
+    assert_eq!(energy.balance_of(&household), 200);
+    assert_eq!(subs.get_subscription(&sub_id).balance, 500);
+
+    // Cancelling refunds the unused balance
+    assert_eq!(subs.cancel(&sub_id), 500);
+    assert_eq!(usdc_client.balance(&household), 10_000 - 2_000);
+    assert!(!subs.get_subscription(&sub_id).is_active);
+    env.ledger().with_mut(|li| li.timestamp += cycle);
+    assert_eq!(
+        subs.try_process_cycle(&sub_id),
+        Err(Ok(SubscriptionError::SubscriptionInactive.into()))
+    );
+}
@@ -0,0 +1,178 @@
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env, Error, Map, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, EnergyGenerator, SourceType, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError, GENERATOR,
+};
+
+const AGGREGATOR: Symbol = StorageKey::Aggregator.symbol();
+const AGGREGATOR_OF: Symbol = StorageKey::AggregatorOf.symbol();
+
+/// Membros do agregador com a capacidade (kW) que cada um aportou ao entrar
+fn members(env: &Env, aggregator: &Address) -> Map<Address, u64> {
+    env.storage()
+        .persistent()
+        .get(&(AGGREGATOR, aggregator.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::AggregatorNotFound))
+}
+
+fn load_generator(env: &Env, address: &Address) -> EnergyGenerator {
+    env.storage()
+        .persistent()
+        .get(&(GENERATOR, address.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::GeneratorNotFound))
+}
+
+/// Atualiza os membros e a capacidade agregada com que o agregador emite
+fn save_pool(env: &Env, aggregator: &Address, members: &Map<Address, u64>, capacity_delta: i128) {
+    env.storage()
+        .persistent()
+        .set(&(AGGREGATOR, aggregator.clone()), members);
+    let mut pool = load_generator(env, aggregator);
+    pool.capacity_kw = (pool.capacity_kw as i128 + capacity_delta) as u64;
+    env.storage()
+        .persistent()
+        .set(&(GENERATOR, aggregator.clone()), &pool);
+}
+
+/// Membros de um agregador emitem apenas por meio dele
+pub(crate) fn require_not_aggregated(env: &Env, generator: &Address) {
+    if let Err(error) = try_require_not_aggregated(env, generator) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `require_not_aggregated`, devolvendo o erro em vez de abortar
+pub(crate) fn try_require_not_aggregated(env: &Env, generator: &Address) -> Result<(), Error> {
+    if env
+        .storage()
+        .persistent()
+        .has(&(AGGREGATOR_OF, generator.clone()))
+    {
+        return Err(STRGRIDExtError::AlreadyAggregated.into());
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Registra um agregador, que emite como gerador com a capacidade somada de seus membros (apenas admin)
+    pub fn register_aggregator(env: Env, aggregator: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "register_aggregator", (aggregator.clone(),));
+        if env
+            .storage()
+            .persistent()
+            .has(&(GENERATOR, aggregator.clone()))
+        {
+            panic_with_error!(&env, STRGRIDExtError::AlreadyRegistered);
+        }
+
+        let pool = EnergyGenerator {
+            address: aggregator.clone(),
+            capacity_kw: 0,
+            current_production: 0,
+            is_active: true,
+            registration_date: env.ledger().timestamp(),
+            source_type: SourceType::Unspecified,
+            zone: 0,
+            last_mint_timestamp: None,
+            metadata_cid: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&(GENERATOR, aggregator.clone()), &pool);
+        env.storage()
+            .persistent()
+            .set(&(AGGREGATOR, aggregator.clone()), &Map::<Address, u64>::new(&env));
+
+        events::aggregator_registered(&env, aggregator);
+    }
+
+    /// Adiciona um gerador ao agregador; sua capacidade passa a compor o pool
+    pub fn add_member(env: Env, aggregator: Address, member: Address) {
+        aggregator.require_auth();
+        member.require_auth();
+        let mut pool_members = members(&env, &aggregator);
+        if member == aggregator {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        require_not_aggregated(&env, &member);
+        let generator = load_generator(&env, &member);
+        if !generator.is_active {
+            panic_with_error!(&env, STRGRIDError::GeneratorInactive);
+        }
+
+        pool_members.set(member.clone(), generator.capacity_kw);
+        save_pool(&env, &aggregator, &pool_members, generator.capacity_kw as i128);
+        env.storage()
+            .persistent()
+            .set(&(AGGREGATOR_OF, member.clone()), &aggregator);
+
+        events::aggregator_member_added(&env, aggregator, member, generator.capacity_kw);
+    }
+
+    /// Remove um membro do agregador, retirando sua capacidade do pool
+    pub fn remove_member(env: Env, aggregator: Address, member: Address) {
+        aggregator.require_auth();
+        let mut pool_members = members(&env, &aggregator);
+        let capacity_kw = pool_members
+            .get(member.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDExtError::NotAggregatorMember));
+
+        pool_members.remove(member.clone());
+        save_pool(&env, &aggregator, &pool_members, -(capacity_kw as i128));
+        env.storage()
+            .persistent()
+            .remove(&(AGGREGATOR_OF, member.clone()));
+
+        events::aggregator_member_removed(&env, aggregator, member, capacity_kw);
+    }
+
+    /// Membros de um agregador e a capacidade aportada por cada um
+    pub fn aggregator_members(env: Env, aggregator: Address) -> Map<Address, u64> {
+        members(&env, &aggregator)
+    }
+
+    /// Agregador ao qual um gerador pertence, se houver
+    pub fn aggregator_of(env: Env, member: Address) -> Option<Address> {
+        env.storage().persistent().get(&(AGGREGATOR_OF, member))
+    }
+
+    /// Reparte `amount` de um ativo de pagamento entre os membros, na proporção da capacidade aportada
+    ///
+    /// O resíduo do arredondamento permanece com o agregador.
+    pub fn distribute_proceeds(
+        env: Env,
+        aggregator: Address,
+        payment_token: Address,
+        amount: i128,
+    ) -> i128 {
+        aggregator.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let pool_members = members(&env, &aggregator);
+        let total_capacity: u64 = pool_members.values().iter().sum();
+        if total_capacity == 0 {
+            panic_with_error!(&env, STRGRIDExtError::NotAggregatorMember);
+        }
+
+        let payment = token::Client::new(&env, &payment_token);
+        let mut distributed = 0i128;
+        for (member, capacity_kw) in pool_members.iter() {
+            let share = amount
+                .checked_mul(capacity_kw as i128)
+                .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ArithmeticOverflow))
+                / total_capacity as i128;
+            if share > 0 {
+                payment.transfer(&aggregator, &member, &share);
+                distributed += share;
+            }
+        }
+
+        events::proceeds_distributed(&env, aggregator, payment_token, distributed);
+        distributed
+    }
+}
@@ -0,0 +1,188 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Val};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, migration, strict, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    STRGRIDExt2Error, ALLOWANCE,
+};
+
+const SPENDING_LIMIT: Symbol = StorageKey::SpendingLimit.symbol();
+
+/// Allowance armazenada; `live_until_ledger` ausente significa sem expiração
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub live_until_ledger: Option<u32>,
+}
+
+impl AllowanceValue {
+    pub fn is_expired(&self, env: &Env) -> bool {
+        self.live_until_ledger
+            .is_some_and(|live_until| env.ledger().sequence() > live_until)
+    }
+}
+
+/// Teto de gasto do spender a cada `period_ledgers`, somado ao saldo da allowance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendingLimit {
+    pub amount: i128,
+    pub period_ledgers: u32,
+    /// Ledger de referência das janelas; definido ao gravar o limite
+    pub period_start: u32,
+    /// Gasto na janela iniciada em `period_start`
+    pub spent: i128,
+}
+
+impl SpendingLimit {
+    /// Avança a referência até a janela corrente, zerando o gasto ao virar o período
+    fn roll(&mut self, env: &Env) {
+        let elapsed = env.ledger().sequence() - self.period_start;
+        if elapsed >= self.period_ledgers {
+            self.period_start += elapsed - elapsed % self.period_ledgers;
+            self.spent = 0;
+        }
+    }
+}
+
+fn spending_limit(env: &Env, owner: &Address, spender: &Address) -> Option<SpendingLimit> {
+    let stored: Option<Val> = env
+        .storage()
+        .persistent()
+        .get(&(SPENDING_LIMIT, owner.clone(), spender.clone()));
+    stored.map(|value| migration::spending_limit(env, &value))
+}
+
+/// Lê a allowance crua, sem considerar expiração
+pub(crate) fn read_raw(env: &Env, owner: &Address, spender: &Address) -> Option<AllowanceValue> {
+    let stored: Option<Val> = env
+        .storage()
+        .persistent()
+        .get(&(ALLOWANCE, owner.clone(), spender.clone()));
+    stored.map(|value| migration::allowance(env, &value))
+}
+
+/// Allowance vigente; expiradas viram zero sem expiração
+pub(crate) fn read_live(env: &Env, owner: &Address, spender: &Address) -> AllowanceValue {
+    match read_raw(env, owner, spender) {
+        Some(value) if !value.is_expired(env) => value,
+        _ => AllowanceValue {
+            amount: 0,
+            live_until_ledger: None,
+        },
+    }
+}
+
+pub(crate) fn write(env: &Env, owner: &Address, spender: &Address, value: &AllowanceValue) {
+    let key = (ALLOWANCE, owner.clone(), spender.clone());
+    // Allowance zerada não precisa ocupar storage
+    if value.amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, value);
+    }
+}
+
+/// Valida a expiração informada em uma aprovação
+pub(crate) fn check_live_until(env: &Env, amount: i128, live_until_ledger: Option<u32>) {
+    if let Some(live_until) = live_until_ledger {
+        if amount > 0 && live_until < env.ledger().sequence() {
+            panic_with_error!(env, STRGRIDError::AllowanceExpired);
+        }
+    }
+}
+
+/// Grava uma aprovação do dono já autenticado, com as mesmas regras para `approve` e `permit`
+pub(crate) fn approve(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    live_until_ledger: Option<u32>,
+) {
+    if amount < 0 {
+        panic_with_error!(env, STRGRIDError::InvalidAmount);
+    }
+
+    // Em modo estrito, aprovação zero só revoga e nunca cria registro
+    if amount == 0 && strict::is_strict(env) && read_raw(env, owner, spender).is_none() {
+        panic_with_error!(env, STRGRIDError::ZeroApproval);
+    }
+    check_live_until(env, amount, live_until_ledger);
+
+    write(env, owner, spender, &AllowanceValue { amount, live_until_ledger });
+}
+
+/// Consome `amount` da allowance, rejeitando allowances expiradas ou insuficientes
+pub(crate) fn spend(env: &Env, owner: &Address, spender: &Address, amount: i128) {
+    let mut value = read_raw(env, owner, spender)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::InsufficientAllowance));
+    if value.is_expired(env) {
+        panic_with_error!(env, STRGRIDError::AllowanceExpired);
+    }
+    if value.amount < amount {
+        panic_with_error!(env, STRGRIDError::InsufficientAllowance);
+    }
+    if let Some(mut limit) = spending_limit(env, owner, spender) {
+        limit.roll(env);
+        if amount > limit.amount - limit.spent {
+            panic_with_error!(env, STRGRIDExt2Error::SpendingLimitExceeded);
+        }
+        limit.spent += amount;
+        env.storage()
+            .persistent()
+            .set(&(SPENDING_LIMIT, owner.clone(), spender.clone()), &limit);
+    }
+    value.amount -= amount;
+    write(env, owner, spender, &value);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Limita o spender a `amount` por `period_ledgers` ledgers, além da allowance; `None` remove
+    ///
+    /// Permite autorizar um varejista a puxar no máximo o consumo esperado de cada mês.
+    pub fn set_spending_limit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        limit: Option<(i128, u32)>,
+    ) {
+        owner.require_auth();
+        let key = (SPENDING_LIMIT, owner.clone(), spender.clone());
+        match limit {
+            Some((amount, period_ledgers)) if amount < 0 || period_ledgers == 0 => {
+                panic_with_error!(&env, STRGRIDError::InvalidAmount)
+            }
+            Some((amount, period_ledgers)) => env.storage().persistent().set(
+                &key,
+                &SpendingLimit {
+                    amount,
+                    period_ledgers,
+                    period_start: env.ledger().sequence(),
+                    spent: 0,
+                },
+            ),
+            None => env.storage().persistent().remove(&key),
+        }
+        events::spending_limit_set(&env, owner, spender, limit);
+    }
+
+    /// Limite periódico do spender, com o gasto da janela corrente
+    pub fn get_spending_limit(env: Env, owner: Address, spender: Address) -> Option<SpendingLimit> {
+        spending_limit(&env, &owner, &spender).map(|mut limit| {
+            limit.roll(&env);
+            limit
+        })
+    }
+
+    /// Quanto o spender ainda pode gastar agora: a allowance vigente limitada ao saldo do período
+    pub fn spendable_allowance(env: Env, owner: Address, spender: Address) -> i128 {
+        let allowance = read_live(&env, &owner, &spender).amount;
+        match Self::get_spending_limit(env, owner, spender) {
+            Some(limit) => allowance.min(limit.amount - limit.spent),
+            None => allowance,
+        }
+    }
+}
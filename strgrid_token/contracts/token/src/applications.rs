@@ -0,0 +1,223 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::query::MAX_PAGE_SIZE;
+use crate::roles::{require_role, Role};
+use crate::tiers::{self, GeneratorTier};
+use crate::{
+    events, insert_generator, EnergyGenerator, SourceType, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError, GENERATOR,
+};
+
+const APPLICATION: Symbol = StorageKey::GeneratorApplication.symbol();
+const APPLICANTS: Symbol = StorageKey::GeneratorApplicants.symbol();
+
+/// Situação da candidatura de um gerador
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Suspended,
+}
+
+/// Candidatura de autocadastro de um gerador, revisada por um operador da rede
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneratorApplication {
+    pub applicant: Address,
+    pub capacity_kw: u64,
+    pub source_type: SourceType,
+    pub zone: u32,
+    /// sha256 do dossiê (licenças, autorização da ANEEL, acordo de conexão)
+    pub docs_hash: BytesN<32>,
+    pub status: ApplicationStatus,
+    pub submitted_at: u64,
+    pub reviewed_by: Option<Address>,
+    pub reviewed_at: Option<u64>,
+}
+
+fn application(env: &Env, applicant: &Address) -> Option<GeneratorApplication> {
+    env.storage()
+        .persistent()
+        .get(&(APPLICATION, applicant.clone()))
+}
+
+fn applicants(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&APPLICANTS)
+        .unwrap_or(Vec::new(env))
+}
+
+fn load(env: &Env, applicant: &Address) -> GeneratorApplication {
+    application(env, applicant)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::ApplicationNotFound))
+}
+
+/// Move a candidatura de `from` para `to`, registrando o operador que decidiu
+fn review(
+    env: &Env,
+    operator: &Address,
+    applicant: &Address,
+    from: ApplicationStatus,
+    to: ApplicationStatus,
+) -> GeneratorApplication {
+    require_role(env, operator, Role::GridOperator);
+    let mut app = load(env, applicant);
+    if app.status != from {
+        panic_with_error!(env, STRGRIDExtError::InvalidApplicationStatus);
+    }
+    app.status = to;
+    app.reviewed_by = Some(operator.clone());
+    app.reviewed_at = Some(env.ledger().timestamp());
+    env.storage()
+        .persistent()
+        .set(&(APPLICATION, applicant.clone()), &app);
+    events::application_status_set(env, applicant.clone(), to, operator.clone());
+    app
+}
+
+/// Liga/desliga a emissão do gerador junto com a suspensão da candidatura
+fn set_active(env: &Env, generator: &Address, is_active: bool) {
+    let key = (GENERATOR, generator.clone());
+    let mut energy_generator: EnergyGenerator = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::GeneratorNotFound));
+    energy_generator.is_active = is_active;
+    env.storage().persistent().set(&key, &energy_generator);
+    events::generator_status_set(env, generator.clone(), is_active);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Candidata-se como gerador; a conta só emite depois da aprovação de um operador da rede
+    ///
+    /// Uma candidatura rejeitada pode ser reenviada com novos dados.
+    pub fn apply_as_generator(
+        env: Env,
+        applicant: Address,
+        capacity_kw: u64,
+        source_type: SourceType,
+        zone: u32,
+        docs_hash: BytesN<32>,
+    ) {
+        applicant.require_auth();
+        if capacity_kw == 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if env.storage().persistent().has(&(GENERATOR, applicant.clone())) {
+            panic_with_error!(&env, STRGRIDExtError::AlreadyRegistered);
+        }
+        match application(&env, &applicant) {
+            None => {
+                let mut list = applicants(&env);
+                list.push_back(applicant.clone());
+                env.storage().persistent().set(&APPLICANTS, &list);
+            }
+            Some(app) if app.status == ApplicationStatus::Rejected => {}
+            Some(_) => panic_with_error!(&env, STRGRIDExtError::AlreadyRegistered),
+        }
+
+        let app = GeneratorApplication {
+            applicant: applicant.clone(),
+            capacity_kw,
+            source_type,
+            zone,
+            docs_hash: docs_hash.clone(),
+            status: ApplicationStatus::Pending,
+            submitted_at: env.ledger().timestamp(),
+            reviewed_by: None,
+            reviewed_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&(APPLICATION, applicant.clone()), &app);
+        events::generator_applied(&env, applicant, capacity_kw, docs_hash);
+    }
+
+    /// Aprova uma candidatura pendente e cadastra o gerador com os dados declarados na classe
+    /// informada (operador da rede)
+    pub fn approve_application(env: Env, operator: Address, applicant: Address, tier: GeneratorTier) {
+        let app = review(
+            &env,
+            &operator,
+            &applicant,
+            ApplicationStatus::Pending,
+            ApplicationStatus::Approved,
+        );
+        insert_generator(&env, &applicant, app.capacity_kw, app.source_type, app.zone);
+        tiers::assign(&env, &applicant, tier);
+    }
+
+    /// Rejeita uma candidatura pendente (operador da rede)
+    pub fn reject_application(env: Env, operator: Address, applicant: Address) {
+        review(
+            &env,
+            &operator,
+            &applicant,
+            ApplicationStatus::Pending,
+            ApplicationStatus::Rejected,
+        );
+    }
+
+    /// Suspende um gerador aprovado, bloqueando novas emissões (operador da rede)
+    pub fn suspend_generator(env: Env, operator: Address, generator: Address) {
+        review(
+            &env,
+            &operator,
+            &generator,
+            ApplicationStatus::Approved,
+            ApplicationStatus::Suspended,
+        );
+        set_active(&env, &generator, false);
+    }
+
+    /// Reativa um gerador suspenso (operador da rede)
+    pub fn reinstate_generator(env: Env, operator: Address, generator: Address) {
+        review(
+            &env,
+            &operator,
+            &generator,
+            ApplicationStatus::Suspended,
+            ApplicationStatus::Approved,
+        );
+        set_active(&env, &generator, true);
+    }
+
+    /// Consulta a candidatura de um endereço
+    pub fn get_application(env: Env, applicant: Address) -> GeneratorApplication {
+        load(&env, &applicant)
+    }
+
+    /// Lista candidaturas em ordem de primeiro envio, opcionalmente filtradas pela situação,
+    /// paginado por offset/limit sobre o resultado filtrado
+    pub fn get_applications(
+        env: Env,
+        status: Option<ApplicationStatus>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<GeneratorApplication> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut page = Vec::new(&env);
+        let mut skipped = 0u32;
+        for applicant in applicants(&env).iter() {
+            if page.len() >= limit {
+                break;
+            }
+            let app = load(&env, &applicant);
+            if status.is_some_and(|status| status != app.status) {
+                continue;
+            }
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            page.push_back(app);
+        }
+        page
+    }
+}
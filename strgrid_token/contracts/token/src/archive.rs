@@ -0,0 +1,182 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Map, Symbol, Vec,
+};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, query, require_admin, stats, EnergyToken, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExt2Error, ENERGY_DATA, MAX_BATCH_SIZE,
+};
+
+const RETENTION: Symbol = StorageKey::ArchiveRetention.symbol();
+const AGGREGATE: Symbol = StorageKey::ArchiveAggregate.symbol();
+
+/// Retenção padrão de lotes encerrados antes da compactação (365 dias)
+pub const DEFAULT_RETENTION_SECONDS: u64 = 365 * 86_400;
+/// Duração de um período de agregação do arquivo (30 dias)
+pub const ARCHIVE_PERIOD_SECONDS: u64 = 30 * 86_400;
+
+/// Resumo dos lotes compactados de um gerador em um período
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArchiveAggregate {
+    pub generator: Address,
+    pub period: u64,
+    pub lot_count: u64,
+    pub total_kwh: u64,
+    pub consumed_kwh: u64,
+    pub voided_kwh: u64,
+    /// Encadeamento das raízes Merkle de cada compactação: sha256(anterior || raiz do lote)
+    pub merkle_root: BytesN<32>,
+    pub batch_count: u32,
+}
+
+fn retention(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&RETENTION)
+        .unwrap_or(DEFAULT_RETENTION_SECONDS)
+}
+
+/// Folha da árvore Merkle: sha256 do XDR do registro removido
+pub(crate) fn leaf_hash(env: &Env, token: &EnergyToken) -> BytesN<32> {
+    env.crypto().sha256(&token.clone().to_xdr(env)).into()
+}
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &left.to_array());
+    data.append(&Bytes::from_array(env, &right.to_array()));
+    env.crypto().sha256(&data).into()
+}
+
+/// Raiz Merkle das folhas; em níveis ímpares o último nó é pareado consigo mesmo
+pub(crate) fn merkle_root(env: &Env, leaves: &Vec<BytesN<32>>) -> BytesN<32> {
+    let mut level = leaves.clone();
+    while level.len() > 1 {
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            let left = level.get_unchecked(i);
+            let right = level.get(i + 1).unwrap_or(left.clone());
+            next.push_back(hash_pair(env, &left, &right));
+            i += 2;
+        }
+        level = next;
+    }
+    level.get_unchecked(0)
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define por quanto tempo após a expiração um lote é mantido antes de poder ser compactado (apenas admin)
+    pub fn set_archive_retention(env: Env, seconds: u64) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_archive_retention", (seconds,));
+        env.storage().instance().set(&RETENTION, &seconds);
+    }
+
+    /// Consulta a retenção vigente, em segundos
+    pub fn get_archive_retention(env: Env) -> u64 {
+        retention(&env)
+    }
+
+    /// Substitui lotes encerrados há mais que a retenção por agregados por gerador/período (apenas admin)
+    ///
+    /// Um ID repetido no lote é rejeitado, para não somar o mesmo lote duas vezes no agregado.
+    pub fn compact_lots(env: Env, token_ids: Vec<u64>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "compact_lots", (token_ids.clone(),));
+        if token_ids.is_empty() || token_ids.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
+        }
+
+        let now = env.ledger().timestamp();
+        let retention = retention(&env);
+        let mut groups: Map<(Address, u64), Vec<EnergyToken>> = Map::new(&env);
+        let mut seen: Map<u64, ()> = Map::new(&env);
+        for token_id in token_ids.iter() {
+            if seen.contains_key(token_id) {
+                panic_with_error!(&env, STRGRIDExt2Error::DuplicateLot);
+            }
+            seen.set(token_id, ());
+            let token = Self::get_energy_token(env.clone(), token_id);
+            // Expiração cobre lotes consumidos, anulados e vencidos sem uso
+            if now < token.expiry_timestamp.max(token.dispute_until).saturating_add(retention) {
+                panic_with_error!(&env, STRGRIDError::LotNotArchivable);
+            }
+            let key = (
+                token.generator_id.clone(),
+                token.creation_timestamp / ARCHIVE_PERIOD_SECONDS,
+            );
+            let mut lots = groups.get(key.clone()).unwrap_or(Vec::new(&env));
+            lots.push_back(token);
+            groups.set(key, lots);
+        }
+
+        for ((generator, period), lots) in groups.iter() {
+            let key = (AGGREGATE, generator.clone(), period);
+            let mut aggregate: ArchiveAggregate =
+                env.storage().persistent().get(&key).unwrap_or(ArchiveAggregate {
+                    generator: generator.clone(),
+                    period,
+                    lot_count: 0,
+                    total_kwh: 0,
+                    consumed_kwh: 0,
+                    voided_kwh: 0,
+                    merkle_root: BytesN::from_array(&env, &[0; 32]),
+                    batch_count: 0,
+                });
+
+            let mut leaves = Vec::new(&env);
+            let mut token_ids = Vec::new(&env);
+            for token in lots.iter() {
+                leaves.push_back(leaf_hash(&env, &token));
+                token_ids.push_back(token.id);
+                aggregate.lot_count += 1;
+                aggregate.total_kwh += token.amount_kwh;
+                if token.is_voided {
+                    aggregate.voided_kwh += token.amount_kwh;
+                } else if token.is_consumed {
+                    aggregate.consumed_kwh += token.amount_kwh;
+                }
+                query::unindex_token(&env, &token);
+                stats::close_lot(&env, &token);
+                env.storage().persistent().remove(&(ENERGY_DATA, token.id));
+            }
+
+            let batch_root = merkle_root(&env, &leaves);
+            aggregate.merkle_root = hash_pair(&env, &aggregate.merkle_root, &batch_root);
+            aggregate.batch_count += 1;
+            env.storage().persistent().set(&key, &aggregate);
+
+            events::lots_archived(&env, generator, period, batch_root, token_ids);
+        }
+    }
+
+    /// Consulta o agregado arquivado de um gerador em um período (timestamp / 2592000)
+    pub fn archive_aggregate(env: Env, generator: Address, period: u64) -> Option<ArchiveAggregate> {
+        env.storage().persistent().get(&(AGGREGATE, generator, period))
+    }
+
+    /// Verifica se um lote removido pertence à raiz Merkle de uma compactação (publicada no evento `archived`)
+    pub fn verify_archived_lot(
+        env: Env,
+        batch_root: BytesN<32>,
+        token: EnergyToken,
+        index: u32,
+        proof: Vec<BytesN<32>>,
+    ) -> bool {
+        let mut node = leaf_hash(&env, &token);
+        let mut index = index;
+        for sibling in proof.iter() {
+            node = if index.is_multiple_of(2) {
+                hash_pair(&env, &node, &sibling)
+            } else {
+                hash_pair(&env, &sibling, &node)
+            };
+            index /= 2;
+        }
+        node == batch_root
+    }
+}
@@ -0,0 +1,177 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::{
+    compliance, events, move_balance, EnergyToken, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError,
+};
+
+const AUCTION: Symbol = StorageKey::Auction.symbol();
+const AUCTION_SEQ: Symbol = StorageKey::AuctionSeq.symbol();
+
+/// Leilão holandês de kWh custodiados; o preço cai linearmente até o piso na expiração do lote
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Auction {
+    pub id: u64,
+    pub seller: Address,
+    pub token_id: u64,
+    pub payment_token: Address,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub amount_kwh: u64,
+    pub remaining_kwh: u64,
+    pub is_settled: bool,
+}
+
+impl Auction {
+    /// Preço por kWh no instante `now`
+    pub fn price_at(&self, now: u64) -> i128 {
+        if now >= self.ends_at {
+            return self.floor_price;
+        }
+        let elapsed = now.saturating_sub(self.starts_at) as i128;
+        let duration = (self.ends_at - self.starts_at) as i128;
+        self.start_price - (self.start_price - self.floor_price) * elapsed / duration
+    }
+}
+
+fn load(env: &Env, auction_id: u64) -> Auction {
+    env.storage()
+        .persistent()
+        .get(&(AUCTION, auction_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::AuctionNotFound))
+}
+
+fn save(env: &Env, auction: &Auction) {
+    env.storage().persistent().set(&(AUCTION, auction.id), auction);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Abre um leilão holandês de kWh até a expiração do lote `token_id`, custodiando os kWh no contrato
+    pub fn start_auction(
+        env: Env,
+        seller: Address,
+        token_id: u64,
+        amount_kwh: u64,
+        payment_token: Address,
+        start_price: i128,
+        floor_price: i128,
+    ) -> u64 {
+        seller.require_auth();
+        if amount_kwh == 0 || floor_price < 0 || start_price < floor_price {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let energy_token: EnergyToken = Self::get_energy_token(env.clone(), token_id);
+        let now = env.ledger().timestamp();
+        if energy_token.is_consumed || energy_token.is_voided || now >= energy_token.expiry_timestamp {
+            panic_with_error!(&env, STRGRIDError::AuctionClosed);
+        }
+
+        move_balance(
+            &env,
+            &seller,
+            &env.current_contract_address(),
+            amount_kwh.into(),
+            OpType::Auction,
+        );
+
+        let id: u64 = env.storage().instance().get(&AUCTION_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&AUCTION_SEQ, &id);
+        let auction = Auction {
+            id,
+            seller,
+            token_id,
+            payment_token,
+            start_price,
+            floor_price,
+            starts_at: now,
+            ends_at: energy_token.expiry_timestamp,
+            amount_kwh,
+            remaining_kwh: amount_kwh,
+            is_settled: false,
+        };
+        save(&env, &auction);
+
+        events::auction_started(&env, &auction);
+        id
+    }
+
+    /// Preço atual por kWh de um leilão
+    pub fn auction_price(env: Env, auction_id: u64) -> i128 {
+        load(&env, auction_id).price_at(env.ledger().timestamp())
+    }
+
+    /// Consulta um leilão
+    pub fn get_auction(env: Env, auction_id: u64) -> Auction {
+        load(&env, auction_id)
+    }
+
+    /// Compra `amount_kwh` ao preço corrente; pagamento e entrega são atômicos
+    pub fn bid(env: Env, buyer: Address, auction_id: u64, amount_kwh: u64) -> i128 {
+        buyer.require_auth();
+        let mut auction = load(&env, auction_id);
+        let now = env.ledger().timestamp();
+        if auction.is_settled || now >= auction.ends_at {
+            panic_with_error!(&env, STRGRIDError::AuctionClosed);
+        }
+        if amount_kwh == 0 || amount_kwh > auction.remaining_kwh {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        // A custódia esconde o par da venda, então o hook vê vendedor → comprador aqui
+        compliance::check_transfer(&env, &auction.seller, &buyer, amount_kwh.into());
+
+        let price = auction.price_at(now);
+        let payment = price
+            .checked_mul(amount_kwh as i128)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ArithmeticOverflow));
+        auction.remaining_kwh -= amount_kwh;
+        save(&env, &auction);
+
+        if payment > 0 {
+            token::Client::new(&env, &auction.payment_token).transfer(&buyer, &auction.seller, &payment);
+        }
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &buyer,
+            amount_kwh.into(),
+            OpType::Auction,
+        );
+
+        events::auction_bid(&env, auction_id, buyer, amount_kwh, payment);
+        payment
+    }
+
+    /// Encerra um leilão esgotado ou expirado, devolvendo ao vendedor os kWh não vendidos
+    pub fn settle(env: Env, auction_id: u64) {
+        let mut auction = load(&env, auction_id);
+        if auction.is_settled {
+            panic_with_error!(&env, STRGRIDError::AuctionClosed);
+        }
+        if auction.remaining_kwh > 0 && env.ledger().timestamp() < auction.ends_at {
+            panic_with_error!(&env, STRGRIDError::AuctionRunning);
+        }
+
+        let unsold = auction.remaining_kwh;
+        auction.remaining_kwh = 0;
+        auction.is_settled = true;
+        save(&env, &auction);
+
+        if unsold > 0 {
+            move_balance(
+                &env,
+                &env.current_contract_address(),
+                &auction.seller,
+                unsold.into(),
+                OpType::Auction,
+            );
+        }
+        events::auction_settled(&env, auction_id, auction.seller, unsold);
+    }
+}
@@ -0,0 +1,63 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, IntoVal, Symbol, Val, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::query::MAX_PAGE_SIZE;
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient};
+
+const AUDIT_LOG: Symbol = StorageKey::AuditLog.symbol();
+const AUDIT_SEQ: Symbol = StorageKey::AuditSeq.symbol();
+
+/// Registro imutável de uma ação administrativa ou de operador
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub actor: Address,
+    /// Nome da função executada
+    pub action: Symbol,
+    /// Parâmetros da chamada, na ordem da assinatura
+    pub params: Vec<Val>,
+    pub timestamp: u64,
+}
+
+fn len(env: &Env) -> u64 {
+    env.storage().instance().get(&AUDIT_SEQ).unwrap_or(0)
+}
+
+/// Acrescenta uma ação ao log de auditoria; entradas nunca são alteradas ou removidas
+pub(crate) fn record(
+    env: &Env,
+    actor: &Address,
+    action: &str,
+    params: impl IntoVal<Env, Vec<Val>>,
+) {
+    let id = len(env);
+    let entry = AuditEntry {
+        id,
+        actor: actor.clone(),
+        action: Symbol::new(env, action),
+        params: params.into_val(env),
+        timestamp: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(&(AUDIT_LOG, id), &entry);
+    env.storage().instance().set(&AUDIT_SEQ, &(id + 1));
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Lista o log de auditoria em ordem cronológica, paginado por offset/limit
+    pub fn get_audit_log(env: Env, offset: u64, limit: u32) -> Vec<AuditEntry> {
+        let end = len(&env).min(offset.saturating_add(limit.min(MAX_PAGE_SIZE) as u64));
+        let mut entries = Vec::new(&env);
+        for id in offset..end {
+            let entry: AuditEntry = env.storage().persistent().get(&(AUDIT_LOG, id)).unwrap();
+            entries.push_back(entry);
+        }
+        entries
+    }
+
+    /// Quantidade de entradas no log de auditoria
+    pub fn audit_log_len(env: Env) -> u64 {
+        len(&env)
+    }
+}
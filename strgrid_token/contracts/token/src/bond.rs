@@ -0,0 +1,155 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, token, Address, Env, Error, Symbol,
+};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, dispute, events, tiers, require_admin, EnergyGenerator, STRGRIDContract,
+    STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError, GENERATOR,
+};
+
+const BOND_CONFIG: Symbol = StorageKey::BondConfig.symbol();
+const BOND: Symbol = StorageKey::Bond.symbol();
+
+/// Ativo (SAC) e valor mínimo de caução exigido dos geradores
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub required_amount: i128,
+}
+
+pub(crate) fn bond_config(env: &Env) -> Option<BondConfig> {
+    env.storage().instance().get(&BOND_CONFIG)
+}
+
+pub(crate) fn bond_of(env: &Env, generator: &Address) -> i128 {
+    env.storage().persistent().get(&(BOND, generator.clone())).unwrap_or(0)
+}
+
+fn set_bond(env: &Env, generator: &Address, amount: i128) {
+    env.storage().persistent().set(&(BOND, generator.clone()), &amount);
+}
+
+/// Bloqueia mints de geradores sem a caução mínima depositada
+pub(crate) fn require_bonded(env: &Env, generator: &Address) -> Result<(), Error> {
+    if let Some(config) = bond_config(env) {
+        if bond_of(env, generator) < tiers::required_bond(env, generator, &config) {
+            return Err(STRGRIDError::BondRequired.into());
+        }
+    }
+    Ok(())
+}
+
+fn require_config(env: &Env) -> BondConfig {
+    bond_config(env).unwrap_or_else(|| panic_with_error!(env, STRGRIDError::BondNotConfigured))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o ativo e o valor mínimo de caução dos geradores (apenas admin)
+    pub fn set_bond_config(env: Env, token: Address, required_amount: i128) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_bond_config", (token.clone(), required_amount));
+        if required_amount < 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage().instance().set(&BOND_CONFIG, &BondConfig { token, required_amount });
+    }
+
+    /// Consulta a configuração de caução, se houver
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        bond_config(&env)
+    }
+
+    /// Consulta a caução depositada por um gerador
+    pub fn bond_of(env: Env, generator: Address) -> i128 {
+        bond_of(&env, &generator)
+    }
+
+    /// Deposita caução do gerador no contrato
+    pub fn deposit_bond(env: Env, generator: Address, amount: i128) {
+        generator.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if !env.storage().persistent().has(&(GENERATOR, generator.clone())) {
+            panic_with_error!(&env, STRGRIDError::GeneratorNotFound);
+        }
+        let config = require_config(&env);
+
+        token::Client::new(&env, &config.token).transfer(
+            &generator,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let bond = bond_of(&env, &generator) + amount;
+        set_bond(&env, &generator, bond);
+
+        events::bond_deposited(&env, generator, amount, bond);
+    }
+
+    /// Retira caução; geradores ativos só podem retirar o excedente ao mínimo exigido
+    pub fn withdraw_bond(env: Env, generator: Address, amount: i128) {
+        generator.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let config = require_config(&env);
+
+        let bond = bond_of(&env, &generator);
+        if amount > bond {
+            panic_with_error!(&env, STRGRIDError::InsufficientBond);
+        }
+        let energy_generator: Option<EnergyGenerator> =
+            env.storage().persistent().get(&(GENERATOR, generator.clone()));
+        let is_active = energy_generator.is_some_and(|g| g.is_active);
+        if is_active && bond - amount < tiers::required_bond(&env, &generator, &config) {
+            panic_with_error!(&env, STRGRIDError::InsufficientBond);
+        }
+        // Caução não pode sair enquanto houver mints em janela de contestação
+        if dispute::locked_balance(&env, &generator) > 0 {
+            panic_with_error!(&env, STRGRIDError::TokensLocked);
+        }
+
+        set_bond(&env, &generator, bond - amount);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &generator,
+            &amount,
+        );
+
+        events::bond_withdrawn(&env, generator, amount, bond - amount);
+    }
+
+    /// Confisca caução de um gerador cujo mint contestado foi mantido (apenas admin/governança)
+    pub fn slash(env: Env, token_id: u64, amount: i128, recipient: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "slash", (token_id, amount, recipient.clone()));
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let config = require_config(&env);
+
+        let challenge = Self::get_challenge(env.clone(), token_id)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ChallengeNotFound));
+        if challenge.slashed {
+            panic_with_error!(&env, STRGRIDError::AlreadySlashed);
+        }
+        let generator = Self::get_energy_token(env.clone(), token_id).generator_id;
+
+        let bond = bond_of(&env, &generator);
+        if amount > bond {
+            panic_with_error!(&env, STRGRIDError::InsufficientBond);
+        }
+        set_bond(&env, &generator, bond - amount);
+        dispute::mark_slashed(&env, &challenge);
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        events::bond_slashed(&env, generator, challenge.token_id, amount, recipient);
+    }
+}
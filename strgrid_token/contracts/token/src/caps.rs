@@ -0,0 +1,130 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::ROLLUP_PERIOD_SECONDS;
+use crate::{
+    audit, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+};
+
+const MINT_CAPS: Symbol = StorageKey::MintCaps.symbol();
+const DAILY_MINTED: Symbol = StorageKey::DailyMinted.symbol();
+const MINT_COOLDOWN: Symbol = StorageKey::MintCooldown.symbol();
+
+/// Ledgers em um dia (~5s por ledger); o contador diário vive em storage temporário
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+pub use strgrid_interface::MintCaps;
+
+pub(crate) fn mint_caps(env: &Env) -> Option<MintCaps> {
+    env.storage().instance().get(&MINT_CAPS)
+}
+
+fn today(env: &Env) -> u64 {
+    env.ledger().timestamp() / ROLLUP_PERIOD_SECONDS
+}
+
+pub(crate) fn minted_today(env: &Env, generator: &Address) -> u64 {
+    env.storage()
+        .temporary()
+        .get(&(DAILY_MINTED, generator.clone(), today(env)))
+        .unwrap_or(0)
+}
+
+/// Rejeita mints fora dos limites configurados; `total_supply_after` já inclui o mint
+pub(crate) fn check_mint(
+    env: &Env,
+    generator: &Address,
+    amount: u64,
+    total_supply_after: i128,
+) -> Result<(), Error> {
+    let Some(caps) = mint_caps(env) else {
+        return Ok(());
+    };
+    if caps.min_mint.is_some_and(|min| amount < min) {
+        return Err(STRGRIDError::MintBelowMinimum.into());
+    }
+    if caps.max_mint.is_some_and(|max| amount > max) {
+        return Err(STRGRIDError::MintAboveMaximum.into());
+    }
+    if caps.max_total_supply.is_some_and(|max| total_supply_after > i128::from(max)) {
+        return Err(STRGRIDError::SupplyCapExceeded.into());
+    }
+    if caps
+        .max_daily_per_generator
+        .is_some_and(|max| minted_today(env, generator) + amount > max)
+    {
+        return Err(STRGRIDError::DailyMintCapExceeded.into());
+    }
+    Ok(())
+}
+
+pub(crate) fn mint_cooldown(env: &Env) -> u64 {
+    env.storage().instance().get(&MINT_COOLDOWN).unwrap_or(0)
+}
+
+/// Valida e grava os limites de emissão; a autorização fica a cargo de quem chama
+pub(crate) fn write_mint_caps(env: &Env, caps: &MintCaps) {
+    if let (Some(min), Some(max)) = (caps.min_mint, caps.max_mint) {
+        if min > max {
+            panic_with_error!(env, STRGRIDError::InvalidAmount);
+        }
+    }
+    env.storage().instance().set(&MINT_CAPS, caps);
+}
+
+pub(crate) fn write_mint_cooldown(env: &Env, seconds: u64) {
+    env.storage().instance().set(&MINT_COOLDOWN, &seconds);
+}
+
+/// Rejeita mints do mesmo gerador antes do fim do intervalo mínimo
+pub(crate) fn check_cooldown(env: &Env, last_mint_timestamp: Option<u64>) -> Result<(), Error> {
+    let cooldown = mint_cooldown(env);
+    if let Some(last_mint) = last_mint_timestamp {
+        if env.ledger().timestamp() < last_mint.saturating_add(cooldown) {
+            return Err(STRGRIDError::MintTooFrequent.into());
+        }
+    }
+    Ok(())
+}
+
+/// Acumula o volume emitido pelo gerador no dia corrente
+pub(crate) fn record_mint(env: &Env, generator: &Address, amount: u64) {
+    let key = (DAILY_MINTED, generator.clone(), today(env));
+    let minted: u64 = env.storage().temporary().get(&key).unwrap_or(0);
+    env.storage().temporary().set(&key, &(minted + amount));
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, DAY_IN_LEDGERS, DAY_IN_LEDGERS);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define os limites de emissão global e por gerador (apenas admin)
+    pub fn set_mint_caps(env: Env, caps: MintCaps) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_mint_caps", (caps.clone(),));
+        write_mint_caps(&env, &caps);
+    }
+
+    /// Consulta os limites de emissão vigentes, se houver
+    pub fn get_mint_caps(env: Env) -> Option<MintCaps> {
+        mint_caps(&env)
+    }
+
+    /// Define o intervalo mínimo, em segundos, entre mints do mesmo gerador; 0 desativa (apenas admin)
+    pub fn set_mint_cooldown(env: Env, seconds: u64) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_mint_cooldown", (seconds,));
+        write_mint_cooldown(&env, seconds);
+    }
+
+    /// Consulta o intervalo mínimo entre mints do mesmo gerador
+    pub fn get_mint_cooldown(env: Env) -> u64 {
+        mint_cooldown(&env)
+    }
+
+    /// Volume já emitido pelo gerador no dia corrente (UTC)
+    pub fn minted_today(env: Env, generator: Address) -> u64 {
+        minted_today(&env, &generator)
+    }
+}
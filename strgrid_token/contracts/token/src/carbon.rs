@@ -0,0 +1,65 @@
+use soroban_sdk::{contractimpl, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, require_admin, SourceType, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+};
+
+const EMISSION_FACTOR: Symbol = StorageKey::EmissionFactor.symbol();
+const CARBON_OFFSET: Symbol = StorageKey::CarbonOffset.symbol();
+const CARBON_TOTAL: Symbol = StorageKey::CarbonTotal.symbol();
+
+fn emission_factor(env: &Env, source_type: SourceType) -> u64 {
+    env.storage()
+        .instance()
+        .get(&(EMISSION_FACTOR, source_type))
+        .unwrap_or(0)
+}
+
+fn carbon_offset_of(env: &Env, address: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(CARBON_OFFSET, address.clone()))
+        .unwrap_or(0)
+}
+
+/// Acumula as emissões evitadas (gCO2) pelo consumo de `amount_kwh` da fonte informada
+pub(crate) fn record(env: &Env, consumer: &Address, source_type: SourceType, amount_kwh: u64) {
+    let avoided = emission_factor(env, source_type) * amount_kwh;
+    if avoided == 0 {
+        return;
+    }
+    env.storage().persistent().set(
+        &(CARBON_OFFSET, consumer.clone()),
+        &(carbon_offset_of(env, consumer) + avoided),
+    );
+    let total: u64 = env.storage().instance().get(&CARBON_TOTAL).unwrap_or(0);
+    env.storage().instance().set(&CARBON_TOTAL, &(total + avoided));
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o fator de emissão evitada de uma fonte, em gCO2/kWh (apenas admin)
+    pub fn set_emission_factor(env: Env, source_type: SourceType, grams_per_kwh: u64) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_emission_factor", (source_type, grams_per_kwh));
+        env.storage()
+            .instance()
+            .set(&(EMISSION_FACTOR, source_type), &grams_per_kwh);
+    }
+
+    /// Consulta o fator de emissão evitada de uma fonte, em gCO2/kWh
+    pub fn get_emission_factor(env: Env, source_type: SourceType) -> u64 {
+        emission_factor(&env, source_type)
+    }
+
+    /// Emissões evitadas (gCO2) acumuladas pelos consumos de um endereço
+    pub fn carbon_offset_of(env: Env, address: Address) -> u64 {
+        carbon_offset_of(&env, &address)
+    }
+
+    /// Emissões evitadas (gCO2) acumuladas por todos os consumos
+    pub fn total_carbon_offset(env: Env) -> u64 {
+        env.storage().instance().get(&CARBON_TOTAL).unwrap_or(0)
+    }
+}
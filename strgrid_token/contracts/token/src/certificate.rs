@@ -0,0 +1,144 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, Env, String, Symbol, Vec,
+};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, EnergyToken, SourceType, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+pub(crate) const CERTIFICATE: Symbol = StorageKey::Certificate.symbol();
+const CERTIFICATE_SEQ: Symbol = StorageKey::CertificateSeq.symbol();
+const CERTIFICATES_OF: Symbol = StorageKey::CertificatesOf.symbol();
+const RETIREMENT: Symbol = StorageKey::Retirement.symbol();
+
+/// Certificado de energia renovável (REC) intransferível emitido a cada consumo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Certificate {
+    pub id: u64,
+    pub consumer: Address,
+    pub token_id: u64,
+    pub generator: Address,
+    pub amount_kwh: u64,
+    pub source_type: SourceType,
+    /// Safra: momento da geração (emissão do lote)
+    pub vintage: u64,
+    pub retired_at: u64,
+    /// CID (IPFS) do documento do certificado, mantido off-chain
+    pub metadata_cid: Option<String>,
+}
+
+/// Reivindicação imutável de um REC em nome de um beneficiário (relatórios de sustentabilidade)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Retirement {
+    pub cert_id: u64,
+    pub owner: Address,
+    pub beneficiary: Address,
+    pub purpose: String,
+    pub timestamp: u64,
+}
+
+/// Emite o REC correspondente ao consumo de `amount_kwh` do lote
+pub(crate) fn issue(env: &Env, consumer: &Address, token: &EnergyToken, amount_kwh: u64) -> u64 {
+    let id: u64 = env.storage().instance().get(&CERTIFICATE_SEQ).unwrap_or(0) + 1;
+    env.storage().instance().set(&CERTIFICATE_SEQ, &id);
+
+    let certificate = Certificate {
+        id,
+        consumer: consumer.clone(),
+        token_id: token.id,
+        generator: token.generator_id.clone(),
+        amount_kwh,
+        source_type: token.source_type,
+        vintage: token.creation_timestamp,
+        retired_at: env.ledger().timestamp(),
+        metadata_cid: None,
+    };
+    env.storage().persistent().set(&(CERTIFICATE, id), &certificate);
+
+    let key = (CERTIFICATES_OF, consumer.clone());
+    let mut ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+
+    events::certificate_issued(env, id, consumer.clone(), amount_kwh);
+    id
+}
+
+/// Passa os certificados de `from`, aposentados ou não, para `to`
+pub(crate) fn reassign(env: &Env, from: &Address, to: &Address) -> u32 {
+    let Some(ids) = env
+        .storage()
+        .persistent()
+        .get::<_, Vec<u64>>(&(CERTIFICATES_OF, from.clone()))
+    else {
+        return 0;
+    };
+    let to_key = (CERTIFICATES_OF, to.clone());
+    let mut to_ids: Vec<u64> = env.storage().persistent().get(&to_key).unwrap_or(Vec::new(env));
+    for id in ids.iter() {
+        let mut certificate: Certificate = env.storage().persistent().get(&(CERTIFICATE, id)).unwrap();
+        certificate.consumer = to.clone();
+        env.storage().persistent().set(&(CERTIFICATE, id), &certificate);
+        to_ids.push_back(id);
+    }
+    env.storage().persistent().set(&to_key, &to_ids);
+    env.storage()
+        .persistent()
+        .remove(&(CERTIFICATES_OF, from.clone()));
+    ids.len()
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Consulta um certificado de energia renovável
+    pub fn get_certificate(env: Env, cert_id: u64) -> Certificate {
+        env.storage()
+            .persistent()
+            .get(&(CERTIFICATE, cert_id))
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::CertificateNotFound))
+    }
+
+    /// Aposenta um certificado em favor de um beneficiário; só pode ocorrer uma vez
+    pub fn retire(env: Env, cert_id: u64, beneficiary: Address, purpose: String) {
+        let certificate = Self::get_certificate(env.clone(), cert_id);
+        certificate.consumer.require_auth();
+
+        let key = (RETIREMENT, cert_id);
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, STRGRIDError::CertificateRetired);
+        }
+        let retirement = Retirement {
+            cert_id,
+            owner: certificate.consumer,
+            beneficiary,
+            purpose,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &retirement);
+
+        events::certificate_retired(&env, retirement);
+    }
+
+    /// Consulta a aposentadoria de um certificado, se houver
+    pub fn get_retirement(env: Env, cert_id: u64) -> Option<Retirement> {
+        env.storage().persistent().get(&(RETIREMENT, cert_id))
+    }
+
+    /// Lista os certificados emitidos para um consumidor, em ordem de emissão
+    pub fn certificates_of(env: Env, consumer: Address) -> Vec<Certificate> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&(CERTIFICATES_OF, consumer))
+            .unwrap_or(Vec::new(&env));
+        let mut certificates = Vec::new(&env);
+        for id in ids.iter() {
+            certificates.push_back(Self::get_certificate(env.clone(), id));
+        }
+        certificates
+    }
+}
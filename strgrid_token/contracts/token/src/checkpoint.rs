@@ -0,0 +1,107 @@
+use soroban_sdk::{contractimpl, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient};
+
+const BALANCE_CHECKPOINTS: Symbol = StorageKey::BalanceCheckpoints.symbol();
+const SUPPLY_CHECKPOINTS: Symbol = StorageKey::SupplyCheckpoints.symbol();
+
+/// Lê a série em i128; séries gravadas em u64 antes da migração são convertidas na leitura
+fn load<K>(env: &Env, key: &K) -> Option<Vec<(u32, i128)>>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    // A conversão de `Vec` só valida os elementos no acesso, então o formato é decidido pelo primeiro
+    let checkpoints: Vec<(u32, i128)> = env.storage().persistent().get(key)?;
+    if checkpoints.try_first().is_ok() {
+        return Some(checkpoints);
+    }
+    let legacy: Vec<(u32, u64)> = env.storage().persistent().get(key)?;
+    let mut checkpoints = Vec::new(env);
+    for (ledger, value) in legacy.iter() {
+        checkpoints.push_back((ledger, i128::from(value)));
+    }
+    Some(checkpoints)
+}
+
+/// Regrava no formato i128 uma série ainda em u64
+fn migrate<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    if let Some(checkpoints) = load(env, key) {
+        env.storage().persistent().set(key, &checkpoints);
+    }
+}
+
+/// Acrescenta o valor do ledger corrente à série; várias mudanças no mesmo ledger ficam em um só ponto
+fn push<K>(env: &Env, key: &K, value: i128)
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let ledger = env.ledger().sequence();
+    let mut checkpoints = load(env, key).unwrap_or(Vec::new(env));
+    match checkpoints.last() {
+        Some((last_ledger, _)) if last_ledger == ledger => {
+            checkpoints.set(checkpoints.len() - 1, (ledger, value))
+        }
+        _ => checkpoints.push_back((ledger, value)),
+    }
+    env.storage().persistent().set(key, &checkpoints);
+}
+
+/// Valor vigente ao fim de `ledger_seq`: o último ponto com ledger menor ou igual, por busca binária
+fn value_at<K>(env: &Env, key: &K, ledger_seq: u32) -> i128
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let checkpoints = match load(env, key) {
+        Some(checkpoints) => checkpoints,
+        None => return 0,
+    };
+    let (mut low, mut high) = (0u32, checkpoints.len());
+    while low < high {
+        let mid = (low + high) / 2;
+        if checkpoints.get_unchecked(mid).0 <= ledger_seq {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        0
+    } else {
+        checkpoints.get_unchecked(low - 1).1
+    }
+}
+
+/// Registra o novo saldo de um endereço no ledger corrente
+pub(crate) fn record_balance(env: &Env, account: &Address, balance: i128) {
+    push(env, &(BALANCE_CHECKPOINTS, account.clone()), balance);
+}
+
+/// Registra o novo supply total no ledger corrente
+pub(crate) fn record_supply(env: &Env, supply: i128) {
+    push(env, &SUPPLY_CHECKPOINTS, supply);
+}
+
+pub(crate) fn migrate_balance(env: &Env, account: &Address) {
+    migrate(env, &(BALANCE_CHECKPOINTS, account.clone()));
+}
+
+pub(crate) fn migrate_supply(env: &Env) {
+    migrate(env, &SUPPLY_CHECKPOINTS);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Saldo de um endereço ao fim do ledger `ledger_seq`
+    pub fn balance_at(env: Env, address: Address, ledger_seq: u32) -> i128 {
+        value_at(&env, &(BALANCE_CHECKPOINTS, address), ledger_seq)
+    }
+
+    /// Supply total ao fim do ledger `ledger_seq`
+    pub fn supply_at(env: Env, ledger_seq: u32) -> i128 {
+        value_at(&env, &SUPPLY_CHECKPOINTS, ledger_seq)
+    }
+}
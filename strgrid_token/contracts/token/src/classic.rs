@@ -0,0 +1,89 @@
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::migration;
+use crate::{
+    audit, events, move_balance, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError,
+};
+
+const CLASSIC_ASSET: Symbol = StorageKey::ClassicAsset.symbol();
+const CLASSIC_LOCKED: Symbol = StorageKey::ClassicLocked.symbol();
+
+fn classic_asset(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&CLASSIC_ASSET)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::ClassicAssetNotConfigured))
+}
+
+/// kWh em custódia, aceitando o valor gravado em u64 antes da migração para i128
+fn locked(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&CLASSIC_LOCKED))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o Stellar Asset Contract do ativo clássico STRGRID (apenas admin)
+    ///
+    /// O contrato precisa já ser o admin do SAC, pois é ele quem emite o ativo clássico no
+    /// `unwrap`.
+    pub fn set_classic_asset(env: Env, asset: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_classic_asset", (asset.clone(),));
+        if token::StellarAssetClient::new(&env, &asset).admin() != env.current_contract_address() {
+            panic_with_error!(&env, STRGRIDExtError::ClassicAssetNotAdmin);
+        }
+        env.storage().instance().set(&CLASSIC_ASSET, &asset);
+        events::classic_asset_set(&env, asset);
+    }
+
+    /// Stellar Asset Contract do ativo clássico, se configurado
+    pub fn get_classic_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CLASSIC_ASSET)
+    }
+
+    /// kWh em custódia do contrato lastreando o ativo clássico em circulação (1:1)
+    pub fn classic_locked(env: Env) -> i128 {
+        locked(&env)
+    }
+
+    /// Converte saldo STRGRID em ativo clássico: trava os kWh em custódia e emite o mesmo valor
+    /// no SAC para a conta, que passa a custodiá-lo em contas clássicas e negociá-lo na SDEX
+    pub fn unwrap(env: Env, account: Address, amount: i128) {
+        account.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let asset = classic_asset(&env);
+        if Self::balance_of(env.clone(), account.clone()) < amount {
+            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        }
+
+        move_balance(&env, &account, &env.current_contract_address(), amount, OpType::Wrap);
+        env.storage()
+            .instance()
+            .set(&CLASSIC_LOCKED, &migration::checked_add(&env, locked(&env), amount));
+        token::StellarAssetClient::new(&env, &asset).mint(&account, &amount);
+        events::unwrapped(&env, account, amount);
+    }
+
+    /// Resgata ativo clássico em STRGRID: queima o ativo no SAC e libera os kWh da custódia
+    pub fn wrap(env: Env, account: Address, amount: i128) {
+        account.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let asset = classic_asset(&env);
+        let custody = locked(&env);
+        if amount > custody {
+            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        }
+
+        token::Client::new(&env, &asset).burn(&account, &amount);
+        env.storage().instance().set(&CLASSIC_LOCKED, &(custody - amount));
+        move_balance(&env, &env.current_contract_address(), &account, amount, OpType::Wrap);
+        events::wrapped(&env, account, amount);
+    }
+}
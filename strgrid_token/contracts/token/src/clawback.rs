@@ -0,0 +1,62 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::{self, JournalAccount, OpType};
+use crate::{
+    audit, checkpoint, events, holders, read_balance, read_total_supply, require_admin, stats,
+    write_balance, write_total_supply, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError,
+};
+
+pub(crate) const CLAWBACK_ENABLED: Symbol = StorageKey::ClawbackEnabled.symbol();
+
+fn clawback_enabled(env: &Env) -> bool {
+    env.storage().instance().get(&CLAWBACK_ENABLED).unwrap_or(false)
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Recupera (queima) tokens de uma conta comprometida ou sancionada (apenas admin)
+    ///
+    /// Só disponível se habilitado no `__constructor`; ignora congelamento e travas de
+    /// contestação.
+    pub fn clawback(env: Env, from: Address, amount: i128) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "clawback", (from.clone(), amount));
+        if !clawback_enabled(&env) {
+            panic_with_error!(&env, STRGRIDError::ClawbackDisabled);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        let balance = read_balance(&env, &from);
+        if balance < amount {
+            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        }
+        let balance = balance - amount;
+        write_balance(&env, &from, balance);
+        checkpoint::record_balance(&env, &from, balance);
+        holders::record_balance(&env, &from, balance);
+
+        let total_supply = read_total_supply(&env) - amount;
+        write_total_supply(&env, total_supply);
+        checkpoint::record_supply(&env, total_supply);
+        stats::record_burn(&env, amount);
+
+        journal::record(
+            &env,
+            JournalAccount::Retired,
+            JournalAccount::Holder(from.clone()),
+            amount,
+            OpType::Clawback,
+            0,
+        );
+        events::clawback(&env, admin, from, amount);
+    }
+
+    /// Consulta se o contrato foi inicializado com clawback habilitado
+    pub fn is_clawback_enabled(env: Env) -> bool {
+        clawback_enabled(&env)
+    }
+}
@@ -0,0 +1,230 @@
+use soroban_sdk::{
+    contractclient, contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol,
+};
+use strgrid_codes::StorageKey;
+
+use crate::fee::BPS_DENOMINATOR;
+use crate::forward::{self, Forward, ForwardStatus};
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, STRGRIDExtError,
+};
+
+const COLLATERAL_CONFIG: Symbol = StorageKey::CollateralConfig.symbol();
+const FORWARD_MARGIN: Symbol = StorageKey::ForwardMargin.symbol();
+
+/// Preço no formato `lastprice` da SEP-40
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Interface SEP-40 do oracle que marca as obrigações a mercado
+///
+/// `lastprice(payment_token)` deve devolver o preço de um kWh no ativo de pagamento.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn lastprice(env: Env, asset: Address) -> Option<PriceData>;
+}
+
+/// Margens exigidas dos vendedores de contratos a termo, em basis points do valor a mercado
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralConfig {
+    pub price_oracle: Address,
+    /// Colateral travado na venda
+    pub initial_margin_bps: u32,
+    /// Abaixo dele qualquer um pode fazer a chamada de margem
+    pub maintenance_margin_bps: u32,
+    /// Prazo para recompor a margem antes da liquidação
+    pub margin_call_period: u64,
+}
+
+/// Colateral travado por uma obrigação aberta e a chamada de margem pendente, se houver
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForwardMargin {
+    pub collateral: i128,
+    pub margin_call_deadline: Option<u64>,
+}
+
+fn collateral_config(env: &Env) -> Option<CollateralConfig> {
+    env.storage().instance().get(&COLLATERAL_CONFIG)
+}
+
+fn require_config(env: &Env) -> CollateralConfig {
+    collateral_config(env)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::CollateralNotConfigured))
+}
+
+fn margin_of(env: &Env, forward_id: u64) -> ForwardMargin {
+    env.storage()
+        .persistent()
+        .get(&(FORWARD_MARGIN, forward_id))
+        .unwrap_or(ForwardMargin {
+            collateral: 0,
+            margin_call_deadline: None,
+        })
+}
+
+fn save_margin(env: &Env, forward_id: u64, margin: &ForwardMargin) {
+    env.storage()
+        .persistent()
+        .set(&(FORWARD_MARGIN, forward_id), margin);
+}
+
+/// Colateral exigido pela obrigação ao preço corrente do oracle
+fn required(env: &Env, config: &CollateralConfig, forward: &Forward, margin_bps: u32) -> i128 {
+    let mark = PriceOracleClient::new(env, &config.price_oracle)
+        .lastprice(&forward.payment_token)
+        .filter(|data| data.price > 0)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::PriceUnavailable));
+    mark.price
+        .checked_mul(forward.amount_kwh as i128)
+        .and_then(|value| value.checked_mul(margin_bps as i128))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::InvalidAmount))
+        / BPS_DENOMINATOR as i128
+}
+
+/// Trava o colateral inicial de uma venda a termo, se houver margens configuradas
+pub(crate) fn lock_initial(env: &Env, forward: &Forward) {
+    let Some(config) = collateral_config(env) else {
+        return;
+    };
+    let collateral = required(env, &config, forward, config.initial_margin_bps);
+    if collateral > 0 {
+        token::Client::new(env, &forward.payment_token).transfer(
+            &forward.generator,
+            &env.current_contract_address(),
+            &collateral,
+        );
+    }
+    save_margin(
+        env,
+        forward.id,
+        &ForwardMargin {
+            collateral,
+            margin_call_deadline: None,
+        },
+    );
+}
+
+/// Libera o colateral de uma obrigação encerrada para `to` e devolve o valor liberado
+pub(crate) fn release(env: &Env, forward: &Forward, to: &Address) -> i128 {
+    let margin = margin_of(env, forward.id);
+    if margin.collateral > 0 {
+        token::Client::new(env, &forward.payment_token).transfer(
+            &env.current_contract_address(),
+            to,
+            &margin.collateral,
+        );
+    }
+    env.storage()
+        .persistent()
+        .remove(&(FORWARD_MARGIN, forward.id));
+    margin.collateral
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define as margens dos contratos a termo; `None` deixa de exigir colateral em novas vendas
+    /// (apenas admin)
+    pub fn set_collateral_config(env: Env, config: Option<CollateralConfig>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_collateral_config", (config.clone(),));
+        match &config {
+            Some(config) if config.maintenance_margin_bps > config.initial_margin_bps => {
+                panic_with_error!(&env, STRGRIDError::InvalidAmount)
+            }
+            Some(config) => env.storage().instance().set(&COLLATERAL_CONFIG, config),
+            None => env.storage().instance().remove(&COLLATERAL_CONFIG),
+        }
+        events::collateral_config_set(&env, config);
+    }
+
+    /// Consulta as margens vigentes, se configuradas
+    pub fn get_collateral_config(env: Env) -> Option<CollateralConfig> {
+        collateral_config(&env)
+    }
+
+    /// Colateral e chamada de margem de um contrato a termo
+    pub fn get_forward_margin(env: Env, forward_id: u64) -> ForwardMargin {
+        margin_of(&env, forward_id)
+    }
+
+    /// Reforça o colateral de uma obrigação aberta; atendida a margem de manutenção, a chamada
+    /// pendente é encerrada
+    pub fn add_collateral(env: Env, forward_id: u64, amount: i128) {
+        let forward = forward::load_open(&env, forward_id);
+        forward.generator.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        token::Client::new(&env, &forward.payment_token).transfer(
+            &forward.generator,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut margin = margin_of(&env, forward_id);
+        margin.collateral += amount;
+        if let Some(config) = collateral_config(&env) {
+            if margin.collateral >= required(&env, &config, &forward, config.maintenance_margin_bps) {
+                margin.margin_call_deadline = None;
+            }
+        }
+        save_margin(&env, forward_id, &margin);
+        events::collateral_added(&env, forward_id, amount, margin.collateral);
+    }
+
+    /// Abre a chamada de margem de uma obrigação abaixo da margem de manutenção; qualquer um
+    /// pode chamar
+    pub fn margin_call(env: Env, forward_id: u64) -> u64 {
+        let config = require_config(&env);
+        let forward = forward::load_open(&env, forward_id);
+        let mut margin = margin_of(&env, forward_id);
+        let required = required(&env, &config, &forward, config.maintenance_margin_bps);
+        if margin.collateral >= required {
+            panic_with_error!(&env, STRGRIDExtError::MarginSufficient);
+        }
+        if let Some(deadline) = margin.margin_call_deadline {
+            return deadline;
+        }
+
+        let deadline = env.ledger().timestamp() + config.margin_call_period;
+        margin.margin_call_deadline = Some(deadline);
+        save_margin(&env, forward_id, &margin);
+        events::margin_called(&env, forward_id, required - margin.collateral, deadline);
+        deadline
+    }
+
+    /// Liquida a obrigação cuja chamada de margem venceu sem recomposição: o comprador recebe o
+    /// preço de volta, a multa e o colateral; qualquer um pode chamar
+    pub fn liquidate_forward(env: Env, forward_id: u64) -> i128 {
+        let config = require_config(&env);
+        let mut forward = forward::load_open(&env, forward_id);
+        let margin = margin_of(&env, forward_id);
+        match margin.margin_call_deadline {
+            Some(deadline) if env.ledger().timestamp() >= deadline => {}
+            _ => panic_with_error!(&env, STRGRIDExtError::MarginCallNotDue),
+        }
+        // O preço pode ter voltado a favor do vendedor durante o prazo
+        if margin.collateral >= required(&env, &config, &forward, config.maintenance_margin_bps) {
+            panic_with_error!(&env, STRGRIDExtError::MarginSufficient);
+        }
+
+        forward.status = ForwardStatus::Liquidated;
+        forward::save(&env, &forward);
+        let collateral = release(&env, &forward, &forward.buyer);
+        token::Client::new(&env, &forward.payment_token).transfer(
+            &env.current_contract_address(),
+            &forward.buyer,
+            &(forward.price + forward.penalty),
+        );
+
+        events::forward_liquidated(&env, forward_id, forward.generator, collateral);
+        forward.price + forward.penalty + collateral
+    }
+}
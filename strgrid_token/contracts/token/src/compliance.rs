@@ -0,0 +1,59 @@
+use soroban_sdk::{contractclient, contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDExtError,
+};
+
+const COMPLIANCE_HOOK: Symbol = StorageKey::ComplianceHook.symbol();
+
+/// Interface do contrato de política consultado antes de cada transferência
+///
+/// O hook não pode chamar o token de volta: o Soroban rejeita reentrância no mesmo contrato.
+#[contractclient(name = "ComplianceHookClient")]
+pub trait ComplianceHook {
+    /// `true` autoriza a movimentação de `amount` kWh de `from` para `to`
+    fn check_transfer(env: Env, from: Address, to: Address, amount: i128) -> bool;
+}
+
+fn compliance_hook(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&COMPLIANCE_HOOK)
+}
+
+/// Consulta o hook configurado, se houver, e rejeita a transferência negada
+///
+/// Movimentações com o próprio contrato numa das pontas (taxas, custódia de leilões, ativo
+/// clássico) não passam pelo hook. Quando a custódia só intermedeia uma venda, quem a liquida
+/// consulta o hook com o par real: o lance de leilão checa vendedor → comprador.
+pub(crate) fn check_transfer(env: &Env, from: &Address, to: &Address, amount: i128) {
+    let Some(hook) = compliance_hook(env) else {
+        return;
+    };
+    let contract = env.current_contract_address();
+    if *from == contract || *to == contract {
+        return;
+    }
+    if !ComplianceHookClient::new(env, &hook).check_transfer(from, to, &amount) {
+        panic_with_error!(env, STRGRIDExtError::TransferRejected);
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o contrato de compliance consultado antes de cada transferência; `None` remove (apenas admin)
+    pub fn set_compliance_hook(env: Env, hook: Option<Address>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_compliance_hook", (hook.clone(),));
+        match &hook {
+            Some(hook) => env.storage().instance().set(&COMPLIANCE_HOOK, hook),
+            None => env.storage().instance().remove(&COMPLIANCE_HOOK),
+        }
+        events::compliance_hook_set(&env, hook);
+    }
+
+    /// Contrato de compliance configurado, se houver
+    pub fn get_compliance_hook(env: Env) -> Option<Address> {
+        compliance_hook(&env)
+    }
+}
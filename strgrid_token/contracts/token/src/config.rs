@@ -0,0 +1,148 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Vec};
+
+use crate::caps::{self, MintCaps};
+use crate::fee::{self, TransferFee};
+use crate::roles::{has_role, Role};
+use crate::{
+    audit, dispute, events, oracle, strict, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, ADMIN,
+};
+
+/// Parâmetros ajustáveis do token reunidos em uma única consulta
+///
+/// Também é passado ao construtor no deploy; o `Default` deixa tudo desligado.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Config {
+    /// Zero quando não há taxa configurada
+    pub transfer_fee_bps: u32,
+    pub treasury: Option<Address>,
+    /// Sem limites configurados, todos os campos vêm vazios
+    pub mint_caps: MintCaps,
+    pub mint_cooldown: u64,
+    pub dispute_window: u64,
+    pub strict_mode: bool,
+    pub mint_oracle: Option<Address>,
+}
+
+/// Alteração de um parâmetro em `set_config`; os demais ficam como estão
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigChange {
+    /// `fee_bps` zero desativa a cobrança
+    TransferFee(TransferFee),
+    MintCaps(MintCaps),
+    MintCooldown(u64),
+    DisputeWindow(u64),
+    StrictMode(bool),
+    /// `None` desliga a validação por oracle
+    MintOracle(Option<Address>),
+}
+
+fn config(env: &Env) -> Config {
+    let transfer_fee = fee::transfer_fee(env);
+    Config {
+        transfer_fee_bps: transfer_fee.as_ref().map_or(0, |fee| fee.fee_bps),
+        treasury: transfer_fee.map(|fee| fee.treasury),
+        mint_caps: caps::mint_caps(env).unwrap_or_default(),
+        mint_cooldown: caps::mint_cooldown(env),
+        dispute_window: dispute::dispute_window(env),
+        strict_mode: strict::is_strict(env),
+        mint_oracle: oracle::mint_oracle(env),
+    }
+}
+
+/// Grava a configuração recebida pelo construtor; parâmetros no valor padrão não são gravados
+pub(crate) fn apply(env: &Env, config: &Config) {
+    match &config.treasury {
+        Some(treasury) => fee::write_transfer_fee(
+            env,
+            &TransferFee {
+                fee_bps: config.transfer_fee_bps,
+                treasury: treasury.clone(),
+            },
+        ),
+        None if config.transfer_fee_bps > 0 => {
+            panic_with_error!(env, STRGRIDError::InvalidAmount)
+        }
+        None => {}
+    }
+    if config.mint_caps != MintCaps::default() {
+        caps::write_mint_caps(env, &config.mint_caps);
+    }
+    if config.mint_cooldown > 0 {
+        caps::write_mint_cooldown(env, config.mint_cooldown);
+    }
+    if config.dispute_window > 0 {
+        dispute::write_dispute_window(env, config.dispute_window);
+    }
+    if config.strict_mode {
+        strict::write_strict_mode(env, true);
+    }
+    if config.mint_oracle.is_some() {
+        oracle::write_mint_oracle(env, config.mint_oracle.clone());
+    }
+}
+
+/// Exige autorização do admin ou de uma conta com o papel `ConfigManager`
+fn require_config_manager(env: &Env, caller: &Address) {
+    caller.require_auth();
+    let admin: Option<Address> = env.storage().instance().get(&ADMIN);
+    if admin.as_ref() != Some(caller) && !has_role(env, caller, Role::ConfigManager) {
+        panic_with_error!(env, STRGRIDError::NotAuthorized);
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Parâmetros vigentes de taxa, emissão, contestação e validação
+    pub fn get_config(env: Env) -> Config {
+        config(&env)
+    }
+
+    /// Aplica as alterações em ordem (admin ou `ConfigManager`) e publica o valor anterior e o
+    /// novo de cada parâmetro alterado
+    pub fn set_config(env: Env, caller: Address, changes: Vec<ConfigChange>) -> Config {
+        require_config_manager(&env, &caller);
+        audit::record(&env, &caller, "set_config", (changes.clone(),));
+        if changes.is_empty() {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        for change in changes.iter() {
+            let before = config(&env);
+            match change.clone() {
+                ConfigChange::TransferFee(transfer_fee) => {
+                    strict::check_update(
+                        &env,
+                        &(before.transfer_fee_bps, before.treasury.clone()),
+                        &(transfer_fee.fee_bps, Some(transfer_fee.treasury.clone())),
+                    );
+                    fee::write_transfer_fee(&env, &transfer_fee);
+                }
+                ConfigChange::MintCaps(mint_caps) => {
+                    strict::check_update(&env, &before.mint_caps, &mint_caps);
+                    caps::write_mint_caps(&env, &mint_caps);
+                }
+                ConfigChange::MintCooldown(seconds) => {
+                    strict::check_update(&env, &before.mint_cooldown, &seconds);
+                    caps::write_mint_cooldown(&env, seconds);
+                }
+                ConfigChange::DisputeWindow(seconds) => {
+                    strict::check_update(&env, &before.dispute_window, &seconds);
+                    dispute::write_dispute_window(&env, seconds);
+                }
+                ConfigChange::StrictMode(enabled) => {
+                    strict::check_update(&env, &before.strict_mode, &enabled);
+                    strict::write_strict_mode(&env, enabled);
+                }
+                ConfigChange::MintOracle(mint_oracle) => {
+                    strict::check_update(&env, &before.mint_oracle, &mint_oracle);
+                    oracle::write_mint_oracle(&env, mint_oracle);
+                }
+            }
+            events::config_changed(&env, caller.clone(), change, &before);
+        }
+        config(&env)
+    }
+}
@@ -0,0 +1,78 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::fee::BPS_DENOMINATOR;
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, events, loss, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+};
+
+const CONGESTION: Symbol = StorageKey::Congestion.symbol();
+
+/// Multiplicador máximo de congestionamento, em basis points (5x)
+pub const MAX_CONGESTION_BPS: u32 = 50_000;
+
+/// Estado de congestionamento declarado pelo operador da rede para uma zona
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ZoneCongestion {
+    pub zone: u32,
+    pub multiplier_bps: u32,
+    pub until: u64,
+}
+
+fn load(env: &Env, zone: u32) -> Option<ZoneCongestion> {
+    env.storage().persistent().get(&(CONGESTION, zone))
+}
+
+/// Multiplicador vigente da zona; sem congestionamento declarado ou após o prazo vale 1x
+fn multiplier(env: &Env, zone: u32) -> u32 {
+    match load(env, zone) {
+        Some(congestion) if env.ledger().timestamp() < congestion.until => {
+            congestion.multiplier_bps
+        }
+        _ => BPS_DENOMINATOR,
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Declara a zona congestionada até `until`, com multiplicador de preço em bps (apenas operador da rede)
+    pub fn set_congestion(env: Env, operator: Address, zone: u32, multiplier_bps: u32, until: u64) {
+        require_role(&env, &operator, Role::GridOperator);
+        audit::record(&env, &operator, "set_congestion", (zone, multiplier_bps, until));
+        if !(BPS_DENOMINATOR..=MAX_CONGESTION_BPS).contains(&multiplier_bps)
+            || until <= env.ledger().timestamp()
+        {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let congestion = ZoneCongestion {
+            zone,
+            multiplier_bps,
+            until,
+        };
+        env.storage().persistent().set(&(CONGESTION, zone), &congestion);
+        events::congestion_set(&env, &congestion);
+    }
+
+    /// Encerra antecipadamente o congestionamento de uma zona (apenas operador da rede)
+    pub fn clear_congestion(env: Env, operator: Address, zone: u32) {
+        require_role(&env, &operator, Role::GridOperator);
+        audit::record(&env, &operator, "clear_congestion", (zone,));
+        if multiplier(&env, zone) == BPS_DENOMINATOR {
+            panic_with_error!(&env, STRGRIDError::NoOpUpdate);
+        }
+        env.storage().persistent().remove(&(CONGESTION, zone));
+        events::congestion_cleared(&env, zone);
+    }
+
+    /// Multiplicador de preço vigente da zona, em bps (10.000 = sem congestionamento)
+    pub fn congestion_multiplier(env: Env, zone: u32) -> u32 {
+        multiplier(&env, zone)
+    }
+
+    /// Zona da rede de um gerador ou consumidor cadastrado
+    pub fn zone_of(env: Env, account: Address) -> Option<u32> {
+        loss::zone_of(&env, &account)
+    }
+}
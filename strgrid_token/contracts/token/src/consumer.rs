@@ -0,0 +1,96 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const CONSUMER: Symbol = StorageKey::Consumer.symbol();
+const CONSUMER_GATE: Symbol = StorageKey::ConsumerGate.symbol();
+
+/// Classe tarifária do consumidor
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConsumerClass {
+    Residential,
+    Commercial,
+    Industrial,
+}
+
+/// Perfil de consumo declarado no cadastro
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumerProfile {
+    pub class: ConsumerClass,
+    pub zone: u32,
+    pub expected_monthly_kwh: u64,
+}
+
+pub(crate) fn profile_of(env: &Env, account: &Address) -> Option<ConsumerProfile> {
+    env.storage().persistent().get(&(CONSUMER, account.clone()))
+}
+
+/// Passa o cadastro de `from` para `to`, se houver
+pub(crate) fn move_profile(env: &Env, from: &Address, to: &Address) {
+    if let Some(profile) = profile_of(env, from) {
+        env.storage().persistent().remove(&(CONSUMER, from.clone()));
+        env.storage().persistent().set(&(CONSUMER, to.clone()), &profile);
+    }
+}
+
+fn is_gated(env: &Env) -> bool {
+    env.storage().instance().get(&CONSUMER_GATE).unwrap_or(false)
+}
+
+pub(crate) fn is_registered(env: &Env, account: &Address) -> bool {
+    env.storage().persistent().has(&(CONSUMER, account.clone()))
+}
+
+/// Com o cadastro obrigatório ativo, apenas consumidores cadastrados podem consumir ou comprar
+pub(crate) fn is_eligible(env: &Env, account: &Address) -> bool {
+    !is_gated(env) || is_registered(env, account)
+}
+
+pub(crate) fn require_eligible(env: &Env, account: &Address) -> Result<(), Error> {
+    if !is_eligible(env, account) {
+        return Err(STRGRIDError::ConsumerNotRegistered.into());
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Cadastra ou atualiza o perfil de consumo de um endereço
+    pub fn register_consumer(env: Env, consumer: Address, profile: ConsumerProfile) {
+        consumer.require_auth();
+        env.storage()
+            .persistent()
+            .set(&(CONSUMER, consumer.clone()), &profile);
+        events::consumer_registered(&env, consumer, profile);
+    }
+
+    /// Consulta o perfil de um consumidor cadastrado
+    pub fn get_consumer(env: Env, consumer: Address) -> ConsumerProfile {
+        profile_of(&env, &consumer)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ConsumerNotRegistered))
+    }
+
+    /// Ativa/desativa a exigência de cadastro para burns e compras (apenas admin)
+    pub fn set_registration_required(env: Env, required: bool) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_registration_required", (required,));
+        env.storage().instance().set(&CONSUMER_GATE, &required);
+        events::consumer_gate_set(&env, required);
+    }
+
+    /// Consulta se o cadastro de consumidor é exigido
+    pub fn is_registration_required(env: Env) -> bool {
+        is_gated(&env)
+    }
+
+    /// Consulta se um endereço pode consumir ou comprar energia sob a regra de cadastro vigente
+    pub fn is_eligible_consumer(env: Env, account: Address) -> bool {
+        is_eligible(&env, &account)
+    }
+}
@@ -0,0 +1,36 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::{view, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient};
+
+const CONSUMPTION: Symbol = StorageKey::Consumption.symbol();
+
+/// Detalhe de um consumo (burn) registrado para o consumidor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumptionRecord {
+    pub token_id: u64,
+    pub generator: Address,
+    pub amount_kwh: u64,
+    pub timestamp: u64,
+}
+
+pub(crate) fn record(env: &Env, consumer: &Address, record: ConsumptionRecord) {
+    let key = (CONSUMPTION, consumer.clone());
+    let mut records: Vec<ConsumptionRecord> =
+        env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    records.push_back(record);
+    env.storage().persistent().set(&key, &records);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Consulta restrita do detalhe de consumo de um consumidor
+    pub fn consumption_of(env: Env, viewer: Address, consumer: Address) -> Vec<ConsumptionRecord> {
+        view::require_viewer(&env, &consumer, &viewer);
+        env.storage()
+            .persistent()
+            .get(&(CONSUMPTION, consumer))
+            .unwrap_or(Vec::new(&env))
+    }
+}
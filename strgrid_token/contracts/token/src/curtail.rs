@@ -0,0 +1,148 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, events, EnergyGenerator, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, STRGRIDExtError,
+};
+
+const CURTAILMENT: Symbol = StorageKey::Curtailment.symbol();
+const CURTAILMENT_SEQ: Symbol = StorageKey::CurtailmentSeq.symbol();
+const CURTAILMENTS_OF: Symbol = StorageKey::CurtailmentsOf.symbol();
+
+/// Ordem do operador da rede que limita a capacidade efetiva de um gerador durante uma janela
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CurtailmentOrder {
+    pub id: u64,
+    pub operator: Address,
+    pub generator: Address,
+    pub max_capacity_kw: u64,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub is_cancelled: bool,
+}
+
+impl CurtailmentOrder {
+    pub fn is_active(&self, now: u64) -> bool {
+        !self.is_cancelled && now >= self.starts_at && now < self.ends_at
+    }
+}
+
+fn load(env: &Env, order_id: u64) -> CurtailmentOrder {
+    env.storage()
+        .persistent()
+        .get(&(CURTAILMENT, order_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::CurtailmentNotFound))
+}
+
+fn orders_of(env: &Env, generator: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(CURTAILMENTS_OF, generator.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Capacidade efetiva: a registrada, reduzida pela ordem ativa mais restritiva
+pub(crate) fn effective_capacity(env: &Env, generator: &EnergyGenerator) -> u64 {
+    let now = env.ledger().timestamp();
+    orders_of(env, &generator.address)
+        .iter()
+        .map(|id| load(env, id))
+        .filter(|order| order.is_active(now))
+        .fold(generator.capacity_kw, |capacity, order| {
+            capacity.min(order.max_capacity_kw)
+        })
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Emite ordem de curtailment limitando a capacidade do gerador na janela (apenas operador da rede)
+    pub fn issue_curtailment(
+        env: Env,
+        operator: Address,
+        generator: Address,
+        max_capacity_kw: u64,
+        starts_at: u64,
+        ends_at: u64,
+    ) -> u64 {
+        require_role(&env, &operator, Role::GridOperator);
+        audit::record(
+            &env,
+            &operator,
+            "issue_curtailment",
+            (generator.clone(), max_capacity_kw, starts_at, ends_at),
+        );
+        let now = env.ledger().timestamp();
+        if ends_at <= starts_at || ends_at <= now {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        Self::get_generator(env.clone(), generator.clone());
+
+        let id: u64 = env.storage().instance().get(&CURTAILMENT_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&CURTAILMENT_SEQ, &id);
+        let order = CurtailmentOrder {
+            id,
+            operator,
+            generator: generator.clone(),
+            max_capacity_kw,
+            starts_at,
+            ends_at,
+            is_cancelled: false,
+        };
+        env.storage().persistent().set(&(CURTAILMENT, id), &order);
+
+        // Mantém no índice apenas ordens que ainda podem vigorar
+        let mut ids = Vec::new(&env);
+        for existing in orders_of(&env, &generator).iter() {
+            let existing_order = load(&env, existing);
+            if !existing_order.is_cancelled && existing_order.ends_at > now {
+                ids.push_back(existing);
+            }
+        }
+        ids.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&(CURTAILMENTS_OF, generator), &ids);
+
+        events::curtailment_issued(&env, &order);
+        id
+    }
+
+    /// Revoga uma ordem de curtailment (apenas operador da rede)
+    pub fn cancel_curtailment(env: Env, operator: Address, order_id: u64) {
+        require_role(&env, &operator, Role::GridOperator);
+        audit::record(&env, &operator, "cancel_curtailment", (order_id,));
+        let mut order = load(&env, order_id);
+        if order.is_cancelled {
+            panic_with_error!(&env, STRGRIDError::NoOpUpdate);
+        }
+        order.is_cancelled = true;
+        env.storage().persistent().set(&(CURTAILMENT, order_id), &order);
+        events::curtailment_cancelled(&env, order_id, order.generator);
+    }
+
+    /// Consulta uma ordem de curtailment
+    pub fn get_curtailment(env: Env, order_id: u64) -> CurtailmentOrder {
+        load(&env, order_id)
+    }
+
+    /// Ordens de curtailment vigentes ou futuras de um gerador
+    pub fn curtailments_of(env: Env, generator: Address) -> Vec<CurtailmentOrder> {
+        let now = env.ledger().timestamp();
+        let mut orders = Vec::new(&env);
+        for id in orders_of(&env, &generator).iter() {
+            let order = load(&env, id);
+            if !order.is_cancelled && order.ends_at > now {
+                orders.push_back(order);
+            }
+        }
+        orders
+    }
+
+    /// Capacidade efetiva do gerador considerando ordens de curtailment ativas
+    pub fn effective_capacity(env: Env, generator: Address) -> u64 {
+        effective_capacity(&env, &Self::get_generator(env.clone(), generator))
+    }
+}
@@ -0,0 +1,242 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+pub use strgrid_interface::DayAheadCommitment;
+
+use crate::imbalance::{self, current_interval, metered, SETTLEMENT_INTERVAL_SECONDS};
+use crate::{insurance, ratings};
+use crate::journal::OpType;
+use crate::{
+    events, move_balance, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    STRGRIDExtError,
+};
+
+const DA_OFFER: Symbol = StorageKey::DaOffer.symbol();
+const DA_OFFER_SEQ: Symbol = StorageKey::DaOfferSeq.symbol();
+const DA_COMMITMENT: Symbol = StorageKey::DaCommitment.symbol();
+const DA_COMMITMENT_SEQ: Symbol = StorageKey::DaCommitmentSeq.symbol();
+const DA_OPEN: Symbol = StorageKey::DaOpen.symbol();
+const DA_ALLOCATED: Symbol = StorageKey::DaAllocated.symbol();
+
+const INTERVALS_PER_DAY: u64 = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+
+/// Oferta de venda do mercado do dia seguinte para um intervalo de liquidação
+///
+/// O segmento em tempo real são as ofertas e o livro de ordens do marketplace, que negociam
+/// kWh já emitidos, e o desvio liquidado ao PLD; aqui o gerador vende produção ainda por emitir.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayAheadOffer {
+    pub id: u64,
+    pub generator: Address,
+    pub interval: u64,
+    pub payment_token: Address,
+    pub price_per_kwh: i128,
+    pub amount_kwh: u64,
+    pub remaining_kwh: u64,
+    pub is_open: bool,
+}
+
+fn load_offer(env: &Env, offer_id: u64) -> DayAheadOffer {
+    env.storage()
+        .persistent()
+        .get(&(DA_OFFER, offer_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::OfferNotFound))
+}
+
+fn load_commitment(env: &Env, commitment_id: u64) -> DayAheadCommitment {
+    env.storage()
+        .persistent()
+        .get(&(DA_COMMITMENT, commitment_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::CommitmentNotFound))
+}
+
+/// O pregão do dia seguinte fecha na virada do dia do intervalo
+fn require_gate_open(env: &Env, interval: u64) {
+    if interval / INTERVALS_PER_DAY <= current_interval(env) / INTERVALS_PER_DAY {
+        panic_with_error!(env, STRGRIDExtError::ScheduleClosed);
+    }
+}
+
+fn cost(env: &Env, price_per_kwh: i128, amount_kwh: u64) -> i128 {
+    price_per_kwh
+        .checked_mul(amount_kwh as i128)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::InvalidAmount))
+}
+
+/// Compromissos do gerador no intervalo ainda não liquidados
+pub(crate) fn open_commitments(env: &Env, generator: &Address, interval: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(DA_OPEN, generator.clone(), interval))
+        .unwrap_or(0)
+}
+
+fn set_open_commitments(env: &Env, generator: &Address, interval: u64, open: u32) {
+    env.storage()
+        .persistent()
+        .set(&(DA_OPEN, generator.clone(), interval), &open);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Oferta `amount_kwh` da produção de um intervalo do dia seguinte em diante ao preço por kWh
+    pub fn offer_day_ahead(
+        env: Env,
+        generator: Address,
+        interval: u64,
+        amount_kwh: u64,
+        payment_token: Address,
+        price_per_kwh: i128,
+    ) -> u64 {
+        generator.require_auth();
+        if amount_kwh == 0 || price_per_kwh <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        require_gate_open(&env, interval);
+        Self::get_generator(env.clone(), generator.clone());
+
+        let id: u64 = env.storage().instance().get(&DA_OFFER_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&DA_OFFER_SEQ, &id);
+        let offer = DayAheadOffer {
+            id,
+            generator,
+            interval,
+            payment_token,
+            price_per_kwh,
+            amount_kwh,
+            remaining_kwh: amount_kwh,
+            is_open: true,
+        };
+        env.storage().persistent().set(&(DA_OFFER, id), &offer);
+        events::day_ahead_offered(&env, &offer);
+        id
+    }
+
+    /// Retira o saldo não vendido de uma oferta; compromissos já aceitos continuam valendo
+    pub fn cancel_day_ahead_offer(env: Env, offer_id: u64) {
+        let mut offer = load_offer(&env, offer_id);
+        offer.generator.require_auth();
+        if !offer.is_open {
+            panic_with_error!(&env, STRGRIDExtError::OfferClosed);
+        }
+        offer.is_open = false;
+        env.storage().persistent().set(&(DA_OFFER, offer_id), &offer);
+        events::day_ahead_offer_cancelled(&env, offer_id, offer.remaining_kwh);
+    }
+
+    /// Compra `amount_kwh` de uma oferta day-ahead, custodiando o pagamento no contrato
+    pub fn accept_day_ahead(env: Env, buyer: Address, offer_id: u64, amount_kwh: u64) -> u64 {
+        buyer.require_auth();
+        let mut offer = load_offer(&env, offer_id);
+        if !offer.is_open {
+            panic_with_error!(&env, STRGRIDExtError::OfferClosed);
+        }
+        if buyer == offer.generator {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        if amount_kwh == 0 || amount_kwh > offer.remaining_kwh {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        require_gate_open(&env, offer.interval);
+
+        token::Client::new(&env, &offer.payment_token).transfer(
+            &buyer,
+            &env.current_contract_address(),
+            &cost(&env, offer.price_per_kwh, amount_kwh),
+        );
+        offer.remaining_kwh -= amount_kwh;
+        offer.is_open = offer.remaining_kwh > 0;
+        env.storage().persistent().set(&(DA_OFFER, offer_id), &offer);
+
+        let id: u64 = env.storage().instance().get(&DA_COMMITMENT_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&DA_COMMITMENT_SEQ, &id);
+        let commitment = DayAheadCommitment {
+            id,
+            offer_id,
+            generator: offer.generator.clone(),
+            buyer,
+            interval: offer.interval,
+            payment_token: offer.payment_token,
+            price_per_kwh: offer.price_per_kwh,
+            amount_kwh,
+            delivered_kwh: 0,
+            settled: false,
+        };
+        env.storage().persistent().set(&(DA_COMMITMENT, id), &commitment);
+        let open = open_commitments(&env, &offer.generator, offer.interval);
+        set_open_commitments(&env, &offer.generator, offer.interval, open + 1);
+
+        events::day_ahead_committed(&env, &commitment);
+        id
+    }
+
+    /// Liquida um compromisso após o fim do intervalo contra os kWh que o gerador emitiu nele
+    ///
+    /// A parte entregue vai ao comprador e o pagamento correspondente ao gerador; a parte não
+    /// entregue é reembolsada ao comprador. O compromisso inteiro entra no programa do gerador no
+    /// intervalo, de modo que o que faltou vira desvio a liquidar ao PLD em `settle_imbalance`.
+    /// Qualquer um pode liquidar.
+    pub fn settle_day_ahead(env: Env, commitment_id: u64) -> u64 {
+        let mut commitment = load_commitment(&env, commitment_id);
+        if commitment.settled {
+            panic_with_error!(&env, STRGRIDExtError::CommitmentSettled);
+        }
+        let interval = commitment.interval;
+        if interval >= current_interval(&env) {
+            panic_with_error!(&env, STRGRIDExtError::IntervalOpen);
+        }
+        let generator = commitment.generator.clone();
+
+        // Compromissos do mesmo intervalo disputam a mesma geração, na ordem de liquidação
+        let allocated_key = (DA_ALLOCATED, generator.clone(), interval);
+        let allocated: u64 = env.storage().persistent().get(&allocated_key).unwrap_or(0);
+        let (generated, _) = metered(&env, &generator, interval);
+        let balance = Self::balance_of(env.clone(), generator.clone());
+        let delivered = commitment
+            .amount_kwh
+            .min(generated.saturating_sub(allocated))
+            .min(u64::try_from(balance).unwrap_or(u64::MAX));
+        let unfilled = commitment.amount_kwh - delivered;
+        env.storage().persistent().set(&allocated_key, &(allocated + delivered));
+
+        commitment.delivered_kwh = delivered;
+        commitment.settled = true;
+        env.storage()
+            .persistent()
+            .set(&(DA_COMMITMENT, commitment_id), &commitment);
+        let open = open_commitments(&env, &generator, interval);
+        set_open_commitments(&env, &generator, interval, open - 1);
+
+        let payment = token::Client::new(&env, &commitment.payment_token);
+        let pool = env.current_contract_address();
+        if delivered > 0 {
+            move_balance(&env, &generator, &commitment.buyer, delivered.into(), OpType::DayAhead);
+            ratings::record_purchase(&env, &commitment.buyer, &generator);
+            let earned = cost(&env, commitment.price_per_kwh, delivered);
+            let earned = insurance::collect_premium(&env, &commitment.payment_token, earned);
+            payment.transfer(&pool, &generator, &earned);
+        }
+        if unfilled > 0 {
+            payment.transfer(
+                &pool,
+                &commitment.buyer,
+                &cost(&env, commitment.price_per_kwh, unfilled),
+            );
+        }
+        imbalance::roll_over(&env, &generator, interval, commitment.amount_kwh);
+
+        events::day_ahead_settled(&env, &commitment);
+        delivered
+    }
+
+    /// Consulta uma oferta day-ahead
+    pub fn get_day_ahead_offer(env: Env, offer_id: u64) -> DayAheadOffer {
+        load_offer(&env, offer_id)
+    }
+
+    /// Consulta um compromisso day-ahead
+    pub fn get_day_ahead_commitment(env: Env, commitment_id: u64) -> DayAheadCommitment {
+        load_commitment(&env, commitment_id)
+    }
+}
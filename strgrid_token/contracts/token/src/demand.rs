@@ -0,0 +1,253 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, events, move_balance, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const DR_EVENT: Symbol = StorageKey::DrEvent.symbol();
+const DR_EVENT_SEQ: Symbol = StorageKey::DrEventSeq.symbol();
+const DR_ENROLLMENT: Symbol = StorageKey::DrEnrollment.symbol();
+
+/// Evento de resposta à demanda com pool de incentivo em STRGRID custodiado no contrato
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DrEvent {
+    pub id: u64,
+    pub operator: Address,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub reward_per_kwh: u64,
+    /// Parte do pool ainda não reservada para recompensas verificadas
+    pub pool_available: u64,
+    pub total_pledged_kwh: u64,
+    pub is_closed: bool,
+}
+
+/// Adesão de um consumidor a um evento; a recompensa é reservada na verificação
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DrEnrollment {
+    pub pledged_kwh: u64,
+    pub verified_kwh: Option<u64>,
+    pub reward: u64,
+    pub claimed: bool,
+}
+
+fn load_event(env: &Env, event_id: u64) -> DrEvent {
+    env.storage()
+        .persistent()
+        .get(&(DR_EVENT, event_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::DrEventNotFound))
+}
+
+fn save_event(env: &Env, event: &DrEvent) {
+    env.storage().persistent().set(&(DR_EVENT, event.id), event);
+}
+
+fn load_enrollment(env: &Env, event_id: u64, consumer: &Address) -> DrEnrollment {
+    env.storage()
+        .persistent()
+        .get(&(DR_ENROLLMENT, event_id, consumer.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::NotEnrolled))
+}
+
+fn save_enrollment(env: &Env, event_id: u64, consumer: &Address, enrollment: &DrEnrollment) {
+    env.storage()
+        .persistent()
+        .set(&(DR_ENROLLMENT, event_id, consumer.clone()), enrollment);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Publica um evento de resposta à demanda, depositando o pool de incentivo (apenas operador da rede)
+    pub fn post_dr_event(
+        env: Env,
+        operator: Address,
+        starts_at: u64,
+        ends_at: u64,
+        reward_per_kwh: u64,
+        pool_amount: u64,
+    ) -> u64 {
+        require_role(&env, &operator, Role::GridOperator);
+        audit::record(
+            &env,
+            &operator,
+            "post_dr_event",
+            (starts_at, ends_at, reward_per_kwh, pool_amount),
+        );
+        if reward_per_kwh == 0 || pool_amount == 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if starts_at <= env.ledger().timestamp() || ends_at <= starts_at {
+            panic_with_error!(&env, STRGRIDError::EnrollmentClosed);
+        }
+
+        move_balance(
+            &env,
+            &operator,
+            &env.current_contract_address(),
+            pool_amount.into(),
+            OpType::DemandResponse,
+        );
+
+        let id: u64 = env.storage().instance().get(&DR_EVENT_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&DR_EVENT_SEQ, &id);
+        let event = DrEvent {
+            id,
+            operator,
+            starts_at,
+            ends_at,
+            reward_per_kwh,
+            pool_available: pool_amount,
+            total_pledged_kwh: 0,
+            is_closed: false,
+        };
+        save_event(&env, &event);
+
+        events::dr_event_posted(&env, &event);
+        id
+    }
+
+    /// Inscreve o consumidor no evento com a redução de consumo prometida, até o início do evento
+    pub fn enroll_dr(env: Env, consumer: Address, event_id: u64, pledged_kwh: u64) {
+        consumer.require_auth();
+        if pledged_kwh == 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let mut event = load_event(&env, event_id);
+        if event.is_closed || env.ledger().timestamp() >= event.starts_at {
+            panic_with_error!(&env, STRGRIDError::EnrollmentClosed);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&(DR_ENROLLMENT, event_id, consumer.clone()))
+        {
+            panic_with_error!(&env, STRGRIDError::AlreadyEnrolled);
+        }
+
+        event.total_pledged_kwh += pledged_kwh;
+        save_event(&env, &event);
+        save_enrollment(
+            &env,
+            event_id,
+            &consumer,
+            &DrEnrollment {
+                pledged_kwh,
+                verified_kwh: None,
+                reward: 0,
+                claimed: false,
+            },
+        );
+
+        events::dr_enrolled(&env, event_id, consumer, pledged_kwh);
+    }
+
+    /// Registra a leitura do oráculo após o evento; a redução conta até o limite prometido (apenas oráculo)
+    pub fn verify_dr(
+        env: Env,
+        oracle: Address,
+        event_id: u64,
+        consumer: Address,
+        baseline_kwh: u64,
+        actual_kwh: u64,
+    ) -> u64 {
+        require_role(&env, &oracle, Role::Oracle);
+        audit::record(
+            &env,
+            &oracle,
+            "verify_dr",
+            (event_id, consumer.clone(), baseline_kwh, actual_kwh),
+        );
+        let mut event = load_event(&env, event_id);
+        if event.is_closed {
+            panic_with_error!(&env, STRGRIDError::DrEventClosed);
+        }
+        if env.ledger().timestamp() < event.ends_at {
+            panic_with_error!(&env, STRGRIDError::DrEventRunning);
+        }
+        let mut enrollment = load_enrollment(&env, event_id, &consumer);
+        if enrollment.verified_kwh.is_some() {
+            panic_with_error!(&env, STRGRIDError::AlreadyVerified);
+        }
+
+        let reduction = baseline_kwh.saturating_sub(actual_kwh).min(enrollment.pledged_kwh);
+        let reward = reduction
+            .saturating_mul(event.reward_per_kwh)
+            .min(event.pool_available);
+        event.pool_available -= reward;
+        save_event(&env, &event);
+
+        enrollment.verified_kwh = Some(reduction);
+        enrollment.reward = reward;
+        save_enrollment(&env, event_id, &consumer, &enrollment);
+
+        events::dr_verified(&env, event_id, consumer, reduction, reward);
+        reward
+    }
+
+    /// Resgata a recompensa verificada do consumidor
+    pub fn claim_dr_reward(env: Env, consumer: Address, event_id: u64) -> u64 {
+        consumer.require_auth();
+        let mut enrollment = load_enrollment(&env, event_id, &consumer);
+        if enrollment.claimed || enrollment.reward == 0 {
+            panic_with_error!(&env, STRGRIDError::NothingToClaim);
+        }
+
+        enrollment.claimed = true;
+        save_enrollment(&env, event_id, &consumer, &enrollment);
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &consumer,
+            enrollment.reward.into(),
+            OpType::DemandResponse,
+        );
+
+        events::dr_reward_claimed(&env, event_id, consumer, enrollment.reward);
+        enrollment.reward
+    }
+
+    /// Encerra as verificações e devolve ao operador o pool não reservado
+    pub fn close_dr_event(env: Env, event_id: u64) -> u64 {
+        let mut event = load_event(&env, event_id);
+        event.operator.require_auth();
+        if event.is_closed {
+            panic_with_error!(&env, STRGRIDError::DrEventClosed);
+        }
+        if env.ledger().timestamp() < event.ends_at {
+            panic_with_error!(&env, STRGRIDError::DrEventRunning);
+        }
+
+        let unused = event.pool_available;
+        event.pool_available = 0;
+        event.is_closed = true;
+        save_event(&env, &event);
+        if unused > 0 {
+            move_balance(
+                &env,
+                &env.current_contract_address(),
+                &event.operator,
+                unused.into(),
+                OpType::DemandResponse,
+            );
+        }
+
+        events::dr_event_closed(&env, event_id, unused);
+        unused
+    }
+
+    /// Consulta um evento de resposta à demanda
+    pub fn get_dr_event(env: Env, event_id: u64) -> DrEvent {
+        load_event(&env, event_id)
+    }
+
+    /// Consulta a adesão de um consumidor a um evento
+    pub fn get_dr_enrollment(env: Env, event_id: u64, consumer: Address) -> DrEnrollment {
+        load_enrollment(&env, event_id, &consumer)
+    }
+}
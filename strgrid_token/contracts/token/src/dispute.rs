@@ -0,0 +1,223 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Error, Symbol, Vec,
+};
+use strgrid_codes::StorageKey;
+
+use crate::journal::{self, JournalAccount, OpType};
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, checkpoint, events, holders, incentives, production, read_balance, read_total_supply,
+    require_admin, stats, write_balance, write_total_supply, EnergyGenerator, EnergyToken,
+    STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError, ENERGY_DATA,
+    GENERATOR,
+};
+
+const DISPUTE_WINDOW: Symbol = StorageKey::DisputeWindow.symbol();
+const LOCKED: Symbol = StorageKey::Locked.symbol();
+const CHALLENGE: Symbol = StorageKey::Challenge.symbol();
+
+/// Registro de contestação de um mint anulado por um auditor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintChallenge {
+    pub token_id: u64,
+    pub auditor: Address,
+    pub evidence_hash: BytesN<32>,
+    pub amount_kwh: u64,
+    pub timestamp: u64,
+    pub slashed: bool,
+}
+
+pub(crate) fn dispute_window(env: &Env) -> u64 {
+    env.storage().instance().get(&DISPUTE_WINDOW).unwrap_or(0)
+}
+
+pub(crate) fn write_dispute_window(env: &Env, seconds: u64) {
+    env.storage().instance().set(&DISPUTE_WINDOW, &seconds);
+}
+
+/// Bloqueia os tokens recém-emitidos até o fim da janela de contestação
+pub(crate) fn lock_minted(env: &Env, generator: &Address, token_id: u64) {
+    let key = (LOCKED, generator.clone());
+    let mut locked: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    locked.push_back(token_id);
+    env.storage().persistent().set(&key, &locked);
+}
+
+/// Indica se o lote ainda pode ser contestado; enquanto isso ele não pode ser consumido
+pub(crate) fn in_dispute_window(env: &Env, token: &EnergyToken) -> bool {
+    token.dispute_until > token.creation_timestamp
+        && env.ledger().timestamp() <= token.dispute_until
+}
+
+/// Lotes da lista que seguem em janela de contestação e a soma dos seus kWh
+fn scan_locked(env: &Env, locked: &Vec<u64>) -> (Vec<u64>, u64) {
+    let now = env.ledger().timestamp();
+    let mut still_locked = Vec::new(env);
+    let mut amount = 0u64;
+    for token_id in locked.iter() {
+        let token: Option<EnergyToken> = env.storage().persistent().get(&(ENERGY_DATA, token_id));
+        if let Some(token) = token {
+            if !token.is_voided && now <= token.dispute_until {
+                amount += token.amount_kwh;
+                still_locked.push_back(token_id);
+            }
+        }
+    }
+    (still_locked, amount)
+}
+
+/// Saldo ainda em janela de contestação, sem gravar nada; usado por consultas e prévias
+pub(crate) fn locked_amount(env: &Env, owner: &Address) -> u64 {
+    let locked: Option<Vec<u64>> = env.storage().persistent().get(&(LOCKED, owner.clone()));
+    locked.map_or(0, |locked| scan_locked(env, &locked).1)
+}
+
+/// Como `locked_amount`, descartando da lista os lotes já liberados; só em caminhos de escrita
+pub(crate) fn locked_balance(env: &Env, owner: &Address) -> u64 {
+    let key = (LOCKED, owner.clone());
+    let locked: Vec<u64> = match env.storage().persistent().get(&key) {
+        Some(locked) => locked,
+        None => return 0,
+    };
+
+    let (still_locked, amount) = scan_locked(env, &locked);
+    if still_locked.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else if still_locked.len() != locked.len() {
+        env.storage().persistent().set(&key, &still_locked);
+    }
+    amount
+}
+
+fn check_locked(balance: i128, locked: u64, amount: i128) -> Result<(), Error> {
+    if balance < amount {
+        return Err(STRGRIDError::InsufficientBalance.into());
+    }
+    if balance - i128::from(locked) < amount {
+        return Err(STRGRIDError::TokensLocked.into());
+    }
+    Ok(())
+}
+
+/// Garante que `amount` pode sair do saldo sem tocar em tokens bloqueados
+pub(crate) fn require_unlocked(env: &Env, owner: &Address, balance: i128, amount: i128) {
+    if let Err(error) = check_locked(balance, locked_balance(env, owner), amount) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `require_unlocked`, sem podar a lista de bloqueios nem abortar; para validações
+/// somente leitura
+pub(crate) fn check_unlocked(
+    env: &Env,
+    owner: &Address,
+    balance: i128,
+    amount: i128,
+) -> Result<(), Error> {
+    check_locked(balance, locked_amount(env, owner), amount)
+}
+
+pub(crate) fn mark_slashed(env: &Env, challenge: &MintChallenge) {
+    let mut challenge = challenge.clone();
+    challenge.slashed = true;
+    env.storage().persistent().set(&(CHALLENGE, challenge.token_id), &challenge);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define a janela de contestação (em segundos) aplicada aos próximos mints (apenas admin)
+    pub fn set_dispute_window(env: Env, seconds: u64) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_dispute_window", (seconds,));
+        write_dispute_window(&env, seconds);
+    }
+
+    /// Consulta a janela de contestação em segundos
+    pub fn get_dispute_window(env: Env) -> u64 {
+        dispute_window(&env)
+    }
+
+    /// Consulta quanto do saldo de um endereço ainda está em janela de contestação
+    pub fn locked_balance_of(env: Env, address: Address) -> u64 {
+        locked_amount(&env, &address)
+    }
+
+    /// Anula um mint fraudulento dentro da janela de contestação (apenas auditor)
+    pub fn challenge_mint(env: Env, auditor: Address, token_id: u64, evidence_hash: BytesN<32>) {
+        require_role(&env, &auditor, Role::Auditor);
+        audit::record(&env, &auditor, "challenge_mint", (token_id, evidence_hash.clone()));
+
+        let token_key = (ENERGY_DATA, token_id);
+        let mut energy_token: EnergyToken = env
+            .storage()
+            .persistent()
+            .get(&token_key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::TokenNotFound));
+
+        if energy_token.is_voided {
+            panic_with_error!(&env, STRGRIDError::TokenVoided);
+        }
+        if energy_token.is_consumed || env.ledger().timestamp() > energy_token.dispute_until {
+            panic_with_error!(&env, STRGRIDError::DisputeWindowClosed);
+        }
+
+        // Remove a energia anulada do saldo do gerador, da produção e do supply
+        let amount = energy_token.amount_kwh;
+        let generator = energy_token.generator_id.clone();
+        let generator_key = (GENERATOR, generator.clone());
+
+        let balance = (read_balance(&env, &generator) - i128::from(amount)).max(0);
+        let mut generator_data: EnergyGenerator = env
+            .storage()
+            .persistent()
+            .get(&generator_key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::GeneratorNotFound));
+        generator_data.current_production = generator_data.current_production.saturating_sub(amount);
+        let total_supply = (read_total_supply(&env) - i128::from(amount)).max(0);
+
+        energy_token.is_voided = true;
+
+        env.storage().persistent().set(&token_key, &energy_token);
+        write_balance(&env, &generator, balance);
+        env.storage().persistent().set(&generator_key, &generator_data);
+        write_total_supply(&env, total_supply);
+        checkpoint::record_balance(&env, &generator, balance);
+        holders::record_balance(&env, &generator, balance);
+        checkpoint::record_supply(&env, total_supply);
+        stats::record_void(&env, amount);
+        stats::close_lot(&env, &energy_token);
+
+        journal::record(
+            &env,
+            JournalAccount::Issuance,
+            JournalAccount::Holder(generator.clone()),
+            amount.into(),
+            OpType::Void,
+            token_id,
+        );
+
+        production::void(&env, &generator, amount, energy_token.creation_timestamp);
+        incentives::void_production(&env, &generator, amount, energy_token.creation_timestamp);
+
+        // Reavalia a lista de bloqueios já sem o lote anulado
+        locked_balance(&env, &generator);
+
+        let challenge = MintChallenge {
+            token_id,
+            auditor: auditor.clone(),
+            evidence_hash: evidence_hash.clone(),
+            amount_kwh: amount,
+            timestamp: env.ledger().timestamp(),
+            slashed: false,
+        };
+        env.storage().persistent().set(&(CHALLENGE, token_id), &challenge);
+
+        events::mint_challenged(&env, token_id, auditor, evidence_hash, amount);
+    }
+
+    /// Consulta a contestação registrada para um token, se houver
+    pub fn get_challenge(env: Env, token_id: u64) -> Option<MintChallenge> {
+        env.storage().persistent().get(&(CHALLENGE, token_id))
+    }
+}
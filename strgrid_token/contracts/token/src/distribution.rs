@@ -0,0 +1,79 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::migration;
+use crate::{
+    audit, events, move_balance, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, MAX_BATCH_SIZE,
+};
+
+const DISTRIBUTION_POOL: Symbol = StorageKey::DistributionPool.symbol();
+
+/// Saldo do pool, aceitando o valor gravado em u64 antes da migração para i128
+fn pool(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&DISTRIBUTION_POOL))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Deposita STRGRID no pool de distribuição usado em airdrops e programas piloto
+    pub fn fund_distribution(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        move_balance(
+            &env,
+            &funder,
+            &env.current_contract_address(),
+            amount,
+            OpType::Distribution,
+        );
+        env.storage()
+            .instance()
+            .set(&DISTRIBUTION_POOL, &migration::checked_add(&env, pool(&env), amount));
+
+        events::distribution_funded(&env, funder, amount);
+    }
+
+    /// Distribui tokens do pool para vários endereços numa única chamada (apenas admin)
+    ///
+    /// O lote é atômico: se o pool não cobre o total, nenhum destinatário recebe.
+    pub fn distribute(env: Env, recipients: Vec<(Address, i128)>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "distribute", (recipients.clone(),));
+        if recipients.is_empty() || recipients.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
+        }
+
+        let mut total = 0i128;
+        for (_, amount) in recipients.iter() {
+            if amount <= 0 {
+                panic_with_error!(&env, STRGRIDError::InvalidAmount);
+            }
+            total = migration::checked_add(&env, total, amount);
+        }
+        let available = pool(&env);
+        if total > available {
+            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        }
+        env.storage().instance().set(&DISTRIBUTION_POOL, &(available - total));
+
+        for (to, amount) in recipients.iter() {
+            move_balance(
+                &env,
+                &env.current_contract_address(),
+                &to,
+                amount,
+                OpType::Distribution,
+            );
+            events::distributed(&env, to, amount);
+        }
+    }
+
+    /// STRGRID disponível no pool de distribuição
+    pub fn distribution_pool(env: Env) -> i128 {
+        pool(&env)
+    }
+}
@@ -0,0 +1,94 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError, GENERATOR,
+};
+
+const DOCUMENT: Symbol = StorageKey::Document.symbol();
+const DOCUMENT_HASH: Symbol = StorageKey::DocumentHash.symbol();
+
+/// Tipos de credencial do gerador ancorados on-chain
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DocumentType {
+    License,
+    AneelAuthorization,
+    InterconnectionAgreement,
+    Other,
+}
+
+impl DocumentType {
+    const ALL: [DocumentType; 4] = [
+        DocumentType::License,
+        DocumentType::AneelAuthorization,
+        DocumentType::InterconnectionAgreement,
+        DocumentType::Other,
+    ];
+}
+
+/// Hash de um documento atestado pelo gerador; o arquivo em si fica off-chain
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnchoredDocument {
+    pub doc_type: DocumentType,
+    pub sha256: BytesN<32>,
+    pub anchored_at: u64,
+}
+
+fn document(env: &Env, generator: &Address, doc_type: DocumentType) -> Option<AnchoredDocument> {
+    env.storage()
+        .persistent()
+        .get(&(DOCUMENT, generator.clone(), doc_type))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Ancora o sha256 de uma credencial do gerador (licença, autorização da ANEEL, acordo de
+    /// conexão); um novo documento do mesmo tipo substitui o anterior
+    pub fn anchor_document(env: Env, generator: Address, doc_type: DocumentType, sha256: BytesN<32>) {
+        generator.require_auth();
+        if !env.storage().persistent().has(&(GENERATOR, generator.clone())) {
+            panic_with_error!(&env, STRGRIDError::GeneratorNotFound);
+        }
+
+        let anchored = AnchoredDocument {
+            doc_type,
+            sha256: sha256.clone(),
+            anchored_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&(DOCUMENT, generator.clone(), doc_type), &anchored);
+        env.storage()
+            .persistent()
+            .set(&(DOCUMENT_HASH, sha256.clone()), &(generator.clone(), doc_type));
+        events::document_anchored(&env, generator, doc_type, sha256);
+    }
+
+    /// Documento vigente do tipo informado, se ancorado
+    pub fn get_document(env: Env, generator: Address, doc_type: DocumentType) -> Option<AnchoredDocument> {
+        document(&env, &generator, doc_type)
+    }
+
+    /// Todos os documentos vigentes do gerador
+    pub fn get_documents(env: Env, generator: Address) -> Vec<AnchoredDocument> {
+        let mut documents = Vec::new(&env);
+        for doc_type in DocumentType::ALL {
+            if let Some(anchored) = document(&env, &generator, doc_type) {
+                documents.push_back(anchored);
+            }
+        }
+        documents
+    }
+
+    /// Confere se o arquivo apresentado ao auditor é o documento vigente do gerador
+    pub fn verify_document(env: Env, generator: Address, doc_type: DocumentType, sha256: BytesN<32>) -> bool {
+        document(&env, &generator, doc_type).is_some_and(|anchored| anchored.sha256 == sha256)
+    }
+
+    /// Gerador e tipo sob os quais um hash foi ancorado, inclusive documentos já substituídos
+    pub fn find_document(env: Env, sha256: BytesN<32>) -> Option<(Address, DocumentType)> {
+        env.storage().persistent().get(&(DOCUMENT_HASH, sha256))
+    }
+}
@@ -0,0 +1,712 @@
+use soroban_sdk::{Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+
+use crate::applications::ApplicationStatus;
+use crate::priority::{priority_of, Priority};
+use crate::auction::Auction;
+use crate::certificate::Retirement;
+use crate::config::{Config, ConfigChange};
+use crate::consumer::ConsumerProfile;
+use crate::congestion::ZoneCongestion;
+use crate::curtail::CurtailmentOrder;
+use crate::keepers::KeeperTask;
+use crate::maintenance::MaintenanceWindow;
+use crate::operators::OperatorGrant;
+use crate::imbalance::{ImbalancePosition, PldPrice};
+use crate::insurance::InsuranceConfig;
+use crate::recovery::RecoveryConfig;
+use crate::reservation::CapacityReservation;
+use crate::dayahead::{DayAheadCommitment, DayAheadOffer};
+use crate::demand::DrEvent;
+use crate::documents::DocumentType;
+use crate::forecast::ForecastResult;
+use crate::collateral::CollateralConfig;
+use crate::forward::Forward;
+use crate::roles::Role;
+use crate::scheduled::ScheduledTransfer;
+use crate::stream::Stream;
+use crate::tiers::{GeneratorTier, TierConfig};
+
+// Tópicos dos eventos publicados pelo contrato
+pub use strgrid_codes::topic_symbols::*;
+
+pub(crate) fn role_granted(env: &Env, role: Role, account: Address) {
+    env.events().publish((ROLE_GRANTED, role), account);
+}
+
+pub(crate) fn role_revoked(env: &Env, role: Role, account: Address) {
+    env.events().publish((ROLE_REVOKED, role), account);
+}
+
+pub(crate) fn mint_challenged(
+    env: &Env,
+    token_id: u64,
+    auditor: Address,
+    evidence_hash: BytesN<32>,
+    amount: u64,
+) {
+    env.events()
+        .publish((MINT_CHALLENGED, token_id, auditor), (evidence_hash, amount));
+}
+
+pub(crate) fn bond_deposited(env: &Env, generator: Address, amount: i128, bond: i128) {
+    env.events().publish((BOND_DEPOSITED, generator), (amount, bond));
+}
+
+pub(crate) fn bond_withdrawn(env: &Env, generator: Address, amount: i128, bond: i128) {
+    env.events().publish((BOND_WITHDRAWN, generator), (amount, bond));
+}
+
+pub(crate) fn bond_slashed(
+    env: &Env,
+    generator: Address,
+    token_id: u64,
+    amount: i128,
+    recipient: Address,
+) {
+    env.events()
+        .publish((BOND_SLASHED, generator, token_id), (amount, recipient));
+}
+
+pub(crate) fn strict_mode_set(env: &Env, enabled: bool) {
+    env.events().publish((STRICT_MODE,), enabled);
+}
+
+pub(crate) fn mint_oracle_set(env: &Env, oracle: Option<Address>) {
+    env.events().publish((MINT_ORACLE_SET,), oracle);
+}
+
+pub(crate) fn permit(env: &Env, owner: Address, spender: Address, amount: i128, nonce: u64) {
+    env.events().publish((PERMIT, owner, spender), (amount, nonce));
+}
+
+pub(crate) fn relayed(env: &Env, signer: Address, nonce: u64) {
+    env.events().publish((RELAYED, signer), nonce);
+}
+
+pub(crate) fn signing_key_set(env: &Env, owner: Address, key: Option<BytesN<32>>) {
+    env.events().publish((SIGNING_KEY_SET, owner), key);
+}
+
+pub(crate) fn allowlisted(env: &Env, account: Address, allowed: bool) {
+    env.events().publish((ALLOWLISTED, account), allowed);
+}
+
+pub(crate) fn kyc_mode_set(env: &Env, enabled: bool) {
+    env.events().publish((KYC_MODE_SET,), enabled);
+}
+
+pub(crate) fn kyc_registry_set(env: &Env, registry: Option<Address>) {
+    env.events().publish((KYC_REGISTRY_SET,), registry);
+}
+
+pub(crate) fn jurisdiction_set(env: &Env, account: Address, jurisdiction: Option<u32>) {
+    env.events().publish((JURISDICTION_SET, account), jurisdiction);
+}
+
+pub(crate) fn jurisdiction_pair_set(env: &Env, from: u32, to: u32, allowed: bool) {
+    env.events().publish((JURISDICTION_PAIR_SET, from, to), allowed);
+}
+
+pub(crate) fn jurisdiction_mode_set(env: &Env, enabled: bool) {
+    env.events().publish((JURISDICTION_MODE_SET,), enabled);
+}
+
+pub(crate) fn document_anchored(env: &Env, generator: Address, doc_type: DocumentType, sha256: BytesN<32>) {
+    env.events().publish((DOCUMENT_ANCHORED, generator, doc_type), sha256);
+}
+
+pub(crate) fn generator_metadata_set(env: &Env, generator: Address, cid: Option<String>) {
+    env.events().publish((GENERATOR_METADATA, generator), cid);
+}
+
+pub(crate) fn token_metadata_set(env: &Env, token_id: u64, cid: Option<String>) {
+    env.events().publish((TOKEN_METADATA, token_id), cid);
+}
+
+pub(crate) fn certificate_metadata_set(env: &Env, cert_id: u64, cid: Option<String>) {
+    env.events().publish((CERTIFICATE_METADATA, cert_id), cid);
+}
+
+pub(crate) fn generator_applied(env: &Env, applicant: Address, capacity_kw: u64, docs_hash: BytesN<32>) {
+    env.events().publish((GENERATOR_APPLIED, applicant), (capacity_kw, docs_hash));
+}
+
+pub(crate) fn application_status_set(env: &Env, applicant: Address, status: ApplicationStatus, operator: Address) {
+    env.events().publish((APPLICATION_STATUS, applicant), (status, operator));
+}
+
+pub(crate) fn tier_config_set(env: &Env, tier: GeneratorTier, config: TierConfig) {
+    env.events().publish((TIER_CONFIG_SET, tier), config);
+}
+
+pub(crate) fn tier_assigned(env: &Env, generator: Address, tier: GeneratorTier) {
+    env.events().publish((TIER_ASSIGNED, generator), tier);
+}
+
+pub(crate) fn maintenance_scheduled(env: &Env, window: &MaintenanceWindow) {
+    env.events().publish(
+        (MAINTENANCE_SCHEDULED, window.id, window.generator.clone()),
+        (window.starts_at, window.ends_at),
+    );
+}
+
+pub(crate) fn maintenance_cancelled(env: &Env, window: &MaintenanceWindow) {
+    env.events().publish(
+        (MAINTENANCE_CANCELLED, window.id, window.generator.clone()),
+        window.ends_at,
+    );
+}
+
+pub(crate) fn forecast_submitted(env: &Env, generator: Address, interval: u64, forecast_kwh: u64) {
+    env.events()
+        .publish((FORECAST_SUBMITTED, generator, interval), forecast_kwh);
+}
+
+pub(crate) fn forecast_settled(env: &Env, generator: Address, result: &ForecastResult) {
+    env.events().publish(
+        (FORECAST_SETTLED, generator, result.interval),
+        (result.deviation_kwh, result.amount),
+    );
+}
+
+pub(crate) fn day_ahead_offered(env: &Env, offer: &DayAheadOffer) {
+    env.events().publish(
+        (DA_OFFERED, offer.id, offer.generator.clone()),
+        (offer.interval, offer.amount_kwh, offer.price_per_kwh),
+    );
+}
+
+pub(crate) fn day_ahead_offer_cancelled(env: &Env, offer_id: u64, remaining_kwh: u64) {
+    env.events().publish((DA_OFFER_CANCELLED, offer_id), remaining_kwh);
+}
+
+pub(crate) fn day_ahead_committed(env: &Env, commitment: &DayAheadCommitment) {
+    env.events().publish(
+        (DA_COMMITTED, commitment.id, commitment.buyer.clone()),
+        (commitment.offer_id, commitment.amount_kwh),
+    );
+}
+
+/// kWh entregues e a parcela não entregue transferida para o desvio em tempo real
+pub(crate) fn day_ahead_settled(env: &Env, commitment: &DayAheadCommitment) {
+    env.events().publish(
+        (DA_SETTLED, commitment.id, commitment.generator.clone()),
+        (commitment.delivered_kwh, commitment.amount_kwh - commitment.delivered_kwh),
+    );
+}
+
+pub(crate) fn insurance_set(env: &Env, config: Option<InsuranceConfig>) {
+    env.events().publish((INSURANCE_SET,), config);
+}
+
+pub(crate) fn insurance_premium(env: &Env, pool: Address, payment_token: Address, premium: i128) {
+    env.events()
+        .publish((INSURANCE_PREMIUM, pool), (payment_token, premium));
+}
+
+pub(crate) fn collateral_config_set(env: &Env, config: Option<CollateralConfig>) {
+    env.events().publish((COLLATERAL_CONFIG_SET,), config);
+}
+
+pub(crate) fn collateral_added(env: &Env, forward_id: u64, amount: i128, collateral: i128) {
+    env.events()
+        .publish((COLLATERAL_ADDED, forward_id), (amount, collateral));
+}
+
+pub(crate) fn margin_called(env: &Env, forward_id: u64, shortfall: i128, deadline: u64) {
+    env.events()
+        .publish((MARGIN_CALLED, forward_id), (shortfall, deadline));
+}
+
+pub(crate) fn forward_liquidated(env: &Env, forward_id: u64, generator: Address, collateral: i128) {
+    env.events()
+        .publish((FORWARD_LIQUIDATED, forward_id, generator), collateral);
+}
+
+pub(crate) fn generator_rated(
+    env: &Env,
+    consumer: Address,
+    generator: Address,
+    period: u64,
+    stars: u32,
+) {
+    env.events()
+        .publish((GENERATOR_RATED, generator, consumer), (period, stars));
+}
+
+pub(crate) fn recovery_set(env: &Env, account: Address, config: Option<RecoveryConfig>) {
+    env.events().publish((RECOVERY_SET, account), config);
+}
+
+pub(crate) fn recovery_proposed(env: &Env, account: Address, guardian: Address, new_address: Address) {
+    env.events()
+        .publish((RECOVERY_PROPOSED, account, guardian), new_address);
+}
+
+pub(crate) fn recovery_approved(
+    env: &Env,
+    account: Address,
+    guardian: Address,
+    executable_at: Option<u64>,
+) {
+    env.events()
+        .publish((RECOVERY_APPROVED, account, guardian), executable_at);
+}
+
+pub(crate) fn recovery_cancelled(env: &Env, account: Address) {
+    env.events().publish((RECOVERY_CANCELLED, account), ());
+}
+
+pub(crate) fn account_recovered(
+    env: &Env,
+    account: Address,
+    new_address: Address,
+    balance: i128,
+    certificates: u32,
+) {
+    env.events()
+        .publish((ACCOUNT_RECOVERED, account, new_address), (balance, certificates));
+}
+
+pub(crate) fn operator_added(
+    env: &Env,
+    generator: Address,
+    operator: Address,
+    grant: &OperatorGrant,
+) {
+    env.events()
+        .publish((OPERATOR_ADDED, generator, operator), grant.clone());
+}
+
+pub(crate) fn operator_revoked(env: &Env, generator: Address, operator: Address) {
+    env.events().publish((OPERATOR_REVOKED, generator, operator), ());
+}
+
+pub(crate) fn operator_rotated(
+    env: &Env,
+    generator: Address,
+    old_key: Address,
+    new_key: Address,
+    grace_until: u64,
+) {
+    env.events()
+        .publish((OPERATOR_ROTATED, generator, old_key), (new_key, grace_until));
+}
+
+pub(crate) fn spending_limit_set(
+    env: &Env,
+    owner: Address,
+    spender: Address,
+    limit: Option<(i128, u32)>,
+) {
+    env.events().publish((SPENDING_LIMIT_SET, owner, spender), limit);
+}
+
+pub(crate) fn stream_opened(env: &Env, stream: &Stream) {
+    env.events().publish(
+        (STREAM_OPENED, stream.payer.clone(), stream.recipient.clone()),
+        (stream.id, stream.rate_per_second, stream.end_time),
+    );
+}
+
+pub(crate) fn stream_withdrawn(env: &Env, stream_id: u64, recipient: Address, amount: i128) {
+    env.events()
+        .publish((STREAM_WITHDRAWN, recipient), (stream_id, amount));
+}
+
+pub(crate) fn stream_cancelled(
+    env: &Env,
+    stream_id: u64,
+    caller: Address,
+    paid: i128,
+    refunded: i128,
+) {
+    env.events()
+        .publish((STREAM_CANCELLED, caller), (stream_id, paid, refunded));
+}
+
+pub(crate) fn transfer_scheduled(env: &Env, scheduled: &ScheduledTransfer) {
+    env.events().publish(
+        (TRANSFER_SCHEDULED, scheduled.from.clone(), scheduled.to.clone()),
+        (scheduled.id, scheduled.amount, scheduled.execute_after),
+    );
+}
+
+pub(crate) fn scheduled_transfer_executed(env: &Env, id: u64, keeper: Address, reward: i128) {
+    env.events().publish((SCHEDULED_EXECUTED, keeper), (id, reward));
+}
+
+pub(crate) fn scheduled_transfer_cancelled(env: &Env, id: u64, from: Address) {
+    env.events().publish((SCHEDULED_CANCELLED, from), id);
+}
+
+pub(crate) fn task_created(env: &Env, task: &KeeperTask) {
+    // Publica a prioridade da execução, que é o que o keeper vai submeter
+    let priority = priority_of(env, &Symbol::new(env, "complete_task"));
+    env.events().publish(
+        (TASK_CREATED, task.creator.clone()),
+        (task.id, task.action.clone(), task.bounty, priority),
+    );
+}
+
+pub(crate) fn task_claimed(env: &Env, task_id: u64, keeper: Address, claim_expires_at: u64) {
+    env.events()
+        .publish((TASK_CLAIMED, keeper), (task_id, claim_expires_at));
+}
+
+pub(crate) fn task_completed(env: &Env, task_id: u64, keeper: Address, bounty: i128) {
+    let priority = priority_of(env, &Symbol::new(env, "complete_task"));
+    env.events()
+        .publish((TASK_COMPLETED, keeper), (task_id, bounty, priority));
+}
+
+pub(crate) fn task_cancelled(env: &Env, task_id: u64, creator: Address) {
+    env.events().publish((TASK_CANCELLED, creator), task_id);
+}
+
+/// Publica o parâmetro alterado com o valor anterior e o novo
+pub(crate) fn config_changed(env: &Env, caller: Address, change: ConfigChange, before: &Config) {
+    let (field, diff): (&str, Val) = match change {
+        ConfigChange::TransferFee(new) => (
+            "transfer_fee",
+            ((before.transfer_fee_bps, before.treasury.clone()), (new.fee_bps, new.treasury))
+                .into_val(env),
+        ),
+        ConfigChange::MintCaps(new) => ("mint_caps", (before.mint_caps.clone(), new).into_val(env)),
+        ConfigChange::MintCooldown(new) => ("mint_cooldown", (before.mint_cooldown, new).into_val(env)),
+        ConfigChange::DisputeWindow(new) => {
+            ("dispute_window", (before.dispute_window, new).into_val(env))
+        }
+        ConfigChange::StrictMode(new) => ("strict_mode", (before.strict_mode, new).into_val(env)),
+        ConfigChange::MintOracle(new) => ("mint_oracle", (before.mint_oracle.clone(), new).into_val(env)),
+    };
+    env.events()
+        .publish((CONFIG_CHANGED, caller, Symbol::new(env, field)), diff);
+}
+
+/// Publica quantas contas tiveram os saldos regravados em i128
+pub(crate) fn amounts_migrated(env: &Env, accounts: u32) {
+    env.events().publish((AMOUNTS_MIGRATED,), accounts);
+}
+
+pub(crate) fn compliance_hook_set(env: &Env, hook: Option<Address>) {
+    env.events().publish((COMPLIANCE_HOOK_SET,), hook);
+}
+
+pub(crate) fn classic_asset_set(env: &Env, asset: Address) {
+    env.events().publish((CLASSIC_ASSET_SET,), asset);
+}
+
+pub(crate) fn wrapped(env: &Env, account: Address, amount: i128) {
+    env.events().publish((WRAP, account), amount);
+}
+
+pub(crate) fn unwrapped(env: &Env, account: Address, amount: i128) {
+    env.events().publish((UNWRAP, account), amount);
+}
+
+pub(crate) fn view_access_granted(env: &Env, owner: Address, viewer: Address) {
+    env.events().publish((VIEW_GRANTED, owner), viewer);
+}
+
+pub(crate) fn view_access_revoked(env: &Env, owner: Address, viewer: Address) {
+    env.events().publish((VIEW_REVOKED, owner), viewer);
+}
+
+pub(crate) fn burned(env: &Env, from: Address, amount: i128) {
+    env.events().publish((BURN, from), amount);
+}
+
+pub(crate) fn operation_priority_set(env: &Env, operation: Symbol, priority: Priority) {
+    env.events().publish((PRIORITY_SET, operation), priority);
+}
+
+/// Inclui a prioridade da operação para que keepers/clientes identifiquem ações críticas
+pub(crate) fn generator_status_set(env: &Env, generator: Address, is_active: bool) {
+    let priority = priority_of(env, &Symbol::new(env, "set_generator_status"));
+    env.events()
+        .publish((GENERATOR_STATUS, generator), (is_active, priority));
+}
+
+pub(crate) fn transfer_memo(env: &Env, from: Address, to: Address, amount: i128, memo: BytesN<32>) {
+    env.events().publish((TRANSFER_MEMO, from, to), (amount, memo));
+}
+
+pub(crate) fn account_frozen(env: &Env, account: Address, frozen: bool) {
+    env.events().publish((ACCOUNT_FROZEN, account), frozen);
+}
+
+/// Publica a raiz Merkle e os ids removidos para que indexadores reconstruam as provas
+pub(crate) fn lots_archived(
+    env: &Env,
+    generator: Address,
+    period: u64,
+    batch_root: BytesN<32>,
+    token_ids: Vec<u64>,
+) {
+    env.events()
+        .publish((LOTS_ARCHIVED, generator, period), (batch_root, token_ids));
+}
+
+pub(crate) fn clawback(env: &Env, admin: Address, from: Address, amount: i128) {
+    env.events().publish((CLAWBACK, admin, from), amount);
+}
+
+/// Valor bruto e taxa cobrada; o destinatário recebe `amount - fee`
+pub(crate) fn transfer_fee(env: &Env, from: Address, to: Address, amount: i128, fee: i128) {
+    env.events().publish((TRANSFER_FEE, from, to), (amount, fee));
+}
+
+pub(crate) fn treasury_withdrawn(env: &Env, treasury: Address, amount: i128) {
+    env.events().publish((TREASURY_WITHDRAWN, treasury), amount);
+}
+
+pub(crate) fn certificate_issued(env: &Env, cert_id: u64, consumer: Address, amount_kwh: u64) {
+    env.events().publish((CERTIFICATE_ISSUED, consumer), (cert_id, amount_kwh));
+}
+
+pub(crate) fn certificate_retired(env: &Env, retirement: Retirement) {
+    env.events().publish(
+        (CERTIFICATE_RETIRED, retirement.cert_id, retirement.beneficiary.clone()),
+        retirement,
+    );
+}
+
+pub(crate) fn swapped(
+    env: &Env,
+    seller: Address,
+    buyer: Address,
+    kwh_amount: u64,
+    payment_token: Address,
+    payment_amount: i128,
+) {
+    env.events().publish(
+        (SWAP, seller, buyer),
+        (kwh_amount, payment_token, payment_amount),
+    );
+}
+
+pub(crate) fn auction_started(env: &Env, auction: &Auction) {
+    env.events().publish(
+        (AUCTION_STARTED, auction.id, auction.seller.clone()),
+        (
+            auction.token_id,
+            auction.amount_kwh,
+            auction.start_price,
+            auction.floor_price,
+            auction.ends_at,
+        ),
+    );
+}
+
+pub(crate) fn auction_bid(env: &Env, auction_id: u64, buyer: Address, amount_kwh: u64, payment: i128) {
+    env.events()
+        .publish((AUCTION_BID, auction_id, buyer), (amount_kwh, payment));
+}
+
+pub(crate) fn auction_settled(env: &Env, auction_id: u64, seller: Address, unsold_kwh: u64) {
+    env.events()
+        .publish((AUCTION_SETTLED, auction_id, seller), unsold_kwh);
+}
+
+pub(crate) fn forward_sold(env: &Env, forward: &Forward) {
+    env.events().publish(
+        (FORWARD_SOLD, forward.id, forward.generator.clone(), forward.buyer.clone()),
+        (forward.amount_kwh, forward.window_start, forward.price, forward.penalty),
+    );
+}
+
+pub(crate) fn forward_delivered(env: &Env, forward_id: u64, buyer: Address, amount_kwh: u64) {
+    env.events()
+        .publish((FORWARD_DELIVERED, forward_id, buyer), amount_kwh);
+}
+
+pub(crate) fn forward_defaulted(env: &Env, forward_id: u64, generator: Address, penalty: i128) {
+    env.events()
+        .publish((FORWARD_DEFAULTED, forward_id, generator), penalty);
+}
+
+pub(crate) fn dr_event_posted(env: &Env, event: &DrEvent) {
+    env.events().publish(
+        (DR_EVENT_POSTED, event.id, event.operator.clone()),
+        (event.starts_at, event.ends_at, event.reward_per_kwh, event.pool_available),
+    );
+}
+
+pub(crate) fn dr_enrolled(env: &Env, event_id: u64, consumer: Address, pledged_kwh: u64) {
+    env.events()
+        .publish((DR_ENROLLED, event_id, consumer), pledged_kwh);
+}
+
+pub(crate) fn dr_verified(env: &Env, event_id: u64, consumer: Address, reduction_kwh: u64, reward: u64) {
+    env.events()
+        .publish((DR_VERIFIED, event_id, consumer), (reduction_kwh, reward));
+}
+
+pub(crate) fn dr_reward_claimed(env: &Env, event_id: u64, consumer: Address, reward: u64) {
+    env.events()
+        .publish((DR_REWARD_CLAIMED, event_id, consumer), reward);
+}
+
+pub(crate) fn dr_event_closed(env: &Env, event_id: u64, unused: u64) {
+    env.events().publish((DR_EVENT_CLOSED, event_id), unused);
+}
+
+pub(crate) fn net_metering_set(env: &Env, prosumer: Address, enabled: bool) {
+    env.events().publish((NET_METERING, prosumer), enabled);
+}
+
+pub(crate) fn consumer_registered(env: &Env, consumer: Address, profile: ConsumerProfile) {
+    env.events().publish((CONSUMER_REGISTERED, consumer), profile);
+}
+
+pub(crate) fn consumer_gate_set(env: &Env, required: bool) {
+    env.events().publish((CONSUMER_GATE,), required);
+}
+
+pub(crate) fn aggregator_registered(env: &Env, aggregator: Address) {
+    env.events().publish((AGGREGATOR_REGISTERED, aggregator), ());
+}
+
+pub(crate) fn aggregator_member_added(env: &Env, aggregator: Address, member: Address, capacity_kw: u64) {
+    env.events()
+        .publish((AGGREGATOR_JOINED, aggregator, member), capacity_kw);
+}
+
+pub(crate) fn aggregator_member_removed(env: &Env, aggregator: Address, member: Address, capacity_kw: u64) {
+    env.events()
+        .publish((AGGREGATOR_LEFT, aggregator, member), capacity_kw);
+}
+
+pub(crate) fn proceeds_distributed(env: &Env, aggregator: Address, payment_token: Address, amount: i128) {
+    env.events()
+        .publish((PROCEEDS_DISTRIBUTED, aggregator, payment_token), amount);
+}
+
+pub(crate) fn curtailment_issued(env: &Env, order: &CurtailmentOrder) {
+    let priority = priority_of(env, &Symbol::new(env, "issue_curtailment"));
+    env.events().publish(
+        (CURTAILMENT_ISSUED, order.id, order.generator.clone()),
+        (order.max_capacity_kw, order.starts_at, order.ends_at, priority),
+    );
+}
+
+pub(crate) fn curtailment_cancelled(env: &Env, order_id: u64, generator: Address) {
+    let priority = priority_of(env, &Symbol::new(env, "cancel_curtailment"));
+    env.events()
+        .publish((CURTAILMENT_CANCELLED, order_id, generator), priority);
+}
+
+pub(crate) fn transmission_loss(
+    env: &Env,
+    from: Address,
+    to: Address,
+    from_zone: u32,
+    to_zone: u32,
+    loss: i128,
+) {
+    env.events()
+        .publish((TRANSMISSION_LOSS, from, to), (from_zone, to_zone, loss));
+}
+
+pub(crate) fn congestion_set(env: &Env, congestion: &ZoneCongestion) {
+    env.events().publish(
+        (CONGESTION_SET, congestion.zone),
+        (congestion.multiplier_bps, congestion.until),
+    );
+}
+
+pub(crate) fn congestion_cleared(env: &Env, zone: u32) {
+    env.events().publish((CONGESTION_CLEARED, zone), ());
+}
+
+pub(crate) fn energy_scheduled(env: &Env, participant: Address, interval: u64, scheduled_kwh: i128) {
+    env.events()
+        .publish((ENERGY_SCHEDULED, participant, interval), scheduled_kwh);
+}
+
+pub(crate) fn pld_published(env: &Env, interval: u64, price: &PldPrice) {
+    env.events().publish(
+        (PLD_PUBLISHED, interval),
+        (price.payment_token.clone(), price.price_per_kwh),
+    );
+}
+
+pub(crate) fn imbalance_settled(env: &Env, participant: Address, imbalance: &ImbalancePosition) {
+    env.events().publish(
+        (IMBALANCE_SETTLED, participant, imbalance.interval),
+        (imbalance.imbalance_kwh, imbalance.amount),
+    );
+}
+
+pub(crate) fn capacity_reserved(env: &Env, reservation: &CapacityReservation) {
+    env.events().publish(
+        (
+            CAPACITY_RESERVED,
+            reservation.id,
+            reservation.consumer.clone(),
+            reservation.generator.clone(),
+        ),
+        (
+            reservation.capacity_kw,
+            reservation.starts_at,
+            reservation.ends_at,
+            reservation.fee,
+        ),
+    );
+}
+
+pub(crate) fn reservation_delivered(env: &Env, reservation_id: u64, token_id: u64, amount_kwh: u64) {
+    env.events()
+        .publish((RESERVATION_DELIVERED, reservation_id), (token_id, amount_kwh));
+}
+
+pub(crate) fn reservation_settled(env: &Env, reservation_id: u64, earned: i128, refund: i128) {
+    env.events()
+        .publish((RESERVATION_SETTLED, reservation_id), (earned, refund));
+}
+
+pub(crate) fn incentives_funded(env: &Env, funder: Address, amount: i128) {
+    env.events().publish((INCENTIVES_FUNDED, funder), amount);
+}
+
+pub(crate) fn incentive_claimed(env: &Env, generator: Address, epoch: u32, amount: i128) {
+    env.events().publish((INCENTIVE_CLAIMED, generator, epoch), amount);
+}
+
+pub(crate) fn distribution_funded(env: &Env, funder: Address, amount: i128) {
+    env.events().publish((DISTRIBUTION_FUNDED, funder), amount);
+}
+
+pub(crate) fn distributed(env: &Env, recipient: Address, amount: i128) {
+    env.events().publish((DISTRIBUTED, recipient), amount);
+}
+
+pub(crate) fn obligation_recorded(env: &Env, from: Address, to: Address, period: u64, amount: u64) {
+    env.events().publish((OBLIGATION_RECORDED, from, to), (period, amount));
+}
+
+pub(crate) fn netting_settled(env: &Env, period: u64, participants: u32, gross: u64, netted: u64) {
+    env.events()
+        .publish((NETTING_SETTLED, period), (participants, gross, netted));
+}
+
+pub(crate) fn lots_swept(env: &Env, token_ids: Vec<u64>) {
+    env.events().publish((LOTS_SWEPT,), token_ids);
+}
+
+pub(crate) fn generator_registered(env: &Env, generator: Address, capacity_kw: u64) {
+    env.events().publish((GENERATOR_REGISTERED, generator), capacity_kw);
+}
+
+pub(crate) fn minted(env: &Env, generator: Address, token_id: u64, amount_kwh: u64, expiry: u64) {
+    env.events().publish((MINT, generator, token_id), (amount_kwh, expiry));
+}
+
+pub(crate) fn transferred(env: &Env, from: Address, to: Address, amount: i128) {
+    env.events().publish((TRANSFER, from, to), amount);
+}
+
+pub(crate) fn consumed(env: &Env, consumer: Address, token_id: u64, amount_kwh: u64) {
+    env.events().publish((CONSUME, consumer, token_id), amount_kwh);
+}
@@ -0,0 +1,115 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::{loss, migration};
+use crate::{
+    audit, events, move_balance, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError,
+};
+
+const TRANSFER_FEE: Symbol = StorageKey::TransferFee.symbol();
+const TREASURY: Symbol = StorageKey::Treasury.symbol();
+
+/// Denominador das taxas em basis points (100% = 10.000 bps)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Taxa cobrada sobre transferências e tesouraria que recebe os valores retirados
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferFee {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+pub(crate) fn transfer_fee(env: &Env) -> Option<TransferFee> {
+    env.storage().instance().get(&TRANSFER_FEE)
+}
+
+/// `bps` basis points de `amount` (não negativo), arredondados para baixo sem estourar i128
+pub(crate) fn bps_of(amount: i128, bps: u32) -> i128 {
+    let denominator = i128::from(BPS_DENOMINATOR);
+    let bps = i128::from(bps);
+    amount / denominator * bps + amount % denominator * bps / denominator
+}
+
+/// Taxa devida sobre `amount`, arredondada para baixo
+pub(crate) fn fee_for(env: &Env, amount: i128) -> i128 {
+    match transfer_fee(env) {
+        Some(config) => bps_of(amount, config.fee_bps),
+        None => 0,
+    }
+}
+
+/// Valida e grava a taxa; a autorização fica a cargo de quem chama
+pub(crate) fn write_transfer_fee(env: &Env, fee: &TransferFee) {
+    if fee.fee_bps > BPS_DENOMINATOR {
+        panic_with_error!(env, STRGRIDError::InvalidAmount);
+    }
+    env.storage().instance().set(&TRANSFER_FEE, fee);
+}
+
+/// Taxas acumuladas, aceitando o valor gravado em u64 antes da migração para i128
+fn treasury_balance(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&TREASURY))
+}
+
+/// Transfere `amount` descontando a taxa, que fica acumulada no próprio contrato, e a perda
+/// de transmissão entre zonas, que é queimada
+pub(crate) fn transfer_with_fee(env: &Env, from: &Address, to: &Address, amount: i128, op: OpType) {
+    let fee = fee_for(env, amount);
+    let lost = loss::apply(env, from, to, amount - fee);
+    move_balance(env, from, to, amount - fee - lost, op);
+    if fee > 0 {
+        move_balance(env, from, &env.current_contract_address(), fee, OpType::Fee);
+        // O saldo do contrato também custodia leilões; a tesouraria é contabilizada à parte
+        env.storage()
+            .instance()
+            .set(&TREASURY, &migration::checked_add(env, treasury_balance(env), fee));
+        events::transfer_fee(env, from.clone(), to.clone(), amount, fee);
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define a taxa de transferência em basis points e a tesouraria; 0 desativa a cobrança (apenas admin)
+    pub fn set_transfer_fee(env: Env, fee_bps: u32, treasury: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_transfer_fee", (fee_bps, treasury.clone()));
+        write_transfer_fee(&env, &TransferFee { fee_bps, treasury });
+    }
+
+    /// Consulta a taxa de transferência vigente, se houver
+    pub fn get_transfer_fee(env: Env) -> Option<TransferFee> {
+        transfer_fee(&env)
+    }
+
+    /// Taxas acumuladas aguardando retirada para a tesouraria
+    pub fn treasury_balance(env: Env) -> i128 {
+        treasury_balance(&env)
+    }
+
+    /// Envia taxas acumuladas para a tesouraria configurada (apenas admin)
+    pub fn withdraw_treasury(env: Env, amount: i128) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "withdraw_treasury", (amount,));
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let treasury = treasury_balance(&env);
+        if amount > treasury {
+            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        }
+        let config = transfer_fee(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::FeeNotConfigured));
+        env.storage().instance().set(&TREASURY, &(treasury - amount));
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &config.treasury,
+            amount,
+            OpType::Fee,
+        );
+        events::treasury_withdrawn(&env, config.treasury, amount);
+    }
+}
@@ -0,0 +1,164 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::imbalance::{current_interval, metered, SETTLEMENT_INTERVAL_SECONDS};
+use crate::{
+    audit, events, maintenance, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError,
+};
+
+const FORECAST: Symbol = StorageKey::Forecast.symbol();
+const FORECAST_CONFIG: Symbol = StorageKey::ForecastConfig.symbol();
+const FORECAST_SETTLED: Symbol = StorageKey::ForecastSettled.symbol();
+
+const INTERVALS_PER_DAY: u64 = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+
+/// Regras de penalidade/prêmio pelo desvio entre previsão e entrega
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForecastConfig {
+    pub payment_token: Address,
+    /// Desvio tolerado, em bps da produção esperada
+    pub tolerance_bps: u32,
+    /// Cobrado por kWh de desvio além da tolerância
+    pub penalty_per_kwh: i128,
+    /// Pago por kWh esperado quando a entrega fica dentro da tolerância
+    pub reward_per_kwh: i128,
+}
+
+/// Comparação entre a previsão day-ahead e a entrega medida de um intervalo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForecastResult {
+    pub interval: u64,
+    pub forecast_kwh: u64,
+    /// Previsão descontada das paradas programadas no intervalo
+    pub expected_kwh: u64,
+    /// kWh mintados pelo gerador no intervalo
+    pub actual_kwh: u64,
+    pub deviation_kwh: u64,
+    /// Positivo é prêmio a receber, negativo é penalidade a pagar
+    pub amount: i128,
+    pub settled: bool,
+}
+
+fn config(env: &Env) -> Option<ForecastConfig> {
+    env.storage().instance().get(&FORECAST_CONFIG)
+}
+
+fn forecast(env: &Env, generator: &Address, interval: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&(FORECAST, generator.clone(), interval))
+}
+
+fn result(env: &Env, generator: &Address, interval: u64) -> ForecastResult {
+    let forecast_kwh = forecast(env, generator, interval)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::ForecastNotFound));
+    let start = interval * SETTLEMENT_INTERVAL_SECONDS;
+    let available =
+        maintenance::available_seconds(env, generator, start, start + SETTLEMENT_INTERVAL_SECONDS);
+    let expected_kwh =
+        (forecast_kwh as u128 * available as u128 / SETTLEMENT_INTERVAL_SECONDS as u128) as u64;
+    let (actual_kwh, _) = metered(env, generator, interval);
+    let deviation_kwh = actual_kwh.abs_diff(expected_kwh);
+
+    let amount = match config(env) {
+        Some(config) => {
+            let tolerance = (expected_kwh as u128 * config.tolerance_bps as u128 / 10_000) as u64;
+            let amount = if deviation_kwh > tolerance {
+                ((deviation_kwh - tolerance) as i128)
+                    .checked_mul(config.penalty_per_kwh)
+                    .map(|penalty| -penalty)
+            } else {
+                (expected_kwh as i128).checked_mul(config.reward_per_kwh)
+            };
+            amount.unwrap_or_else(|| panic_with_error!(env, STRGRIDError::InvalidAmount))
+        }
+        None => 0,
+    };
+    ForecastResult {
+        interval,
+        forecast_kwh,
+        expected_kwh,
+        actual_kwh,
+        deviation_kwh,
+        amount,
+        settled: env
+            .storage()
+            .persistent()
+            .has(&(FORECAST_SETTLED, generator.clone(), interval)),
+    }
+}
+
+/// Grava a previsão; a autorização (do gerador ou de um operador) é conferida por quem chama
+pub(crate) fn submit(env: &Env, generator: Address, interval: u64, forecast_kwh: u64) {
+    STRGRIDContract::get_generator(env.clone(), generator.clone());
+    if interval / INTERVALS_PER_DAY <= current_interval(env) / INTERVALS_PER_DAY {
+        panic_with_error!(env, STRGRIDExtError::ScheduleClosed);
+    }
+    env.storage()
+        .persistent()
+        .set(&(FORECAST, generator.clone(), interval), &forecast_kwh);
+    events::forecast_submitted(env, generator, interval, forecast_kwh);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define token, tolerância e valores de penalidade/prêmio das previsões (apenas admin)
+    pub fn set_forecast_config(env: Env, config: ForecastConfig) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_forecast_config", (config.clone(),));
+        if config.penalty_per_kwh < 0 || config.reward_per_kwh < 0 || config.tolerance_bps > 10_000 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage().instance().set(&FORECAST_CONFIG, &config);
+    }
+
+    /// Configuração de penalidades das previsões, se houver
+    pub fn get_forecast_config(env: Env) -> Option<ForecastConfig> {
+        config(&env)
+    }
+
+    /// Registra a previsão de produção de um intervalo de liquidação do dia seguinte em diante
+    ///
+    /// A previsão pode ser revisada até a virada do dia do intervalo.
+    pub fn submit_forecast(env: Env, generator: Address, interval: u64, forecast_kwh: u64) {
+        generator.require_auth();
+        submit(&env, generator, interval, forecast_kwh);
+    }
+
+    /// Previsão vs. entrega do gerador no intervalo; o valor é provisório até o intervalo fechar
+    pub fn get_forecast_result(env: Env, generator: Address, interval: u64) -> ForecastResult {
+        result(&env, &generator, interval)
+    }
+
+    /// Liquida o desvio de um intervalo encerrado: a penalidade é paga ao contrato e o prêmio
+    /// é pago pelo contrato, com o saldo acumulado das penalidades
+    pub fn settle_forecast(env: Env, generator: Address, interval: u64) -> i128 {
+        generator.require_auth();
+        let config = config(&env)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDExtError::ForecastNotConfigured));
+        if interval >= current_interval(&env) {
+            panic_with_error!(&env, STRGRIDExtError::IntervalOpen);
+        }
+        let result = result(&env, &generator, interval);
+        if result.settled {
+            panic_with_error!(&env, STRGRIDExtError::ForecastSettled);
+        }
+        env.storage()
+            .persistent()
+            .set(&(FORECAST_SETTLED, generator.clone(), interval), &true);
+
+        let payment = token::Client::new(&env, &config.payment_token);
+        let pool = env.current_contract_address();
+        if result.amount < 0 {
+            payment.transfer(&generator, &pool, &-result.amount);
+        } else if result.amount > 0 {
+            payment.transfer(&pool, &generator, &result.amount);
+        }
+
+        events::forecast_settled(&env, generator, &result);
+        result.amount
+    }
+}
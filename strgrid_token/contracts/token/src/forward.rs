@@ -0,0 +1,156 @@
+// `#[contractimpl]` gera `STRGRIDContractArgs::sell_forward` sem o allow do método
+#![allow(clippy::too_many_arguments)]
+
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{collateral, insurance, ratings};
+use crate::journal::OpType;
+use crate::{
+    events, move_balance, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+};
+
+const FORWARD: Symbol = StorageKey::Forward.symbol();
+const FORWARD_SEQ: Symbol = StorageKey::ForwardSeq.symbol();
+
+pub use strgrid_interface::{Forward, ForwardStatus};
+
+fn load(env: &Env, forward_id: u64) -> Forward {
+    env.storage()
+        .persistent()
+        .get(&(FORWARD, forward_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::ForwardNotFound))
+}
+
+pub(crate) fn load_open(env: &Env, forward_id: u64) -> Forward {
+    let forward = load(env, forward_id);
+    if forward.status != ForwardStatus::Open {
+        panic_with_error!(env, STRGRIDError::ForwardClosed);
+    }
+    forward
+}
+
+pub(crate) fn save(env: &Env, forward: &Forward) {
+    env.storage().persistent().set(&(FORWARD, forward.id), forward);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Vende `amount_kwh` para entrega na janela [`window_start`, `window_end`); o comprador
+    /// custodia `price` e o gerador custodia `penalty` no mesmo ativo de pagamento
+    pub fn sell_forward(
+        env: Env,
+        generator: Address,
+        buyer: Address,
+        amount_kwh: u64,
+        window_start: u64,
+        window_end: u64,
+        payment_token: Address,
+        price: i128,
+        penalty: i128,
+    ) -> u64 {
+        if generator == buyer {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        generator.require_auth();
+        buyer.require_auth();
+        if amount_kwh == 0 || price <= 0 || penalty < 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if window_start <= env.ledger().timestamp() || window_end <= window_start {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+        Self::get_generator(env.clone(), generator.clone());
+
+        let payment = token::Client::new(&env, &payment_token);
+        payment.transfer(&buyer, &env.current_contract_address(), &price);
+        if penalty > 0 {
+            payment.transfer(&generator, &env.current_contract_address(), &penalty);
+        }
+
+        let id: u64 = env.storage().instance().get(&FORWARD_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&FORWARD_SEQ, &id);
+        let forward = Forward {
+            id,
+            generator,
+            buyer,
+            amount_kwh,
+            window_start,
+            window_end,
+            payment_token,
+            price,
+            penalty,
+            status: ForwardStatus::Open,
+        };
+        save(&env, &forward);
+        collateral::lock_initial(&env, &forward);
+
+        events::forward_sold(&env, &forward);
+        id
+    }
+
+    /// Liquida a obrigação dentro da janela com energia emitida nela; o gerador recebe preço e caução,
+    /// descontada a fatia do pool de seguro
+    pub fn deliver_forward(env: Env, forward_id: u64) {
+        let mut forward = load_open(&env, forward_id);
+        forward.generator.require_auth();
+        let now = env.ledger().timestamp();
+        if now < forward.window_start || now >= forward.window_end {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+        let generator = Self::get_generator(env.clone(), forward.generator.clone());
+        if generator.last_mint_timestamp.is_none_or(|t| t < forward.window_start) {
+            panic_with_error!(&env, STRGRIDError::DeliveryNotMinted);
+        }
+
+        forward.status = ForwardStatus::Delivered;
+        save(&env, &forward);
+
+        move_balance(
+            &env,
+            &forward.generator,
+            &forward.buyer,
+            forward.amount_kwh.into(),
+            OpType::Forward,
+        );
+        ratings::record_purchase(&env, &forward.buyer, &forward.generator);
+        let price = insurance::collect_premium(&env, &forward.payment_token, forward.price);
+        token::Client::new(&env, &forward.payment_token).transfer(
+            &env.current_contract_address(),
+            &forward.generator,
+            &(price + forward.penalty),
+        );
+        collateral::release(&env, &forward, &forward.generator);
+
+        events::forward_delivered(&env, forward_id, forward.buyer, forward.amount_kwh);
+    }
+
+    /// Após a janela sem entrega, devolve o preço ao comprador e lhe paga a multa e o colateral
+    /// caucionados
+    pub fn claim_forward_default(env: Env, forward_id: u64) -> i128 {
+        let mut forward = load_open(&env, forward_id);
+        forward.buyer.require_auth();
+        if env.ledger().timestamp() < forward.window_end {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+
+        forward.status = ForwardStatus::Defaulted;
+        save(&env, &forward);
+
+        let payout = forward.price + forward.penalty;
+        token::Client::new(&env, &forward.payment_token).transfer(
+            &env.current_contract_address(),
+            &forward.buyer,
+            &payout,
+        );
+        let payout = payout + collateral::release(&env, &forward, &forward.buyer);
+
+        events::forward_defaulted(&env, forward_id, forward.generator, forward.penalty);
+        payout
+    }
+
+    /// Consulta um contrato a termo
+    pub fn get_forward(env: Env, forward_id: u64) -> Forward {
+        load(&env, forward_id)
+    }
+}
@@ -0,0 +1,52 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const FROZEN: Symbol = StorageKey::Frozen.symbol();
+
+pub(crate) fn is_frozen(env: &Env, account: &Address) -> bool {
+    env.storage().persistent().has(&(FROZEN, account.clone()))
+}
+
+/// Bloqueia saídas de saldo (transferências e burns) de contas congeladas
+pub(crate) fn require_not_frozen(env: &Env, account: &Address) {
+    if let Err(error) = try_require_not_frozen(env, account) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `require_not_frozen`, devolvendo o erro em vez de abortar
+pub(crate) fn try_require_not_frozen(env: &Env, account: &Address) -> Result<(), Error> {
+    if is_frozen(env, account) {
+        return Err(STRGRIDError::AccountFrozen.into());
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Congela uma conta, bloqueando transferências e burns (apenas admin)
+    pub fn freeze_account(env: Env, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "freeze_account", (account.clone(),));
+        env.storage().persistent().set(&(FROZEN, account.clone()), &true);
+        events::account_frozen(&env, account, true);
+    }
+
+    /// Descongela uma conta (apenas admin)
+    pub fn unfreeze_account(env: Env, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "unfreeze_account", (account.clone(),));
+        env.storage().persistent().remove(&(FROZEN, account.clone()));
+        events::account_frozen(&env, account, false);
+    }
+
+    /// Consulta se uma conta está congelada
+    pub fn is_frozen(env: Env, account: Address) -> bool {
+        is_frozen(&env, &account)
+    }
+}
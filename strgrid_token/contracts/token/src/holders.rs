@@ -0,0 +1,59 @@
+use soroban_sdk::{contractimpl, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::query::MAX_PAGE_SIZE;
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient};
+
+const HOLDERS: Symbol = StorageKey::Holders.symbol();
+const HOLDER_POSITION: Symbol = StorageKey::HolderPosition.symbol();
+
+fn holders(env: &Env) -> Vec<Address> {
+    env.storage().persistent().get(&HOLDERS).unwrap_or(Vec::new(env))
+}
+
+/// Mantém o registro de titulares em dia com o novo saldo do endereço
+///
+/// Remoções trocam o último titular para a posição liberada, então a ordem não é a de entrada.
+pub(crate) fn record_balance(env: &Env, account: &Address, balance: i128) {
+    let position_key = (HOLDER_POSITION, account.clone());
+    let position: Option<u32> = env.storage().persistent().get(&position_key);
+    match (position, balance > 0) {
+        (None, true) => {
+            let mut list = holders(env);
+            env.storage().persistent().set(&position_key, &list.len());
+            list.push_back(account.clone());
+            env.storage().persistent().set(&HOLDERS, &list);
+        }
+        (Some(position), false) => {
+            let mut list = holders(env);
+            let last = list.pop_back_unchecked();
+            if position < list.len() {
+                list.set(position, last.clone());
+                env.storage()
+                    .persistent()
+                    .set(&(HOLDER_POSITION, last), &position);
+            }
+            env.storage().persistent().remove(&position_key);
+            env.storage().persistent().set(&HOLDERS, &list);
+        }
+        _ => {}
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Número de endereços com saldo diferente de zero
+    pub fn holder_count(env: Env) -> u32 {
+        holders(&env).len()
+    }
+
+    /// Lista os endereços com saldo diferente de zero, paginado por offset/limit
+    pub fn holders(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let list = holders(&env);
+        let end = list.len().min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        if offset >= end {
+            return Vec::new(&env);
+        }
+        list.slice(offset..end)
+    }
+}
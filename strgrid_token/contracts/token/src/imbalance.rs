@@ -0,0 +1,192 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, dayahead, events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, STRGRIDExtError,
+};
+
+const SCHEDULE: Symbol = StorageKey::Schedule.symbol();
+const METERED: Symbol = StorageKey::Metered.symbol();
+const PLD: Symbol = StorageKey::Pld.symbol();
+const IMBALANCE_SETTLED: Symbol = StorageKey::ImbalanceSettled.symbol();
+
+pub use strgrid_interface::{PldPrice, SETTLEMENT_INTERVAL_SECONDS};
+
+/// Posição de um participante no intervalo: positiva é sobra a receber, negativa é falta a pagar
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImbalancePosition {
+    pub interval: u64,
+    /// Saldo programado (geração menos consumo)
+    pub scheduled_kwh: i128,
+    /// Saldo medido: kWh mintados menos kWh queimados no intervalo
+    pub metered_kwh: i128,
+    pub imbalance_kwh: i128,
+    /// Valor ao PLD do intervalo; zero enquanto o PLD não for publicado
+    pub amount: i128,
+    pub settled: bool,
+}
+
+pub(crate) fn current_interval(env: &Env) -> u64 {
+    env.ledger().timestamp() / SETTLEMENT_INTERVAL_SECONDS
+}
+
+fn scheduled(env: &Env, participant: &Address, interval: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(SCHEDULE, participant.clone(), interval))
+        .unwrap_or(0)
+}
+
+pub(crate) fn metered(env: &Env, participant: &Address, interval: u64) -> (u64, u64) {
+    env.storage()
+        .persistent()
+        .get(&(METERED, participant.clone(), interval))
+        .unwrap_or((0, 0))
+}
+
+fn pld(env: &Env, interval: u64) -> Option<PldPrice> {
+    env.storage().persistent().get(&(PLD, interval))
+}
+
+fn is_settled(env: &Env, participant: &Address, interval: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(IMBALANCE_SETTLED, participant.clone(), interval))
+}
+
+fn add_metered(env: &Env, participant: &Address, generated: u64, consumed: u64) {
+    let interval = current_interval(env);
+    let (total_generated, total_consumed) = metered(env, participant, interval);
+    env.storage().persistent().set(
+        &(METERED, participant.clone(), interval),
+        &(total_generated + generated, total_consumed + consumed),
+    );
+}
+
+/// Soma um mint à energia entregue pelo gerador no intervalo corrente
+pub(crate) fn record_delivery(env: &Env, generator: &Address, amount: u64) {
+    add_metered(env, generator, amount, 0);
+}
+
+/// Soma um burn à energia consumida no intervalo corrente
+pub(crate) fn record_consumption(env: &Env, consumer: &Address, amount: u64) {
+    add_metered(env, consumer, 0, amount);
+}
+
+/// Soma ao programa do gerador um compromisso day-ahead liquidado: a parte entregue se anula com a
+/// medição do intervalo e a não entregue passa a ser liquidada como desvio ao PLD
+pub(crate) fn roll_over(env: &Env, participant: &Address, interval: u64, committed_kwh: u64) {
+    let key = (SCHEDULE, participant.clone(), interval);
+    let scheduled = scheduled(env, participant, interval) + committed_kwh as i128;
+    env.storage().persistent().set(&key, &scheduled);
+}
+
+fn position(env: &Env, participant: &Address, interval: u64) -> ImbalancePosition {
+    let scheduled_kwh = scheduled(env, participant, interval);
+    let (generated, consumed) = metered(env, participant, interval);
+    let metered_kwh = generated as i128 - consumed as i128;
+    let imbalance_kwh = metered_kwh - scheduled_kwh;
+    let amount = match pld(env, interval) {
+        Some(price) => imbalance_kwh
+            .checked_mul(price.price_per_kwh)
+            .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::InvalidAmount)),
+        None => 0,
+    };
+    ImbalancePosition {
+        interval,
+        scheduled_kwh,
+        metered_kwh,
+        imbalance_kwh,
+        amount,
+        settled: is_settled(env, participant, interval),
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Programa o saldo líquido (geração menos consumo) de um intervalo futuro
+    pub fn schedule_energy(env: Env, participant: Address, interval: u64, scheduled_kwh: i128) {
+        participant.require_auth();
+        if interval <= current_interval(&env) {
+            panic_with_error!(&env, STRGRIDExtError::ScheduleClosed);
+        }
+        env.storage()
+            .persistent()
+            .set(&(SCHEDULE, participant.clone(), interval), &scheduled_kwh);
+        events::energy_scheduled(&env, participant, interval, scheduled_kwh);
+    }
+
+    /// Publica o PLD de um intervalo encerrado (apenas oracle)
+    pub fn publish_pld(
+        env: Env,
+        oracle: Address,
+        interval: u64,
+        payment_token: Address,
+        price_per_kwh: i128,
+    ) {
+        require_role(&env, &oracle, Role::Oracle);
+        audit::record(
+            &env,
+            &oracle,
+            "publish_pld",
+            (interval, payment_token.clone(), price_per_kwh),
+        );
+        if interval >= current_interval(&env) {
+            panic_with_error!(&env, STRGRIDExtError::IntervalOpen);
+        }
+        if price_per_kwh <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if pld(&env, interval).is_some() {
+            panic_with_error!(&env, STRGRIDExtError::PldAlreadyPublished);
+        }
+        let price = PldPrice {
+            payment_token,
+            price_per_kwh,
+        };
+        env.storage().persistent().set(&(PLD, interval), &price);
+        events::pld_published(&env, interval, &price);
+    }
+
+    /// Consulta o PLD publicado para um intervalo
+    pub fn get_pld(env: Env, interval: u64) -> PldPrice {
+        pld(&env, interval)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDExtError::PldNotPublished))
+    }
+
+    /// Posição de desvio de um participante no intervalo
+    pub fn get_imbalance(env: Env, participant: Address, interval: u64) -> ImbalancePosition {
+        position(&env, &participant, interval)
+    }
+
+    /// Liquida o desvio do intervalo ao PLD: a falta é paga ao contrato e a sobra é paga pelo contrato
+    pub fn settle_imbalance(env: Env, participant: Address, interval: u64) -> i128 {
+        participant.require_auth();
+        let price = pld(&env, interval)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDExtError::PldNotPublished));
+        if dayahead::open_commitments(&env, &participant, interval) > 0 {
+            panic_with_error!(&env, STRGRIDExtError::DayAheadUnsettled);
+        }
+        let imbalance = position(&env, &participant, interval);
+        if imbalance.settled {
+            panic_with_error!(&env, STRGRIDExtError::ImbalanceSettled);
+        }
+        env.storage()
+            .persistent()
+            .set(&(IMBALANCE_SETTLED, participant.clone(), interval), &true);
+
+        let payment = token::Client::new(&env, &price.payment_token);
+        let pool = env.current_contract_address();
+        if imbalance.amount < 0 {
+            payment.transfer(&participant, &pool, &-imbalance.amount);
+        } else if imbalance.amount > 0 {
+            payment.transfer(&pool, &participant, &imbalance.amount);
+        }
+
+        events::imbalance_settled(&env, participant, &imbalance);
+        imbalance.amount
+    }
+}
@@ -0,0 +1,254 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::migration;
+use crate::{
+    audit, dispute, events, move_balance, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError,
+};
+
+const INCENTIVE_SCHEDULE: Symbol = StorageKey::IncentiveSchedule.symbol();
+const INCENTIVE_POOL: Symbol = StorageKey::IncentivePool.symbol();
+const INCENTIVE_PRODUCTION: Symbol = StorageKey::IncentiveProduction.symbol();
+const INCENTIVE_TOTAL: Symbol = StorageKey::IncentiveTotal.symbol();
+const INCENTIVE_CLAIMED: Symbol = StorageKey::IncentiveClaimed.symbol();
+
+/// Curva de emissão do bootstrapping: cada época emite `decay_bps` a menos que a anterior
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncentiveSchedule {
+    pub start: u64,
+    pub epoch_seconds: u64,
+    pub epochs: u32,
+    pub initial_emission: u64,
+    pub decay_bps: u32,
+}
+
+impl IncentiveSchedule {
+    /// Época do bootstrapping que contém `timestamp`, se houver
+    fn epoch_at(&self, timestamp: u64) -> Option<u32> {
+        if timestamp < self.start {
+            return None;
+        }
+        let epoch = (timestamp - self.start) / self.epoch_seconds;
+        if epoch < self.epochs as u64 {
+            Some(epoch as u32)
+        } else {
+            None
+        }
+    }
+
+    /// STRGRID distribuído entre os geradores na época
+    pub fn emission(&self, epoch: u32) -> u64 {
+        if epoch >= self.epochs {
+            return 0;
+        }
+        let mut amount = self.initial_emission as u128;
+        for _ in 0..epoch {
+            amount = amount * (10_000 - self.decay_bps) as u128 / 10_000;
+        }
+        amount as u64
+    }
+}
+
+fn schedule(env: &Env) -> Option<IncentiveSchedule> {
+    env.storage().instance().get(&INCENTIVE_SCHEDULE)
+}
+
+fn load_schedule(env: &Env) -> IncentiveSchedule {
+    schedule(env).unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::IncentivesNotConfigured))
+}
+
+/// Saldo do pool, aceitando o valor gravado em u64 antes da migração para i128
+fn pool(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&INCENTIVE_POOL))
+}
+
+fn production(env: &Env, epoch: u32, generator: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(INCENTIVE_PRODUCTION, epoch, generator.clone()))
+        .unwrap_or(0)
+}
+
+fn total_production(env: &Env, epoch: u32) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(INCENTIVE_TOTAL, epoch))
+        .unwrap_or(0)
+}
+
+fn set_production(env: &Env, epoch: u32, generator: &Address, amount: u64, total: u64) {
+    env.storage()
+        .persistent()
+        .set(&(INCENTIVE_PRODUCTION, epoch, generator.clone()), &amount);
+    env.storage().persistent().set(&(INCENTIVE_TOTAL, epoch), &total);
+}
+
+/// Recompensa do gerador na época, limitada ao que resta no pool
+fn reward_of(env: &Env, schedule: &IncentiveSchedule, epoch: u32, generator: &Address) -> i128 {
+    let total = total_production(env, epoch);
+    if total == 0 {
+        return 0;
+    }
+    let share = schedule.emission(epoch) as u128 * production(env, epoch, generator) as u128
+        / total as u128;
+    i128::from(share as u64).min(pool(env))
+}
+
+/// Soma a produção emitida à época corrente do bootstrapping
+pub(crate) fn record_production(env: &Env, generator: &Address, amount_kwh: u64, minted_at: u64) {
+    let epoch = match schedule(env).and_then(|schedule| schedule.epoch_at(minted_at)) {
+        Some(epoch) => epoch,
+        None => return,
+    };
+    set_production(
+        env,
+        epoch,
+        generator,
+        production(env, epoch, generator) + amount_kwh,
+        total_production(env, epoch) + amount_kwh,
+    );
+}
+
+/// Retira da época a produção de um mint anulado na contestação
+pub(crate) fn void_production(env: &Env, generator: &Address, amount_kwh: u64, minted_at: u64) {
+    let epoch = match schedule(env).and_then(|schedule| schedule.epoch_at(minted_at)) {
+        Some(epoch) => epoch,
+        None => return,
+    };
+    set_production(
+        env,
+        epoch,
+        generator,
+        production(env, epoch, generator).saturating_sub(amount_kwh),
+        total_production(env, epoch).saturating_sub(amount_kwh),
+    );
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define a curva de emissão do bootstrapping, enquanto ele não começou (apenas admin)
+    pub fn configure_incentives(
+        env: Env,
+        start: u64,
+        epoch_seconds: u64,
+        epochs: u32,
+        initial_emission: u64,
+        decay_bps: u32,
+    ) {
+        let admin = require_admin(&env);
+        audit::record(
+            &env,
+            &admin,
+            "configure_incentives",
+            (start, epoch_seconds, epochs, initial_emission, decay_bps),
+        );
+        if epoch_seconds == 0 || epochs == 0 || initial_emission == 0 || decay_bps > 10_000 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if let Some(current) = schedule(&env) {
+            if env.ledger().timestamp() >= current.start {
+                panic_with_error!(&env, STRGRIDExtError::IncentivesStarted);
+            }
+        }
+
+        let schedule = IncentiveSchedule {
+            start,
+            epoch_seconds,
+            epochs,
+            initial_emission,
+            decay_bps,
+        };
+        env.storage().instance().set(&INCENTIVE_SCHEDULE, &schedule);
+    }
+
+    /// Deposita STRGRID no pool de incentivos; as recompensas nunca emitem tokens novos
+    pub fn fund_incentives(env: Env, funder: Address, amount: i128) {
+        funder.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        move_balance(
+            &env,
+            &funder,
+            &env.current_contract_address(),
+            amount,
+            OpType::Incentive,
+        );
+        env.storage()
+            .instance()
+            .set(&INCENTIVE_POOL, &migration::checked_add(&env, pool(&env), amount));
+
+        events::incentives_funded(&env, funder, amount);
+    }
+
+    /// Resgata a recompensa de uma época encerrada, pro-rata à produção verificada do gerador
+    ///
+    /// A época só é final depois que a janela de contestação dos seus últimos mints expira.
+    pub fn claim_incentives(env: Env, generator: Address, epoch: u32) -> i128 {
+        generator.require_auth();
+        let schedule = load_schedule(&env);
+        let epoch_end = schedule.start + (epoch as u64 + 1) * schedule.epoch_seconds;
+        if epoch >= schedule.epochs
+            || env.ledger().timestamp() < epoch_end + dispute::dispute_window(&env)
+        {
+            panic_with_error!(&env, STRGRIDExtError::EpochNotFinal);
+        }
+        let claimed_key = (INCENTIVE_CLAIMED, epoch, generator.clone());
+        if env.storage().persistent().has(&claimed_key) {
+            panic_with_error!(&env, STRGRIDError::NothingToClaim);
+        }
+        let reward = reward_of(&env, &schedule, epoch, &generator);
+        if reward == 0 {
+            panic_with_error!(&env, STRGRIDError::NothingToClaim);
+        }
+
+        env.storage().persistent().set(&claimed_key, &reward);
+        env.storage().instance().set(&INCENTIVE_POOL, &(pool(&env) - reward));
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &generator,
+            reward,
+            OpType::Incentive,
+        );
+
+        events::incentive_claimed(&env, generator, epoch, reward);
+        reward
+    }
+
+    /// Consulta a curva de emissão configurada
+    pub fn get_incentive_schedule(env: Env) -> IncentiveSchedule {
+        load_schedule(&env)
+    }
+
+    /// STRGRID emitido na época pela curva
+    pub fn epoch_emission(env: Env, epoch: u32) -> u64 {
+        load_schedule(&env).emission(epoch)
+    }
+
+    /// Recompensa ainda não resgatada pelo gerador na época
+    pub fn pending_incentives(env: Env, generator: Address, epoch: u32) -> i128 {
+        let schedule = load_schedule(&env);
+        if env
+            .storage()
+            .persistent()
+            .has(&(INCENTIVE_CLAIMED, epoch, generator.clone()))
+        {
+            return 0;
+        }
+        reward_of(&env, &schedule, epoch, &generator)
+    }
+
+    /// Produção verificada do gerador na época
+    pub fn incentive_production(env: Env, generator: Address, epoch: u32) -> u64 {
+        production(&env, epoch, &generator)
+    }
+
+    /// STRGRID disponível no pool de incentivos
+    pub fn incentive_pool(env: Env) -> i128 {
+        pool(&env)
+    }
+}
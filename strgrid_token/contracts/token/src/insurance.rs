@@ -0,0 +1,64 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::fee::BPS_DENOMINATOR;
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const INSURANCE: Symbol = StorageKey::Insurance.symbol();
+
+/// Pool de seguro contra inadimplência de entrega e a fatia que ele retém das liquidações
+///
+/// A fatia incide sobre o pagamento repassado ao gerador quando um contrato a termo é entregue
+/// ou um compromisso day-ahead é liquidado, no mesmo ativo do pagamento.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceConfig {
+    pub pool: Address,
+    pub premium_bps: u32,
+}
+
+fn insurance(env: &Env) -> Option<InsuranceConfig> {
+    env.storage().instance().get(&INSURANCE)
+}
+
+/// Envia ao pool a fatia de `gross`, custodiado pelo contrato, e devolve o que sobra ao gerador
+pub(crate) fn collect_premium(env: &Env, payment_token: &Address, gross: i128) -> i128 {
+    let Some(config) = insurance(env) else {
+        return gross;
+    };
+    let premium = gross * config.premium_bps as i128 / BPS_DENOMINATOR as i128;
+    if premium > 0 {
+        token::Client::new(env, payment_token).transfer(
+            &env.current_contract_address(),
+            &config.pool,
+            &premium,
+        );
+        events::insurance_premium(env, config.pool, payment_token.clone(), premium);
+    }
+    gross - premium
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o pool de seguro e sua fatia em basis points; `None` desativa a retenção (apenas admin)
+    pub fn set_insurance(env: Env, config: Option<InsuranceConfig>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_insurance", (config.clone(),));
+        match &config {
+            Some(config) if config.premium_bps > BPS_DENOMINATOR => {
+                panic_with_error!(&env, STRGRIDError::InvalidAmount)
+            }
+            Some(config) => env.storage().instance().set(&INSURANCE, config),
+            None => env.storage().instance().remove(&INSURANCE),
+        }
+        events::insurance_set(&env, config);
+    }
+
+    /// Consulta o pool de seguro vigente, se houver
+    pub fn get_insurance(env: Env) -> Option<InsuranceConfig> {
+        insurance(&env)
+    }
+}
@@ -0,0 +1,147 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Env, Symbol, Val, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::migration;
+use crate::query::MAX_PAGE_SIZE;
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient};
+
+const JOURNAL: Symbol = StorageKey::Journal.symbol();
+const JOURNAL_SEQ: Symbol = StorageKey::JournalSeq.symbol();
+const ROLLUP: Symbol = StorageKey::Rollup.symbol();
+
+/// Duração de um período de consolidação do diário (1 dia)
+pub const ROLLUP_PERIOD_SECONDS: u64 = 86_400;
+
+/// Contas do diário; `Issuance` e `Retired` são as contrapartidas de mint e burn
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JournalAccount {
+    Holder(Address),
+    Issuance,
+    Retired,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OpType {
+    Mint,
+    Burn,
+    Transfer,
+    TransferFrom,
+    Void,
+    Clawback,
+    Fee,
+    Swap,
+    Auction,
+    Forward,
+    DemandResponse,
+    TransmissionLoss,
+    Reservation,
+    Incentive,
+    Distribution,
+    Netting,
+    Wrap,
+    DayAhead,
+    Recovery,
+    Scheduled,
+    Keeper,
+}
+
+/// Lançamento de partida dobrada: débito e crédito sempre do mesmo valor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalEntry {
+    pub seq: u64,
+    pub debit: JournalAccount,
+    pub credit: JournalAccount,
+    pub amount: i128,
+    pub op: OpType,
+    pub reference: u64,
+    pub timestamp: u64,
+}
+
+/// Consolidação dos lançamentos de um período
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JournalRollup {
+    pub period: u64,
+    pub first_seq: u64,
+    pub last_seq: u64,
+    pub entry_count: u64,
+    pub total_debits: i128,
+    pub total_credits: i128,
+}
+
+/// Consolidação do período, aceitando a gravada em u64 antes da migração para i128
+fn read_rollup(env: &Env, period: u64) -> Option<JournalRollup> {
+    let stored: Option<Val> = env.storage().persistent().get(&(ROLLUP, period));
+    stored.map(|value| migration::rollup(env, &value))
+}
+
+/// Registra um movimento de valor no diário e na consolidação do período
+pub(crate) fn record(
+    env: &Env,
+    debit: JournalAccount,
+    credit: JournalAccount,
+    amount: i128,
+    op: OpType,
+    reference: u64,
+) {
+    let seq: u64 = env.storage().instance().get(&JOURNAL_SEQ).unwrap_or(0) + 1;
+    let timestamp = env.ledger().timestamp();
+    let entry = JournalEntry {
+        seq,
+        debit,
+        credit,
+        amount,
+        op,
+        reference,
+        timestamp,
+    };
+    env.storage().persistent().set(&(JOURNAL, seq), &entry);
+    env.storage().instance().set(&JOURNAL_SEQ, &seq);
+
+    let period = timestamp / ROLLUP_PERIOD_SECONDS;
+    let mut rollup = read_rollup(env, period).unwrap_or(JournalRollup {
+        period,
+        first_seq: seq,
+        last_seq: seq,
+        entry_count: 0,
+        total_debits: 0,
+        total_credits: 0,
+    });
+    rollup.last_seq = seq;
+    rollup.entry_count += 1;
+    rollup.total_debits = migration::checked_add(env, rollup.total_debits, amount);
+    rollup.total_credits = migration::checked_add(env, rollup.total_credits, amount);
+    env.storage().persistent().set(&(ROLLUP, period), &rollup);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Exporta lançamentos do diário a partir de `start_seq` (inclusive), paginado
+    pub fn journal_entries(env: Env, start_seq: u64, limit: u32) -> Vec<JournalEntry> {
+        let last_seq: u64 = env.storage().instance().get(&JOURNAL_SEQ).unwrap_or(0);
+        let limit = limit.min(MAX_PAGE_SIZE) as u64;
+        let mut entries = Vec::new(&env);
+        let mut seq = start_seq.max(1);
+        while seq <= last_seq && (entries.len() as u64) < limit {
+            let stored: Option<Val> = env.storage().persistent().get(&(JOURNAL, seq));
+            if let Some(value) = stored {
+                entries.push_back(migration::journal_entry(&env, &value));
+            }
+            seq += 1;
+        }
+        entries
+    }
+
+    /// Consulta a consolidação do diário para um período (timestamp / 86400)
+    pub fn journal_rollup(env: Env, period: u64) -> Option<JournalRollup> {
+        read_rollup(&env, period)
+    }
+
+    /// Número total de lançamentos registrados
+    pub fn journal_length(env: Env) -> u64 {
+        env.storage().instance().get(&JOURNAL_SEQ).unwrap_or(0)
+    }
+}
@@ -0,0 +1,107 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, consumer, events, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDExtError, GENERATOR,
+};
+
+const JURISDICTION: Symbol = StorageKey::Jurisdiction.symbol();
+const JURISDICTION_PAIR: Symbol = StorageKey::JurisdictionPair.symbol();
+const JURISDICTION_MODE: Symbol = StorageKey::JurisdictionMode.symbol();
+
+fn restrictions_enabled(env: &Env) -> bool {
+    env.storage().instance().get(&JURISDICTION_MODE).unwrap_or(false)
+}
+
+pub(crate) fn jurisdiction_of(env: &Env, account: &Address) -> Option<u32> {
+    env.storage().persistent().get(&(JURISDICTION, account.clone()))
+}
+
+fn pair_allowed(env: &Env, from: u32, to: u32) -> bool {
+    from == to || env.storage().persistent().has(&(JURISDICTION_PAIR, from, to))
+}
+
+/// Com as restrições ativas, rejeita transferências entre jurisdições sem par liberado
+///
+/// Só participantes com jurisdição atribuída entram na matriz; contas sem código (inclusive o
+/// próprio contrato) ficam a cargo das demais regras de compliance, como a allowlist de KYC.
+pub(crate) fn check_transfer(env: &Env, from: &Address, to: &Address) {
+    if let Err(error) = try_check_transfer(env, from, to) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `check_transfer`, devolvendo o erro em vez de abortar
+pub(crate) fn try_check_transfer(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+    if !restrictions_enabled(env) {
+        return Ok(());
+    }
+    if let (Some(from), Some(to)) = (jurisdiction_of(env, from), jurisdiction_of(env, to)) {
+        if !pair_allowed(env, from, to) {
+            return Err(STRGRIDExtError::JurisdictionRestricted.into());
+        }
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Atribui o código de jurisdição (ex.: ISO 3166-1 numérico) a um gerador ou consumidor
+    /// cadastrado; `None` remove (apenas admin)
+    pub fn set_jurisdiction(env: Env, account: Address, jurisdiction: Option<u32>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_jurisdiction", (account.clone(), jurisdiction));
+        let key = (JURISDICTION, account.clone());
+        match jurisdiction {
+            Some(code) => {
+                let registered = env.storage().persistent().has(&(GENERATOR, account.clone()))
+                    || consumer::is_registered(&env, &account);
+                if !registered {
+                    panic_with_error!(&env, STRGRIDExtError::ParticipantNotRegistered);
+                }
+                env.storage().persistent().set(&key, &code);
+            }
+            None => env.storage().persistent().remove(&key),
+        }
+        events::jurisdiction_set(&env, account, jurisdiction);
+    }
+
+    /// Código de jurisdição do participante, se atribuído
+    pub fn jurisdiction_of(env: Env, account: Address) -> Option<u32> {
+        jurisdiction_of(&env, &account)
+    }
+
+    /// Libera ou bloqueia transferências de `from` para `to` na matriz (apenas admin)
+    ///
+    /// A matriz é direcional; transferências dentro da mesma jurisdição são sempre permitidas.
+    pub fn set_jurisdiction_pair(env: Env, from: u32, to: u32, allowed: bool) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_jurisdiction_pair", (from, to, allowed));
+        let key = (JURISDICTION_PAIR, from, to);
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::jurisdiction_pair_set(&env, from, to, allowed);
+    }
+
+    /// Consulta se a matriz permite transferências de `from` para `to`
+    pub fn is_jurisdiction_pair_allowed(env: Env, from: u32, to: u32) -> bool {
+        pair_allowed(&env, from, to)
+    }
+
+    /// Ativa/desativa a aplicação da matriz de jurisdições nas transferências (apenas admin)
+    pub fn set_jurisdiction_restrictions(env: Env, enabled: bool) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_jurisdiction_restrictions", (enabled,));
+        env.storage().instance().set(&JURISDICTION_MODE, &enabled);
+        events::jurisdiction_mode_set(&env, enabled);
+    }
+
+    /// Consulta se a matriz de jurisdições está em vigor
+    pub fn is_jurisdiction_restricted(env: Env) -> bool {
+        restrictions_enabled(&env)
+    }
+}
@@ -0,0 +1,162 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Val};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::migration;
+use crate::{
+    events, move_balance, scheduled, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, STRGRIDExt2Error,
+};
+
+const KEEPER_TASK: Symbol = StorageKey::KeeperTask.symbol();
+const KEEPER_TASK_SEQ: Symbol = StorageKey::KeeperTaskSeq.symbol();
+
+/// Tempo de exclusividade de um keeper sobre a tarefa reivindicada
+pub const TASK_CLAIM_SECONDS: u64 = 600;
+
+/// Trabalho de manutenção que um keeper executa em nome do criador da tarefa
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeeperAction {
+    /// Varre até `limit` lotes vencidos
+    SweepExpired(u32),
+    ScheduledTransfer(u64),
+    SettleAuction(u64),
+}
+
+/// Tarefa com recompensa em kWh custodiada no contrato até a conclusão ou o cancelamento
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperTask {
+    pub id: u64,
+    pub creator: Address,
+    pub action: KeeperAction,
+    pub bounty: i128,
+    pub claimant: Option<Address>,
+    pub claim_expires_at: u64,
+}
+
+impl KeeperTask {
+    /// Keeper com reivindicação ainda vigente, se houver
+    pub fn active_claimant(&self, now: u64) -> Option<Address> {
+        self.claimant.clone().filter(|_| now < self.claim_expires_at)
+    }
+}
+
+fn load(env: &Env, task_id: u64) -> KeeperTask {
+    let stored: Val = env
+        .storage()
+        .persistent()
+        .get(&(KEEPER_TASK, task_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::TaskNotFound));
+    migration::keeper_task(env, &stored)
+}
+
+fn save(env: &Env, task: &KeeperTask) {
+    env.storage().persistent().set(&(KEEPER_TASK, task.id), task);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Registra uma tarefa de keeper, custodiando a recompensa paga pelo criador
+    pub fn create_task(env: Env, creator: Address, action: KeeperAction, bounty: i128) -> u64 {
+        creator.require_auth();
+        if bounty <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        move_balance(
+            &env,
+            &creator,
+            &env.current_contract_address(),
+            bounty,
+            OpType::Keeper,
+        );
+
+        let id: u64 = env.storage().instance().get(&KEEPER_TASK_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&KEEPER_TASK_SEQ, &id);
+        let task = KeeperTask {
+            id,
+            creator,
+            action,
+            bounty,
+            claimant: None,
+            claim_expires_at: 0,
+        };
+        save(&env, &task);
+
+        events::task_created(&env, &task);
+        id
+    }
+
+    /// Consulta uma tarefa em aberto
+    pub fn get_task(env: Env, task_id: u64) -> KeeperTask {
+        load(&env, task_id)
+    }
+
+    /// Reserva a tarefa ao keeper por `TASK_CLAIM_SECONDS`, evitando execuções concorrentes
+    pub fn claim_task(env: Env, keeper: Address, task_id: u64) -> u64 {
+        keeper.require_auth();
+        let mut task = load(&env, task_id);
+        let now = env.ledger().timestamp();
+        if task
+            .active_claimant(now)
+            .is_some_and(|claimant| claimant != keeper)
+        {
+            panic_with_error!(&env, STRGRIDExt2Error::TaskClaimed);
+        }
+        task.claimant = Some(keeper.clone());
+        task.claim_expires_at = now + TASK_CLAIM_SECONDS;
+        save(&env, &task);
+
+        events::task_claimed(&env, task_id, keeper, task.claim_expires_at);
+        task.claim_expires_at
+    }
+
+    /// Executa a ação reivindicada e paga a recompensa ao keeper
+    pub fn complete_task(env: Env, keeper: Address, task_id: u64) -> i128 {
+        keeper.require_auth();
+        let task = load(&env, task_id);
+        if task.active_claimant(env.ledger().timestamp()) != Some(keeper.clone()) {
+            panic_with_error!(&env, STRGRIDExt2Error::NotTaskClaimant);
+        }
+        env.storage().persistent().remove(&(KEEPER_TASK, task_id));
+
+        match task.action {
+            KeeperAction::SweepExpired(limit) => {
+                if Self::sweep_expired(env.clone(), limit) == 0 {
+                    panic_with_error!(&env, STRGRIDExt2Error::TaskNotReady);
+                }
+            }
+            KeeperAction::ScheduledTransfer(id) => scheduled::execute(&env, &keeper, id),
+            KeeperAction::SettleAuction(id) => Self::settle(env.clone(), id),
+        }
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &keeper,
+            task.bounty,
+            OpType::Keeper,
+        );
+
+        events::task_completed(&env, task_id, keeper, task.bounty);
+        task.bounty
+    }
+
+    /// Retira uma tarefa sem reivindicação vigente, devolvendo a recompensa ao criador
+    pub fn cancel_task(env: Env, task_id: u64) {
+        let task = load(&env, task_id);
+        task.creator.require_auth();
+        if task.active_claimant(env.ledger().timestamp()).is_some() {
+            panic_with_error!(&env, STRGRIDExt2Error::TaskClaimed);
+        }
+        env.storage().persistent().remove(&(KEEPER_TASK, task_id));
+        move_balance(
+            &env,
+            &env.current_contract_address(),
+            &task.creator,
+            task.bounty,
+            OpType::Keeper,
+        );
+        events::task_cancelled(&env, task_id, task.creator);
+    }
+}
@@ -0,0 +1,104 @@
+use soroban_sdk::{contractclient, contractimpl, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDExtError,
+};
+
+const KYC_MODE: Symbol = StorageKey::KycMode.symbol();
+const KYC_ALLOWED: Symbol = StorageKey::KycAllowed.symbol();
+const KYC_REGISTRY: Symbol = StorageKey::KycRegistry.symbol();
+
+/// Interface de um registro externo de KYC consultado quando a conta não está na allowlist local
+#[contractclient(name = "KycRegistryClient")]
+pub trait KycRegistry {
+    /// `true` se a conta passou pela verificação de identidade
+    fn is_allowed(env: Env, account: Address) -> bool;
+}
+
+fn kyc_mode(env: &Env) -> bool {
+    env.storage().instance().get(&KYC_MODE).unwrap_or(false)
+}
+
+fn kyc_registry(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&KYC_REGISTRY)
+}
+
+/// Conta na allowlist local ou aprovada pelo registro externo; o próprio contrato sempre passa
+pub(crate) fn is_allowed(env: &Env, account: &Address) -> bool {
+    if *account == env.current_contract_address()
+        || env.storage().persistent().has(&(KYC_ALLOWED, account.clone()))
+    {
+        return true;
+    }
+    kyc_registry(env).is_some_and(|registry| KycRegistryClient::new(env, &registry).is_allowed(account))
+}
+
+/// Com o modo KYC ativo, as duas pontas da movimentação precisam estar liberadas
+pub(crate) fn check_transfer(env: &Env, from: &Address, to: &Address) {
+    if let Err(error) = try_check_transfer(env, from, to) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `check_transfer`, devolvendo o erro em vez de abortar
+pub(crate) fn try_check_transfer(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+    if kyc_mode(env) && (!is_allowed(env, from) || !is_allowed(env, to)) {
+        return Err(STRGRIDExtError::NotAllowlisted.into());
+    }
+    Ok(())
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Ativa/desativa o modo KYC, que restringe transferências a contas liberadas (apenas admin)
+    pub fn set_kyc_mode(env: Env, enabled: bool) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_kyc_mode", (enabled,));
+        env.storage().instance().set(&KYC_MODE, &enabled);
+        events::kyc_mode_set(&env, enabled);
+    }
+
+    /// Consulta se o modo KYC está ativo
+    pub fn is_kyc_mode(env: Env) -> bool {
+        kyc_mode(&env)
+    }
+
+    /// Define o registro externo de KYC consultado além da allowlist; `None` remove (apenas admin)
+    pub fn set_kyc_registry(env: Env, registry: Option<Address>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_kyc_registry", (registry.clone(),));
+        match &registry {
+            Some(registry) => env.storage().instance().set(&KYC_REGISTRY, registry),
+            None => env.storage().instance().remove(&KYC_REGISTRY),
+        }
+        events::kyc_registry_set(&env, registry);
+    }
+
+    /// Registro externo de KYC configurado, se houver
+    pub fn get_kyc_registry(env: Env) -> Option<Address> {
+        kyc_registry(&env)
+    }
+
+    /// Libera uma conta na allowlist local (apenas admin)
+    pub fn add_to_allowlist(env: Env, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "add_to_allowlist", (account.clone(),));
+        env.storage().persistent().set(&(KYC_ALLOWED, account.clone()), &true);
+        events::allowlisted(&env, account, true);
+    }
+
+    /// Retira uma conta da allowlist local (apenas admin)
+    pub fn remove_from_allowlist(env: Env, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "remove_from_allowlist", (account.clone(),));
+        env.storage().persistent().remove(&(KYC_ALLOWED, account.clone()));
+        events::allowlisted(&env, account, false);
+    }
+
+    /// Consulta se a conta pode transferir com o modo KYC ativo
+    pub fn is_allowed(env: Env, account: Address) -> bool {
+        is_allowed(&env, &account)
+    }
+}
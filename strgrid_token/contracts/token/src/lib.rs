@@ -1,84 +1,475 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Env, String, 
-    BytesN, panic_with_error, Symbol
+    contract, contractimpl, Address, Env, Error, String, 
+    BytesN, panic_with_error, Symbol, Val, Vec
 };
 
+use strgrid_codes::StorageKey;
+
 // Símbolos para armazenamento de dados
-const BALANCE: Symbol = symbol_short!("BALANCE");
-const ALLOWANCE: Symbol = symbol_short!("ALLOW");
-const ADMIN: Symbol = symbol_short!("ADMIN");
-const GENERATOR: Symbol = symbol_short!("GEN");
-const TOTAL_SUPPLY: Symbol = symbol_short!("TOTAL");
-const METADATA: Symbol = symbol_short!("META");
-const ENERGY_DATA: Symbol = symbol_short!("ENERGY");
+pub(crate) const BALANCE: Symbol = StorageKey::Balance.symbol();
+pub(crate) const ALLOWANCE: Symbol = StorageKey::Allowance.symbol();
+pub(crate) const ADMIN: Symbol = StorageKey::Admin.symbol();
+pub(crate) const GENERATOR: Symbol = StorageKey::Generator.symbol();
+pub(crate) const TOTAL_SUPPLY: Symbol = StorageKey::TotalSupply.symbol();
+pub(crate) const METADATA: Symbol = StorageKey::Metadata.symbol();
+pub(crate) const ENERGY_DATA: Symbol = StorageKey::EnergyData.symbol();
+pub(crate) const TOKEN_SEQ: Symbol = StorageKey::TokenSeq.symbol();
+
+mod aggregator;
+mod allowance;
+mod applications;
+mod archive;
+mod auction;
+mod audit;
+mod bond;
+mod carbon;
+mod caps;
+mod certificate;
+mod checkpoint;
+mod classic;
+mod clawback;
+mod collateral;
+mod compliance;
+mod config;
+mod congestion;
+mod consumer;
+mod consumption;
+mod curtail;
+mod dayahead;
+mod demand;
+mod dispute;
+mod documents;
+mod distribution;
+mod events;
+mod fee;
+mod forecast;
+mod forward;
+mod freeze;
+mod holders;
+mod imbalance;
+mod incentives;
+mod insurance;
+mod journal;
+mod keepers;
+mod jurisdiction;
+mod kyc;
+mod loss;
+mod maintenance;
+mod metadata;
+mod migration;
+mod netmeter;
+mod netting;
+mod nonces;
+mod operators;
+mod oracle;
+mod permit;
+mod preview;
+mod priority;
+mod production;
+mod query;
+mod ratings;
+mod receiver;
+mod recovery;
+mod relay;
+mod reservation;
+mod roles;
+mod scheduled;
+mod signature;
+mod stats;
+mod stream;
+mod strict;
+mod swap;
+mod tiers;
+mod view;
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+
+pub use allowance::{AllowanceValue, SpendingLimit};
+pub use applications::{ApplicationStatus, GeneratorApplication};
+pub use archive::ArchiveAggregate;
+pub use auction::Auction;
+pub use audit::AuditEntry;
+pub use bond::BondConfig;
+pub use caps::MintCaps;
+pub use certificate::{Certificate, Retirement};
+pub use collateral::{CollateralConfig, ForwardMargin, PriceData, PriceOracle, PriceOracleClient};
+pub use config::{Config, ConfigChange};
+pub use congestion::{ZoneCongestion, MAX_CONGESTION_BPS};
+pub use consumer::{ConsumerClass, ConsumerProfile};
+pub use consumption::ConsumptionRecord;
+pub use curtail::CurtailmentOrder;
+pub use dayahead::{DayAheadCommitment, DayAheadOffer};
+pub use demand::{DrEnrollment, DrEvent};
+pub use fee::TransferFee;
+pub use imbalance::{ImbalancePosition, PldPrice, SETTLEMENT_INTERVAL_SECONDS};
+pub use netting::NETTING_PERIOD_SECONDS;
+pub use incentives::IncentiveSchedule;
+pub use insurance::InsuranceConfig;
+pub use forecast::{ForecastConfig, ForecastResult};
+pub use forward::{Forward, ForwardStatus};
+pub use dispute::MintChallenge;
+pub use documents::{AnchoredDocument, DocumentType};
+pub use journal::{JournalAccount, JournalEntry, JournalRollup, OpType};
+pub use keepers::{KeeperAction, KeeperTask, TASK_CLAIM_SECONDS};
+pub use maintenance::MaintenanceWindow;
+pub use netmeter::{NetPosition, BILLING_PERIOD_SECONDS};
+pub use operators::{OperatorGrant, OperatorPermission, OPERATOR_ROTATION_GRACE_SECONDS};
+pub use oracle::{MintOracle, MintOracleClient};
+pub use permit::PermitMessage;
+pub use relay::{RelayAction, RelayMessage};
+pub use compliance::{ComplianceHook, ComplianceHookClient};
+pub use kyc::{KycRegistry, KycRegistryClient};
+pub use preview::{BurnPreview, MintPreview, TransferPreview};
+pub use priority::Priority;
+pub use production::{DailyProduction, MAX_HISTORY_DAYS};
+pub use ratings::{GeneratorRating, MAX_RATING};
+pub use query::TokenFilter;
+pub use recovery::{RecoveryConfig, RecoveryRequest};
+pub use reservation::CapacityReservation;
+pub use roles::Role;
+pub use scheduled::ScheduledTransfer;
+pub use stats::SupplyStats;
+pub use stream::Stream;
+pub use tiers::{GeneratorTier, TierConfig};
+
+// Estruturas de dados compartilhadas com quem chama o contrato
+pub use strgrid_interface::{EnergyGenerator, EnergyToken, SourceType, TokenMetadata};
+
+// Erros customizados, definidos no crate compartilhado de códigos
+pub use strgrid_codes::{STRGRIDError, STRGRIDExt2Error, STRGRIDExtError};
+
+/// Número máximo de itens aceitos pelas operações em lote
+pub const MAX_BATCH_SIZE: u32 = 100;
 
-// Estruturas de dados
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct EnergyGenerator {
-    pub address: Address,
-    pub capacity_kw: u64,
-    pub current_production: u64,
-    pub is_active: bool,
-    pub registration_date: u64,
+#[contract]
+pub struct STRGRIDContract;
+
+/// Exige a autorização do admin e o retorna
+pub(crate) fn require_admin(env: &Env) -> Address {
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&ADMIN)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::NotAuthorized));
+    admin.require_auth();
+    admin
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct EnergyToken {
-    pub id: u64,
-    pub generator_id: Address,
-    pub amount_kwh: u64,
-    pub creation_timestamp: u64,
-    pub expiry_timestamp: u64,
-    pub is_consumed: bool,
+/// Saldo do endereço, aceitando entradas gravadas em u64 antes da migração para i128
+pub(crate) fn read_balance(env: &Env, address: &Address) -> i128 {
+    migration::amount(env, env.storage().persistent().get(&(BALANCE, address.clone())))
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct TokenMetadata {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u32,
-    pub total_supply: u64,
+pub(crate) fn write_balance(env: &Env, address: &Address, balance: i128) {
+    env.storage().persistent().set(&(BALANCE, address.clone()), &balance);
 }
 
-// Erros customizados
-#[soroban_sdk::contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum STRGRIDError {
-    NotAuthorized = 1,
-    InvalidAmount = 2,
-    InsufficientBalance = 3,
-    GeneratorNotFound = 4,
-    GeneratorInactive = 5,
-    InsufficientCapacity = 6,
-    TokenNotFound = 7,
-    InsufficientAllowance = 8,
-    AlreadyBurned = 9,
+/// Supply total, aceitando o valor gravado em u64 antes da migração para i128
+pub(crate) fn read_total_supply(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&TOTAL_SUPPLY))
 }
 
-#[contract]
-pub struct STRGRIDContract;
+pub(crate) fn write_total_supply(env: &Env, supply: i128) {
+    env.storage().instance().set(&TOTAL_SUPPLY, &supply);
+}
+
+/// Metadados com o supply lido de `TOTAL_SUPPLY`, a única fonte do valor
+pub(crate) fn read_metadata(env: &Env) -> TokenMetadata {
+    let stored: Val = env.storage().instance().get(&METADATA).unwrap();
+    TokenMetadata {
+        total_supply: read_total_supply(env),
+        ..migration::metadata(env, &stored)
+    }
+}
+
+/// Move saldo entre endereços respeitando tokens bloqueados e o hook de compliance, registra no
+/// diário e publica o `transfer`
+pub(crate) fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128, op: OpType) {
+    freeze::require_not_frozen(env, from);
+    kyc::check_transfer(env, from, to);
+    jurisdiction::check_transfer(env, from, to);
+    compliance::check_transfer(env, from, to, amount);
+    
+    let from_balance = read_balance(env, from);
+    dispute::require_unlocked(env, from, from_balance, amount);
+    let from_balance = from_balance - amount;
+    write_balance(env, from, from_balance);
+    checkpoint::record_balance(env, from, from_balance);
+    holders::record_balance(env, from, from_balance);
+    
+    let to_balance = migration::checked_add(env, read_balance(env, to), amount);
+    write_balance(env, to, to_balance);
+    checkpoint::record_balance(env, to, to_balance);
+    holders::record_balance(env, to, to_balance);
+    
+    journal::record(
+        env,
+        JournalAccount::Holder(to.clone()),
+        JournalAccount::Holder(from.clone()),
+        amount,
+        op,
+        0,
+    );
+    events::transferred(env, from.clone(), to.clone(), amount);
+}
+
+/// Grava um gerador ativo recém-cadastrado (pelo admin ou por aprovação de candidatura)
+pub(crate) fn insert_generator(
+    env: &Env,
+    generator: &Address,
+    capacity_kw: u64,
+    source_type: SourceType,
+    zone: u32,
+) {
+    if capacity_kw == 0 {
+        panic_with_error!(env, STRGRIDError::InvalidAmount);
+    }
+    
+    let energy_generator = EnergyGenerator {
+        address: generator.clone(),
+        capacity_kw,
+        current_production: 0,
+        is_active: true,
+        registration_date: env.ledger().timestamp(),
+        source_type,
+        zone,
+        last_mint_timestamp: None,
+        metadata_cid: None,
+    };
+    
+    env.storage().persistent().set(&(GENERATOR, generator.clone()), &energy_generator);
+    events::generator_registered(env, generator.clone(), capacity_kw);
+}
+
+/// Remove saldo de um endereço e do supply total, registrando no diário
+pub(crate) fn burn_balance(env: &Env, from: &Address, amount: i128, op: OpType, reference: u64) {
+    debit_balance(env, from, amount);
+    journal::record(
+        env,
+        JournalAccount::Retired,
+        JournalAccount::Holder(from.clone()),
+        amount,
+        op,
+        reference,
+    );
+}
+
+/// Debita saldo e supply de uma só vez; o lançamento no journal fica a cargo de quem chama
+fn debit_balance(env: &Env, from: &Address, amount: i128) {
+    freeze::require_not_frozen(env, from);
+    
+    let from_balance = read_balance(env, from);
+    dispute::require_unlocked(env, from, from_balance, amount);
+    let from_balance = from_balance - amount;
+    write_balance(env, from, from_balance);
+    checkpoint::record_balance(env, from, from_balance);
+    holders::record_balance(env, from, from_balance);
+    
+    let total_supply = read_total_supply(env) - amount;
+    write_total_supply(env, total_supply);
+    checkpoint::record_supply(env, total_supply);
+    stats::record_burn(env, amount);
+}
+
+/// kWh de um lote emitido a partir da quantidade informada; acima de u64 excede qualquer
+/// capacidade de gerador
+pub(crate) fn lot_amount(env: &Env, amount: i128) -> u64 {
+    try_lot_amount(amount).unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+/// Como `lot_amount`, devolvendo o erro em vez de abortar
+pub(crate) fn try_lot_amount(amount: i128) -> Result<u64, Error> {
+    if amount < 0 {
+        return Err(STRGRIDError::InvalidAmount.into());
+    }
+    u64::try_from(amount).map_err(|_| STRGRIDError::InsufficientCapacity.into())
+}
+
+/// Emite um lote de energia para o gerador; a autorização fica a cargo de quem chama
+pub(crate) fn mint_lot(
+    env: &Env,
+    generator: &Address,
+    energy_amount_kwh: u64,
+    expiry_hours: u64,
+) -> u64 {
+    // Valida o mint e calcula os efeitos (mesma lógica de preview_mint)
+    let plan = preview::plan_mint(env, generator, energy_amount_kwh, expiry_hours);
+    issue_lot(env, generator, energy_amount_kwh, plan)
+}
+
+/// Grava o lote de um mint já validado por `preview::plan_mint`
+fn issue_lot(env: &Env, generator: &Address, energy_amount_kwh: u64, plan: MintPreview) -> u64 {
+    // Pre-compute storage keys to avoid repeated cloning
+    let generator_key = (GENERATOR, generator.clone());
+    
+    let mut energy_generator: EnergyGenerator = env.storage()
+        .persistent()
+        .get(&generator_key)
+        .expect("Generator not found");
+    
+    // Gera ID único para o token
+    let token_id = next_token_id(env);
+    
+    let energy_token = EnergyToken {
+        id: token_id,
+        generator_id: generator.clone(),
+        amount_kwh: energy_amount_kwh,
+        creation_timestamp: env.ledger().timestamp(),
+        expiry_timestamp: plan.expiry_timestamp,
+        is_consumed: false,
+        dispute_until: plan.dispute_until,
+        is_voided: false,
+        source_type: energy_generator.source_type,
+        zone: energy_generator.zone,
+        vintage: vintage_of(env.ledger().timestamp()),
+        metadata_cid: None,
+    };
+    
+    // Atualiza produção atual e o último mint do gerador
+    energy_generator.current_production = plan.production_after;
+    energy_generator.last_mint_timestamp = Some(energy_token.creation_timestamp);
+    
+    // Batch storage updates com chaves pré-computadas
+    env.storage().persistent().set(&generator_key, &energy_generator);
+    env.storage().persistent().set(&(ENERGY_DATA, token_id), &energy_token);
+    write_balance(env, generator, plan.balance_after);
+    write_total_supply(env, plan.total_supply_after);
+    checkpoint::record_balance(env, generator, plan.balance_after);
+    holders::record_balance(env, generator, plan.balance_after);
+    checkpoint::record_supply(env, plan.total_supply_after);
+    
+    query::index_token(env, &energy_token);
+    stats::record_mint(env, &energy_token);
+    events::minted(
+        env,
+        generator.clone(),
+        token_id,
+        energy_amount_kwh,
+        energy_token.expiry_timestamp,
+    );
+    caps::record_mint(env, generator, energy_amount_kwh);
+    netmeter::record_generation(env, generator, energy_amount_kwh);
+    imbalance::record_delivery(env, generator, energy_amount_kwh);
+    production::record(env, generator, energy_amount_kwh, energy_token.creation_timestamp);
+    incentives::record_production(
+        env,
+        generator,
+        energy_amount_kwh,
+        energy_token.creation_timestamp,
+    );
+    journal::record(
+        env,
+        JournalAccount::Holder(generator.clone()),
+        JournalAccount::Issuance,
+        energy_amount_kwh.into(),
+        OpType::Mint,
+        token_id,
+    );
+    
+    // Tokens ficam intransferíveis até o fim da janela de contestação
+    if plan.dispute_until > energy_token.creation_timestamp {
+        dispute::lock_minted(env, generator, token_id);
+    }
+    
+    token_id
+}
+
+/// Consome um lote de energia do saldo do consumidor; a autorização fica a cargo de quem chama
+pub(crate) fn burn_lot(env: &Env, consumer: &Address, token_id: u64, amount: u64) -> u64 {
+    // Valida o burn e calcula os efeitos (mesma lógica de preview_burn)
+    let plan = preview::plan_burn(env, consumer, token_id, amount);
+    
+    // Debita saldo do consumidor e supply total
+    burn_balance(env, consumer, amount.into(), OpType::Burn, token_id);
+    consume_lot(env, consumer, token_id, amount, &plan.generator)
+}
+
+/// Encerra o lote e registra o consumo, com saldo e supply já debitados; retorna o REC emitido
+fn consume_lot(env: &Env, consumer: &Address, token_id: u64, amount: u64, generator: &Address) -> u64 {
+    let energy_token_key = (ENERGY_DATA, token_id);
+    let mut energy_token: EnergyToken = env.storage()
+        .persistent()
+        .get(&energy_token_key)
+        .expect("Token not found");
+    
+    // Obtém e atualiza dados do gerador (otimizado)
+    let generator_key = (GENERATOR, generator.clone());
+    let mut generator_data: EnergyGenerator = env.storage()
+        .persistent()
+        .get(&generator_key)
+        .expect("Generator not found");
+    generator_data.current_production = generator_data.current_production.saturating_sub(amount);
+    
+    // Marca token como consumido
+    energy_token.is_consumed = true;
+    stats::close_lot(env, &energy_token);
+    
+    // Batch de atualizações para otimizar storage
+    env.storage().persistent().set(&energy_token_key, &energy_token);
+    env.storage().persistent().set(&generator_key, &generator_data);
+    
+    ratings::record_purchase(env, consumer, &energy_token.generator_id);
+    consumption::record(env, consumer, ConsumptionRecord {
+        token_id,
+        generator: energy_token.generator_id.clone(),
+        amount_kwh: amount,
+        timestamp: env.ledger().timestamp(),
+    });
+    
+    carbon::record(env, consumer, energy_token.source_type, amount);
+    netmeter::record_consumption(env, consumer, amount);
+    imbalance::record_consumption(env, consumer, amount);
+    events::consumed(env, consumer.clone(), token_id, amount);
+    
+    // Comprovante auditável de consumo renovável
+    certificate::issue(env, consumer, &energy_token, amount)
+}
+
+/// Converte um timestamp Unix na safra (ano, mês) UTC
+pub(crate) fn vintage_of(timestamp: u64) -> (u32, u32) {
+    // Algoritmo civil_from_days (H. Hinnant) sobre dias desde 1970-01-01
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as u32, month as u32)
+}
+
+/// Gera o próximo ID sequencial de token de energia
+fn next_token_id(env: &Env) -> u64 {
+    let token_id: u64 = env.storage().instance().get(&TOKEN_SEQ).unwrap_or(0) + 1;
+    env.storage().instance().set(&TOKEN_SEQ, &token_id);
+    token_id
+}
 
 #[contractimpl]
 
 impl STRGRIDContract {
-    /// Inicializa o contrato com metadados do token
-    pub fn initialize(
+    /// Construtor executado no deploy com admin, metadados e configuração inicial
+    ///
+    /// Tudo é gravado na mesma transação que cria o contrato, sem a janela em que outra conta
+    /// poderia inicializá-lo antes do deployer.
+    pub fn __constructor(
         env: Env,
         admin: Address,
         name: String,
         symbol: String,
         decimals: u32,
+        clawback_enabled: bool,
+        config: Config,
     ) {
-        if env.storage().instance().has(&ADMIN) {
-            panic_with_error!(&env, STRGRIDError::NotAuthorized);
-        }
-        
-        admin.require_auth();
+        audit::record(
+            &env,
+            &admin,
+            "__constructor",
+            (name.clone(), symbol.clone(), decimals, clawback_enabled, config.clone()),
+        );
         
         let metadata = TokenMetadata {
             name,
@@ -89,10 +480,15 @@ impl STRGRIDContract {
         
         env.storage().instance().set(&ADMIN, &admin);
         env.storage().instance().set(&METADATA, &metadata);
-        env.storage().instance().set(&TOTAL_SUPPLY, &0u64);
+        write_total_supply(&env, 0);
+        env.storage().instance().set(&clawback::CLAWBACK_ENABLED, &clawback_enabled);
+        config::apply(&env, &config);
     }
     
-    /// Registra uma nova fonte geradora de energia
+    /// Registra uma nova fonte geradora de energia diretamente (apenas admin)
+    ///
+    /// Reservado a migrações e cargas iniciais; geradores novos entram pela candidatura
+    /// `apply_as_generator`, revisada por um operador da rede.
     pub fn register_generator(
         env: Env,
         generator: Address,
@@ -101,139 +497,128 @@ impl STRGRIDContract {
         let admin: Address = env.storage().instance().get(&ADMIN)
             .expect("Not authorized");
         admin.require_auth();
+        audit::record(&env, &admin, "register_generator", (generator.clone(), capacity_kw));
         
-        if capacity_kw == 0 {
-            panic_with_error!(&env, STRGRIDError::InvalidAmount);
-        }
-        
-        let energy_generator = EnergyGenerator {
-            address: generator.clone(),
-            capacity_kw,
-            current_production: 0,
-            is_active: true,
-            registration_date: env.ledger().timestamp(),
-        };
-        
-        env.storage().persistent().set(&(GENERATOR, generator), &energy_generator);
+        insert_generator(&env, &generator, capacity_kw, SourceType::Unspecified, 0);
     }
     
     /// Mint de tokens de energia por fontes geradoras com suporte a oracle proof
     pub fn mint_energy_tokens(
         env: Env,
         generator: Address,
-        energy_amount_kwh: u64,
+        energy_amount_kwh: i128,
         expiry_hours: u64,
         oracle_proof: Option<BytesN<32>>,
     ) -> u64 {
         generator.require_auth();
+        let energy_amount_kwh = lot_amount(&env, energy_amount_kwh);
         
-        // Future integration: Validate with oracle proof from CCEE
-        if let Some(_proof) = oracle_proof {
-            // TODO: Implement oracle proof validation for CCEE PLD data
-            // This will validate energy pricing against official CCEE rates
-        }
+        // Com oracle de medição configurado, a leitura precisa estar atestada
+        oracle::check_mint_proof(&env, &generator, energy_amount_kwh, &oracle_proof);
         
-        // Pre-compute storage keys to avoid repeated cloning
-        let generator_key = (GENERATOR, generator.clone());
-        let balance_key = (BALANCE, generator.clone());
+        mint_lot(&env, &generator, energy_amount_kwh, expiry_hours)
+    }
+    
+    /// Emite um lote por leitura (kWh, validade em horas, prova do oracle), p.ex. os intervalos
+    /// de 15 minutos de um dia, e retorna os IDs na mesma ordem
+    ///
+    /// O intervalo mínimo entre mints vale para a chamada, não para cada leitura.
+    pub fn mint_energy_tokens_batch(
+        env: Env,
+        generator: Address,
+        readings: Vec<(i128, u64, Option<BytesN<32>>)>,
+    ) -> Vec<u64> {
+        generator.require_auth();
         
-        // Verifica se o gerador está registrado e ativo
-        let mut energy_generator: EnergyGenerator = env.storage()
-            .persistent()
-            .get(&generator_key)
-            .expect("Generator not found");
-            
-        if !energy_generator.is_active {
-            panic_with_error!(&env, STRGRIDError::GeneratorInactive);
+        if readings.is_empty() || readings.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
         }
         
-        // Verifica capacidade disponível
-        if energy_generator.current_production + energy_amount_kwh > energy_generator.capacity_kw {
-            panic_with_error!(&env, STRGRIDError::InsufficientCapacity);
+        let mut token_ids = Vec::new(&env);
+        for (energy_amount_kwh, expiry_hours, oracle_proof) in readings.iter() {
+            let energy_amount_kwh = lot_amount(&env, energy_amount_kwh);
+            oracle::check_mint_proof(&env, &generator, energy_amount_kwh, &oracle_proof);
+            let plan = if token_ids.is_empty() {
+                preview::plan_mint(&env, &generator, energy_amount_kwh, expiry_hours)
+            } else {
+                preview::plan_batch_reading(&env, &generator, energy_amount_kwh, expiry_hours)
+            };
+            token_ids.push_back(issue_lot(&env, &generator, energy_amount_kwh, plan));
         }
-        
-        // Gera ID único para o token e obtém timestamp uma vez
-        let current_time = env.ledger().timestamp();
-        let token_id = current_time;
-        let expiry_timestamp = current_time + (expiry_hours * 3600);
-        
-        let energy_token = EnergyToken {
-            id: token_id,
-            generator_id: generator.clone(),
-            amount_kwh: energy_amount_kwh,
-            creation_timestamp: current_time,
-            expiry_timestamp,
-            is_consumed: false,
-        };
-        
-        // Atualiza produção atual do gerador
-        energy_generator.current_production += energy_amount_kwh;
-        
-        // Obtém valores atuais
-        let current_balance = env.storage().persistent().get(&balance_key).unwrap_or(0u64);
-        let total_supply: u64 = env.storage().instance().get(&TOTAL_SUPPLY).unwrap_or(0);
-        
-        // Batch storage updates com chaves pré-computadas
-        env.storage().persistent().set(&generator_key, &energy_generator);
-        env.storage().persistent().set(&(ENERGY_DATA, token_id), &energy_token);
-        env.storage().persistent().set(&balance_key, &(current_balance + energy_amount_kwh));
-        env.storage().instance().set(&TOTAL_SUPPLY, &(total_supply + energy_amount_kwh));
-        
-        token_id
+        token_ids
     }
     
-    /// Queima tokens quando energia é consumida (otimizado)
+    /// Queima tokens quando energia é consumida e retorna o ID do REC emitido
     pub fn burn_energy_tokens(
         env: Env,
         consumer: Address,
         token_id: u64,
-        amount: u64,
-    ) {
+        amount: i128,
+    ) -> u64 {
         consumer.require_auth();
         
-        // Verifica se o token existe e obtém dados
-        let energy_token_key = (ENERGY_DATA, token_id);
-        let mut energy_token: EnergyToken = env.storage()
-            .persistent()
-            .get(&energy_token_key)
-            .expect("Token not found");
-            
-        // Verifica se o token não expirou
-        if env.ledger().timestamp() > energy_token.expiry_timestamp {
-            panic_with_error!(&env, STRGRIDError::TokenNotFound);
-        }
+        burn_lot(&env, &consumer, token_id, lot_amount(&env, amount))
+    }
+    
+    /// Consome vários lotes com uma única autorização e um único débito de saldo e supply
+    ///
+    /// Todos os lotes são validados antes do débito; retorna os IDs dos RECs na mesma ordem.
+    pub fn burn_energy_tokens_batch(
+        env: Env,
+        consumer: Address,
+        burns: Vec<(u64, i128)>,
+    ) -> Vec<u64> {
+        consumer.require_auth();
         
-        // Verifica se já foi consumido
-        if energy_token.is_consumed {
-            panic_with_error!(&env, STRGRIDError::AlreadyBurned);
+        if burns.is_empty() || burns.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
         }
         
-        // Verifica saldo do consumidor (otimizado)
-        let consumer_balance_key = (BALANCE, consumer.clone());
-        let consumer_balance = env.storage().persistent().get(&consumer_balance_key).unwrap_or(0u64);
-        if consumer_balance < amount {
-            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        let mut generators = Vec::new(&env);
+        let mut total: u64 = 0;
+        for (index, (token_id, amount)) in burns.iter().enumerate() {
+            if burns.iter().take(index).any(|(seen, _)| seen == token_id) {
+                panic_with_error!(&env, STRGRIDError::AlreadyBurned);
+            }
+            let amount = lot_amount(&env, amount);
+            generators.push_back(preview::plan_burn(&env, &consumer, token_id, amount).generator);
+            total = total
+                .checked_add(amount)
+                .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ArithmeticOverflow));
         }
         
-        // Obtém e atualiza dados do gerador (otimizado)
-        let generator_key = (GENERATOR, energy_token.generator_id.clone());
-        let mut generator_data: EnergyGenerator = env.storage()
-            .persistent()
-            .get(&generator_key)
-            .expect("Generator not found");
-        generator_data.current_production -= amount;
-        
-        // Marca token como consumido
-        energy_token.is_consumed = true;
-        
-        // Batch de atualizações para otimizar storage
-        env.storage().persistent().set(&energy_token_key, &energy_token);
-        env.storage().persistent().set(&consumer_balance_key, &(consumer_balance - amount));
-        env.storage().persistent().set(&generator_key, &generator_data);
+        debit_balance(&env, &consumer, total.into());
+        let mut certificate_ids = Vec::new(&env);
+        for ((token_id, amount), generator) in burns.iter().zip(generators.iter()) {
+            let amount = lot_amount(&env, amount);
+            journal::record(
+                &env,
+                JournalAccount::Retired,
+                JournalAccount::Holder(consumer.clone()),
+                amount.into(),
+                OpType::Burn,
+                token_id,
+            );
+            certificate_ids.push_back(consume_lot(&env, &consumer, token_id, amount, &generator));
+        }
+        certificate_ids
+    }
+    
+    /// Consome energia em nome do consumidor usando a allowance concedida ao operador
+    ///
+    /// Permite que varejistas e agregadores aprovados liquidem o consumo automaticamente.
+    pub fn burn_energy_tokens_from(
+        env: Env,
+        operator: Address,
+        consumer: Address,
+        token_id: u64,
+        amount: i128,
+    ) -> u64 {
+        operator.require_auth();
         
-        // Atualiza supply total
-        let total_supply: u64 = env.storage().instance().get(&TOTAL_SUPPLY).unwrap_or(0);
-        env.storage().instance().set(&TOTAL_SUPPLY, &(total_supply - amount));
+        let amount_kwh = lot_amount(&env, amount);
+        allowance::spend(&env, &consumer, &operator, amount);
+        burn_lot(&env, &consumer, token_id, amount_kwh)
     }
     
     /// Transfere tokens entre endereços
@@ -241,28 +626,83 @@ impl STRGRIDContract {
         env: Env,
         from: Address,
         to: Address,
-        amount: u64,
+        amount: i128,
     ) {
         from.require_auth();
         
-        if amount == 0 {
+        if amount <= 0 {
             panic_with_error!(&env, STRGRIDError::InvalidAmount);
         }
+        strict::check_transfer(&env, &from, &to);
         
-        // Otimizado - pre-compute keys to avoid repeated cloning
-        let from_key = (BALANCE, from.clone());
-        let to_key = (BALANCE, to.clone());
+        fee::transfer_with_fee(&env, &from, &to, amount, OpType::Transfer);
+    }
+    
+    /// Transfere tokens anexando um memo/referência publicado no evento (conciliação de faturas)
+    pub fn transfer_with_memo(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        memo: BytesN<32>,
+    ) {
+        Self::transfer(env.clone(), from.clone(), to.clone(), amount);
+        events::transfer_memo(&env, from, to, amount, memo);
+    }
+    
+    /// Transfere para vários destinatários com uma única autorização
+    pub fn transfer_batch(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>,
+    ) {
+        from.require_auth();
         
-        let from_balance = env.storage().persistent().get(&from_key).unwrap_or(0u64);
-        if from_balance < amount {
-            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
+        if recipients.is_empty() || recipients.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
         }
         
-        let to_balance = env.storage().persistent().get(&to_key).unwrap_or(0u64);
+        for (to, amount) in recipients.iter() {
+            if amount <= 0 {
+                panic_with_error!(&env, STRGRIDError::InvalidAmount);
+            }
+            strict::check_transfer(&env, &from, &to);
+            fee::transfer_with_fee(&env, &from, &to, amount, OpType::Transfer);
+        }
+    }
+    
+    /// Queima tokens do próprio saldo, reduzindo o supply (SEP-41 like)
+    pub fn burn(
+        env: Env,
+        from: Address,
+        amount: i128,
+    ) {
+        from.require_auth();
+        
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
         
-        // Batch storage updates with pre-computed keys
-        env.storage().persistent().set(&from_key, &(from_balance - amount));
-        env.storage().persistent().set(&to_key, &(to_balance + amount));
+        burn_balance(&env, &from, amount, OpType::Burn, 0);
+        events::burned(&env, from, amount);
+    }
+    
+    /// Queima tokens de `from` consumindo a allowance do spender (SEP-41 like)
+    pub fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+    ) {
+        spender.require_auth();
+        
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        
+        allowance::spend(&env, &from, &spender, amount);
+        burn_balance(&env, &from, amount, OpType::Burn, 0);
+        events::burned(&env, from, amount);
     }
     
     /// Aprova um endereço para gastar tokens em nome do proprietário (ERC-20 like)
@@ -270,12 +710,75 @@ impl STRGRIDContract {
         env: Env,
         owner: Address,
         spender: Address,
-        amount: u64,
+        amount: i128,
+    ) {
+        Self::approve_until(env, owner, spender, amount, None);
+    }
+    
+    /// Aprova um spender até o ledger `live_until_ledger` (SEP-41 like); `None` não expira
+    pub fn approve_until(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: Option<u32>,
     ) {
         owner.require_auth();
+        allowance::approve(&env, &owner, &spender, amount, live_until_ledger);
+    }
+    
+    /// Aumenta a allowance atual de um spender em `delta`, mantendo a expiração
+    ///
+    /// Uma allowance já expirada não é renovada por aqui: o dono precisa aprovar de novo, com
+    /// nova expiração, em `approve_until`.
+    pub fn increase_allowance(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        delta: i128,
+    ) -> i128 {
+        owner.require_auth();
         
-        let allowance_key = (ALLOWANCE, owner, spender);
-        env.storage().persistent().set(&allowance_key, &amount);
+        if delta <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        
+        let mut value = allowance::read_raw(&env, &owner, &spender).unwrap_or(AllowanceValue {
+            amount: 0,
+            live_until_ledger: None,
+        });
+        if value.is_expired(&env) {
+            panic_with_error!(&env, STRGRIDError::AllowanceExpired);
+        }
+        value.amount = value.amount
+            .checked_add(delta)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ArithmeticOverflow));
+        
+        allowance::write(&env, &owner, &spender, &value);
+        value.amount
+    }
+    
+    /// Reduz a allowance atual de um spender em `delta`, mantendo a expiração
+    pub fn decrease_allowance(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        delta: i128,
+    ) -> i128 {
+        owner.require_auth();
+        
+        if delta <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        
+        let mut value = allowance::read_live(&env, &owner, &spender);
+        if value.amount < delta {
+            panic_with_error!(&env, STRGRIDError::InsufficientAllowance);
+        }
+        value.amount -= delta;
+        
+        allowance::write(&env, &owner, &spender, &value);
+        value.amount
     }
     
     /// Transfere tokens usando allowance (ERC-20 like)
@@ -284,58 +787,44 @@ impl STRGRIDContract {
         spender: Address,
         from: Address,
         to: Address,
-        amount: u64,
+        amount: i128,
     ) {
         spender.require_auth();
         
-        if amount == 0 {
+        if amount <= 0 {
             panic_with_error!(&env, STRGRIDError::InvalidAmount);
         }
+        strict::check_transfer(&env, &from, &to);
         
-        // Pre-compute storage keys para evitar clonagem repetida
-        let allowance_key = (ALLOWANCE, from.clone(), spender.clone());
-        let from_key = (BALANCE, from.clone());
-        let to_key = (BALANCE, to.clone());
-        
-        // Verifica allowance
-        let current_allowance = env.storage().persistent().get(&allowance_key).unwrap_or(0u64);
-        if current_allowance < amount {
-            panic_with_error!(&env, STRGRIDError::InsufficientAllowance);
-        }
-        
-        // Verifica saldo do from
-        let from_balance = env.storage().persistent().get(&from_key).unwrap_or(0u64);
-        if from_balance < amount {
-            panic_with_error!(&env, STRGRIDError::InsufficientBalance);
-        }
-        
-        // Obtém saldo do destinatário
-        let to_balance = env.storage().persistent().get(&to_key).unwrap_or(0u64);
+        // Verifica e consome allowance
+        allowance::spend(&env, &from, &spender, amount);
         
-        // Batch de atualizações com chaves pré-computadas
-        env.storage().persistent().set(&from_key, &(from_balance - amount));
-        env.storage().persistent().set(&to_key, &(to_balance + amount));
-        env.storage().persistent().set(&allowance_key, &(current_allowance - amount));
+        fee::transfer_with_fee(&env, &from, &to, amount, OpType::TransferFrom);
     }
     
-    /// Consulta allowance entre owner e spender
-    pub fn allowance(env: Env, owner: Address, spender: Address) -> u64 {
-        env.storage().persistent().get(&(ALLOWANCE, owner, spender)).unwrap_or(0)
+    /// Consulta allowance entre owner e spender (zero após a expiração)
+    pub fn allowance(env: Env, owner: Address, spender: Address) -> i128 {
+        allowance::read_live(&env, &owner, &spender).amount
+    }
+    
+    /// Consulta allowance completa, incluindo o ledger de expiração
+    pub fn get_allowance(env: Env, owner: Address, spender: Address) -> Option<AllowanceValue> {
+        allowance::read_raw(&env, &owner, &spender)
     }
     
     /// Consulta saldo de um endereço
-    pub fn balance_of(env: Env, address: Address) -> u64 {
-        env.storage().persistent().get(&(BALANCE, address)).unwrap_or(0)
+    pub fn balance_of(env: Env, address: Address) -> i128 {
+        read_balance(&env, &address)
     }
     
     /// Consulta supply total
-    pub fn total_supply(env: Env) -> u64 {
-        env.storage().instance().get(&TOTAL_SUPPLY).unwrap_or(0)
+    pub fn total_supply(env: Env) -> i128 {
+        read_total_supply(&env)
     }
     
     /// Consulta metadados do token
     pub fn get_metadata(env: Env) -> TokenMetadata {
-        env.storage().instance().get(&METADATA).unwrap()
+        read_metadata(&env)
     }
     
     /// Consulta dados de um gerador
@@ -354,6 +843,16 @@ impl STRGRIDContract {
             .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::TokenNotFound))
     }
     
+    /// Capacidade registrada (kW) de um gerador
+    pub fn capacity_of(env: Env, generator: Address) -> u64 {
+        Self::get_generator(env, generator).capacity_kw
+    }
+    
+    /// Gerador que emitiu um token de energia
+    pub fn generator_of(env: Env, token_id: u64) -> Address {
+        Self::get_energy_token(env, token_id).generator_id
+    }
+    
     /// Ativa/desativa um gerador (apenas admin)
     pub fn set_generator_status(
         env: Env,
@@ -363,6 +862,7 @@ impl STRGRIDContract {
         let admin: Address = env.storage().instance().get(&ADMIN)
             .expect("Not authorized");
         admin.require_auth();
+        audit::record(&env, &admin, "set_generator_status", (generator.clone(), is_active));
         
         let generator_key = (GENERATOR, generator.clone());
         let mut energy_generator: EnergyGenerator = env
@@ -371,8 +871,11 @@ impl STRGRIDContract {
             .get(&generator_key)
             .expect("Generator not found");
             
+        strict::check_update(&env, &energy_generator.is_active, &is_active);
         energy_generator.is_active = is_active;
         env.storage().persistent().set(&generator_key, &energy_generator);
+        
+        events::generator_status_set(&env, generator, is_active);
     }
     
     /// Atualiza capacidade de um gerador (apenas admin)
@@ -384,6 +887,12 @@ impl STRGRIDContract {
         let admin: Address = env.storage().instance().get(&ADMIN)
             .expect("Not authorized");
         admin.require_auth();
+        audit::record(
+            &env,
+            &admin,
+            "update_generator_capacity",
+            (generator.clone(), new_capacity_kw),
+        );
         
         let generator_key = (GENERATOR, generator.clone());
         let mut energy_generator: EnergyGenerator = env
@@ -392,9 +901,42 @@ impl STRGRIDContract {
             .get(&generator_key)
             .expect("Generator not found");
             
+        strict::check_update(&env, &energy_generator.capacity_kw, &new_capacity_kw);
         energy_generator.capacity_kw = new_capacity_kw;
         env.storage().persistent().set(&generator_key, &energy_generator);
     }
+    
+    /// Define fonte de energia e zona da rede de um gerador (apenas admin)
+    pub fn set_generator_profile(
+        env: Env,
+        generator: Address,
+        source_type: SourceType,
+        zone: u32,
+    ) {
+        let admin = require_admin(&env);
+        audit::record(
+            &env,
+            &admin,
+            "set_generator_profile",
+            (generator.clone(), source_type, zone),
+        );
+        
+        let generator_key = (GENERATOR, generator.clone());
+        let mut energy_generator: EnergyGenerator = env
+            .storage()
+            .persistent()
+            .get(&generator_key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::GeneratorNotFound));
+            
+        strict::check_update(
+            &env,
+            &(energy_generator.source_type, energy_generator.zone),
+            &(source_type, zone),
+        );
+        energy_generator.source_type = source_type;
+        energy_generator.zone = zone;
+        env.storage().persistent().set(&generator_key, &energy_generator);
+    }
 }
 
 #[cfg(test)]
@@ -403,3 +945,5 @@ mod test;
 mod simple_test;
 #[cfg(test)]
 mod debug_test;
+#[cfg(test)]
+mod prop_test;
@@ -0,0 +1,79 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::fee::{self, BPS_DENOMINATOR};
+use crate::journal::OpType;
+use crate::{
+    audit, burn_balance, consumer, events, require_admin, EnergyGenerator, STRGRIDContract,
+    STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError, GENERATOR,
+};
+
+const LOSS_FACTOR: Symbol = StorageKey::LossFactor.symbol();
+
+/// Zona da rede de um endereço: a do gerador ou, para consumidores, a do perfil cadastrado
+pub(crate) fn zone_of(env: &Env, account: &Address) -> Option<u32> {
+    let generator: Option<EnergyGenerator> =
+        env.storage().persistent().get(&(GENERATOR, account.clone()));
+    match generator {
+        Some(generator) => Some(generator.zone),
+        None => consumer::profile_of(env, account).map(|profile| profile.zone),
+    }
+}
+
+fn loss_factor(env: &Env, from_zone: u32, to_zone: u32) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(LOSS_FACTOR, from_zone, to_zone))
+        .unwrap_or(0)
+}
+
+/// Zonas de origem e destino e a perda de transmissão de `amount` entre elas, sem alterar estado
+///
+/// Endereços sem zona conhecida ou na mesma zona não sofrem perda.
+fn plan(env: &Env, from: &Address, to: &Address, amount: i128) -> Option<(u32, u32, i128)> {
+    let (Some(from_zone), Some(to_zone)) = (zone_of(env, from), zone_of(env, to)) else {
+        return None;
+    };
+    if from_zone == to_zone {
+        return None;
+    }
+    let bps = loss_factor(env, from_zone, to_zone);
+    Some((from_zone, to_zone, fee::bps_of(amount, bps)))
+}
+
+/// Perda de transmissão que `apply` queimaria
+pub(crate) fn loss_for(env: &Env, from: &Address, to: &Address, amount: i128) -> i128 {
+    plan(env, from, to, amount).map_or(0, |(_, _, loss)| loss)
+}
+
+/// Queima no remetente a perda de transmissão de `amount` entre zonas e retorna o valor perdido
+pub(crate) fn apply(env: &Env, from: &Address, to: &Address, amount: i128) -> i128 {
+    let Some((from_zone, to_zone, loss)) = plan(env, from, to, amount) else {
+        return 0;
+    };
+    if loss > 0 {
+        burn_balance(env, from, loss, OpType::TransmissionLoss, 0);
+        events::transmission_loss(env, from.clone(), to.clone(), from_zone, to_zone, loss);
+    }
+    loss
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o fator de perda (bps) das transferências da zona `from_zone` para `to_zone` (apenas admin)
+    pub fn set_loss_factor(env: Env, from_zone: u32, to_zone: u32, loss_bps: u32) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_loss_factor", (from_zone, to_zone, loss_bps));
+        if from_zone == to_zone || loss_bps > BPS_DENOMINATOR {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&(LOSS_FACTOR, from_zone, to_zone), &loss_bps);
+    }
+
+    /// Consulta o fator de perda (bps) entre duas zonas
+    pub fn get_loss_factor(env: Env, from_zone: u32, to_zone: u32) -> u32 {
+        loss_factor(&env, from_zone, to_zone)
+    }
+}
@@ -0,0 +1,170 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, Env, Error, Symbol, Vec,
+};
+use strgrid_codes::StorageKey;
+
+use crate::production::MAX_HISTORY_DAYS;
+use crate::{
+    events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    STRGRIDExtError,
+};
+
+const MAINTENANCE: Symbol = StorageKey::Maintenance.symbol();
+const MAINTENANCE_SEQ: Symbol = StorageKey::MaintenanceSeq.symbol();
+const MAINTENANCE_OF: Symbol = StorageKey::MaintenanceOf.symbol();
+
+/// Janelas encerradas há mais que isto saem do calendário do gerador
+const CALENDAR_RETENTION_SECONDS: u64 = MAX_HISTORY_DAYS * 86_400;
+
+/// Parada programada do gerador; mints são bloqueados e a produção esperada é descontada
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaintenanceWindow {
+    pub id: u64,
+    pub generator: Address,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub is_cancelled: bool,
+}
+
+impl MaintenanceWindow {
+    pub fn is_active(&self, now: u64) -> bool {
+        !self.is_cancelled && now >= self.starts_at && now < self.ends_at
+    }
+
+    /// Segundos da janela dentro de `[from, to)`
+    fn overlap(&self, from: u64, to: u64) -> u64 {
+        if self.is_cancelled {
+            return 0;
+        }
+        self.ends_at.min(to).saturating_sub(self.starts_at.max(from))
+    }
+}
+
+fn load(env: &Env, window_id: u64) -> MaintenanceWindow {
+    env.storage()
+        .persistent()
+        .get(&(MAINTENANCE, window_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::MaintenanceNotFound))
+}
+
+fn windows_of(env: &Env, generator: &Address) -> Vec<MaintenanceWindow> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&(MAINTENANCE_OF, generator.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut windows = Vec::new(env);
+    for id in ids.iter() {
+        windows.push_back(load(env, id));
+    }
+    windows
+}
+
+/// Bloqueia mints durante uma parada programada
+pub(crate) fn require_available(env: &Env, generator: &Address) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    if windows_of(env, generator).iter().any(|w| w.is_active(now)) {
+        return Err(STRGRIDExtError::GeneratorInMaintenance.into());
+    }
+    Ok(())
+}
+
+/// Segundos de `[from, to)` em que o gerador estava disponível (fora de manutenção); base das
+/// linhas de produção esperada usadas em previsões e penalidades
+pub(crate) fn available_seconds(env: &Env, generator: &Address, from: u64, to: u64) -> u64 {
+    let downtime: u64 = windows_of(env, generator)
+        .iter()
+        .map(|w| w.overlap(from, to))
+        .sum();
+    to.saturating_sub(from).saturating_sub(downtime)
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Programa uma parada do gerador em `[start, end)`; janelas do mesmo gerador não se sobrepõem
+    pub fn schedule_maintenance(env: Env, generator: Address, start: u64, end: u64) -> u64 {
+        generator.require_auth();
+        let now = env.ledger().timestamp();
+        if end <= start || end <= now {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        Self::get_generator(env.clone(), generator.clone());
+
+        // Mantém no calendário apenas janelas recentes ou futuras
+        let mut ids = Vec::new(&env);
+        for existing in windows_of(&env, &generator).iter() {
+            if existing.overlap(start, end) > 0 {
+                panic_with_error!(&env, STRGRIDExtError::MaintenanceOverlap);
+            }
+            if !existing.is_cancelled && existing.ends_at + CALENDAR_RETENTION_SECONDS > now {
+                ids.push_back(existing.id);
+            }
+        }
+
+        let id: u64 = env.storage().instance().get(&MAINTENANCE_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&MAINTENANCE_SEQ, &id);
+        let window = MaintenanceWindow {
+            id,
+            generator: generator.clone(),
+            starts_at: start,
+            ends_at: end,
+            is_cancelled: false,
+        };
+        env.storage().persistent().set(&(MAINTENANCE, id), &window);
+        ids.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&(MAINTENANCE_OF, generator), &ids);
+
+        events::maintenance_scheduled(&env, &window);
+        id
+    }
+
+    /// Cancela uma parada futura ou encerra agora uma parada em andamento
+    pub fn cancel_maintenance(env: Env, generator: Address, window_id: u64) {
+        generator.require_auth();
+        let mut window = load(&env, window_id);
+        let now = env.ledger().timestamp();
+        if window.generator != generator {
+            panic_with_error!(&env, STRGRIDError::NotAuthorized);
+        }
+        if window.is_cancelled || window.ends_at <= now {
+            panic_with_error!(&env, STRGRIDError::NoOpUpdate);
+        }
+        if window.starts_at > now {
+            window.is_cancelled = true;
+        } else {
+            window.ends_at = now;
+        }
+        env.storage().persistent().set(&(MAINTENANCE, window_id), &window);
+        events::maintenance_cancelled(&env, &window);
+    }
+
+    /// Consulta uma janela de manutenção
+    pub fn get_maintenance(env: Env, window_id: u64) -> MaintenanceWindow {
+        load(&env, window_id)
+    }
+
+    /// Calendário de manutenção do gerador: janelas recentes, em andamento e futuras
+    pub fn maintenance_calendar(env: Env, generator: Address) -> Vec<MaintenanceWindow> {
+        let mut calendar = Vec::new(&env);
+        for window in windows_of(&env, &generator).iter() {
+            if !window.is_cancelled {
+                calendar.push_back(window);
+            }
+        }
+        calendar
+    }
+
+    /// Consulta se o gerador está em parada programada agora
+    pub fn is_under_maintenance(env: Env, generator: Address) -> bool {
+        let now = env.ledger().timestamp();
+        windows_of(&env, &generator).iter().any(|w| w.is_active(now))
+    }
+
+    /// Segundos de `[from, to)` em que o gerador esteve ou estará fora de manutenção
+    pub fn available_seconds(env: Env, generator: Address, from: u64, to: u64) -> u64 {
+        available_seconds(&env, &generator, from, to)
+    }
+}
@@ -0,0 +1,76 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, String};
+
+use crate::certificate::{Certificate, CERTIFICATE};
+use crate::roles::{has_role, Role};
+use crate::{
+    events, EnergyGenerator, EnergyToken, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError, ENERGY_DATA, GENERATOR,
+};
+
+/// Maior CID aceito; CIDv1 em base32 com sha2-256 tem 59 caracteres
+const MAX_CID_LEN: u32 = 128;
+
+/// Exige autorização de quem atualiza: o dono do registro ou um auditor
+fn require_updater(env: &Env, updater: &Address, owner: &Address) {
+    updater.require_auth();
+    if updater != owner && !has_role(env, updater, Role::Auditor) {
+        panic_with_error!(env, STRGRIDError::NotAuthorized);
+    }
+}
+
+fn check_cid(env: &Env, cid: &Option<String>) {
+    if let Some(cid) = cid {
+        if cid.is_empty() || cid.len() > MAX_CID_LEN {
+            panic_with_error!(env, STRGRIDExtError::InvalidCid);
+        }
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Aponta os documentos off-chain do gerador; o próprio gerador ou um auditor, `None` remove
+    pub fn set_generator_metadata(env: Env, updater: Address, generator: Address, cid: Option<String>) {
+        require_updater(&env, &updater, &generator);
+        check_cid(&env, &cid);
+        let key = (GENERATOR, generator.clone());
+        let mut energy_generator: EnergyGenerator = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::GeneratorNotFound));
+        energy_generator.metadata_cid = cid.clone();
+        env.storage().persistent().set(&key, &energy_generator);
+        events::generator_metadata_set(&env, generator, cid);
+    }
+
+    /// Aponta os documentos off-chain de um lote; o gerador emissor ou um auditor, `None` remove
+    pub fn set_token_metadata(env: Env, updater: Address, token_id: u64, cid: Option<String>) {
+        let key = (ENERGY_DATA, token_id);
+        let mut token: EnergyToken = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::TokenNotFound));
+        require_updater(&env, &updater, &token.generator_id);
+        check_cid(&env, &cid);
+        token.metadata_cid = cid.clone();
+        env.storage().persistent().set(&key, &token);
+        events::token_metadata_set(&env, token_id, cid);
+    }
+
+    /// Aponta o documento off-chain de um certificado; o consumidor titular ou um auditor,
+    /// `None` remove
+    pub fn set_certificate_metadata(env: Env, updater: Address, cert_id: u64, cid: Option<String>) {
+        let key = (CERTIFICATE, cert_id);
+        let mut certificate: Certificate = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::CertificateNotFound));
+        require_updater(&env, &updater, &certificate.consumer);
+        check_cid(&env, &cid);
+        certificate.metadata_cid = cid.clone();
+        env.storage().persistent().set(&key, &certificate);
+        events::certificate_metadata_set(&env, cert_id, cid);
+    }
+}
@@ -0,0 +1,217 @@
+use soroban_sdk::{
+    contractimpl, contracttype, panic_with_error, Address, Env, String, TryFromVal, Val, Vec,
+};
+
+use crate::allowance::{AllowanceValue, SpendingLimit};
+use crate::journal::{JournalAccount, JournalEntry, JournalRollup, OpType};
+use crate::keepers::{KeeperAction, KeeperTask};
+use crate::scheduled::ScheduledTransfer;
+use crate::{
+    audit, checkpoint, events, read_metadata, read_total_supply, require_admin, write_balance,
+    write_total_supply, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    TokenMetadata, BALANCE, MAX_BATCH_SIZE, METADATA,
+};
+
+/// Allowance como era gravada antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyAllowanceValue {
+    pub amount: u64,
+    pub live_until_ledger: Option<u32>,
+}
+
+/// Metadados como eram gravados antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyTokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    pub total_supply: u64,
+}
+
+/// Limite de gasto como era gravado antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacySpendingLimit {
+    pub amount: u64,
+    pub period_ledgers: u32,
+    pub period_start: u32,
+    pub spent: u64,
+}
+
+/// Lançamento do diário como era gravado antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyJournalEntry {
+    pub seq: u64,
+    pub debit: JournalAccount,
+    pub credit: JournalAccount,
+    pub amount: u64,
+    pub op: OpType,
+    pub reference: u64,
+    pub timestamp: u64,
+}
+
+/// Consolidação do diário como era gravada antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyJournalRollup {
+    pub period: u64,
+    pub first_seq: u64,
+    pub last_seq: u64,
+    pub entry_count: u64,
+    pub total_debits: u64,
+    pub total_credits: u64,
+}
+
+/// Transferência agendada como era gravada antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyScheduledTransfer {
+    pub id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: u64,
+    pub execute_after: u64,
+    pub keeper_reward: u64,
+}
+
+/// Tarefa de keeper como era gravada antes da migração para i128
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct LegacyKeeperTask {
+    pub id: u64,
+    pub creator: Address,
+    pub action: KeeperAction,
+    pub bounty: u64,
+    pub claimant: Option<Address>,
+    pub claim_expires_at: u64,
+}
+
+/// Decodifica um valor gravado no formato atual ou, se for anterior à migração, no antigo
+pub(crate) fn decode<T, L>(env: &Env, value: &Val, upgrade: impl FnOnce(L) -> T) -> T
+where
+    T: TryFromVal<Env, Val>,
+    L: TryFromVal<Env, Val>,
+{
+    T::try_from_val(env, value)
+        .ok()
+        .or_else(|| L::try_from_val(env, value).ok().map(upgrade))
+        .expect("Invalid stored value")
+}
+
+/// Quantidade gravada em i128 ou, antes da migração, em u64; ausente vale zero
+pub(crate) fn amount(env: &Env, value: Option<Val>) -> i128 {
+    value.map_or(0, |value| decode(env, &value, |legacy: u64| i128::from(legacy)))
+}
+
+pub(crate) fn allowance(env: &Env, value: &Val) -> AllowanceValue {
+    decode(env, value, |legacy: LegacyAllowanceValue| AllowanceValue {
+        amount: legacy.amount.into(),
+        live_until_ledger: legacy.live_until_ledger,
+    })
+}
+
+pub(crate) fn spending_limit(env: &Env, value: &Val) -> SpendingLimit {
+    decode(env, value, |legacy: LegacySpendingLimit| SpendingLimit {
+        amount: legacy.amount.into(),
+        period_ledgers: legacy.period_ledgers,
+        period_start: legacy.period_start,
+        spent: legacy.spent.into(),
+    })
+}
+
+pub(crate) fn metadata(env: &Env, value: &Val) -> TokenMetadata {
+    decode(env, value, |legacy: LegacyTokenMetadata| TokenMetadata {
+        name: legacy.name,
+        symbol: legacy.symbol,
+        decimals: legacy.decimals,
+        total_supply: legacy.total_supply.into(),
+    })
+}
+
+pub(crate) fn journal_entry(env: &Env, value: &Val) -> JournalEntry {
+    decode(env, value, |legacy: LegacyJournalEntry| JournalEntry {
+        seq: legacy.seq,
+        debit: legacy.debit,
+        credit: legacy.credit,
+        amount: legacy.amount.into(),
+        op: legacy.op,
+        reference: legacy.reference,
+        timestamp: legacy.timestamp,
+    })
+}
+
+pub(crate) fn rollup(env: &Env, value: &Val) -> JournalRollup {
+    decode(env, value, |legacy: LegacyJournalRollup| JournalRollup {
+        period: legacy.period,
+        first_seq: legacy.first_seq,
+        last_seq: legacy.last_seq,
+        entry_count: legacy.entry_count,
+        total_debits: legacy.total_debits.into(),
+        total_credits: legacy.total_credits.into(),
+    })
+}
+
+pub(crate) fn scheduled_transfer(env: &Env, value: &Val) -> ScheduledTransfer {
+    decode(env, value, |legacy: LegacyScheduledTransfer| ScheduledTransfer {
+        id: legacy.id,
+        from: legacy.from,
+        to: legacy.to,
+        amount: legacy.amount.into(),
+        execute_after: legacy.execute_after,
+        keeper_reward: legacy.keeper_reward.into(),
+    })
+}
+
+pub(crate) fn keeper_task(env: &Env, value: &Val) -> KeeperTask {
+    decode(env, value, |legacy: LegacyKeeperTask| KeeperTask {
+        id: legacy.id,
+        creator: legacy.creator,
+        action: legacy.action,
+        bounty: legacy.bounty.into(),
+        claimant: legacy.claimant,
+        claim_expires_at: legacy.claim_expires_at,
+    })
+}
+
+/// Soma quantidades em i128, rejeitando estouro
+pub(crate) fn checked_add(env: &Env, value: i128, delta: i128) -> i128 {
+    value
+        .checked_add(delta)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDError::ArithmeticOverflow))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Regrava em i128 o supply, os metadados e os saldos das contas informadas (apenas admin)
+    ///
+    /// Leituras já aceitam o formato u64 antigo e cada escrita grava o novo, então a migração
+    /// é opcional; serve para converter de uma vez as contas ativas, em lotes de até
+    /// `MAX_BATCH_SIZE`. Contas sem saldo gravado são ignoradas. Allowances, limites de gasto,
+    /// o diário, os pools, as tarefas de keeper e os agendamentos migram na próxima escrita.
+    pub fn migrate_amounts(env: Env, accounts: Vec<Address>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "migrate_amounts", (accounts.clone(),));
+        if accounts.len() > MAX_BATCH_SIZE {
+            panic_with_error!(&env, STRGRIDError::BatchTooLarge);
+        }
+
+        write_total_supply(&env, read_total_supply(&env));
+        if env.storage().instance().has(&METADATA) {
+            env.storage().instance().set(&METADATA, &read_metadata(&env));
+        }
+        for account in accounts.iter() {
+            // Regrava só saldos existentes, sem criar entradas zeradas
+            let stored: Option<Val> = env.storage().persistent().get(&(BALANCE, account.clone()));
+            if stored.is_some() {
+                write_balance(&env, &account, amount(&env, stored));
+            }
+            checkpoint::migrate_balance(&env, &account);
+        }
+        checkpoint::migrate_supply(&env);
+
+        events::amounts_migrated(&env, accounts.len());
+    }
+}
@@ -0,0 +1,89 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError, GENERATOR,
+};
+
+const NET_METERING: Symbol = StorageKey::NetMetering.symbol();
+const NET_USAGE: Symbol = StorageKey::NetUsage.symbol();
+
+/// Duração do período de faturamento da compensação (30 dias)
+pub const BILLING_PERIOD_SECONDS: u64 = 30 * 86_400;
+
+/// Saldo de compensação de um prosumidor no período: positivo é crédito, negativo é valor devido
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetPosition {
+    pub period: u64,
+    pub generated_kwh: u64,
+    pub consumed_kwh: u64,
+    pub net_kwh: i128,
+}
+
+fn is_enabled(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(NET_METERING, account.clone()))
+        .unwrap_or(false)
+}
+
+fn usage(env: &Env, account: &Address, period: u64) -> (u64, u64) {
+    env.storage()
+        .persistent()
+        .get(&(NET_USAGE, account.clone(), period))
+        .unwrap_or((0, 0))
+}
+
+fn add_usage(env: &Env, account: &Address, generated: u64, consumed: u64) {
+    if !is_enabled(env, account) {
+        return;
+    }
+    let period = env.ledger().timestamp() / BILLING_PERIOD_SECONDS;
+    let (total_generated, total_consumed) = usage(env, account, period);
+    env.storage().persistent().set(
+        &(NET_USAGE, account.clone(), period),
+        &(total_generated + generated, total_consumed + consumed),
+    );
+}
+
+/// Soma um mint à geração do período, se o gerador estiver em compensação
+pub(crate) fn record_generation(env: &Env, generator: &Address, amount: u64) {
+    add_usage(env, generator, amount, 0);
+}
+
+/// Soma um burn ao consumo do período, se o consumidor estiver em compensação
+pub(crate) fn record_consumption(env: &Env, consumer: &Address, amount: u64) {
+    add_usage(env, consumer, 0, amount);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Ativa ou desativa a compensação de energia de um gerador que também consome
+    pub fn set_net_metering(env: Env, prosumer: Address, enabled: bool) {
+        prosumer.require_auth();
+        if !env.storage().persistent().has(&(GENERATOR, prosumer.clone())) {
+            panic_with_error!(&env, STRGRIDError::GeneratorNotFound);
+        }
+        env.storage()
+            .persistent()
+            .set(&(NET_METERING, prosumer.clone()), &enabled);
+        events::net_metering_set(&env, prosumer, enabled);
+    }
+
+    /// Consulta se um endereço está em compensação de energia
+    pub fn is_net_metering(env: Env, account: Address) -> bool {
+        is_enabled(&env, &account)
+    }
+
+    /// Geração e consumo compensados de um endereço no período de faturamento
+    pub fn net_position(env: Env, account: Address, period: u64) -> NetPosition {
+        let (generated_kwh, consumed_kwh) = usage(&env, &account, period);
+        NetPosition {
+            period,
+            generated_kwh,
+            consumed_kwh,
+            net_kwh: generated_kwh as i128 - consumed_kwh as i128,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::{
+    events, move_balance, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    STRGRIDExtError, MAX_BATCH_SIZE,
+};
+
+const NETTING_POSITION: Symbol = StorageKey::NettingPosition.symbol();
+const NETTING_PARTIES: Symbol = StorageKey::NettingParties.symbol();
+const NETTING_GROSS: Symbol = StorageKey::NettingGross.symbol();
+const NETTING_SETTLED: Symbol = StorageKey::NettingSettled.symbol();
+
+/// Duração do período de compensação multilateral (fim do dia)
+pub const NETTING_PERIOD_SECONDS: u64 = 86_400;
+
+fn current_period(env: &Env) -> u64 {
+    env.ledger().timestamp() / NETTING_PERIOD_SECONDS
+}
+
+fn position(env: &Env, participant: &Address, period: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(NETTING_POSITION, participant.clone(), period))
+        .unwrap_or(0)
+}
+
+fn parties(env: &Env, period: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(NETTING_PARTIES, period))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn gross(env: &Env, period: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(NETTING_GROSS, period))
+        .unwrap_or(0)
+}
+
+/// Ajusta a posição líquida do participante, incluindo-o no lote do período se for novo
+fn add_position(env: &Env, participant: &Address, period: u64, delta: i128) {
+    let key = (NETTING_POSITION, participant.clone(), period);
+    if !env.storage().persistent().has(&key) {
+        let mut parties = parties(env, period);
+        if parties.len() >= MAX_BATCH_SIZE {
+            panic_with_error!(env, STRGRIDError::BatchTooLarge);
+        }
+        parties.push_back(participant.clone());
+        env.storage().persistent().set(&(NETTING_PARTIES, period), &parties);
+    }
+    env.storage()
+        .persistent()
+        .set(&key, &(position(env, participant, period) + delta));
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Registra uma obrigação intradiária de `from` para `to`, liquidada só pelo saldo líquido
+    /// no fim do período
+    pub fn record_obligation(env: Env, from: Address, to: Address, amount: u64) {
+        from.require_auth();
+        if amount == 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if from == to {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+
+        let period = current_period(&env);
+        add_position(&env, &from, period, -(amount as i128));
+        add_position(&env, &to, period, amount as i128);
+        env.storage()
+            .persistent()
+            .set(&(NETTING_GROSS, period), &(gross(&env, period) + amount));
+
+        events::obligation_recorded(&env, from, to, period, amount);
+    }
+
+    /// Liquida as posições líquidas de um período encerrado num único lote (qualquer um pode chamar)
+    ///
+    /// Os devedores pagam primeiro ao contrato e os credores recebem em seguida; se algum
+    /// devedor não tiver saldo, o lote inteiro é revertido.
+    pub fn settle_netting(env: Env, period: u64) -> u64 {
+        if period >= current_period(&env) {
+            panic_with_error!(&env, STRGRIDExtError::IntervalOpen);
+        }
+        if env.storage().persistent().has(&(NETTING_SETTLED, period)) {
+            panic_with_error!(&env, STRGRIDExtError::NettingSettled);
+        }
+        env.storage().persistent().set(&(NETTING_SETTLED, period), &true);
+
+        let parties = parties(&env, period);
+        let contract = env.current_contract_address();
+        let mut netted = 0u64;
+        for debtor in parties.iter() {
+            let net = position(&env, &debtor, period);
+            if net < 0 {
+                move_balance(&env, &debtor, &contract, -net, OpType::Netting);
+                netted += (-net) as u64;
+            }
+        }
+        for creditor in parties.iter() {
+            let net = position(&env, &creditor, period);
+            if net > 0 {
+                move_balance(&env, &contract, &creditor, net, OpType::Netting);
+            }
+        }
+
+        events::netting_settled(&env, period, parties.len(), gross(&env, period), netted);
+        netted
+    }
+
+    /// Posição líquida do participante no período: positiva a receber, negativa a pagar
+    pub fn netting_position(env: Env, participant: Address, period: u64) -> i128 {
+        position(&env, &participant, period)
+    }
+
+    /// Período de compensação corrente
+    pub fn current_netting_period(env: Env) -> u64 {
+        current_period(&env)
+    }
+}
@@ -0,0 +1,43 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDExtError};
+
+const NONCE: Symbol = StorageKey::Nonce.symbol();
+
+/// Ledgers em 30 dias (~5s por ledger); abaixo disso o TTL do nonce é renovado
+const NONCE_TTL_THRESHOLD: u32 = 518_400;
+/// TTL renovado a cada uso: ~120 dias
+const NONCE_TTL_EXTEND_TO: u32 = 2_073_600;
+
+fn current(env: &Env, address: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(NONCE, address.clone()))
+        .unwrap_or(0)
+}
+
+/// Confere que a mensagem usa o nonce vigente do endereço e avança o contador
+///
+/// Nonces só crescem: uma assinatura vale para exatamente um uso e qualquer mensagem com nonce
+/// antigo ou futuro é rejeitada. O contador fica em storage persistente, que é arquivado (e não
+/// apagado) se o TTL vencer, então ele nunca volta a zero.
+pub(crate) fn consume(env: &Env, address: &Address, nonce: u64) {
+    let expected = current(env, address);
+    if nonce != expected {
+        panic_with_error!(env, STRGRIDExtError::InvalidNonce);
+    }
+    let key = (NONCE, address.clone());
+    env.storage().persistent().set(&key, &(expected + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, NONCE_TTL_THRESHOLD, NONCE_TTL_EXTEND_TO);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Nonce que a próxima mensagem assinada pelo endereço deve usar
+    pub fn nonce_of(env: Env, address: Address) -> u64 {
+        current(&env, &address)
+    }
+}
@@ -0,0 +1,180 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, forecast, lot_amount, mint_lot, oracle, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExt2Error,
+};
+
+const OPERATOR: Symbol = StorageKey::Operator.symbol();
+const OPERATORS_OF: Symbol = StorageKey::OperatorsOf.symbol();
+
+/// Sobreposição em que a chave antiga segue válida após uma rotação
+pub const OPERATOR_ROTATION_GRACE_SECONDS: u64 = 86_400;
+
+/// Ações que o dono da usina pode delegar a uma chave de operação
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperatorPermission {
+    Mint,
+    Forecast,
+}
+
+/// Escopo de uma chave de operação (SCADA, supervisório) em nome do gerador
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorGrant {
+    pub permissions: Vec<OperatorPermission>,
+    /// Teto por mint; `None` segue apenas os limites do próprio gerador
+    pub max_mint_kwh: Option<u64>,
+    pub expires_at: Option<u64>,
+}
+
+fn grant(env: &Env, generator: &Address, operator: &Address) -> Option<OperatorGrant> {
+    env.storage()
+        .persistent()
+        .get(&(OPERATOR, generator.clone(), operator.clone()))
+}
+
+fn operators(env: &Env, generator: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(OPERATORS_OF, generator.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Exige a assinatura do operador e uma delegação vigente do gerador com a permissão pedida
+pub(crate) fn require_operator(
+    env: &Env,
+    generator: &Address,
+    operator: &Address,
+    permission: OperatorPermission,
+) -> OperatorGrant {
+    operator.require_auth();
+    grant(env, generator, operator)
+        .filter(|grant| grant.permissions.contains(permission))
+        .filter(|grant| {
+            grant
+                .expires_at
+                .is_none_or(|expires_at| env.ledger().timestamp() < expires_at)
+        })
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::NotOperator))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Delega ao operador as ações listadas na concessão; regravar substitui o escopo anterior
+    pub fn add_operator(env: Env, generator: Address, operator: Address, grant: OperatorGrant) {
+        generator.require_auth();
+        Self::get_generator(env.clone(), generator.clone());
+        if operator == generator || grant.permissions.is_empty() || grant.max_mint_kwh == Some(0) {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage()
+            .persistent()
+            .set(&(OPERATOR, generator.clone(), operator.clone()), &grant);
+        let mut list = operators(&env, &generator);
+        if !list.contains(&operator) {
+            list.push_back(operator.clone());
+            env.storage()
+                .persistent()
+                .set(&(OPERATORS_OF, generator.clone()), &list);
+        }
+        events::operator_added(&env, generator, operator, &grant);
+    }
+
+    /// Revoga imediatamente a chave de operação
+    pub fn revoke_operator(env: Env, generator: Address, operator: Address) {
+        generator.require_auth();
+        if grant(&env, &generator, &operator).is_none() {
+            panic_with_error!(&env, STRGRIDExt2Error::NotOperator);
+        }
+        env.storage()
+            .persistent()
+            .remove(&(OPERATOR, generator.clone(), operator.clone()));
+        let mut list = operators(&env, &generator);
+        if let Some(index) = list.first_index_of(&operator) {
+            list.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&(OPERATORS_OF, generator.clone()), &list);
+        events::operator_revoked(&env, generator, operator);
+    }
+
+    /// Troca a chave do operador mantendo o escopo: a nova vale de imediato e a antiga expira ao
+    /// fim da carência, para migrar gateways de medição sem parada
+    pub fn rotate_operator_key(env: Env, generator: Address, old_key: Address, new_key: Address) {
+        generator.require_auth();
+        let mut old_grant = grant(&env, &generator, &old_key)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDExt2Error::NotOperator));
+        if new_key == generator || grant(&env, &generator, &new_key).is_some() {
+            panic_with_error!(&env, STRGRIDExt2Error::OperatorExists);
+        }
+
+        let grace_until = env.ledger().timestamp() + OPERATOR_ROTATION_GRACE_SECONDS;
+        env.storage()
+            .persistent()
+            .set(&(OPERATOR, generator.clone(), new_key.clone()), &old_grant);
+        old_grant.expires_at = Some(
+            old_grant
+                .expires_at
+                .map_or(grace_until, |expires_at| expires_at.min(grace_until)),
+        );
+        env.storage()
+            .persistent()
+            .set(&(OPERATOR, generator.clone(), old_key.clone()), &old_grant);
+
+        let mut list = operators(&env, &generator);
+        list.push_back(new_key.clone());
+        env.storage()
+            .persistent()
+            .set(&(OPERATORS_OF, generator.clone()), &list);
+        events::operator_rotated(&env, generator, old_key, new_key, grace_until);
+    }
+
+    /// Escopo delegado ao operador, se houver (inclusive expirado)
+    pub fn get_operator(env: Env, generator: Address, operator: Address) -> Option<OperatorGrant> {
+        grant(&env, &generator, &operator)
+    }
+
+    /// Operadores com delegação registrada pelo gerador
+    pub fn operators_of(env: Env, generator: Address) -> Vec<Address> {
+        operators(&env, &generator)
+    }
+
+    /// Mint assinado pelo operador em nome do gerador, com as mesmas regras de `mint_energy_tokens`
+    pub fn operator_mint(
+        env: Env,
+        operator: Address,
+        generator: Address,
+        energy_amount_kwh: i128,
+        expiry_hours: u64,
+        oracle_proof: Option<BytesN<32>>,
+    ) -> u64 {
+        let grant = require_operator(&env, &generator, &operator, OperatorPermission::Mint);
+        let energy_amount_kwh = lot_amount(&env, energy_amount_kwh);
+        if grant
+            .max_mint_kwh
+            .is_some_and(|max_mint_kwh| energy_amount_kwh > max_mint_kwh)
+        {
+            panic_with_error!(&env, STRGRIDExt2Error::OperatorLimitExceeded);
+        }
+
+        oracle::check_mint_proof(&env, &generator, energy_amount_kwh, &oracle_proof);
+
+        mint_lot(&env, &generator, energy_amount_kwh, expiry_hours)
+    }
+
+    /// Previsão de produção enviada pelo operador em nome do gerador
+    pub fn operator_submit_forecast(
+        env: Env,
+        operator: Address,
+        generator: Address,
+        interval: u64,
+        forecast_kwh: u64,
+    ) {
+        require_operator(&env, &generator, &operator, OperatorPermission::Forecast);
+        forecast::submit(&env, generator, interval, forecast_kwh);
+    }
+}
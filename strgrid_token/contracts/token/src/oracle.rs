@@ -0,0 +1,69 @@
+use soroban_sdk::{contractclient, contractimpl, panic_with_error, Address, BytesN, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDExtError,
+};
+
+const MINT_ORACLE: Symbol = StorageKey::MintOracle.symbol();
+const USED_PROOF: Symbol = StorageKey::UsedOracleProof.symbol();
+
+/// Interface que o oracle de medição precisa expor para validar mints
+#[contractclient(name = "MintOracleClient")]
+pub trait MintOracle {
+    /// Confirma que `proof` atesta a leitura de `amount_kwh` do gerador
+    fn verify_reading(env: Env, generator: Address, amount_kwh: u64, proof: BytesN<32>) -> bool;
+}
+
+pub(crate) fn mint_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&MINT_ORACLE)
+}
+
+pub(crate) fn write_mint_oracle(env: &Env, oracle: Option<Address>) {
+    match &oracle {
+        Some(oracle) => env.storage().instance().set(&MINT_ORACLE, oracle),
+        None => env.storage().instance().remove(&MINT_ORACLE),
+    }
+    events::mint_oracle_set(env, oracle);
+}
+
+/// Com oracle configurado, exige uma prova válida e ainda não usada para o mint
+///
+/// Sem oracle a prova é opcional e ignorada, como antes da integração.
+pub(crate) fn check_mint_proof(
+    env: &Env,
+    generator: &Address,
+    amount_kwh: u64,
+    proof: &Option<BytesN<32>>,
+) {
+    let Some(oracle) = mint_oracle(env) else {
+        return;
+    };
+    let proof = proof
+        .clone()
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::OracleProofRequired));
+    let used_key = (USED_PROOF, proof.clone());
+    if env.storage().persistent().has(&used_key) {
+        panic_with_error!(env, STRGRIDExtError::OracleProofUsed);
+    }
+    if !MintOracleClient::new(env, &oracle).verify_reading(generator, &amount_kwh, &proof) {
+        panic_with_error!(env, STRGRIDExtError::InvalidOracleProof);
+    }
+    env.storage().persistent().set(&used_key, &true);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define o oracle que valida as provas de mint; `None` desliga a validação (apenas admin)
+    pub fn set_mint_oracle(env: Env, oracle: Option<Address>) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_mint_oracle", (oracle.clone(),));
+        write_mint_oracle(&env, oracle);
+    }
+
+    /// Oracle que valida as provas de mint, se configurado
+    pub fn get_mint_oracle(env: Env) -> Option<Address> {
+        mint_oracle(&env)
+    }
+}
@@ -0,0 +1,64 @@
+// `#[contractimpl]` gera `STRGRIDContractArgs::permit` sem o allow do método
+#![allow(clippy::too_many_arguments)]
+
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env};
+
+use crate::allowance;
+use crate::{
+    events, nonces, signature, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDExtError,
+};
+
+/// Mensagem que o dono assina para conceder uma allowance sem enviar transação
+///
+/// `contract` e `nonce` prendem a assinatura a este token e a um único uso; o digest assinado
+/// é o sha256 do XDR desta struct. A allowance concedida expira em `live_until_ledger`, como
+/// em `approve_until`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitMessage {
+    pub contract: Address,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub live_until_ledger: u32,
+    pub deadline: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Concede a allowance com a assinatura da chave registrada pelo dono (`set_signing_key`);
+    /// qualquer um pode submeter
+    ///
+    /// `nonce` deve ser o vigente do dono (`nonce_of`); a mensagem vale até o timestamp `deadline`.
+    /// Segue as regras de `approve_until`, com expiração obrigatória.
+    pub fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+        deadline: u64,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) {
+        if env.ledger().timestamp() > deadline {
+            panic_with_error!(&env, STRGRIDExtError::SignatureExpired);
+        }
+        let message = PermitMessage {
+            contract: env.current_contract_address(),
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+            live_until_ledger,
+            deadline,
+            nonce,
+        };
+        signature::verify(&env, &owner, message, &signature);
+        nonces::consume(&env, &owner, nonce);
+
+        allowance::approve(&env, &owner, &spender, amount, Some(live_until_ledger));
+        events::permit(&env, owner, spender, amount, nonce);
+    }
+}
@@ -0,0 +1,342 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Error};
+
+use crate::{
+    aggregator, bond, caps, consumer, curtail, dispute, fee, freeze, jurisdiction, kyc,
+    loss, maintenance, migration, read_balance, read_total_supply, reservation, strict, tiers,
+    try_lot_amount, EnergyGenerator, EnergyToken, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError, ENERGY_DATA, GENERATOR, TOKEN_SEQ,
+};
+
+/// Efeitos que um mint teria, calculados sem alterar estado
+///
+/// Com `ok == false`, `error` traz o código do erro que o mint levantaria e os efeitos ficam
+/// zerados.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintPreview {
+    pub ok: bool,
+    pub error: Option<u32>,
+    pub token_id: u64,
+    pub generator: Address,
+    pub amount_kwh: u64,
+    pub balance_before: i128,
+    pub balance_after: i128,
+    pub total_supply_after: i128,
+    pub production_after: u64,
+    pub capacity_kw: u64,
+    pub expiry_timestamp: u64,
+    pub dispute_until: u64,
+}
+
+/// Efeitos que um burn de energia teria, calculados sem alterar estado
+///
+/// Com `ok == false`, `error` traz o código do erro que o burn levantaria e os efeitos ficam
+/// zerados.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurnPreview {
+    pub ok: bool,
+    pub error: Option<u32>,
+    pub token_id: u64,
+    pub consumer: Address,
+    pub generator: Address,
+    pub amount_kwh: u64,
+    pub balance_before: i128,
+    pub balance_after: i128,
+    pub total_supply_after: i128,
+    pub generator_production_after: u64,
+}
+
+/// Efeitos que uma transferência teria, calculados sem alterar estado
+///
+/// Com `ok == false`, `error` traz o código do erro que a transferência levantaria e os
+/// efeitos ficam zerados.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferPreview {
+    pub ok: bool,
+    pub error: Option<u32>,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    /// Taxa de transferência retida pela tesouraria
+    pub fee: i128,
+    /// Perda de transmissão entre zonas, queimada no remetente
+    pub loss: i128,
+    /// kWh creditados ao destinatário
+    pub received: i128,
+    pub from_balance_before: i128,
+    pub from_balance_after: i128,
+    pub to_balance_after: i128,
+}
+
+/// Valida um mint e calcula seus efeitos; compartilhado por `mint_energy_tokens`
+pub(crate) fn plan_mint(
+    env: &Env,
+    generator: &Address,
+    amount_kwh: u64,
+    expiry_hours: u64,
+) -> MintPreview {
+    plan(env, generator, amount_kwh, expiry_hours, true)
+        .unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+/// Como `plan_mint` para as leituras após a primeira de um lote, isentas do intervalo mínimo
+pub(crate) fn plan_batch_reading(
+    env: &Env,
+    generator: &Address,
+    amount_kwh: u64,
+    expiry_hours: u64,
+) -> MintPreview {
+    plan(env, generator, amount_kwh, expiry_hours, false)
+        .unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+fn plan(
+    env: &Env,
+    generator: &Address,
+    amount_kwh: u64,
+    expiry_hours: u64,
+    check_cooldown: bool,
+) -> Result<MintPreview, Error> {
+    // Verifica se o gerador está registrado e ativo
+    let energy_generator: EnergyGenerator = env
+        .storage()
+        .persistent()
+        .get(&(GENERATOR, generator.clone()))
+        .ok_or(STRGRIDError::GeneratorNotFound)?;
+
+    if !energy_generator.is_active {
+        return Err(STRGRIDError::GeneratorInactive.into());
+    }
+    aggregator::try_require_not_aggregated(env, generator)?;
+    maintenance::require_available(env, generator)?;
+    bond::require_bonded(env, generator)?;
+    if check_cooldown {
+        caps::check_cooldown(env, energy_generator.last_mint_timestamp)?;
+    }
+
+    // Verifica capacidade disponível
+    let production_after = energy_generator.current_production + amount_kwh;
+    if production_after > energy_generator.capacity_kw {
+        return Err(STRGRIDError::InsufficientCapacity.into());
+    }
+    if production_after > curtail::effective_capacity(env, &energy_generator) {
+        return Err(STRGRIDExtError::CapacityCurtailed.into());
+    }
+    if production_after + reservation::outstanding_capacity(env, generator)
+        > energy_generator.capacity_kw
+    {
+        return Err(STRGRIDExtError::CapacityReserved.into());
+    }
+
+    let total_supply_after = read_total_supply(env)
+        .checked_add(amount_kwh.into())
+        .ok_or(STRGRIDError::ArithmeticOverflow)?;
+    caps::check_mint(env, generator, amount_kwh, total_supply_after)?;
+    tiers::check_mint(env, generator, amount_kwh)?;
+
+    let now = env.ledger().timestamp();
+    let balance_before = read_balance(env, generator);
+    Ok(MintPreview {
+        ok: true,
+        error: None,
+        token_id: env.storage().instance().get(&TOKEN_SEQ).unwrap_or(0u64) + 1,
+        generator: generator.clone(),
+        amount_kwh,
+        balance_before,
+        balance_after: migration::checked_add(env, balance_before, amount_kwh.into()),
+        total_supply_after,
+        production_after,
+        capacity_kw: energy_generator.capacity_kw,
+        expiry_timestamp: now + expiry_hours * 3600,
+        dispute_until: now + tiers::dispute_window(env, generator),
+    })
+}
+
+/// Valida um burn de energia e calcula seus efeitos; compartilhado por `burn_energy_tokens` e `burn_energy_tokens_from`
+pub(crate) fn plan_burn(env: &Env, consumer: &Address, token_id: u64, amount: u64) -> BurnPreview {
+    try_plan_burn(env, consumer, token_id, amount)
+        .unwrap_or_else(|error| panic_with_error!(env, error))
+}
+
+/// Como `plan_burn`, devolvendo o erro em vez de abortar
+fn try_plan_burn(
+    env: &Env,
+    consumer: &Address,
+    token_id: u64,
+    amount: u64,
+) -> Result<BurnPreview, Error> {
+    if amount == 0 {
+        return Err(STRGRIDError::InvalidAmount.into());
+    }
+    let energy_token: EnergyToken = env
+        .storage()
+        .persistent()
+        .get(&(ENERGY_DATA, token_id))
+        .ok_or(STRGRIDError::TokenNotFound)?;
+
+    // Verifica se o token não expirou
+    if env.ledger().timestamp() > energy_token.expiry_timestamp {
+        return Err(STRGRIDError::TokenNotFound.into());
+    }
+
+    // Verifica se já foi consumido ou anulado
+    if energy_token.is_consumed {
+        return Err(STRGRIDError::AlreadyBurned.into());
+    }
+    if energy_token.is_voided {
+        return Err(STRGRIDError::TokenVoided.into());
+    }
+    // Consumir o lote antes do fim da janela o tiraria do alcance de `challenge_mint`
+    if dispute::in_dispute_window(env, &energy_token) {
+        return Err(STRGRIDError::TokensLocked.into());
+    }
+
+    freeze::try_require_not_frozen(env, consumer)?;
+    consumer::require_eligible(env, consumer)?;
+    let balance_before = read_balance(env, consumer);
+    dispute::check_unlocked(env, consumer, balance_before, amount.into())?;
+
+    let generator_data: EnergyGenerator = env
+        .storage()
+        .persistent()
+        .get(&(GENERATOR, energy_token.generator_id.clone()))
+        .ok_or(STRGRIDError::GeneratorNotFound)?;
+
+    Ok(BurnPreview {
+        ok: true,
+        error: None,
+        token_id,
+        consumer: consumer.clone(),
+        generator: energy_token.generator_id,
+        amount_kwh: amount,
+        balance_before,
+        balance_after: balance_before - i128::from(amount),
+        total_supply_after: read_total_supply(env) - i128::from(amount),
+        generator_production_after: generator_data.current_production.saturating_sub(amount),
+    })
+}
+
+/// Valida uma transferência e calcula seus efeitos com as regras de `transfer`
+///
+/// Só lê estado: não consulta o hook de compliance, que é um contrato externo, nem poda a
+/// lista de bloqueios.
+fn plan_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Result<TransferPreview, Error> {
+    if amount <= 0 {
+        return Err(STRGRIDError::InvalidAmount.into());
+    }
+    strict::try_check_transfer(env, from, to)?;
+
+    let fee = fee::fee_for(env, amount);
+    let loss = loss::loss_for(env, from, to, amount - fee);
+    let received = amount - fee - loss;
+
+    freeze::try_require_not_frozen(env, from)?;
+    kyc::try_check_transfer(env, from, to)?;
+    jurisdiction::try_check_transfer(env, from, to)?;
+    let from_balance_before = read_balance(env, from);
+    dispute::check_unlocked(env, from, from_balance_before, amount)?;
+
+    let from_balance_after = from_balance_before - amount;
+    let to_balance_before = if from == to {
+        from_balance_after
+    } else {
+        read_balance(env, to)
+    };
+    let to_balance_after = to_balance_before
+        .checked_add(received)
+        .ok_or(STRGRIDError::ArithmeticOverflow)?;
+    Ok(TransferPreview {
+        ok: true,
+        error: None,
+        from: from.clone(),
+        to: to.clone(),
+        amount,
+        fee,
+        loss,
+        received,
+        from_balance_before,
+        from_balance_after,
+        to_balance_after,
+    })
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Simula `mint_energy_tokens` e retorna seus efeitos sem alterar estado
+    ///
+    /// Não falha: um mint inválido volta com `ok == false` e o código do erro em `error`.
+    pub fn preview_mint(
+        env: Env,
+        generator: Address,
+        energy_amount_kwh: i128,
+        expiry_hours: u64,
+    ) -> MintPreview {
+        try_lot_amount(energy_amount_kwh)
+            .and_then(|amount_kwh| plan(&env, &generator, amount_kwh, expiry_hours, true))
+            .unwrap_or_else(|error| MintPreview {
+                ok: false,
+                error: Some(error.get_code()),
+                token_id: 0,
+                generator,
+                amount_kwh: u64::try_from(energy_amount_kwh).unwrap_or(0),
+                balance_before: 0,
+                balance_after: 0,
+                total_supply_after: 0,
+                production_after: 0,
+                capacity_kw: 0,
+                expiry_timestamp: 0,
+                dispute_until: 0,
+            })
+    }
+
+    /// Simula `transfer` e retorna seus efeitos sem alterar estado
+    ///
+    /// Não falha: uma transferência inválida volta com `ok == false` e o código do erro que ela
+    /// levantaria, para a carteira exibir o motivo. O hook de compliance não é consultado.
+    pub fn preview_transfer(env: Env, from: Address, to: Address, amount: i128) -> TransferPreview {
+        plan_transfer(&env, &from, &to, amount).unwrap_or_else(|error| TransferPreview {
+            ok: false,
+            error: Some(error.get_code()),
+            from,
+            to,
+            amount,
+            fee: 0,
+            loss: 0,
+            received: 0,
+            from_balance_before: 0,
+            from_balance_after: 0,
+            to_balance_after: 0,
+        })
+    }
+
+    /// Simula `burn_energy_tokens` e retorna seus efeitos sem alterar estado
+    ///
+    /// Não falha: um burn inválido volta com `ok == false` e o código do erro em `error`.
+    pub fn preview_burn(env: Env, consumer: Address, token_id: u64, amount: i128) -> BurnPreview {
+        try_lot_amount(amount)
+            .and_then(|amount_kwh| try_plan_burn(&env, &consumer, token_id, amount_kwh))
+            .unwrap_or_else(|error| BurnPreview {
+                ok: false,
+                error: Some(error.get_code()),
+                token_id,
+                generator: env
+                    .storage()
+                    .persistent()
+                    .get::<_, EnergyToken>(&(ENERGY_DATA, token_id))
+                    .map_or(consumer.clone(), |lot| lot.generator_id),
+                consumer,
+                amount_kwh: u64::try_from(amount).unwrap_or(0),
+                balance_before: 0,
+                balance_after: 0,
+                total_supply_after: 0,
+                generator_production_after: 0,
+            })
+    }
+}
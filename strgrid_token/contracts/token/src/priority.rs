@@ -0,0 +1,44 @@
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+};
+
+const PRIORITY: Symbol = StorageKey::Priority.symbol();
+
+/// Classificação de prioridade de uma operação, publicada em eventos
+/// para que clientes e keepers apliquem taxas de inclusão maiores
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Priority {
+    Normal,
+    GridCritical,
+}
+
+pub(crate) fn priority_of(env: &Env, operation: &Symbol) -> Priority {
+    env.storage()
+        .instance()
+        .get(&(PRIORITY, operation.clone()))
+        .unwrap_or(Priority::Normal)
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Classifica uma operação (nome da função) como normal ou crítica para a rede (apenas admin)
+    pub fn set_operation_priority(env: Env, operation: Symbol, priority: Priority) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_operation_priority", (operation.clone(), priority));
+        let key = (PRIORITY, operation.clone());
+        match priority {
+            Priority::Normal => env.storage().instance().remove(&key),
+            Priority::GridCritical => env.storage().instance().set(&key, &priority),
+        }
+        events::operation_priority_set(&env, operation, priority);
+    }
+
+    /// Consulta a classificação de prioridade de uma operação
+    pub fn operation_priority(env: Env, operation: Symbol) -> Priority {
+        priority_of(&env, &operation)
+    }
+}
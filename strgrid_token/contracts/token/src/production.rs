@@ -0,0 +1,119 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    vintage_of, EnergyGenerator, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, GENERATOR,
+};
+
+const PRODUCTION_DAILY: Symbol = StorageKey::ProductionDaily.symbol();
+const PRODUCTION_MONTHLY: Symbol = StorageKey::ProductionMonthly.symbol();
+
+/// Intervalo máximo, em dias, aceito pelas consultas de histórico
+pub const MAX_HISTORY_DAYS: u64 = 366;
+
+/// Produção verificada de um gerador em um dia (timestamp / 86400)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyProduction {
+    pub day: u64,
+    pub kwh: u64,
+}
+
+fn daily(env: &Env, generator: &Address, day: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(PRODUCTION_DAILY, generator.clone(), day))
+        .unwrap_or(0)
+}
+
+fn monthly(env: &Env, generator: &Address, vintage: (u32, u32)) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(PRODUCTION_MONTHLY, generator.clone(), vintage))
+        .unwrap_or(0)
+}
+
+fn require_range(env: &Env, from_day: u64, to_day: u64) {
+    if to_day < from_day || to_day - from_day >= MAX_HISTORY_DAYS {
+        panic_with_error!(env, STRGRIDError::InvalidAmount);
+    }
+}
+
+fn set_production(env: &Env, generator: &Address, minted_at: u64, day_kwh: u64, month_kwh: u64) {
+    env.storage()
+        .persistent()
+        .set(&(PRODUCTION_DAILY, generator.clone(), minted_at / 86_400), &day_kwh);
+    env.storage()
+        .persistent()
+        .set(&(PRODUCTION_MONTHLY, generator.clone(), vintage_of(minted_at)), &month_kwh);
+}
+
+/// Soma um mint aos agregados diário e mensal do gerador
+pub(crate) fn record(env: &Env, generator: &Address, amount_kwh: u64, minted_at: u64) {
+    set_production(
+        env,
+        generator,
+        minted_at,
+        daily(env, generator, minted_at / 86_400) + amount_kwh,
+        monthly(env, generator, vintage_of(minted_at)) + amount_kwh,
+    );
+}
+
+/// Retira dos agregados a produção de um mint anulado na contestação
+pub(crate) fn void(env: &Env, generator: &Address, amount_kwh: u64, minted_at: u64) {
+    set_production(
+        env,
+        generator,
+        minted_at,
+        daily(env, generator, minted_at / 86_400).saturating_sub(amount_kwh),
+        monthly(env, generator, vintage_of(minted_at)).saturating_sub(amount_kwh),
+    );
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Produção diária do gerador entre `from_day` e `to_day` (inclusive), omitindo dias sem produção
+    pub fn production_history(
+        env: Env,
+        generator: Address,
+        from_day: u64,
+        to_day: u64,
+    ) -> Vec<DailyProduction> {
+        require_range(&env, from_day, to_day);
+        let mut history = Vec::new(&env);
+        for day in from_day..=to_day {
+            let kwh = daily(&env, &generator, day);
+            if kwh > 0 {
+                history.push_back(DailyProduction { day, kwh });
+            }
+        }
+        history
+    }
+
+    /// Produção do gerador no mês UTC informado
+    pub fn monthly_production(env: Env, generator: Address, year: u32, month: u32) -> u64 {
+        monthly(&env, &generator, (year, month))
+    }
+
+    /// Fator de capacidade no intervalo, em basis points: produção sobre capacidade × horas
+    pub fn capacity_factor(env: Env, generator: Address, from_day: u64, to_day: u64) -> u32 {
+        require_range(&env, from_day, to_day);
+        let generator_data: EnergyGenerator = env
+            .storage()
+            .persistent()
+            .get(&(GENERATOR, generator.clone()))
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::GeneratorNotFound));
+
+        let mut produced = 0u128;
+        for day in from_day..=to_day {
+            produced += daily(&env, &generator, day) as u128;
+        }
+        let hours = (to_day - from_day + 1) as u128 * 24;
+        let potential = generator_data.capacity_kw as u128 * hours;
+        if potential == 0 {
+            return 0;
+        }
+        (produced * 10_000 / potential) as u32
+    }
+}
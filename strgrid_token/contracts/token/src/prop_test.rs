@@ -0,0 +1,192 @@
+#![cfg(test)]
+
+//! Property-based tests: random sequences of mint/transfer/burn/approve operations
+//! with accounting invariants checked after every step.
+
+extern crate std;
+
+use super::*;
+use crate::testutils::{assert_invariants, FixtureBuilder};
+use proptest::prelude::*;
+use soroban_sdk::testutils::{EnvTestConfig, Ledger};
+use soroban_sdk::{Address, Env};
+
+const GENERATORS: usize = 2;
+const USERS: usize = 3;
+const ACCOUNTS: usize = GENERATORS + USERS;
+const CAPACITY_KW: u64 = 5_000;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Mint { generator: usize, amount: u64 },
+    Transfer { from: usize, to: usize, amount: u64 },
+    Burn { from: usize, amount: u64 },
+    Consume { consumer: usize, lot: usize, amount: u64 },
+    Approve { owner: usize, spender: usize, amount: u64 },
+    TransferFrom { spender: usize, from: usize, to: usize, amount: u64 },
+    Advance { hours: u64 },
+}
+
+fn account() -> impl Strategy<Value = usize> {
+    0..ACCOUNTS
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    // Zero is included on purpose to exercise the InvalidAmount paths
+    0u64..600
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (0..GENERATORS, amount()).prop_map(|(generator, amount)| Op::Mint { generator, amount }),
+        4 => (account(), account(), amount()).prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+        2 => (account(), amount()).prop_map(|(from, amount)| Op::Burn { from, amount }),
+        2 => (account(), 0usize..16, amount())
+            .prop_map(|(consumer, lot, amount)| Op::Consume { consumer, lot, amount }),
+        2 => (account(), account(), amount())
+            .prop_map(|(owner, spender, amount)| Op::Approve { owner, spender, amount }),
+        2 => (account(), account(), account(), amount())
+            .prop_map(|(spender, from, to, amount)| Op::TransferFrom { spender, from, to, amount }),
+        1 => (1u64..48).prop_map(|hours| Op::Advance { hours }),
+    ]
+}
+
+struct Harness<'a> {
+    env: Env,
+    client: STRGRIDContractClient<'a>,
+    /// Generators first, then plain users
+    accounts: std::vec::Vec<Address>,
+    lots: std::vec::Vec<u64>,
+}
+
+impl Harness<'_> {
+    fn new() -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.cost_estimate().budget().reset_unlimited();
+        let fixture = FixtureBuilder::new(&env)
+            .generators(GENERATORS as u32, CAPACITY_KW)
+            .users(USERS as u32, 600)
+            .build();
+
+        // Generators keep a balance of their own so they can transfer and burn too
+        let mut lots = std::vec::Vec::new();
+        for generator in fixture.generators.iter() {
+            lots.push(fixture.client.mint_energy_tokens(&generator, &1_000i128, &72u64, &None));
+        }
+        let accounts = fixture.generators.iter().chain(fixture.users.iter()).collect();
+
+        Harness {
+            env,
+            client: fixture.client,
+            accounts,
+            lots,
+        }
+    }
+
+    fn balances(&self) -> std::vec::Vec<i128> {
+        self.accounts.iter().map(|account| self.client.balance_of(account)).collect()
+    }
+
+    /// Applies the operation and reports whether the contract accepted it
+    fn apply(&mut self, op: &Op) -> bool {
+        let a = &self.accounts;
+        match *op {
+            Op::Mint { generator, amount } => {
+                match self.client.try_mint_energy_tokens(&a[generator], &i128::from(amount), &24u64, &None) {
+                    Ok(Ok(token_id)) => {
+                        self.lots.push(token_id);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            Op::Transfer { from, to, amount } => {
+                self.client.try_transfer(&a[from], &a[to], &i128::from(amount)).is_ok()
+            }
+            Op::Burn { from, amount } => self.client.try_burn(&a[from], &i128::from(amount)).is_ok(),
+            Op::Consume { consumer, lot, amount } => {
+                if self.lots.is_empty() {
+                    return false;
+                }
+                let token_id = self.lots[lot % self.lots.len()];
+                self.client
+                    .try_burn_energy_tokens(&a[consumer], &token_id, &i128::from(amount))
+                    .is_ok()
+            }
+            Op::Approve { owner, spender, amount } => {
+                self.client.try_approve(&a[owner], &a[spender], &amount.into()).is_ok()
+            }
+            Op::TransferFrom { spender, from, to, amount } => self
+                .client
+                .try_transfer_from(&a[spender], &a[from], &a[to], &i128::from(amount))
+                .is_ok(),
+            Op::Advance { hours } => {
+                self.env.ledger().with_mut(|ledger| ledger.timestamp += hours * 3600);
+                true
+            }
+        }
+    }
+
+    fn assert_invariants(&self, op: &Op, accepted: bool, before: &[i128]) {
+        assert_invariants(&self.client);
+        let balances = self.balances();
+
+        // No fees are configured, so the harness accounts hold the whole supply
+        let sum: i128 = balances.iter().sum();
+        assert_eq!(sum, self.client.total_supply(), "supply != Σ balances after {:?}", op);
+
+        if !accepted {
+            // A rejected operation must leave every balance untouched
+            assert_eq!(balances, before, "rejected {:?} changed balances", op);
+            return;
+        }
+        match *op {
+            Op::Transfer { from, to, amount } | Op::TransferFrom { from, to, amount, .. } => {
+                if from != to {
+                    assert_eq!(balances[from], before[from] - i128::from(amount));
+                    assert_eq!(balances[to], before[to] + i128::from(amount));
+                }
+            }
+            Op::Burn { from, amount } => {
+                assert_eq!(balances[from], before[from] - i128::from(amount))
+            }
+            Op::Consume { consumer, amount, .. } => {
+                assert_eq!(balances[consumer], before[consumer] - i128::from(amount))
+            }
+            Op::Mint { generator, amount } => {
+                assert_eq!(balances[generator], before[generator] + i128::from(amount))
+            }
+            Op::Approve { .. } | Op::Advance { .. } => assert_eq!(balances, before),
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn prop_accounting_invariants(ops in prop::collection::vec(op(), 1..24)) {
+        let mut harness = Harness::new();
+        for op in &ops {
+            let before = harness.balances();
+            let accepted = harness.apply(op);
+            harness.assert_invariants(op, accepted, &before);
+        }
+    }
+
+    #[test]
+    fn prop_zero_amounts_rejected(from in account(), to in account()) {
+        let mut harness = Harness::new();
+        // Zero-amount mints are accepted by the contract (empty lot), so they are not covered here
+        let ops = [
+            Op::Transfer { from, to, amount: 0 },
+            Op::Burn { from, amount: 0 },
+        ];
+        for op in &ops {
+            let accepted = harness.apply(op);
+            prop_assert!(!accepted, "zero amount accepted: {:?}", op);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use soroban_sdk::{
+    contractimpl, contracttype, Address, Env, IntoVal, Symbol, Val, Vec,
+};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    EnergyToken, SourceType, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    ENERGY_DATA, TOKEN_SEQ,
+};
+
+const IDX_GENERATOR: Symbol = StorageKey::IndexGenerator.symbol();
+const IDX_SOURCE: Symbol = StorageKey::IndexSource.symbol();
+const IDX_ZONE: Symbol = StorageKey::IndexZone.symbol();
+const IDX_VINTAGE: Symbol = StorageKey::IndexVintage.symbol();
+const IDX_EXPIRY: Symbol = StorageKey::IndexExpiry.symbol();
+
+/// Tamanho máximo de página retornado pelas consultas paginadas
+pub(crate) const MAX_PAGE_SIZE: u32 = 100;
+
+/// Filtros combináveis para listagem de tokens; campos `None` (ou lista vazia) não restringem
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenFilter {
+    pub generator: Option<Address>,
+    pub source_types: Vec<SourceType>,
+    pub zone: Option<u32>,
+    pub expiry_from: Option<u64>,
+    pub expiry_to: Option<u64>,
+    pub is_consumed: Option<bool>,
+    pub vintage_year: Option<u32>,
+    pub vintage_month: Option<u32>,
+}
+
+fn append<K: IntoVal<Env, Val>>(env: &Env, key: &K, token_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    ids.push_back(token_id);
+    env.storage().persistent().set(key, &ids);
+}
+
+fn remove<K: IntoVal<Env, Val>>(env: &Env, key: &K, token_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    if let Some(position) = ids.first_index_of(token_id) {
+        ids.remove(position);
+        env.storage().persistent().set(key, &ids);
+    }
+}
+
+/// Lotes em aberto (nem consumidos, nem anulados, nem compactados) como (expiração, id), em ordem
+pub(crate) fn open_lots(env: &Env) -> Vec<(u64, u64)> {
+    env.storage().persistent().get(&IDX_EXPIRY).unwrap_or(Vec::new(env))
+}
+
+/// Quantos lotes em aberto já passaram da expiração; eles formam o início do índice
+pub(crate) fn expired_count(env: &Env) -> u32 {
+    // Ids começam em 1, então (agora, 0) nunca está no índice e a busca devolve a fronteira
+    match open_lots(env).binary_search((env.ledger().timestamp(), 0u64)) {
+        Ok(position) | Err(position) => position,
+    }
+}
+
+/// Retira um lote consumido, anulado ou compactado do índice por expiração; `false` se já não estava
+pub(crate) fn close_lot(env: &Env, token: &EnergyToken) -> bool {
+    let mut lots = open_lots(env);
+    match lots.binary_search((token.expiry_timestamp, token.id)) {
+        Ok(position) => {
+            lots.remove(position);
+            env.storage().persistent().set(&IDX_EXPIRY, &lots);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Retira do início do índice até `limit` lotes vencidos, devolvendo seus ids
+pub(crate) fn take_expired(env: &Env, limit: u32) -> Vec<u64> {
+    let lots = open_lots(env);
+    let count = expired_count(env).min(limit);
+    let mut ids = Vec::new(env);
+    for (_, token_id) in lots.slice(..count).iter() {
+        ids.push_back(token_id);
+    }
+    if count > 0 {
+        env.storage().persistent().set(&IDX_EXPIRY, &lots.slice(count..));
+    }
+    ids
+}
+
+/// Registra um token recém-emitido nos índices por gerador, fonte, zona, safra e expiração
+pub(crate) fn index_token(env: &Env, token: &EnergyToken) {
+    append(env, &(IDX_GENERATOR, token.generator_id.clone()), token.id);
+    append(env, &(IDX_SOURCE, token.source_type), token.id);
+    append(env, &(IDX_ZONE, token.zone), token.id);
+    append(env, &(IDX_VINTAGE, token.vintage), token.id);
+
+    let mut lots = open_lots(env);
+    let entry = (token.expiry_timestamp, token.id);
+    if let Err(position) = lots.binary_search(entry) {
+        lots.insert(position, entry);
+        env.storage().persistent().set(&IDX_EXPIRY, &lots);
+    }
+}
+
+/// Retira dos índices um token cujo registro foi arquivado
+pub(crate) fn unindex_token(env: &Env, token: &EnergyToken) {
+    remove(env, &(IDX_GENERATOR, token.generator_id.clone()), token.id);
+    remove(env, &(IDX_SOURCE, token.source_type), token.id);
+    remove(env, &(IDX_ZONE, token.zone), token.id);
+    remove(env, &(IDX_VINTAGE, token.vintage), token.id);
+}
+
+fn matches(filter: &TokenFilter, token: &EnergyToken) -> bool {
+    if token.is_voided {
+        return false;
+    }
+    if let Some(generator) = &filter.generator {
+        if token.generator_id != *generator {
+            return false;
+        }
+    }
+    if !filter.source_types.is_empty() && !filter.source_types.contains(token.source_type) {
+        return false;
+    }
+    if let Some(zone) = filter.zone {
+        if token.zone != zone {
+            return false;
+        }
+    }
+    if let Some(expiry_from) = filter.expiry_from {
+        if token.expiry_timestamp < expiry_from {
+            return false;
+        }
+    }
+    if let Some(expiry_to) = filter.expiry_to {
+        if token.expiry_timestamp > expiry_to {
+            return false;
+        }
+    }
+    if let Some(is_consumed) = filter.is_consumed {
+        if token.is_consumed != is_consumed {
+            return false;
+        }
+    }
+    if let Some(year) = filter.vintage_year {
+        if token.vintage.0 != year {
+            return false;
+        }
+    }
+    if let Some(month) = filter.vintage_month {
+        if token.vintage.1 != month {
+            return false;
+        }
+    }
+    true
+}
+
+/// Escolhe o índice mais seletivo entre os filtros informados
+fn candidates(env: &Env, filter: &TokenFilter) -> Option<Vec<u64>> {
+    let mut best: Option<Vec<u64>> = None;
+    let mut consider = |ids: Vec<u64>| {
+        if best.as_ref().is_none_or(|current| ids.len() < current.len()) {
+            best = Some(ids);
+        }
+    };
+
+    let storage = env.storage().persistent();
+    if let Some(generator) = &filter.generator {
+        consider(storage.get(&(IDX_GENERATOR, generator.clone())).unwrap_or(Vec::new(env)));
+    }
+    if filter.source_types.len() == 1 {
+        let source_type = filter.source_types.get_unchecked(0);
+        consider(storage.get(&(IDX_SOURCE, source_type)).unwrap_or(Vec::new(env)));
+    }
+    if let Some(zone) = filter.zone {
+        consider(storage.get(&(IDX_ZONE, zone)).unwrap_or(Vec::new(env)));
+    }
+    if let (Some(year), Some(month)) = (filter.vintage_year, filter.vintage_month) {
+        consider(storage.get(&(IDX_VINTAGE, (year, month))).unwrap_or(Vec::new(env)));
+    }
+    best
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Lista tokens de energia que atendem a todos os filtros, paginado por offset/limit
+    pub fn find_tokens(env: Env, filter: TokenFilter, offset: u32, limit: u32) -> Vec<EnergyToken> {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut result = Vec::new(&env);
+        if limit == 0 {
+            return result;
+        }
+
+        let mut skipped = 0u32;
+        let mut visit = |token_id: u64| -> bool {
+            let token: Option<EnergyToken> = env.storage().persistent().get(&(ENERGY_DATA, token_id));
+            if let Some(token) = token {
+                if matches(&filter, &token) {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else {
+                        result.push_back(token);
+                    }
+                }
+            }
+            result.len() < limit
+        };
+
+        match candidates(&env, &filter) {
+            Some(ids) => {
+                for token_id in ids.iter() {
+                    if !visit(token_id) {
+                        break;
+                    }
+                }
+            }
+            None => {
+                let last_id: u64 = env.storage().instance().get(&TOKEN_SEQ).unwrap_or(0);
+                for token_id in 1..=last_id {
+                    if !visit(token_id) {
+                        break;
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Lista lotes vencidos sem consumo, do mais antigo para o mais recente, para varredura
+    pub fn expired_tokens(env: Env, offset: u32, limit: u32) -> Vec<EnergyToken> {
+        let lots = open_lots(&env);
+        let end = expired_count(&env).min(offset.saturating_add(limit.min(MAX_PAGE_SIZE)));
+        let mut result = Vec::new(&env);
+        for position in offset..end {
+            let (_, token_id) = lots.get_unchecked(position);
+            if let Some(token) = env.storage().persistent().get(&(ENERGY_DATA, token_id)) {
+                result.push_back(token);
+            }
+        }
+        result
+    }
+}
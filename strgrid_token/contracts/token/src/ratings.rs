@@ -0,0 +1,82 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+pub use strgrid_interface::GeneratorRating;
+
+use crate::netmeter::BILLING_PERIOD_SECONDS;
+use crate::{
+    events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDExt2Error,
+    STRGRIDExtError,
+};
+
+const RATING_ELIGIBLE: Symbol = StorageKey::RatingEligible.symbol();
+const RATING: Symbol = StorageKey::Rating.symbol();
+const RATING_TOTAL: Symbol = StorageKey::RatingTotal.symbol();
+
+/// Nota máxima de uma avaliação; a mínima é 1
+pub const MAX_RATING: u32 = 5;
+
+fn current_period(env: &Env) -> u64 {
+    env.ledger().timestamp() / BILLING_PERIOD_SECONDS
+}
+
+/// Registra que o consumidor recebeu ou consumiu energia do gerador no período corrente,
+/// habilitando uma avaliação nele
+pub(crate) fn record_purchase(env: &Env, consumer: &Address, generator: &Address) {
+    if consumer == generator {
+        return;
+    }
+    env.storage().persistent().set(
+        &(RATING_ELIGIBLE, consumer.clone(), generator.clone(), current_period(env)),
+        &true,
+    );
+}
+
+fn rating_total(env: &Env, generator: &Address) -> GeneratorRating {
+    env.storage()
+        .persistent()
+        .get(&(RATING_TOTAL, generator.clone()))
+        .unwrap_or(GeneratorRating { count: 0, sum: 0 })
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Avalia o gerador de 1 a 5; exige compra ou consumo de energia dele no ciclo de faturamento
+    /// corrente e admite uma avaliação por ciclo
+    pub fn rate_generator(env: Env, consumer: Address, generator: Address, stars: u32) {
+        consumer.require_auth();
+        if stars == 0 || stars > MAX_RATING {
+            panic_with_error!(&env, STRGRIDExtError::InvalidRating);
+        }
+        let period = current_period(&env);
+        let eligible_key = (RATING_ELIGIBLE, consumer.clone(), generator.clone(), period);
+        if !env.storage().persistent().has(&eligible_key) {
+            panic_with_error!(&env, STRGRIDExt2Error::NotACustomer);
+        }
+        let rating_key = (RATING, consumer.clone(), generator.clone(), period);
+        if env.storage().persistent().has(&rating_key) {
+            panic_with_error!(&env, STRGRIDExt2Error::AlreadyRated);
+        }
+        env.storage().persistent().set(&rating_key, &stars);
+
+        let mut total = rating_total(&env, &generator);
+        total.count += 1;
+        total.sum += stars as u64;
+        env.storage()
+            .persistent()
+            .set(&(RATING_TOTAL, generator.clone()), &total);
+        events::generator_rated(&env, consumer, generator, period, stars);
+    }
+
+    /// Avaliação dada pelo consumidor ao gerador em um ciclo de faturamento, se houver
+    pub fn rating_of(env: Env, consumer: Address, generator: Address, period: u64) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&(RATING, consumer, generator, period))
+    }
+
+    /// Total das avaliações recebidas pelo gerador
+    pub fn get_generator_rating(env: Env, generator: Address) -> GeneratorRating {
+        rating_total(&env, &generator)
+    }
+}
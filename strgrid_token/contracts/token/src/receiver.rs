@@ -0,0 +1,33 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env};
+use strgrid_interface::TokenReceiverClient;
+
+use crate::{STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDExtError};
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Transfere para um contrato e o notifica via `on_token_received` na mesma invocação
+    ///
+    /// O receptor recebe o valor efetivamente creditado (descontadas taxa e perdas) e pode
+    /// recusar devolvendo `false`, o que reverte a transferência.
+    pub fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to_contract: Address,
+        amount: i128,
+        data: Bytes,
+    ) {
+        let before = Self::balance_of(env.clone(), to_contract.clone());
+        Self::transfer(env.clone(), from.clone(), to_contract.clone(), amount);
+        let received = Self::balance_of(env.clone(), to_contract.clone()) - before;
+
+        let accepted = TokenReceiverClient::new(&env, &to_contract).on_token_received(
+            &env.current_contract_address(),
+            &from,
+            &received,
+            &data,
+        );
+        if !accepted {
+            panic_with_error!(&env, STRGRIDExtError::ReceiverRejected);
+        }
+    }
+}
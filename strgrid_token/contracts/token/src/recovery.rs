@@ -0,0 +1,168 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::{
+    certificate, consumer, events, move_balance, read_balance, STRGRIDContract,
+    STRGRIDContractArgs, STRGRIDContractClient, STRGRIDExt2Error,
+};
+
+const RECOVERY: Symbol = StorageKey::Recovery.symbol();
+const RECOVERY_REQUEST: Symbol = StorageKey::RecoveryRequest.symbol();
+
+/// Guardiões que podem recuperar a conta, quantos precisam concordar e a carência até a execução
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+    pub delay: u64,
+}
+
+/// Pedido de recuperação em andamento; executável a partir de `executable_at`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_address: Address,
+    pub approvals: Vec<Address>,
+    /// Preenchido quando as aprovações atingem o quórum
+    pub executable_at: Option<u64>,
+}
+
+fn recovery_config(env: &Env, account: &Address) -> Option<RecoveryConfig> {
+    env.storage().persistent().get(&(RECOVERY, account.clone()))
+}
+
+fn require_guardian(env: &Env, account: &Address, guardian: &Address) -> RecoveryConfig {
+    guardian.require_auth();
+    recovery_config(env, account)
+        .filter(|config| config.guardians.contains(guardian))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::NotGuardian))
+}
+
+fn load_request(env: &Env, account: &Address) -> RecoveryRequest {
+    env.storage()
+        .persistent()
+        .get(&(RECOVERY_REQUEST, account.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::RecoveryNotFound))
+}
+
+/// Registra a aprovação do guardião e abre a carência ao atingir o quórum
+fn approve(
+    env: &Env,
+    account: &Address,
+    config: &RecoveryConfig,
+    request: &mut RecoveryRequest,
+    guardian: &Address,
+) {
+    if !request.approvals.contains(guardian) {
+        request.approvals.push_back(guardian.clone());
+    }
+    if request.executable_at.is_none() && request.approvals.len() >= config.threshold {
+        request.executable_at = Some(env.ledger().timestamp() + config.delay);
+    }
+    env.storage()
+        .persistent()
+        .set(&(RECOVERY_REQUEST, account.clone()), request);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define os guardiões da conta; `None` desativa a recuperação e descarta pedidos pendentes
+    pub fn set_recovery(env: Env, account: Address, config: Option<RecoveryConfig>) {
+        account.require_auth();
+        let key = (RECOVERY, account.clone());
+        match &config {
+            Some(config)
+                if config.threshold == 0
+                    || config.threshold > config.guardians.len()
+                    || config.guardians.contains(&account) =>
+            {
+                panic_with_error!(&env, STRGRIDExt2Error::InvalidRecoveryConfig)
+            }
+            Some(config) => env.storage().persistent().set(&key, config),
+            None => env.storage().persistent().remove(&key),
+        }
+        env.storage()
+            .persistent()
+            .remove(&(RECOVERY_REQUEST, account.clone()));
+        events::recovery_set(&env, account, config);
+    }
+
+    /// Consulta os guardiões da conta, se configurados
+    pub fn get_recovery(env: Env, account: Address) -> Option<RecoveryConfig> {
+        recovery_config(&env, &account)
+    }
+
+    /// Um guardião propõe mover a conta para `new_address`, já contando a própria aprovação
+    pub fn propose_recovery(env: Env, guardian: Address, account: Address, new_address: Address) {
+        let config = require_guardian(&env, &account, &guardian);
+        if env
+            .storage()
+            .persistent()
+            .has(&(RECOVERY_REQUEST, account.clone()))
+        {
+            panic_with_error!(&env, STRGRIDExt2Error::RecoveryPending);
+        }
+        if new_address == account {
+            panic_with_error!(&env, STRGRIDExt2Error::InvalidRecoveryConfig);
+        }
+        let mut request = RecoveryRequest {
+            new_address: new_address.clone(),
+            approvals: Vec::new(&env),
+            executable_at: None,
+        };
+        approve(&env, &account, &config, &mut request, &guardian);
+        events::recovery_proposed(&env, account, guardian, new_address);
+    }
+
+    /// Outro guardião apoia o pedido pendente
+    pub fn approve_recovery(env: Env, guardian: Address, account: Address) {
+        let config = require_guardian(&env, &account, &guardian);
+        let mut request = load_request(&env, &account);
+        approve(&env, &account, &config, &mut request, &guardian);
+        events::recovery_approved(&env, account, guardian, request.executable_at);
+    }
+
+    /// O titular, se ainda tiver a chave, cancela o pedido durante a carência
+    pub fn cancel_recovery(env: Env, account: Address) {
+        account.require_auth();
+        load_request(&env, &account);
+        env.storage()
+            .persistent()
+            .remove(&(RECOVERY_REQUEST, account.clone()));
+        events::recovery_cancelled(&env, account);
+    }
+
+    /// Consulta o pedido de recuperação pendente, se houver
+    pub fn get_recovery_request(env: Env, account: Address) -> Option<RecoveryRequest> {
+        env.storage().persistent().get(&(RECOVERY_REQUEST, account))
+    }
+
+    /// Após a carência, move saldo, certificados e cadastro de consumidor para o novo endereço;
+    /// qualquer um pode executar
+    pub fn execute_recovery(env: Env, account: Address) -> i128 {
+        let request = load_request(&env, &account);
+        match request.executable_at {
+            Some(at) if env.ledger().timestamp() >= at => {}
+            _ => panic_with_error!(&env, STRGRIDExt2Error::RecoveryNotReady),
+        }
+        env.storage()
+            .persistent()
+            .remove(&(RECOVERY_REQUEST, account.clone()));
+        env.storage()
+            .persistent()
+            .remove(&(RECOVERY, account.clone()));
+
+        let new_address = request.new_address;
+        let balance = read_balance(&env, &account);
+        if balance > 0 {
+            move_balance(&env, &account, &new_address, balance, OpType::Recovery);
+        }
+        let certificates = certificate::reassign(&env, &account, &new_address);
+        consumer::move_profile(&env, &account, &new_address);
+
+        events::account_recovered(&env, account, new_address, balance, certificates);
+        balance
+    }
+}
@@ -0,0 +1,77 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, BytesN, Env};
+
+use crate::journal::OpType;
+use crate::{
+    burn_balance, events, fee, nonces, signature, strict, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExtError,
+};
+
+/// Operação que o titular autoriza por assinatura para um relayer submeter
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RelayAction {
+    /// Transfere `amount` kWh para o endereço
+    Transfer(Address, i128),
+    /// Queima `amount` kWh do saldo
+    Burn(i128),
+}
+
+/// Payload assinado off-chain pelo titular; o digest é o sha256 do XDR desta struct
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RelayMessage {
+    pub contract: Address,
+    pub signer: Address,
+    pub action: RelayAction,
+    pub deadline: u64,
+    pub nonce: u64,
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Executa uma operação assinada pelo titular; quem submete (e paga as taxas da rede) pode
+    /// ser qualquer relayer
+    ///
+    /// Permite que residências sem XLM movimentem o saldo: a assinatura Ed25519 sobre
+    /// `RelayMessage`, feita com a chave registrada em `set_signing_key`, substitui o
+    /// `require_auth` do titular. O nonce é o mesmo de `permit`.
+    pub fn relay(
+        env: Env,
+        signer: Address,
+        action: RelayAction,
+        deadline: u64,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) {
+        if env.ledger().timestamp() > deadline {
+            panic_with_error!(&env, STRGRIDExtError::SignatureExpired);
+        }
+        let message = RelayMessage {
+            contract: env.current_contract_address(),
+            signer: signer.clone(),
+            action: action.clone(),
+            deadline,
+            nonce,
+        };
+        signature::verify(&env, &signer, message, &signature);
+        nonces::consume(&env, &signer, nonce);
+
+        match action {
+            RelayAction::Transfer(to, amount) => {
+                if amount <= 0 {
+                    panic_with_error!(&env, STRGRIDError::InvalidAmount);
+                }
+                strict::check_transfer(&env, &signer, &to);
+                fee::transfer_with_fee(&env, &signer, &to, amount, OpType::Transfer);
+            }
+            RelayAction::Burn(amount) => {
+                if amount <= 0 {
+                    panic_with_error!(&env, STRGRIDError::InvalidAmount);
+                }
+                burn_balance(&env, &signer, amount, OpType::Burn, 0);
+                events::burned(&env, signer.clone(), amount);
+            }
+        }
+        events::relayed(&env, signer, nonce);
+    }
+}
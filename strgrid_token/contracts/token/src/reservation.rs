@@ -0,0 +1,220 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::{
+    events, mint_lot, move_balance, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError, STRGRIDExtError,
+};
+
+const RESERVATION: Symbol = StorageKey::Reservation.symbol();
+const RESERVATION_SEQ: Symbol = StorageKey::ReservationSeq.symbol();
+const RESERVATIONS_OF: Symbol = StorageKey::ReservationsOf.symbol();
+
+/// Reserva de capacidade de um gerador para um consumidor durante uma janela, paga com taxa custodiada
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapacityReservation {
+    pub id: u64,
+    pub consumer: Address,
+    pub generator: Address,
+    pub capacity_kw: u64,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub payment_token: Address,
+    pub fee: i128,
+    pub delivered_kwh: u64,
+    pub is_settled: bool,
+}
+
+impl CapacityReservation {
+    pub fn is_active(&self, now: u64) -> bool {
+        !self.is_settled && now >= self.starts_at && now < self.ends_at
+    }
+
+    fn overlaps(&self, starts_at: u64, ends_at: u64) -> bool {
+        !self.is_settled && self.starts_at < ends_at && starts_at < self.ends_at
+    }
+}
+
+fn load(env: &Env, reservation_id: u64) -> CapacityReservation {
+    env.storage()
+        .persistent()
+        .get(&(RESERVATION, reservation_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::ReservationNotFound))
+}
+
+fn save(env: &Env, reservation: &CapacityReservation) {
+    env.storage()
+        .persistent()
+        .set(&(RESERVATION, reservation.id), reservation);
+}
+
+fn reservations_of(env: &Env, generator: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&(RESERVATIONS_OF, generator.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Capacidade reservada e ainda não entregue nas reservas ativas do gerador
+pub(crate) fn outstanding_capacity(env: &Env, generator: &Address) -> u64 {
+    let now = env.ledger().timestamp();
+    reservations_of(env, generator)
+        .iter()
+        .map(|id| load(env, id))
+        .filter(|reservation| reservation.is_active(now))
+        .map(|reservation| reservation.capacity_kw - reservation.delivered_kwh)
+        .sum()
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Reserva capacidade futura de um gerador; a taxa fica custodiada até a liquidação
+    ///
+    /// Consumidor e gerador assinam. A capacidade reservada fica fora do mint de mercado
+    /// aberto durante a janela.
+    #[allow(clippy::too_many_arguments)]
+    pub fn reserve_capacity(
+        env: Env,
+        consumer: Address,
+        generator: Address,
+        capacity_kw: u64,
+        starts_at: u64,
+        ends_at: u64,
+        payment_token: Address,
+        fee: i128,
+    ) -> u64 {
+        if consumer == generator {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        consumer.require_auth();
+        generator.require_auth();
+        if capacity_kw == 0 || fee < 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        if ends_at <= starts_at || ends_at <= env.ledger().timestamp() {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+
+        // Reservas sobrepostas não podem somar mais que a capacidade registrada
+        let energy_generator = Self::get_generator(env.clone(), generator.clone());
+        let mut ids = Vec::new(&env);
+        let mut overlapping = 0u64;
+        for id in reservations_of(&env, &generator).iter() {
+            let existing = load(&env, id);
+            if existing.is_settled {
+                continue;
+            }
+            if existing.overlaps(starts_at, ends_at) {
+                overlapping += existing.capacity_kw;
+            }
+            ids.push_back(id);
+        }
+        if overlapping + capacity_kw > energy_generator.capacity_kw {
+            panic_with_error!(&env, STRGRIDError::InsufficientCapacity);
+        }
+
+        if fee > 0 {
+            token::Client::new(&env, &payment_token).transfer(
+                &consumer,
+                &env.current_contract_address(),
+                &fee,
+            );
+        }
+
+        let id: u64 = env.storage().instance().get(&RESERVATION_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&RESERVATION_SEQ, &id);
+        let reservation = CapacityReservation {
+            id,
+            consumer,
+            generator: generator.clone(),
+            capacity_kw,
+            starts_at,
+            ends_at,
+            payment_token,
+            fee,
+            delivered_kwh: 0,
+            is_settled: false,
+        };
+        save(&env, &reservation);
+        ids.push_back(id);
+        env.storage()
+            .persistent()
+            .set(&(RESERVATIONS_OF, generator), &ids);
+
+        events::capacity_reserved(&env, &reservation);
+        id
+    }
+
+    /// Emite energia na capacidade reservada e a entrega ao consumidor (apenas o gerador, durante a janela)
+    pub fn deliver_reservation(
+        env: Env,
+        reservation_id: u64,
+        amount_kwh: u64,
+        expiry_hours: u64,
+    ) -> u64 {
+        let mut reservation = load(&env, reservation_id);
+        reservation.generator.require_auth();
+        if !reservation.is_active(env.ledger().timestamp()) {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+        if amount_kwh == 0 || reservation.delivered_kwh + amount_kwh > reservation.capacity_kw {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        // Registra a entrega antes do mint para liberar a capacidade reservada correspondente
+        reservation.delivered_kwh += amount_kwh;
+        save(&env, &reservation);
+        let token_id = mint_lot(&env, &reservation.generator, amount_kwh, expiry_hours);
+        move_balance(
+            &env,
+            &reservation.generator,
+            &reservation.consumer,
+            amount_kwh.into(),
+            OpType::Reservation,
+        );
+
+        events::reservation_delivered(&env, reservation_id, token_id, amount_kwh);
+        token_id
+    }
+
+    /// Liquida a reserva após a janela: o gerador recebe a taxa proporcional ao entregue e o
+    /// restante é reembolsado ao consumidor
+    pub fn settle_reservation(env: Env, reservation_id: u64) -> i128 {
+        let mut reservation = load(&env, reservation_id);
+        if reservation.is_settled {
+            panic_with_error!(&env, STRGRIDExtError::ReservationSettled);
+        }
+        if env.ledger().timestamp() < reservation.ends_at {
+            panic_with_error!(&env, STRGRIDError::OutsideDeliveryWindow);
+        }
+        reservation.is_settled = true;
+        save(&env, &reservation);
+
+        let earned = reservation.fee * reservation.delivered_kwh as i128
+            / reservation.capacity_kw as i128;
+        let refund = reservation.fee - earned;
+        let payment = token::Client::new(&env, &reservation.payment_token);
+        let escrow = env.current_contract_address();
+        if earned > 0 {
+            payment.transfer(&escrow, &reservation.generator, &earned);
+        }
+        if refund > 0 {
+            payment.transfer(&escrow, &reservation.consumer, &refund);
+        }
+
+        events::reservation_settled(&env, reservation_id, earned, refund);
+        refund
+    }
+
+    /// Consulta uma reserva de capacidade
+    pub fn get_reservation(env: Env, reservation_id: u64) -> CapacityReservation {
+        load(&env, reservation_id)
+    }
+
+    /// Capacidade reservada ainda não entregue nas reservas ativas do gerador
+    pub fn reserved_capacity(env: Env, generator: Address) -> u64 {
+        outstanding_capacity(&env, &generator)
+    }
+}
@@ -0,0 +1,50 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const ROLE: Symbol = StorageKey::Role.symbol();
+
+pub use strgrid_interface::Role;
+
+pub(crate) fn has_role(env: &Env, account: &Address, role: Role) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(ROLE, role, account.clone()))
+        .unwrap_or(false)
+}
+
+/// Exige autorização da conta e que ela possua o papel informado
+pub(crate) fn require_role(env: &Env, account: &Address, role: Role) {
+    account.require_auth();
+    if !has_role(env, account, role) {
+        panic_with_error!(env, STRGRIDError::NotAuthorized);
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Concede um papel a uma conta (apenas admin)
+    pub fn grant_role(env: Env, role: Role, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "grant_role", (role, account.clone()));
+        env.storage().persistent().set(&(ROLE, role, account.clone()), &true);
+        events::role_granted(&env, role, account);
+    }
+
+    /// Revoga um papel de uma conta (apenas admin)
+    pub fn revoke_role(env: Env, role: Role, account: Address) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "revoke_role", (role, account.clone()));
+        env.storage().persistent().remove(&(ROLE, role, account.clone()));
+        events::role_revoked(&env, role, account);
+    }
+
+    /// Consulta se uma conta possui um papel
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        has_role(&env, &account, role)
+    }
+}
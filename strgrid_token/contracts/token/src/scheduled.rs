@@ -0,0 +1,134 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Symbol, Val};
+use strgrid_codes::StorageKey;
+
+use crate::journal::OpType;
+use crate::migration;
+use crate::{
+    audit, events, fee, move_balance, require_admin, strict, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, STRGRIDExt2Error,
+};
+
+const SCHEDULED_TRANSFER: Symbol = StorageKey::ScheduledTransfer.symbol();
+const SCHEDULED_TRANSFER_SEQ: Symbol = StorageKey::ScheduledTransferSeq.symbol();
+const KEEPER_REWARD: Symbol = StorageKey::KeeperReward.symbol();
+
+/// Ordem permanente de transferência; o saldo só é debitado na execução pelo keeper
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledTransfer {
+    pub id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub execute_after: u64,
+    /// kWh pagos por `from` ao keeper que executar; fixado no agendamento
+    pub keeper_reward: i128,
+}
+
+fn load(env: &Env, id: u64) -> ScheduledTransfer {
+    let stored: Val = env
+        .storage()
+        .persistent()
+        .get(&(SCHEDULED_TRANSFER, id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::ScheduledTransferNotFound));
+    migration::scheduled_transfer(env, &stored)
+}
+
+/// Executa uma transferência vencida, com as mesmas regras de `transfer`, e paga o keeper;
+/// a autorização do keeper fica a cargo de quem chama
+pub(crate) fn execute(env: &Env, keeper: &Address, id: u64) {
+    let scheduled = load(env, id);
+    if env.ledger().timestamp() < scheduled.execute_after {
+        panic_with_error!(env, STRGRIDExt2Error::ScheduledTransferNotDue);
+    }
+    env.storage().persistent().remove(&(SCHEDULED_TRANSFER, id));
+
+    strict::check_transfer(env, &scheduled.from, &scheduled.to);
+    fee::transfer_with_fee(
+        env,
+        &scheduled.from,
+        &scheduled.to,
+        scheduled.amount,
+        OpType::Scheduled,
+    );
+    if scheduled.keeper_reward > 0 {
+        move_balance(env, &scheduled.from, keeper, scheduled.keeper_reward, OpType::Keeper);
+    }
+    events::scheduled_transfer_executed(env, id, keeper.clone(), scheduled.keeper_reward);
+}
+
+/// Recompensa configurada, aceitando o valor gravado em u64 antes da migração para i128
+fn keeper_reward(env: &Env) -> i128 {
+    migration::amount(env, env.storage().instance().get(&KEEPER_REWARD))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define a recompensa em kWh paga ao keeper por transferência agendada executada (apenas admin)
+    pub fn set_keeper_reward(env: Env, reward_kwh: i128) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_keeper_reward", (reward_kwh,));
+        if reward_kwh < 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage().instance().set(&KEEPER_REWARD, &reward_kwh);
+    }
+
+    /// Recompensa vigente para novos agendamentos
+    pub fn get_keeper_reward(env: Env) -> i128 {
+        keeper_reward(&env)
+    }
+
+    /// Agenda uma transferência executável por qualquer keeper a partir de `execute_after`
+    pub fn schedule_transfer(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        execute_after: u64,
+    ) -> u64 {
+        from.require_auth();
+        if from == to {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        let id: u64 = env.storage().instance().get(&SCHEDULED_TRANSFER_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&SCHEDULED_TRANSFER_SEQ, &id);
+        let scheduled = ScheduledTransfer {
+            id,
+            from,
+            to,
+            amount,
+            execute_after,
+            keeper_reward: keeper_reward(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&(SCHEDULED_TRANSFER, id), &scheduled);
+
+        events::transfer_scheduled(&env, &scheduled);
+        id
+    }
+
+    /// Consulta uma transferência agendada ainda pendente
+    pub fn get_scheduled_transfer(env: Env, id: u64) -> ScheduledTransfer {
+        load(&env, id)
+    }
+
+    /// Cancela a transferência agendada (apenas o remetente)
+    pub fn cancel_scheduled_transfer(env: Env, id: u64) {
+        let scheduled = load(&env, id);
+        scheduled.from.require_auth();
+        env.storage().persistent().remove(&(SCHEDULED_TRANSFER, id));
+        events::scheduled_transfer_cancelled(&env, id, scheduled.from);
+    }
+
+    /// Executa uma transferência vencida, com as mesmas regras de `transfer`, e paga o keeper
+    pub fn execute_scheduled_transfer(env: Env, keeper: Address, id: u64) {
+        keeper.require_auth();
+        execute(&env, &keeper, id);
+    }
+}
@@ -0,0 +1,59 @@
+use soroban_sdk::{
+    contractimpl, panic_with_error, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val,
+};
+use strgrid_codes::StorageKey;
+
+use crate::{events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDExtError};
+
+const SIGNING_KEY: Symbol = StorageKey::SigningKey.symbol();
+
+fn signing_key(env: &Env, address: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&(SIGNING_KEY, address.clone()))
+}
+
+/// Digest assinado off-chain: sha256 do XDR da mensagem
+pub(crate) fn digest<T: IntoVal<Env, Val>>(env: &Env, message: T) -> BytesN<32> {
+    let val: Val = message.into_val(env);
+    env.crypto().sha256(&val.to_xdr(env)).into()
+}
+
+/// Confere a assinatura Ed25519 sobre o digest da mensagem com a chave registrada pelo endereço
+///
+/// A chave não é derivada do endereço: a master key de uma conta pode ter sido desativada ou
+/// não bastar para os thresholds dela. Sem chave registrada a mensagem é recusada; assinatura
+/// inválida aborta a invocação com erro de criptografia do host.
+pub(crate) fn verify<T: IntoVal<Env, Val>>(
+    env: &Env,
+    signer: &Address,
+    message: T,
+    signature: &BytesN<64>,
+) {
+    let key = signing_key(env, signer)
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExtError::InvalidSigner));
+    let digest: Bytes = digest(env, message).into();
+    env.crypto().ed25519_verify(&key, &digest, signature);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Registra (ou remove, com `None`) a chave Ed25519 que assina `permit` e `relay` pelo endereço
+    ///
+    /// O registro passa pelo `require_auth` do endereço, então respeita os signatários e
+    /// thresholds da conta ou o `__check_auth` do contrato.
+    pub fn set_signing_key(env: Env, owner: Address, key: Option<BytesN<32>>) {
+        owner.require_auth();
+        let storage_key = (SIGNING_KEY, owner.clone());
+        match &key {
+            Some(key) => env.storage().persistent().set(&storage_key, key),
+            None => env.storage().persistent().remove(&storage_key),
+        }
+        events::signing_key_set(&env, owner, key);
+    }
+
+    /// Chave registrada para mensagens assinadas pelo endereço, se houver
+    pub fn signing_key_of(env: Env, address: Address) -> Option<BytesN<32>> {
+        signing_key(&env, &address)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{STRGRIDContract, STRGRIDContractClient};
+use crate::{testutils, Config, STRGRIDContract, STRGRIDContractClient};
 use soroban_sdk::*;
 use soroban_sdk::testutils::Address as AddressUtils;
 
@@ -9,19 +9,17 @@ mod simple_tests {
     #[test]
     fn test_contract_creation() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, STRGRIDContract);
+        let admin = <Address as AddressUtils>::generate(&env);
+        let contract_id = testutils::deploy(&env, &admin, false);
         let _ = STRGRIDContractClient::new(&env, &contract_id);
         
         // Just test that we can create the contract client
         // without calling any functions that might cause issues
-        assert!(true); // If we get here, contract creation worked
     }
     
     #[test]
     fn test_basic_initialization() {
         let env = Env::default();
-        let contract_id = env.register_contract(None, STRGRIDContract);
-        let client = STRGRIDContractClient::new(&env, &contract_id);
         
         let admin = <Address as AddressUtils>::generate(&env);
         
@@ -29,15 +27,21 @@ mod simple_tests {
         env.mock_all_auths();
         
         // Just try to initialize without any complex operations
-        client.initialize(
-            &admin,
-            &String::from_str(&env, "TEST"),
-            &String::from_str(&env, "TST"),
-            &7u32
+        let contract_id = env.register(
+            STRGRIDContract,
+            (
+                admin.clone(),
+                String::from_str(&env, "TEST"),
+                String::from_str(&env, "TST"),
+                7u32,
+                false,
+                Config::default(),
+            ),
         );
+        let client = STRGRIDContractClient::new(&env, &contract_id);
         
         // If we get here, initialization worked
         // Test a simple getter function
-        assert_eq!(client.total_supply(), 0u64);
+        assert_eq!(client.total_supply(), 0);
     }
 }
\ No newline at end of file
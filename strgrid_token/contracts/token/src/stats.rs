@@ -0,0 +1,136 @@
+use soroban_sdk::{contractimpl, contracttype, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, migration, query, read_total_supply, EnergyToken, SourceType, STRGRIDContract,
+    STRGRIDContractArgs, STRGRIDContractClient, ENERGY_DATA, MAX_BATCH_SIZE,
+};
+
+const TOTAL_MINTED: Symbol = StorageKey::TotalMinted.symbol();
+const TOTAL_BURNED: Symbol = StorageKey::TotalBurned.symbol();
+const SUPPLY_BY_SOURCE: Symbol = StorageKey::SupplyBySource.symbol();
+const SUPPLY_BY_ZONE: Symbol = StorageKey::SupplyByZone.symbol();
+
+/// Estatísticas agregadas do supply; `total_supply` é sempre `total_minted - total_burned`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplyStats {
+    pub total_supply: i128,
+    /// kWh emitidos, já descontados os mints anulados em contestação
+    pub total_minted: i128,
+    /// kWh retirados de circulação por burn, perdas técnicas ou clawback
+    pub total_burned: i128,
+    /// Lotes vencidos sem consumo que ainda não foram varridos nem compactados
+    pub expired_unswept: u32,
+    /// Lotes dentro da validade, nem consumidos nem anulados
+    pub active_tokens: u32,
+}
+
+fn counter(env: &Env, key: &Symbol) -> i128 {
+    migration::amount(env, env.storage().instance().get(key))
+}
+
+fn source_supply(env: &Env, source_type: SourceType) -> i128 {
+    migration::amount(env, env.storage().persistent().get(&(SUPPLY_BY_SOURCE, source_type)))
+}
+
+fn zone_supply(env: &Env, zone: u32) -> i128 {
+    migration::amount(env, env.storage().persistent().get(&(SUPPLY_BY_ZONE, zone)))
+}
+
+/// Retira um lote em aberto dos agregados por fonte e zona
+fn remove_breakdown(env: &Env, token: &EnergyToken) {
+    env.storage().persistent().set(
+        &(SUPPLY_BY_SOURCE, token.source_type),
+        &(source_supply(env, token.source_type) - i128::from(token.amount_kwh)).max(0),
+    );
+    env.storage().persistent().set(
+        &(SUPPLY_BY_ZONE, token.zone),
+        &(zone_supply(env, token.zone) - i128::from(token.amount_kwh)).max(0),
+    );
+}
+
+/// Contabiliza um lote recém-emitido no total e nos agregados por fonte e zona
+pub(crate) fn record_mint(env: &Env, token: &EnergyToken) {
+    let amount = i128::from(token.amount_kwh);
+    env.storage().instance().set(
+        &TOTAL_MINTED,
+        &migration::checked_add(env, counter(env, &TOTAL_MINTED), amount),
+    );
+    env.storage().persistent().set(
+        &(SUPPLY_BY_SOURCE, token.source_type),
+        &migration::checked_add(env, source_supply(env, token.source_type), amount),
+    );
+    env.storage().persistent().set(
+        &(SUPPLY_BY_ZONE, token.zone),
+        &migration::checked_add(env, zone_supply(env, token.zone), amount),
+    );
+}
+
+/// Encerra um lote consumido, anulado ou compactado, se ainda estava em aberto
+pub(crate) fn close_lot(env: &Env, token: &EnergyToken) {
+    if query::close_lot(env, token) {
+        remove_breakdown(env, token);
+    }
+}
+
+/// Contabiliza energia retirada de circulação
+pub(crate) fn record_burn(env: &Env, amount: i128) {
+    env.storage().instance().set(
+        &TOTAL_BURNED,
+        &migration::checked_add(env, counter(env, &TOTAL_BURNED), amount),
+    );
+}
+
+/// Estorna o mint de um lote anulado em contestação
+pub(crate) fn record_void(env: &Env, amount: u64) {
+    env.storage().instance().set(
+        &TOTAL_MINTED,
+        &(counter(env, &TOTAL_MINTED) - i128::from(amount)).max(0),
+    );
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Consulta as estatísticas de emissão, queima e lotes em aberto
+    pub fn get_stats(env: Env) -> SupplyStats {
+        let open_lots = query::open_lots(&env).len();
+        let expired_unswept = query::expired_count(&env);
+
+        SupplyStats {
+            total_supply: read_total_supply(&env),
+            total_minted: counter(&env, &TOTAL_MINTED),
+            total_burned: counter(&env, &TOTAL_BURNED),
+            expired_unswept,
+            active_tokens: open_lots - expired_unswept,
+        }
+    }
+
+    /// Encerra até `limit` lotes vencidos sem consumo, retirando-os dos agregados
+    ///
+    /// Qualquer um pode chamar; keepers localizam os lotes com `expired_tokens`.
+    pub fn sweep_expired(env: Env, limit: u32) -> u32 {
+        let token_ids = query::take_expired(&env, limit.min(MAX_BATCH_SIZE));
+        for token_id in token_ids.iter() {
+            let token: Option<EnergyToken> =
+                env.storage().persistent().get(&(ENERGY_DATA, token_id));
+            if let Some(token) = token {
+                remove_breakdown(&env, &token);
+            }
+        }
+        if !token_ids.is_empty() {
+            events::lots_swept(&env, token_ids.clone());
+        }
+        token_ids.len()
+    }
+
+    /// kWh em lotes em aberto (inclusive vencidos ainda não varridos) por fonte de energia
+    pub fn supply_by_source(env: Env, source_type: SourceType) -> i128 {
+        source_supply(&env, source_type)
+    }
+
+    /// kWh em lotes em aberto (inclusive vencidos ainda não varridos) por zona da rede
+    pub fn supply_by_zone(env: Env, zone: u32) -> i128 {
+        zone_supply(&env, zone)
+    }
+}
@@ -0,0 +1,155 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, token, Address, Env, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError,
+    STRGRIDExt2Error,
+};
+
+const STREAM: Symbol = StorageKey::Stream.symbol();
+const STREAM_SEQ: Symbol = StorageKey::StreamSeq.symbol();
+
+/// Pagamento contínuo do fornecimento: o depósito do pagador é liberado ao recebedor por segundo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stream {
+    pub id: u64,
+    pub payer: Address,
+    pub recipient: Address,
+    pub payment_token: Address,
+    pub rate_per_second: i128,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Já sacado pelo recebedor
+    pub withdrawn: i128,
+    /// Instante do cancelamento; o acúmulo para aí
+    pub cancelled_at: Option<u64>,
+}
+
+impl Stream {
+    /// Total acumulado para o recebedor até `now`
+    pub fn accrued_at(&self, now: u64) -> i128 {
+        let until = now.min(self.end_time).min(self.cancelled_at.unwrap_or(u64::MAX));
+        self.rate_per_second * until.saturating_sub(self.start_time) as i128
+    }
+}
+
+fn load(env: &Env, stream_id: u64) -> Stream {
+    env.storage()
+        .persistent()
+        .get(&(STREAM, stream_id))
+        .unwrap_or_else(|| panic_with_error!(env, STRGRIDExt2Error::StreamNotFound))
+}
+
+fn save(env: &Env, stream: &Stream) {
+    env.storage().persistent().set(&(STREAM, stream.id), stream);
+}
+
+/// Paga ao recebedor o acumulado ainda não sacado
+fn pay_accrued(env: &Env, stream: &mut Stream) -> i128 {
+    let amount = stream.accrued_at(env.ledger().timestamp()) - stream.withdrawn;
+    if amount > 0 {
+        stream.withdrawn += amount;
+        token::Client::new(env, &stream.payment_token).transfer(
+            &env.current_contract_address(),
+            &stream.recipient,
+            &amount,
+        );
+    }
+    amount
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Abre um stream de `rate_per_second` até `end_time`, depositando o valor total no contrato
+    pub fn open_stream(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        payment_token: Address,
+        rate_per_second: i128,
+        end_time: u64,
+    ) -> u64 {
+        payer.require_auth();
+        let now = env.ledger().timestamp();
+        if rate_per_second <= 0 || end_time <= now || payer == recipient {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        let deposit = rate_per_second
+            .checked_mul((end_time - now) as i128)
+            .unwrap_or_else(|| panic_with_error!(&env, STRGRIDError::ArithmeticOverflow));
+        token::Client::new(&env, &payment_token).transfer(
+            &payer,
+            &env.current_contract_address(),
+            &deposit,
+        );
+
+        let id: u64 = env.storage().instance().get(&STREAM_SEQ).unwrap_or(0) + 1;
+        env.storage().instance().set(&STREAM_SEQ, &id);
+        let stream = Stream {
+            id,
+            payer,
+            recipient,
+            payment_token,
+            rate_per_second,
+            start_time: now,
+            end_time,
+            withdrawn: 0,
+            cancelled_at: None,
+        };
+        save(&env, &stream);
+
+        events::stream_opened(&env, &stream);
+        id
+    }
+
+    /// Consulta um stream
+    pub fn get_stream(env: Env, stream_id: u64) -> Stream {
+        load(&env, stream_id)
+    }
+
+    /// Valor que o recebedor pode sacar agora
+    pub fn stream_withdrawable(env: Env, stream_id: u64) -> i128 {
+        let stream = load(&env, stream_id);
+        stream.accrued_at(env.ledger().timestamp()) - stream.withdrawn
+    }
+
+    /// Saca o acumulado do stream para o recebedor
+    pub fn withdraw_stream(env: Env, stream_id: u64) -> i128 {
+        let mut stream = load(&env, stream_id);
+        stream.recipient.require_auth();
+        let amount = pay_accrued(&env, &mut stream);
+        save(&env, &stream);
+
+        events::stream_withdrawn(&env, stream_id, stream.recipient, amount);
+        amount
+    }
+
+    /// Encerra o stream antes do fim: o recebedor leva o acumulado e o pagador o restante
+    pub fn cancel_stream(env: Env, caller: Address, stream_id: u64) -> i128 {
+        caller.require_auth();
+        let mut stream = load(&env, stream_id);
+        if caller != stream.payer && caller != stream.recipient {
+            panic_with_error!(&env, STRGRIDExt2Error::NotStreamParty);
+        }
+        let now = env.ledger().timestamp();
+        if stream.cancelled_at.is_some() || now >= stream.end_time {
+            panic_with_error!(&env, STRGRIDExt2Error::StreamEnded);
+        }
+        stream.cancelled_at = Some(now);
+        let paid = pay_accrued(&env, &mut stream);
+        let refund = stream.rate_per_second * (stream.end_time - stream.start_time) as i128
+            - stream.withdrawn;
+        save(&env, &stream);
+
+        if refund > 0 {
+            token::Client::new(&env, &stream.payment_token).transfer(
+                &env.current_contract_address(),
+                &stream.payer,
+                &refund,
+            );
+        }
+        events::stream_cancelled(&env, stream_id, caller, paid, refund);
+        refund
+    }
+}
@@ -0,0 +1,55 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::{
+    audit, events, require_admin, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient,
+    STRGRIDError,
+};
+
+const STRICT_MODE: Symbol = StorageKey::StrictMode.symbol();
+
+pub(crate) fn is_strict(env: &Env) -> bool {
+    env.storage().instance().get(&STRICT_MODE).unwrap_or(false)
+}
+
+pub(crate) fn write_strict_mode(env: &Env, enabled: bool) {
+    env.storage().instance().set(&STRICT_MODE, &enabled);
+    events::strict_mode_set(env, enabled);
+}
+
+/// Em modo estrito, rejeita transferências para o próprio remetente
+pub(crate) fn check_transfer(env: &Env, from: &Address, to: &Address) {
+    if let Err(error) = try_check_transfer(env, from, to) {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Como `check_transfer`, devolvendo o erro em vez de abortar
+pub(crate) fn try_check_transfer(env: &Env, from: &Address, to: &Address) -> Result<(), Error> {
+    if from == to && is_strict(env) {
+        return Err(STRGRIDError::SelfTransfer.into());
+    }
+    Ok(())
+}
+
+/// Em modo estrito, rejeita atualizações que não alteram nada
+pub(crate) fn check_update<T: PartialEq>(env: &Env, current: &T, new: &T) {
+    if current == new && is_strict(env) {
+        panic_with_error!(env, STRGRIDError::NoOpUpdate);
+    }
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Ativa/desativa o modo estrito de validação (apenas admin)
+    pub fn set_strict_mode(env: Env, enabled: bool) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_strict_mode", (enabled,));
+        write_strict_mode(&env, enabled);
+    }
+
+    /// Consulta se o modo estrito está ativo
+    pub fn is_strict_mode(env: Env) -> bool {
+        is_strict(&env)
+    }
+}
@@ -0,0 +1,33 @@
+use soroban_sdk::{contractimpl, panic_with_error, token, Address, Env};
+
+use crate::journal::OpType;
+use crate::{events, fee, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError};
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Troca atômica OTC: `kwh_amount` do vendedor contra `payment_amount` do comprador em um ativo Stellar (SAC)
+    pub fn swap(
+        env: Env,
+        seller: Address,
+        buyer: Address,
+        kwh_amount: u64,
+        payment_token: Address,
+        payment_amount: i128,
+    ) {
+        if seller == buyer {
+            panic_with_error!(&env, STRGRIDError::SelfTransfer);
+        }
+        seller.require_auth();
+        buyer.require_auth();
+
+        if kwh_amount == 0 || payment_amount <= 0 {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+
+        // Qualquer falha em uma das pernas reverte a invocação inteira
+        token::Client::new(&env, &payment_token).transfer(&buyer, &seller, &payment_amount);
+        fee::transfer_with_fee(&env, &seller, &buyer, kwh_amount.into(), OpType::Swap);
+
+        events::swapped(&env, seller, buyer, kwh_amount, payment_token, payment_amount);
+    }
+}
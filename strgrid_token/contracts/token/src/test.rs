@@ -1,13 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String, BytesN};
+use soroban_sdk::{testutils::{Address as _, Events, Ledger}, token, vec, xdr::ToXdr, Address, Bytes, Env, IntoVal, String, BytesN, Symbol, Vec};
 
 #[test]
 fn test_initialize_contract() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let name = String::from_str(&env, "StellarGrid Energy Token");
@@ -15,7 +13,18 @@ fn test_initialize_contract() {
     let decimals = 7u32;
     
     env.mock_all_auths();
-    client.initialize(&admin, &name, &symbol, &decimals);
+    let contract_id = env.register(
+        STRGRIDContract,
+        (
+            admin.clone(),
+            name.clone(),
+            symbol.clone(),
+            decimals,
+            false,
+            Config::default(),
+        ),
+    );
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let metadata = client.get_metadata();
     assert_eq!(metadata.name, name);
@@ -27,8 +36,6 @@ fn test_initialize_contract() {
 #[test]
 fn test_register_generator() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -36,12 +43,8 @@ fn test_register_generator() {
     
     // Initialize contract
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     // Register generator
     client.register_generator(&generator, &capacity_kw);
@@ -50,14 +53,12 @@ fn test_register_generator() {
     assert_eq!(generator_data.address, generator);
     assert_eq!(generator_data.capacity_kw, capacity_kw);
     assert_eq!(generator_data.current_production, 0);
-    assert_eq!(generator_data.is_active, true);
+    assert!(generator_data.is_active);
 }
 
 #[test]
 fn test_mint_energy_tokens() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -67,18 +68,14 @@ fn test_mint_energy_tokens() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
     
     // Mint tokens without oracle proof
     let token_id = client.mint_energy_tokens(
         &generator,
-        &energy_amount,
+        &i128::from(energy_amount),
         &expiry_hours,
         &None
     );
@@ -87,11 +84,11 @@ fn test_mint_energy_tokens() {
     let energy_token = client.get_energy_token(&token_id);
     assert_eq!(energy_token.generator_id, generator);
     assert_eq!(energy_token.amount_kwh, energy_amount);
-    assert_eq!(energy_token.is_consumed, false);
+    assert!(!energy_token.is_consumed);
     
     // Verify balance and supply
-    assert_eq!(client.balance_of(&generator), energy_amount);
-    assert_eq!(client.total_supply(), energy_amount);
+    assert_eq!(client.balance_of(&generator), i128::from(energy_amount));
+    assert_eq!(client.total_supply(), i128::from(energy_amount));
     
     // Verify generator production update
     let generator_data = client.get_generator(&generator);
@@ -101,8 +98,6 @@ fn test_mint_energy_tokens() {
 #[test]
 fn test_mint_with_oracle_proof() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -115,18 +110,14 @@ fn test_mint_with_oracle_proof() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
     
     // Mint tokens with oracle proof
     let token_id = client.mint_energy_tokens(
         &generator,
-        &energy_amount,
+        &i128::from(energy_amount),
         &expiry_hours,
         &Some(oracle_proof)
     );
@@ -140,8 +131,6 @@ fn test_mint_with_oracle_proof() {
 #[test]
 fn test_transfer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -153,36 +142,30 @@ fn test_transfer() {
     
     // Setup and mint tokens
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
-    client.mint_energy_tokens(&generator, &energy_amount, &24u64, &None);
+    client.mint_energy_tokens(&generator, &i128::from(energy_amount), &24u64, &None);
     
     // Transfer from generator to user1
-    client.transfer(&generator, &user1, &transfer_amount);
+    client.transfer(&generator, &user1, &i128::from(transfer_amount));
     
     // Verify balances after first transfer
-    assert_eq!(client.balance_of(&generator), energy_amount - transfer_amount);
-    assert_eq!(client.balance_of(&user1), transfer_amount);
+    assert_eq!(client.balance_of(&generator), i128::from(energy_amount - transfer_amount));
+    assert_eq!(client.balance_of(&user1), i128::from(transfer_amount));
     assert_eq!(client.balance_of(&user2), 0);
     
     // Transfer from user1 to user2
-    client.transfer(&user1, &user2, &100u64);
+    client.transfer(&user1, &user2, &100i128);
     
     // Verify final balances
-    assert_eq!(client.balance_of(&user1), transfer_amount - 100);
+    assert_eq!(client.balance_of(&user1), i128::from(transfer_amount - 100));
     assert_eq!(client.balance_of(&user2), 100);
 }
 
 #[test]
 fn test_approve_and_transfer_from() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -196,38 +179,32 @@ fn test_approve_and_transfer_from() {
     
     // Setup and mint tokens
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
-    client.mint_energy_tokens(&generator, &energy_amount, &24u64, &None);
+    client.mint_energy_tokens(&generator, &i128::from(energy_amount), &24u64, &None);
     
     // Transfer tokens to owner
-    client.transfer(&generator, &owner, &energy_amount);
+    client.transfer(&generator, &owner, &i128::from(energy_amount));
     
     // Approve spender
-    client.approve(&owner, &spender, &approve_amount);
+    client.approve(&owner, &spender, &i128::from(approve_amount));
     
     // Verify allowance
-    assert_eq!(client.allowance(&owner, &spender), approve_amount);
+    assert_eq!(client.allowance(&owner, &spender), i128::from(approve_amount));
     
     // Transfer from owner to recipient using allowance
-    client.transfer_from(&spender, &owner, &recipient, &transfer_amount);
+    client.transfer_from(&spender, &owner, &recipient, &i128::from(transfer_amount));
     
     // Verify balances and allowance
-    assert_eq!(client.balance_of(&owner), energy_amount - transfer_amount);
-    assert_eq!(client.balance_of(&recipient), transfer_amount);
-    assert_eq!(client.allowance(&owner, &spender), approve_amount - transfer_amount);
+    assert_eq!(client.balance_of(&owner), i128::from(energy_amount - transfer_amount));
+    assert_eq!(client.balance_of(&recipient), i128::from(transfer_amount));
+    assert_eq!(client.allowance(&owner, &spender), i128::from(approve_amount - transfer_amount));
 }
 
 #[test]
 fn test_burn_energy_tokens() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -238,28 +215,24 @@ fn test_burn_energy_tokens() {
     
     // Setup and mint tokens
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
-    let token_id = client.mint_energy_tokens(&generator, &energy_amount, &24u64, &None);
+    let token_id = client.mint_energy_tokens(&generator, &i128::from(energy_amount), &24u64, &None);
     
     // Transfer tokens to consumer
-    client.transfer(&generator, &consumer, &energy_amount);
+    client.transfer(&generator, &consumer, &i128::from(energy_amount));
     
     // Burn tokens
-    client.burn_energy_tokens(&consumer, &token_id, &burn_amount);
+    client.burn_energy_tokens(&consumer, &token_id, &i128::from(burn_amount));
     
     // Verify token is consumed
     let energy_token = client.get_energy_token(&token_id);
-    assert_eq!(energy_token.is_consumed, true);
+    assert!(energy_token.is_consumed);
     
     // Verify balance and supply reduction
-    assert_eq!(client.balance_of(&consumer), energy_amount - burn_amount);
-    assert_eq!(client.total_supply(), energy_amount - burn_amount);
+    assert_eq!(client.balance_of(&consumer), i128::from(energy_amount - burn_amount));
+    assert_eq!(client.total_supply(), i128::from(energy_amount - burn_amount));
     
     // Verify generator production reduction
     let generator_data = client.get_generator(&generator);
@@ -269,8 +242,6 @@ fn test_burn_energy_tokens() {
 #[test]
 fn test_generator_management() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -279,23 +250,19 @@ fn test_generator_management() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &initial_capacity);
     
     // Test deactivating generator
     client.set_generator_status(&generator, &false);
     let generator_data = client.get_generator(&generator);
-    assert_eq!(generator_data.is_active, false);
+    assert!(!generator_data.is_active);
     
     // Test reactivating generator
     client.set_generator_status(&generator, &true);
     let generator_data = client.get_generator(&generator);
-    assert_eq!(generator_data.is_active, true);
+    assert!(generator_data.is_active);
     
     // Test updating capacity
     client.update_generator_capacity(&generator, &new_capacity);
@@ -304,11 +271,9 @@ fn test_generator_management() {
 }
 
 #[test]
-#[should_panic(expected = "InsufficientCapacity")]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_mint_exceeds_capacity() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -317,24 +282,18 @@ fn test_mint_exceeds_capacity() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &capacity_kw);
     
     // This should panic
-    client.mint_energy_tokens(&generator, &excessive_amount, &24u64, &None);
+    client.mint_energy_tokens(&generator, &i128::from(excessive_amount), &24u64, &None);
 }
 
 #[test]
-#[should_panic(expected = "InsufficientAllowance")]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_transfer_from_insufficient_allowance() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -344,29 +303,23 @@ fn test_transfer_from_insufficient_allowance() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &1000u64);
-    client.mint_energy_tokens(&generator, &500u64, &24u64, &None);
-    client.transfer(&generator, &owner, &500u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &owner, &500i128);
     
     // Approve only 100 tokens
-    client.approve(&owner, &spender, &100u64);
+    client.approve(&owner, &spender, &100i128);
     
     // Try to transfer 200 tokens (should panic)
-    client.transfer_from(&spender, &owner, &recipient, &200u64);
+    client.transfer_from(&spender, &owner, &recipient, &200i128);
 }
 
 #[test]
-#[should_panic(expected = "TokenAlreadyConsumed")]
+#[should_panic(expected = "Error(Contract, #9)")]
 fn test_double_burn() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, STRGRIDContract);
-    let client = STRGRIDContractClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
     let generator = Address::generate(&env);
@@ -374,19 +327,4419 @@ fn test_double_burn() {
     
     // Setup
     env.mock_all_auths();
-    client.initialize(
-        &admin,
-        &String::from_str(&env, "STRGRID"),
-        &String::from_str(&env, "STRGRID"),
-        &7u32
-    );
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
     client.register_generator(&generator, &1000u64);
-    let token_id = client.mint_energy_tokens(&generator, &500u64, &24u64, &None);
-    client.transfer(&generator, &consumer, &500u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &500i128);
     
     // First burn should succeed
-    client.burn_energy_tokens(&consumer, &token_id, &200u64);
+    client.burn_energy_tokens(&consumer, &token_id, &200i128);
     
     // Second burn should panic
-    client.burn_energy_tokens(&consumer, &token_id, &100u64);
+    client.burn_energy_tokens(&consumer, &token_id, &100i128);
+}
+
+#[test]
+fn test_challenge_mint_voids_tokens() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let evidence = BytesN::from_array(&env, &[7u8; 32]);
+    
+    // Setup with a 1 hour dispute window
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_dispute_window(&3600u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    
+    let honest_id = client.mint_energy_tokens(&generator, &300i128, &24u64, &None);
+    let fraud_id = client.mint_energy_tokens(&generator, &200i128, &24u64, &None);
+    assert_ne!(honest_id, fraud_id);
+    assert_eq!(client.locked_balance_of(&generator), 500);
+    
+    // Auditor voids the fraudulent mint
+    client.challenge_mint(&auditor, &fraud_id, &evidence);
+    
+    let energy_token = client.get_energy_token(&fraud_id);
+    assert!(energy_token.is_voided);
+    assert_eq!(client.balance_of(&generator), 300);
+    assert_eq!(client.total_supply(), 300);
+    assert_eq!(client.get_generator(&generator).current_production, 300);
+    assert_eq!(client.locked_balance_of(&generator), 300);
+    
+    let challenge = client.get_challenge(&fraud_id).unwrap();
+    assert_eq!(challenge.auditor, auditor);
+    assert_eq!(challenge.evidence_hash, evidence);
+    assert_eq!(challenge.amount_kwh, 200);
+    
+    // After the window the remaining tokens become tradeable
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    assert_eq!(client.locked_balance_of(&generator), 0);
+    client.transfer(&generator, &auditor, &300i128);
+    assert_eq!(client.balance_of(&auditor), 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_transfer_locked_during_dispute() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_dispute_window(&3600u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    // Tokens are still inside the dispute window (should panic)
+    client.transfer(&generator, &user1, &100i128);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_challenge_after_window_closed() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_dispute_window(&3600u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    // Window has passed (should panic)
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    client.challenge_mint(&auditor, &token_id, &BytesN::from_array(&env, &[7u8; 32]));
+}
+
+#[test]
+fn test_find_tokens_with_filters() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let solar = Address::generate(&env);
+    let wind = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup: one solar generator in zone 1, one wind generator in zone 2
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&solar, &1000u64);
+    client.register_generator(&wind, &1000u64);
+    client.set_generator_profile(&solar, &SourceType::Solar, &1u32);
+    client.set_generator_profile(&wind, &SourceType::Wind, &2u32);
+    
+    let solar_short = client.mint_energy_tokens(&solar, &100i128, &1u64, &None);
+    let solar_long = client.mint_energy_tokens(&solar, &100i128, &48u64, &None);
+    let wind_long = client.mint_energy_tokens(&wind, &100i128, &48u64, &None);
+    client.transfer(&solar, &consumer, &100i128);
+    client.burn_energy_tokens(&consumer, &solar_short, &100i128);
+    
+    let any = TokenFilter {
+        generator: None,
+        source_types: Vec::new(&env),
+        zone: None,
+        expiry_from: None,
+        expiry_to: None,
+        is_consumed: None,
+        vintage_year: None,
+        vintage_month: None,
+    };
+    assert_eq!(client.find_tokens(&any, &0u32, &10u32).len(), 3);
+    
+    // Source + consumed flag
+    let mut filter = any.clone();
+    filter.source_types = vec![&env, SourceType::Solar];
+    filter.is_consumed = Some(false);
+    let found = client.find_tokens(&filter, &0u32, &10u32);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found.get(0).unwrap().id, solar_long);
+    
+    // Zone + expiry range
+    let mut filter = any.clone();
+    filter.zone = Some(2);
+    filter.expiry_from = Some(24 * 3600);
+    let found = client.find_tokens(&filter, &0u32, &10u32);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found.get(0).unwrap().id, wind_long);
+    
+    // Pagination
+    let page = client.find_tokens(&any, &1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, solar_long);
+}
+
+#[test]
+fn test_generator_bond_and_slash() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    // Bond asset (SAC) funded for the generator
+    env.mock_all_auths();
+    let bond_token = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &bond_token).mint(&generator, &5_000i128);
+    let bond_client = token::Client::new(&env, &bond_token);
+    
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_bond_config(&bond_token, &1_000i128);
+    client.set_dispute_window(&3600u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    
+    // Bond is required before minting
+    assert!(client.try_mint_energy_tokens(&generator, &100i128, &24u64, &None).is_err());
+    client.deposit_bond(&generator, &1_500i128);
+    assert_eq!(client.bond_of(&generator), 1_500);
+    assert_eq!(bond_client.balance(&contract_id), 1_500);
+    
+    // Challenged mint is upheld and the bond slashed
+    let token_id = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    client.challenge_mint(&auditor, &token_id, &BytesN::from_array(&env, &[9u8; 32]));
+    client.slash(&token_id, &400i128, &treasury);
+    assert_eq!(client.bond_of(&generator), 1_100);
+    assert_eq!(bond_client.balance(&treasury), 400);
+    assert!(client.get_challenge(&token_id).unwrap().slashed);
+    assert!(client.try_slash(&token_id, &1i128, &treasury).is_err());
+    
+    // Active generators may only withdraw the excess
+    assert!(client.try_withdraw_bond(&generator, &200i128).is_err());
+    client.withdraw_bond(&generator, &100i128);
+    assert_eq!(client.bond_of(&generator), 1_000);
+    assert_eq!(bond_client.balance(&generator), 3_600);
+}
+
+#[test]
+fn test_strict_mode_validation() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    // Legacy behaviour is kept until strict mode is enabled
+    client.transfer(&generator, &generator, &100i128);
+    client.set_generator_status(&generator, &true);
+    assert!(!client.is_strict_mode());
+    
+    client.set_strict_mode(&true);
+    assert!(client.is_strict_mode());
+    assert_eq!(
+        client.try_transfer(&generator, &generator, &100i128),
+        Err(Ok(STRGRIDError::SelfTransfer.into()))
+    );
+    assert_eq!(
+        client.try_approve(&generator, &spender, &0i128),
+        Err(Ok(STRGRIDError::ZeroApproval.into()))
+    );
+    assert_eq!(
+        client.try_set_generator_status(&generator, &true),
+        Err(Ok(STRGRIDError::NoOpUpdate.into()))
+    );
+    assert_eq!(
+        client.try_update_generator_capacity(&generator, &1000u64),
+        Err(Ok(STRGRIDError::NoOpUpdate.into()))
+    );
+    
+    // Zero approval still revokes an existing allowance
+    client.approve(&generator, &spender, &50i128);
+    client.approve(&generator, &spender, &0i128);
+    assert_eq!(client.allowance(&generator, &spender), 0);
+}
+
+#[test]
+fn test_increase_and_decrease_allowance() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let spender = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    
+    assert_eq!(client.increase_allowance(&owner, &spender, &100i128), 100);
+    assert_eq!(client.increase_allowance(&owner, &spender, &50i128), 150);
+    assert_eq!(client.decrease_allowance(&owner, &spender, &30i128), 120);
+    assert_eq!(client.allowance(&owner, &spender), 120);
+    
+    // Underflow and overflow are rejected
+    assert_eq!(
+        client.try_decrease_allowance(&owner, &spender, &121i128),
+        Err(Ok(STRGRIDError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        client.try_increase_allowance(&owner, &spender, &i128::MAX),
+        Err(Ok(STRGRIDError::ArithmeticOverflow.into()))
+    );
+    
+    assert_eq!(client.decrease_allowance(&owner, &spender, &120i128), 0);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    
+    // Increases keep the expiration of a live allowance
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.approve_until(&owner, &spender, &10i128, &Some(110u32));
+    assert_eq!(client.increase_allowance(&owner, &spender, &5i128), 15);
+    assert_eq!(client.get_allowance(&owner, &spender).unwrap().live_until_ledger, Some(110));
+    
+    // An expired allowance is not turned into a permanent one
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(
+        client.try_increase_allowance(&owner, &spender, &5i128),
+        Err(Ok(STRGRIDError::AllowanceExpired.into()))
+    );
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    client.approve_until(&owner, &spender, &5i128, &Some(200u32));
+    assert_eq!(client.increase_allowance(&owner, &spender, &5i128), 10);
+    assert_eq!(client.get_allowance(&owner, &spender).unwrap().live_until_ledger, Some(200));
+}
+
+#[test]
+fn test_allowance_expiration() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.approve_until(&generator, &spender, &200i128, &Some(110u32));
+    client.transfer_from(&spender, &generator, &recipient, &50i128);
+    assert_eq!(client.allowance(&generator, &spender), 150);
+    
+    // Past the live-until ledger the allowance lapses
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    assert_eq!(client.allowance(&generator, &spender), 0);
+    assert_eq!(
+        client.try_transfer_from(&spender, &generator, &recipient, &50i128),
+        Err(Ok(STRGRIDError::AllowanceExpired.into()))
+    );
+    
+    // Approvals already in the past are rejected
+    assert_eq!(
+        client.try_approve_until(&generator, &spender, &10i128, &Some(50u32)),
+        Err(Ok(STRGRIDError::AllowanceExpired.into()))
+    );
+}
+
+#[test]
+fn test_view_access_grants() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let compliance = Address::generate(&env);
+    
+    // Setup and consume some energy
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &300i128);
+    client.burn_energy_tokens(&consumer, &token_id, &120i128);
+    
+    // The consumer can always read its own data
+    let records = client.consumption_of(&consumer, &consumer);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records.get(0).unwrap().amount_kwh, 120);
+    
+    // Third parties need a grant or the compliance role
+    assert!(client.try_consumption_of(&auditor, &consumer).is_err());
+    client.grant_view_access(&consumer, &auditor);
+    assert_eq!(client.view_grants_of(&consumer), vec![&env, auditor.clone()]);
+    assert_eq!(client.consumption_of(&auditor, &consumer), records);
+    
+    client.revoke_view_access(&consumer, &auditor);
+    assert!(client.try_consumption_of(&auditor, &consumer).is_err());
+    
+    client.grant_role(&Role::Compliance, &compliance);
+    assert_eq!(client.consumption_of(&compliance, &consumer), records);
+}
+
+#[test]
+fn test_journal_records_balanced_entries() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup and move value around
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &300i128);
+    client.burn_energy_tokens(&consumer, &token_id, &100i128);
+    
+    assert_eq!(client.journal_length(), 3);
+    let entries = client.journal_entries(&1u64, &10u32);
+    assert_eq!(entries.len(), 3);
+    
+    let mint = entries.get(0).unwrap();
+    assert_eq!(mint.op, OpType::Mint);
+    assert_eq!(mint.debit, JournalAccount::Holder(generator.clone()));
+    assert_eq!(mint.credit, JournalAccount::Issuance);
+    assert_eq!(mint.reference, token_id);
+    
+    let transfer = entries.get(1).unwrap();
+    assert_eq!(transfer.debit, JournalAccount::Holder(consumer.clone()));
+    assert_eq!(transfer.credit, JournalAccount::Holder(generator.clone()));
+    assert_eq!(transfer.amount, 300);
+    
+    let burn = entries.get(2).unwrap();
+    assert_eq!(burn.op, OpType::Burn);
+    assert_eq!(burn.debit, JournalAccount::Retired);
+    
+    // Daily roll-up stays balanced
+    let rollup = client.journal_rollup(&0u64).unwrap();
+    assert_eq!(rollup.entry_count, 3);
+    assert_eq!(rollup.total_debits, rollup.total_credits);
+    assert_eq!(rollup.total_debits, 900);
+    
+    // Paging from a later sequence
+    assert_eq!(client.journal_entries(&3u64, &10u32).len(), 1);
+}
+
+#[test]
+fn test_burn_and_burn_from() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let spender = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    client.burn(&generator, &100i128);
+    assert_eq!(client.balance_of(&generator), 400);
+    assert_eq!(client.total_supply(), 400);
+    
+    client.approve(&generator, &spender, &150i128);
+    client.burn_from(&spender, &generator, &150i128);
+    assert_eq!(client.balance_of(&generator), 250);
+    assert_eq!(client.total_supply(), 250);
+    assert_eq!(client.allowance(&generator, &spender), 0);
+    
+    assert_eq!(
+        client.try_burn_from(&spender, &generator, &1i128),
+        Err(Ok(STRGRIDError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        client.try_burn(&generator, &251i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+}
+
+#[test]
+fn test_transfer_batch() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    client.transfer_batch(&generator, &vec![
+        &env,
+        (user1.clone(), 100i128),
+        (user2.clone(), 150i128),
+        (user3.clone(), 50i128),
+    ]);
+    assert_eq!(client.balance_of(&generator), 200);
+    assert_eq!(client.balance_of(&user1), 100);
+    assert_eq!(client.balance_of(&user2), 150);
+    assert_eq!(client.balance_of(&user3), 50);
+    
+    // The whole batch fails atomically if any leg is short
+    let result = client.try_transfer_batch(&generator, &vec![
+        &env,
+        (user1.clone(), 150i128),
+        (user2.clone(), 100i128),
+    ]);
+    assert_eq!(result, Err(Ok(STRGRIDError::InsufficientBalance.into())));
+    assert_eq!(client.balance_of(&generator), 200);
+    assert_eq!(client.balance_of(&user1), 100);
+}
+
+#[test]
+fn test_preview_mint_and_burn() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    
+    // Preview does not change state and matches the real mint
+    let mint_preview = client.preview_mint(&generator, &400i128, &24u64);
+    assert!(mint_preview.ok);
+    assert_eq!(mint_preview.error, None);
+    assert_eq!(mint_preview.balance_after, 400);
+    assert_eq!(mint_preview.total_supply_after, 400);
+    assert_eq!(mint_preview.production_after, 400);
+    assert_eq!(client.total_supply(), 0);
+    
+    let token_id = client.mint_energy_tokens(&generator, &400i128, &24u64, &None);
+    assert_eq!(token_id, mint_preview.token_id);
+    assert_eq!(client.get_energy_token(&token_id).expiry_timestamp, mint_preview.expiry_timestamp);
+    
+    // Failing previews do not abort; they report the error the real call would raise
+    let failed = client.preview_mint(&generator, &700i128, &24u64);
+    assert!(!failed.ok);
+    assert_eq!(failed.error, Some(STRGRIDError::InsufficientCapacity as u32));
+    assert_eq!((failed.amount_kwh, failed.balance_after), (700, 0));
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &700i128, &24u64, &None),
+        Err(Ok(STRGRIDError::InsufficientCapacity.into()))
+    );
+    
+    client.transfer(&generator, &consumer, &400i128);
+    let burn_preview = client.preview_burn(&consumer, &token_id, &150i128);
+    assert_eq!(burn_preview.balance_after, 250);
+    assert_eq!(burn_preview.total_supply_after, 250);
+    assert_eq!(burn_preview.generator_production_after, 250);
+    assert_eq!(client.balance_of(&consumer), 400);
+    
+    client.burn_energy_tokens(&consumer, &token_id, &150i128);
+    assert_eq!(client.balance_of(&consumer), burn_preview.balance_after);
+    assert_eq!(client.get_generator(&generator).current_production, 250);
+}
+
+#[test]
+fn test_operation_priority_classification() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let operation = Symbol::new(&env, "set_generator_status");
+    
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    
+    assert_eq!(client.operation_priority(&operation), Priority::Normal);
+    client.set_operation_priority(&operation, &Priority::GridCritical);
+    assert_eq!(client.operation_priority(&operation), Priority::GridCritical);
+    client.set_operation_priority(&operation, &Priority::Normal);
+    assert_eq!(client.operation_priority(&operation), Priority::Normal);
+}
+
+#[test]
+fn test_transfer_with_memo() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let retailer = Address::generate(&env);
+    let invoice_ref = BytesN::from_array(&env, &[42u8; 32]);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    client.transfer_with_memo(&generator, &retailer, &120i128, &invoice_ref);
+    let emitted = env.events().all();
+    assert_eq!(client.balance_of(&retailer), 120);
+    
+    // The memo is published for billing reconciliation
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(
+        topics,
+        (events::TRANSFER_MEMO, generator.clone(), retailer.clone()).into_val(&env)
+    );
+    let (amount, memo): (i128, BytesN<32>) = data.into_val(&env);
+    assert_eq!(amount, 120);
+    assert_eq!(memo, invoice_ref);
+}
+
+#[test]
+fn test_freeze_account_blocks_outflows() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &200i128);
+    
+    client.freeze_account(&consumer);
+    assert!(client.is_frozen(&consumer));
+    assert_eq!(
+        client.try_transfer(&consumer, &generator, &50i128),
+        Err(Ok(STRGRIDError::AccountFrozen.into()))
+    );
+    assert_eq!(
+        client.try_burn_energy_tokens(&consumer, &token_id, &50i128),
+        Err(Ok(STRGRIDError::AccountFrozen.into()))
+    );
+    
+    // Frozen accounts can still receive
+    client.transfer(&generator, &consumer, &50i128);
+    assert_eq!(client.balance_of(&consumer), 250);
+    
+    client.unfreeze_account(&consumer);
+    client.transfer(&consumer, &generator, &50i128);
+    assert_eq!(client.balance_of(&consumer), 200);
+}
+
+#[test]
+fn test_compact_lots_after_retention() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let first_id = client.mint_energy_tokens(&generator, &300i128, &24u64, &None);
+    let second_id = client.mint_energy_tokens(&generator, &200i128, &24u64, &None);
+    client.burn_energy_tokens(&generator, &first_id, &300i128);
+    let first = client.get_energy_token(&first_id);
+    let second = client.get_energy_token(&second_id);
+    
+    // Retention not elapsed yet
+    client.set_archive_retention(&86_400u64);
+    assert_eq!(
+        client.try_compact_lots(&vec![&env, first_id, second_id]),
+        Err(Ok(STRGRIDError::LotNotArchivable.into()))
+    );
+    
+    env.ledger().with_mut(|li| li.timestamp += 2 * 86_400 + 1);
+    
+    // A repeated id would count the same lot twice in the aggregate
+    assert_eq!(
+        client.try_compact_lots(&vec![&env, first_id, second_id, first_id]),
+        Err(Ok(STRGRIDExt2Error::DuplicateLot.into()))
+    );
+    assert_eq!(client.get_energy_token(&first_id), first);
+    
+    client.compact_lots(&vec![&env, first_id, second_id]);
+    let emitted = env.events().all();
+    
+    assert_eq!(
+        client.try_get_energy_token(&first_id),
+        Err(Ok(STRGRIDError::TokenNotFound.into()))
+    );
+    let filter = TokenFilter {
+        generator: Some(generator.clone()),
+        source_types: Vec::new(&env),
+        zone: None,
+        expiry_from: None,
+        expiry_to: None,
+        is_consumed: None,
+        vintage_year: None,
+        vintage_month: None,
+    };
+    assert_eq!(client.find_tokens(&filter, &0u32, &10u32).len(), 0);
+    
+    let period = first.creation_timestamp / 2_592_000;
+    let aggregate = client.archive_aggregate(&generator, &period).unwrap();
+    assert_eq!(aggregate.lot_count, 2);
+    assert_eq!(aggregate.total_kwh, 500);
+    assert_eq!(aggregate.consumed_kwh, 300);
+    assert_eq!(aggregate.batch_count, 1);
+    
+    // Batch root is published in the event; each lot proves membership with its sibling
+    let (_, _, data) = emitted.last().unwrap();
+    let (batch_root, token_ids): (BytesN<32>, Vec<u64>) = data.into_val(&env);
+    assert_eq!(token_ids, vec![&env, first_id, second_id]);
+    let first_leaf: BytesN<32> = env.crypto().sha256(&first.clone().to_xdr(&env)).into();
+    let second_leaf: BytesN<32> = env.crypto().sha256(&second.clone().to_xdr(&env)).into();
+    assert!(client.verify_archived_lot(&batch_root, &first, &0u32, &vec![&env, second_leaf]));
+    assert!(client.verify_archived_lot(&batch_root, &second, &1u32, &vec![&env, first_leaf.clone()]));
+    assert!(!client.verify_archived_lot(&batch_root, &second, &0u32, &vec![&env, first_leaf]));
+}
+
+#[test]
+fn test_clawback_requires_flag() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let thief = Address::generate(&env);
+    
+    env.mock_all_auths();
+    
+    // Disabled at initialization
+    let disabled_id = testutils::deploy(&env, &admin, false);
+    let disabled = STRGRIDContractClient::new(&env, &disabled_id);
+    assert!(!disabled.is_clawback_enabled());
+    assert_eq!(
+        disabled.try_clawback(&thief, &10i128),
+        Err(Ok(STRGRIDError::ClawbackDisabled.into()))
+    );
+    
+    // Enabled at initialization
+    let contract_id = testutils::deploy(&env, &admin, true);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &thief, &200i128);
+    client.freeze_account(&thief);
+    
+    // Works even on frozen accounts
+    client.clawback(&thief, &150i128);
+    let emitted = env.events().all();
+    assert_eq!(client.balance_of(&thief), 50);
+    assert_eq!(client.total_supply(), 350);
+    
+    let (_, topics, data) = emitted.last().unwrap();
+    let topic: Symbol = topics.get(0).unwrap().into_val(&env);
+    let amount: i128 = data.into_val(&env);
+    assert_eq!(topic, Symbol::new(&env, "clawback"));
+    assert_eq!(amount, 150);
+    
+    assert_eq!(
+        client.try_clawback(&thief, &100i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+}
+
+#[test]
+fn test_transfer_fee_accumulates_to_treasury() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    
+    assert_eq!(
+        client.try_set_transfer_fee(&10_001u32, &treasury),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.try_withdraw_treasury(&1i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    
+    // 2.5% fee
+    client.set_transfer_fee(&250u32, &treasury);
+    assert_eq!(client.get_transfer_fee().unwrap().fee_bps, 250);
+    client.transfer(&generator, &consumer, &400i128);
+    
+    let (_, _, data) = env.events().all().last().unwrap();
+    let (amount, fee): (i128, i128) = data.into_val(&env);
+    assert_eq!((amount, fee), (400, 10));
+    assert_eq!(client.balance_of(&generator), 600);
+    assert_eq!(client.balance_of(&consumer), 390);
+    assert_eq!(client.treasury_balance(), 10);
+    assert_eq!(client.total_supply(), 1000);
+    
+    client.withdraw_treasury(&10i128);
+    assert_eq!(client.treasury_balance(), 0);
+    assert_eq!(client.balance_of(&treasury), 10);
+    
+    // Zero bps disables the fee
+    client.set_transfer_fee(&0u32, &treasury);
+    client.transfer(&consumer, &generator, &100i128);
+    assert_eq!(client.balance_of(&consumer), 290);
+    assert_eq!(client.treasury_balance(), 0);
+}
+
+#[test]
+fn test_mint_caps() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let other = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &10_000u64);
+    client.register_generator(&other, &10_000u64);
+    client.set_mint_caps(&MintCaps {
+        max_total_supply: Some(1500),
+        max_daily_per_generator: Some(1000),
+        min_mint: Some(10),
+        max_mint: Some(600),
+    });
+    
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &5i128, &24u64, &None),
+        Err(Ok(STRGRIDError::MintBelowMinimum.into()))
+    );
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &601i128, &24u64, &None),
+        Err(Ok(STRGRIDError::MintAboveMaximum.into()))
+    );
+    
+    client.mint_energy_tokens(&generator, &600i128, &24u64, &None);
+    client.mint_energy_tokens(&generator, &400i128, &24u64, &None);
+    assert_eq!(client.minted_today(&generator), 1000);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDError::DailyMintCapExceeded.into()))
+    );
+    
+    // Global supply cap applies across generators
+    client.mint_energy_tokens(&other, &500i128, &24u64, &None);
+    assert_eq!(
+        client.try_mint_energy_tokens(&other, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDError::SupplyCapExceeded.into()))
+    );
+    
+    // Daily counter resets on the next day
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.minted_today(&generator), 0);
+}
+
+#[test]
+fn test_mint_cooldown() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    
+    // One mint per 15-minute settlement interval
+    client.set_mint_cooldown(&900u64);
+    client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    assert_eq!(client.get_generator(&generator).last_mint_timestamp, Some(env.ledger().timestamp()));
+    
+    env.ledger().with_mut(|li| li.timestamp += 899);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &100i128, &24u64, &None),
+        Err(Ok(STRGRIDError::MintTooFrequent.into()))
+    );
+    
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    assert_eq!(client.balance_of(&generator), 200);
+}
+
+#[test]
+fn test_burn_issues_certificate() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_generator_profile(&generator, &SourceType::Solar, &3u32);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &300i128);
+    
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    let cert_id = client.burn_energy_tokens(&consumer, &token_id, &300i128);
+    
+    let certificate = client.get_certificate(&cert_id);
+    assert_eq!(certificate.consumer, consumer);
+    assert_eq!(certificate.token_id, token_id);
+    assert_eq!(certificate.generator, generator);
+    assert_eq!(certificate.amount_kwh, 300);
+    assert_eq!(certificate.source_type, SourceType::Solar);
+    assert_eq!(certificate.vintage, client.get_energy_token(&token_id).creation_timestamp);
+    assert_eq!(certificate.retired_at, env.ledger().timestamp());
+    
+    assert_eq!(client.certificates_of(&consumer), vec![&env, certificate]);
+    assert_eq!(
+        client.try_get_certificate(&99u64),
+        Err(Ok(STRGRIDError::CertificateNotFound.into()))
+    );
+}
+
+#[test]
+fn test_retire_certificate() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    let cert_id = client.burn_energy_tokens(&generator, &token_id, &200i128);
+    assert_eq!(client.get_retirement(&cert_id), None);
+    
+    let purpose = String::from_str(&env, "Scope 2 report 2026");
+    client.retire(&cert_id, &beneficiary, &purpose);
+    
+    let retirement = client.get_retirement(&cert_id).unwrap();
+    assert_eq!(retirement.owner, generator);
+    assert_eq!(retirement.beneficiary, beneficiary);
+    assert_eq!(retirement.purpose, purpose);
+    
+    // Retirement is immutable
+    assert_eq!(
+        client.try_retire(&cert_id, &generator, &String::from_str(&env, "again")),
+        Err(Ok(STRGRIDError::CertificateRetired.into()))
+    );
+}
+
+#[test]
+fn test_carbon_offset_accounting() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let solar = Address::generate(&env);
+    let wind = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&solar, &1000u64);
+    client.register_generator(&wind, &1000u64);
+    client.set_generator_profile(&solar, &SourceType::Solar, &1u32);
+    client.set_generator_profile(&wind, &SourceType::Wind, &1u32);
+    client.set_emission_factor(&SourceType::Solar, &450u64);
+    client.set_emission_factor(&SourceType::Wind, &500u64);
+    assert_eq!(client.get_emission_factor(&SourceType::Hydro), 0);
+    
+    let solar_token = client.mint_energy_tokens(&solar, &100i128, &24u64, &None);
+    let wind_token = client.mint_energy_tokens(&wind, &100i128, &24u64, &None);
+    let own_token = client.mint_energy_tokens(&wind, &100i128, &24u64, &None);
+    client.transfer(&solar, &consumer, &100i128);
+    client.transfer(&wind, &consumer, &40i128);
+    
+    client.burn_energy_tokens(&consumer, &solar_token, &100i128);
+    client.burn_energy_tokens(&consumer, &wind_token, &40i128);
+    client.burn_energy_tokens(&wind, &own_token, &10i128);
+    
+    assert_eq!(client.carbon_offset_of(&consumer), 100 * 450 + 40 * 500);
+    assert_eq!(client.carbon_offset_of(&wind), 10 * 500);
+    assert_eq!(client.total_carbon_offset(), 100 * 450 + 50 * 500);
+}
+
+#[test]
+fn test_vintage_set_at_mint_and_queryable() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &10_000u64);
+    
+    // 2024-02-29T12:00:00Z and 2024-03-01T00:00:00Z
+    env.ledger().with_mut(|li| li.timestamp = 1_709_208_000);
+    let february = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    env.ledger().with_mut(|li| li.timestamp = 1_709_251_200);
+    let march = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    
+    assert_eq!(client.get_energy_token(&february).vintage, (2024, 2));
+    assert_eq!(client.get_energy_token(&march).vintage, (2024, 3));
+    
+    let mut filter = TokenFilter {
+        generator: None,
+        source_types: Vec::new(&env),
+        zone: None,
+        expiry_from: None,
+        expiry_to: None,
+        is_consumed: None,
+        vintage_year: Some(2024),
+        vintage_month: None,
+    };
+    assert_eq!(client.find_tokens(&filter, &0u32, &10u32).len(), 2);
+    filter.vintage_month = Some(3);
+    let found = client.find_tokens(&filter, &0u32, &10u32);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found.get(0).unwrap().id, march);
+}
+
+#[test]
+fn test_atomic_swap() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &1_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    client.swap(&generator, &buyer, &200u64, &usdc, &600i128);
+    assert_eq!(client.balance_of(&buyer), 200);
+    assert_eq!(client.balance_of(&generator), 300);
+    assert_eq!(usdc_client.balance(&generator), 600);
+    assert_eq!(usdc_client.balance(&buyer), 400);
+    
+    // Either leg failing reverts both
+    assert!(client.try_swap(&generator, &buyer, &100u64, &usdc, &500i128).is_err());
+    assert!(client.try_swap(&generator, &buyer, &400u64, &usdc, &100i128).is_err());
+    assert_eq!(client.balance_of(&buyer), 200);
+    assert_eq!(usdc_client.balance(&buyer), 400);
+    
+    assert_eq!(
+        client.try_swap(&generator, &generator, &1u64, &usdc, &1i128),
+        Err(Ok(STRGRIDError::SelfTransfer.into()))
+    );
+}
+
+#[test]
+fn test_dutch_auction_price_decay_and_settle() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &10u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    // Price decays from 100 to 10 per kWh until the lot expires (10h)
+    let auction_id = client.start_auction(&generator, &token_id, &200u64, &usdc, &100i128, &10i128);
+    assert_eq!(client.balance_of(&generator), 300);
+    assert_eq!(client.auction_price(&auction_id), 100);
+    assert_eq!(client.treasury_balance(), 0);
+    
+    env.ledger().with_mut(|li| li.timestamp += 5 * 3600);
+    assert_eq!(client.auction_price(&auction_id), 55);
+    assert_eq!(client.bid(&buyer, &auction_id, &50u64), 2_750);
+    assert_eq!(client.balance_of(&buyer), 50);
+    assert_eq!(usdc_client.balance(&generator), 2_750);
+    assert_eq!(client.get_auction(&auction_id).remaining_kwh, 150);
+    
+    assert_eq!(
+        client.try_settle(&auction_id),
+        Err(Ok(STRGRIDError::AuctionRunning.into()))
+    );
+    
+    // After expiry no more bids; unsold kWh return to the seller
+    env.ledger().with_mut(|li| li.timestamp += 5 * 3600);
+    assert_eq!(
+        client.try_bid(&buyer, &auction_id, &1u64),
+        Err(Ok(STRGRIDError::AuctionClosed.into()))
+    );
+    client.settle(&auction_id);
+    assert_eq!(client.balance_of(&generator), 450);
+    assert!(client.get_auction(&auction_id).is_settled);
+    assert_eq!(
+        client.try_settle(&auction_id),
+        Err(Ok(STRGRIDError::AuctionClosed.into()))
+    );
+}
+
+#[test]
+fn test_forward_delivery_and_default() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&generator, &1_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    // Two obligations for the same window: one delivered, one defaulted
+    let delivered = client.sell_forward(&generator, &buyer, &200u64, &3600u64, &7200u64, &usdc, &2_000i128, &500i128);
+    let defaulted = client.sell_forward(&generator, &buyer, &100u64, &3600u64, &7200u64, &usdc, &1_000i128, &500i128);
+    assert_eq!(usdc_client.balance(&contract_id), 4_000);
+    assert_eq!(
+        client.try_sell_forward(&generator, &buyer, &100u64, &0u64, &7200u64, &usdc, &1_000i128, &0i128),
+        Err(Ok(STRGRIDError::OutsideDeliveryWindow.into()))
+    );
+    
+    // Energy minted before the window does not count towards delivery
+    client.mint_energy_tokens(&generator, &200i128, &24u64, &None);
+    assert_eq!(
+        client.try_deliver_forward(&delivered),
+        Err(Ok(STRGRIDError::OutsideDeliveryWindow.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    assert_eq!(
+        client.try_deliver_forward(&delivered),
+        Err(Ok(STRGRIDError::DeliveryNotMinted.into()))
+    );
+    client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    client.deliver_forward(&delivered);
+    assert_eq!(client.balance_of(&buyer), 200);
+    assert_eq!(usdc_client.balance(&generator), 2_500);
+    assert_eq!(client.get_forward(&delivered).status, ForwardStatus::Delivered);
+    
+    // The buyer collects price and penalty once the window closes undelivered
+    assert_eq!(
+        client.try_claim_forward_default(&defaulted),
+        Err(Ok(STRGRIDError::OutsideDeliveryWindow.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    assert_eq!(client.claim_forward_default(&defaulted), 1_500);
+    assert_eq!(usdc_client.balance(&buyer), 10_000 - 3_000 + 1_500);
+    assert_eq!(usdc_client.balance(&contract_id), 0);
+    assert_eq!(
+        client.try_deliver_forward(&defaulted),
+        Err(Ok(STRGRIDError::ForwardClosed.into()))
+    );
+}
+
+#[test]
+fn test_demand_response_rewards() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer_a = Address::generate(&env);
+    let consumer_b = Address::generate(&env);
+    
+    // Setup: the grid operator funds the incentive pool with STRGRID
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &operator, &300i128);
+    
+    assert_eq!(
+        client.try_post_dr_event(&operator, &3600u64, &7200u64, &2u64, &100u64),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.grant_role(&Role::GridOperator, &operator);
+    client.grant_role(&Role::Oracle, &oracle);
+    let event_id = client.post_dr_event(&operator, &3600u64, &7200u64, &2u64, &100u64);
+    assert_eq!(client.balance_of(&operator), 200);
+    
+    client.enroll_dr(&consumer_a, &event_id, &40u64);
+    client.enroll_dr(&consumer_b, &event_id, &30u64);
+    assert_eq!(
+        client.try_enroll_dr(&consumer_a, &event_id, &10u64),
+        Err(Ok(STRGRIDError::AlreadyEnrolled.into()))
+    );
+    assert_eq!(client.get_dr_event(&event_id).total_pledged_kwh, 70);
+    
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    assert_eq!(
+        client.try_enroll_dr(&Address::generate(&env), &event_id, &10u64),
+        Err(Ok(STRGRIDError::EnrollmentClosed.into()))
+    );
+    assert_eq!(
+        client.try_verify_dr(&oracle, &event_id, &consumer_a, &100u64, &50u64),
+        Err(Ok(STRGRIDError::DrEventRunning.into()))
+    );
+    
+    // Reductions count up to the pledge; rewards are reserved from the pool
+    env.ledger().with_mut(|li| li.timestamp = 7200);
+    assert_eq!(client.verify_dr(&oracle, &event_id, &consumer_a, &100u64, &50u64), 80);
+    assert_eq!(client.verify_dr(&oracle, &event_id, &consumer_b, &100u64, &90u64), 20);
+    assert_eq!(
+        client.try_verify_dr(&oracle, &event_id, &consumer_b, &100u64, &0u64),
+        Err(Ok(STRGRIDError::AlreadyVerified.into()))
+    );
+    assert_eq!(client.get_dr_enrollment(&event_id, &consumer_a).verified_kwh, Some(40));
+    
+    // Closing returns only the unreserved pool; verified rewards stay claimable
+    assert_eq!(client.close_dr_event(&event_id), 0);
+    assert_eq!(client.claim_dr_reward(&consumer_a, &event_id), 80);
+    assert_eq!(client.claim_dr_reward(&consumer_b, &event_id), 20);
+    assert_eq!(client.balance_of(&consumer_a), 80);
+    assert_eq!(client.balance_of(&contract_id), 0);
+    assert_eq!(
+        client.try_claim_dr_reward(&consumer_a, &event_id),
+        Err(Ok(STRGRIDError::NothingToClaim.into()))
+    );
+}
+
+#[test]
+fn test_net_metering_prosumer() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let prosumer = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&prosumer, &1000u64);
+    assert_eq!(
+        client.try_set_net_metering(&consumer, &true),
+        Err(Ok(STRGRIDError::GeneratorNotFound.into()))
+    );
+    
+    // Mints before enabling are not netted
+    let early = client.mint_energy_tokens(&prosumer, &100i128, &24u64, &None);
+    client.set_net_metering(&prosumer, &true);
+    assert!(client.is_net_metering(&prosumer));
+    
+    // Period 0: generates 300, consumes 100 -> net credit of 200
+    let lot = client.mint_energy_tokens(&prosumer, &300i128, &(24 * 60u64), &None);
+    client.burn_energy_tokens(&prosumer, &early, &100i128);
+    let position = client.net_position(&prosumer, &0u64);
+    assert_eq!(position.generated_kwh, 300);
+    assert_eq!(position.consumed_kwh, 100);
+    assert_eq!(position.net_kwh, 200);
+    
+    // Period 1: consumes without generating -> amount owed
+    env.ledger().with_mut(|li| li.timestamp = BILLING_PERIOD_SECONDS);
+    client.burn_energy_tokens(&prosumer, &lot, &250i128);
+    assert_eq!(client.net_position(&prosumer, &1u64).net_kwh, -250);
+    assert_eq!(client.net_position(&prosumer, &0u64).net_kwh, 200);
+    assert_eq!(client.net_position(&consumer, &1u64).net_kwh, 0);
+}
+
+#[test]
+fn test_consumer_registration_gates_burns() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let first = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    let second = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &200i128);
+    
+    // Without the compliance flag anyone may burn
+    assert!(client.is_eligible_consumer(&consumer));
+    client.burn_energy_tokens(&consumer, &first, &50i128);
+    
+    client.set_registration_required(&true);
+    assert!(!client.is_eligible_consumer(&consumer));
+    assert_eq!(
+        client.try_burn_energy_tokens(&consumer, &second, &50i128),
+        Err(Ok(STRGRIDError::ConsumerNotRegistered.into()))
+    );
+    assert_eq!(
+        client.preview_burn(&consumer, &second, &50i128).error,
+        Some(STRGRIDError::ConsumerNotRegistered as u32)
+    );
+    
+    let profile = ConsumerProfile {
+        class: ConsumerClass::Commercial,
+        zone: 3,
+        expected_monthly_kwh: 1_200,
+    };
+    client.register_consumer(&consumer, &profile);
+    assert_eq!(client.get_consumer(&consumer), profile);
+    client.burn_energy_tokens(&consumer, &second, &50i128);
+    assert_eq!(client.balance_of(&consumer), 100);
+}
+
+#[test]
+fn test_aggregator_pools_members() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let aggregator = Address::generate(&env);
+    let roof_a = Address::generate(&env);
+    let roof_b = Address::generate(&env);
+    
+    // Setup: two small rooftop generators
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&roof_a, &10u64);
+    client.register_generator(&roof_b, &30u64);
+    client.register_aggregator(&aggregator);
+    assert_eq!(
+        client.try_register_aggregator(&roof_a),
+        Err(Ok(STRGRIDExtError::AlreadyRegistered.into()))
+    );
+    
+    // Pooled capacity is the sum of the members' capacity
+    client.add_member(&aggregator, &roof_a);
+    client.add_member(&aggregator, &roof_b);
+    assert_eq!(client.get_generator(&aggregator).capacity_kw, 40);
+    assert_eq!(client.aggregator_of(&roof_a), Some(aggregator.clone()));
+    assert_eq!(
+        client.try_add_member(&aggregator, &roof_a),
+        Err(Ok(STRGRIDExtError::AlreadyAggregated.into()))
+    );
+    
+    // Members mint only through the aggregator
+    assert_eq!(
+        client.try_mint_energy_tokens(&roof_a, &5i128, &24u64, &None),
+        Err(Ok(STRGRIDExtError::AlreadyAggregated.into()))
+    );
+    client.mint_energy_tokens(&aggregator, &35i128, &24u64, &None);
+    assert_eq!(client.balance_of(&aggregator), 35);
+    
+    // Proceeds split 1:3 by contributed capacity
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&aggregator, &1_001i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    assert_eq!(client.distribute_proceeds(&aggregator, &usdc, &1_001i128), 1_000);
+    assert_eq!(usdc_client.balance(&roof_a), 250);
+    assert_eq!(usdc_client.balance(&roof_b), 750);
+    assert_eq!(usdc_client.balance(&aggregator), 1);
+    
+    // Leaving shrinks the pool and restores independent minting
+    client.remove_member(&aggregator, &roof_a);
+    assert_eq!(client.get_generator(&aggregator).capacity_kw, 30);
+    assert_eq!(client.aggregator_members(&aggregator).len(), 1);
+    client.mint_energy_tokens(&roof_a, &5i128, &24u64, &None);
+    assert_eq!(
+        client.try_remove_member(&aggregator, &roof_a),
+        Err(Ok(STRGRIDExtError::NotAggregatorMember.into()))
+    );
+}
+
+#[test]
+fn test_curtailment_orders_limit_minting() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    
+    assert_eq!(
+        client.try_issue_curtailment(&operator, &generator, &200u64, &3600u64, &7200u64),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.grant_role(&Role::GridOperator, &operator);
+    client.set_operation_priority(&Symbol::new(&env, "issue_curtailment"), &Priority::GridCritical);
+    let order_id = client.issue_curtailment(&operator, &generator, &200u64, &3600u64, &7200u64);
+    let emitted = env.events().all();
+    assert_eq!(client.curtailments_of(&generator).len(), 1);
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(
+        topics,
+        (Symbol::new(&env, "curtail"), order_id, generator.clone()).into_val(&env)
+    );
+    // The order carries its priority classification, like generator status changes
+    let (_, _, _, priority): (u64, u64, u64, Priority) = data.into_val(&env);
+    assert_eq!(priority, Priority::GridCritical);
+    
+    // Not yet in force
+    assert_eq!(client.effective_capacity(&generator), 1000);
+    client.mint_energy_tokens(&generator, &150i128, &24u64, &None);
+    
+    // During the window only 200 kW of production is allowed
+    env.ledger().with_mut(|li| li.timestamp = 3600);
+    assert_eq!(client.effective_capacity(&generator), 200);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &100i128, &24u64, &None),
+        Err(Ok(STRGRIDExtError::CapacityCurtailed.into()))
+    );
+    client.mint_energy_tokens(&generator, &50i128, &24u64, &None);
+    
+    // Cancelled orders no longer apply
+    client.cancel_curtailment(&operator, &order_id);
+    let priority: Priority = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(priority, Priority::Normal);
+    assert!(client.get_curtailment(&order_id).is_cancelled);
+    client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    assert_eq!(client.curtailments_of(&generator).len(), 0);
+}
+
+#[test]
+fn test_transmission_loss_on_cross_zone_transfer() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let local = Address::generate(&env);
+    let remote = Address::generate(&env);
+    let unknown = Address::generate(&env);
+    
+    // Setup: generator in zone 1, consumers in zones 1 and 2
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.set_generator_profile(&generator, &SourceType::Solar, &1u32);
+    client.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    client.register_consumer(&local, &ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: 1,
+        expected_monthly_kwh: 300,
+    });
+    client.register_consumer(&remote, &ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: 2,
+        expected_monthly_kwh: 300,
+    });
+    
+    assert_eq!(
+        client.try_set_loss_factor(&1u32, &2u32, &10_001u32),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    client.set_loss_factor(&1u32, &2u32, &500u32);
+    assert_eq!(client.get_loss_factor(&1u32, &2u32), 500);
+    assert_eq!(client.get_loss_factor(&2u32, &1u32), 0);
+    
+    // 5% of a cross-zone transfer is burned in transit
+    client.transfer(&generator, &remote, &200i128);
+    assert_eq!(client.balance_of(&remote), 190);
+    assert_eq!(client.balance_of(&generator), 800);
+    assert_eq!(client.total_supply(), 990);
+    
+    // Same zone and addresses without a zone are not charged
+    client.transfer(&generator, &local, &100i128);
+    assert_eq!(client.balance_of(&local), 100);
+    client.transfer(&generator, &unknown, &100i128);
+    assert_eq!(client.balance_of(&unknown), 100);
+    assert_eq!(client.total_supply(), 990);
+}
+
+#[test]
+fn test_congestion_state_set_by_grid_operator() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.grant_role(&Role::GridOperator, &operator);
+    
+    assert_eq!(
+        client.try_set_congestion(&stranger, &1u32, &15_000u32, &1_000u64),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    // Multipliers below 1x or above the cap are rejected
+    assert_eq!(
+        client.try_set_congestion(&operator, &1u32, &9_000u32, &1_000u64),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.try_set_congestion(&operator, &1u32, &(MAX_CONGESTION_BPS + 1), &1_000u64),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    
+    client.set_congestion(&operator, &1u32, &20_000u32, &1_000u64);
+    assert_eq!(client.congestion_multiplier(&1u32), 20_000);
+    
+    client.clear_congestion(&operator, &1u32);
+    assert_eq!(client.congestion_multiplier(&1u32), 10_000);
+    assert_eq!(
+        client.try_clear_congestion(&operator, &1u32),
+        Err(Ok(STRGRIDError::NoOpUpdate.into()))
+    );
+}
+
+#[test]
+fn test_imbalance_settlement_at_pld() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.grant_role(&Role::Oracle, &oracle);
+    client.register_generator(&generator, &1000u64);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&consumer, &1_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&contract_id, &1_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    // Schedules are declared ahead of the interval
+    client.schedule_energy(&generator, &1u64, &100i128);
+    client.schedule_energy(&consumer, &1u64, &-50i128);
+    env.ledger().with_mut(|li| li.timestamp = SETTLEMENT_INTERVAL_SECONDS);
+    assert_eq!(
+        client.try_schedule_energy(&generator, &1u64, &90i128),
+        Err(Ok(STRGRIDExtError::ScheduleClosed.into()))
+    );
+    
+    // Generator over-delivers by 20 kWh, consumer over-consumes by 10 kWh
+    let token_id = client.mint_energy_tokens(&generator, &120i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &60i128);
+    client.burn_energy_tokens(&consumer, &token_id, &60i128);
+    assert_eq!(
+        client.try_publish_pld(&oracle, &1u64, &usdc, &5i128),
+        Err(Ok(STRGRIDExtError::IntervalOpen.into()))
+    );
+    
+    env.ledger().with_mut(|li| li.timestamp = 2 * SETTLEMENT_INTERVAL_SECONDS);
+    assert_eq!(
+        client.try_settle_imbalance(&generator, &1u64),
+        Err(Ok(STRGRIDExtError::PldNotPublished.into()))
+    );
+    client.publish_pld(&oracle, &1u64, &usdc, &5i128);
+    assert_eq!(
+        client.try_publish_pld(&oracle, &1u64, &usdc, &6i128),
+        Err(Ok(STRGRIDExtError::PldAlreadyPublished.into()))
+    );
+    
+    let position = client.get_imbalance(&consumer, &1u64);
+    assert_eq!(position.scheduled_kwh, -50);
+    assert_eq!(position.metered_kwh, -60);
+    assert_eq!(position.imbalance_kwh, -10);
+    assert_eq!(position.amount, -50);
+    
+    assert_eq!(client.settle_imbalance(&generator, &1u64), 100);
+    assert_eq!(client.settle_imbalance(&consumer, &1u64), -50);
+    assert_eq!(usdc_client.balance(&generator), 100);
+    assert_eq!(usdc_client.balance(&consumer), 950);
+    assert_eq!(usdc_client.balance(&contract_id), 950);
+    assert!(client.get_imbalance(&generator, &1u64).settled);
+    assert_eq!(
+        client.try_settle_imbalance(&generator, &1u64),
+        Err(Ok(STRGRIDExtError::ImbalanceSettled.into()))
+    );
+}
+
+#[test]
+fn test_capacity_reservation_delivery_and_refund() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let factory = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&factory, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    // Factory reserves 400 kW between t = 100 and t = 200 for a 1_000 fee
+    let reservation_id = client.reserve_capacity(
+        &factory, &generator, &400u64, &100u64, &200u64, &usdc, &1_000i128
+    );
+    assert_eq!(usdc_client.balance(&contract_id), 1_000);
+    assert_eq!(
+        client.try_reserve_capacity(&factory, &generator, &700u64, &150u64, &250u64, &usdc, &0i128),
+        Err(Ok(STRGRIDError::InsufficientCapacity.into()))
+    );
+    
+    // During the window open-market minting cannot touch the reserved capacity
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.reserved_capacity(&generator), 400);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &700i128, &24u64, &None),
+        Err(Ok(STRGRIDExtError::CapacityReserved.into()))
+    );
+    client.mint_energy_tokens(&generator, &600i128, &24u64, &None);
+    
+    // Reserved energy is minted and handed straight to the consumer
+    client.deliver_reservation(&reservation_id, &300u64, &24u64);
+    assert_eq!(client.balance_of(&factory), 300);
+    assert_eq!(client.reserved_capacity(&generator), 100);
+    assert_eq!(
+        client.try_settle_reservation(&reservation_id),
+        Err(Ok(STRGRIDError::OutsideDeliveryWindow.into()))
+    );
+    
+    // Undelivered 25% of the reservation is refunded
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.reserved_capacity(&generator), 0);
+    assert_eq!(
+        client.try_deliver_reservation(&reservation_id, &100u64, &24u64),
+        Err(Ok(STRGRIDError::OutsideDeliveryWindow.into()))
+    );
+    assert_eq!(client.settle_reservation(&reservation_id), 250);
+    assert_eq!(usdc_client.balance(&generator), 750);
+    assert_eq!(usdc_client.balance(&factory), 9_250);
+    assert!(client.get_reservation(&reservation_id).is_settled);
+    assert_eq!(
+        client.try_settle_reservation(&reservation_id),
+        Err(Ok(STRGRIDExtError::ReservationSettled.into()))
+    );
+}
+
+#[test]
+fn test_balance_and_supply_checkpoints() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let holder = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    
+    // Two transfers in the same ledger collapse into one checkpoint
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    client.transfer(&generator, &holder, &100i128);
+    client.transfer(&generator, &holder, &50i128);
+    
+    env.ledger().with_mut(|li| li.sequence_number = 30);
+    let token_id = client.mint_energy_tokens(&generator, &200i128, &24u64, &None);
+    client.transfer(&generator, &holder, &100i128);
+    client.burn_energy_tokens(&holder, &token_id, &80i128);
+    
+    assert_eq!(client.balance_at(&holder, &9u32), 0);
+    assert_eq!(client.balance_at(&holder, &19u32), 0);
+    assert_eq!(client.balance_at(&holder, &20u32), 150);
+    assert_eq!(client.balance_at(&holder, &29u32), 150);
+    assert_eq!(client.balance_at(&holder, &30u32), 170);
+    assert_eq!(client.balance_at(&generator, &10u32), 500);
+    assert_eq!(client.balance_at(&generator, &25u32), 350);
+    assert_eq!(client.balance_at(&generator, &1_000u32), client.balance_of(&generator));
+    
+    assert_eq!(client.supply_at(&9u32), 0);
+    assert_eq!(client.supply_at(&10u32), 500);
+    assert_eq!(client.supply_at(&29u32), 500);
+    assert_eq!(client.supply_at(&30u32), 620);
+}
+
+#[test]
+fn test_admin_actions_recorded_in_audit_log() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.grant_role(&Role::GridOperator, &operator);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.issue_curtailment(&operator, &generator, &500u64, &2_000u64, &3_000u64);
+    
+    // Ordinary holder actions are not audited
+    client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    assert_eq!(client.audit_log_len(), 4);
+    
+    let log = client.get_audit_log(&0u64, &10u32);
+    assert_eq!(log.len(), 4);
+    let entry = log.get(1).unwrap();
+    assert_eq!(entry.id, 1);
+    assert_eq!(entry.actor, admin);
+    assert_eq!(entry.action, Symbol::new(&env, "register_generator"));
+    assert_eq!(entry.timestamp, 1_000);
+    let audited_generator: Address = entry.params.get_unchecked(0).into_val(&env);
+    let audited_capacity: u64 = entry.params.get_unchecked(1).into_val(&env);
+    assert_eq!((audited_generator, audited_capacity), (generator.clone(), 1000u64));
+    
+    let curtailment = log.get(3).unwrap();
+    assert_eq!(curtailment.actor, operator);
+    assert_eq!(curtailment.action, Symbol::new(&env, "issue_curtailment"));
+    assert_eq!(curtailment.timestamp, 2_000);
+    
+    // Pagination
+    let page = client.get_audit_log(&2u64, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().action, Symbol::new(&env, "grant_role"));
+    assert_eq!(client.get_audit_log(&4u64, &10u32).len(), 0);
+}
+
+#[test]
+fn test_bootstrapping_incentives_decay_and_claims() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let funder = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&funder, &2000u64);
+    client.register_generator(&early, &1000u64);
+    client.register_generator(&late, &1000u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    client.mint_energy_tokens(&funder, &1500i128, &24u64, &None);
+    
+    // 3 epochs of 1000s, halving each epoch: 1000, 500, 250
+    client.configure_incentives(&1_000u64, &1_000u64, &3u32, &1_000u64, &5_000u32);
+    assert_eq!(client.epoch_emission(&1u32), 500);
+    assert_eq!(client.epoch_emission(&2u32), 250);
+    assert_eq!(client.epoch_emission(&3u32), 0);
+    client.fund_incentives(&funder, &1500i128);
+    assert_eq!(client.incentive_pool(), 1500);
+    client.set_dispute_window(&100u64);
+    
+    // Epoch 0: a voided mint does not count as verified production
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.mint_energy_tokens(&early, &300i128, &24u64, &None);
+    client.mint_energy_tokens(&late, &100i128, &24u64, &None);
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    let fraud_id = client.mint_energy_tokens(&early, &100i128, &24u64, &None);
+    client.challenge_mint(&auditor, &fraud_id, &BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(client.incentive_production(&early, &0u32), 300);
+    
+    let result = client.try_configure_incentives(&5_000u64, &1_000u64, &3u32, &1_000u64, &0u32);
+    assert_eq!(result, Err(Ok(STRGRIDExtError::IncentivesStarted.into())));
+    
+    // Epoch 1
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    client.mint_energy_tokens(&late, &200i128, &24u64, &None);
+    
+    // Epoch 0 only becomes final once its dispute window has passed
+    env.ledger().with_mut(|li| li.timestamp = 2_050);
+    let result = client.try_claim_incentives(&early, &0u32);
+    assert_eq!(result, Err(Ok(STRGRIDExtError::EpochNotFinal.into())));
+    
+    env.ledger().with_mut(|li| li.timestamp = 2_100);
+    assert_eq!(client.pending_incentives(&early, &0u32), 750);
+    assert_eq!(client.claim_incentives(&early, &0u32), 750);
+    assert_eq!(client.claim_incentives(&late, &0u32), 250);
+    assert_eq!(client.balance_of(&early), 1050);
+    assert_eq!(client.pending_incentives(&early, &0u32), 0);
+    let result = client.try_claim_incentives(&early, &0u32);
+    assert_eq!(result, Err(Ok(STRGRIDError::NothingToClaim.into())));
+    
+    env.ledger().with_mut(|li| li.timestamp = 3_100);
+    assert_eq!(client.claim_incentives(&late, &1u32), 500);
+    assert_eq!(client.incentive_pool(), 0);
+    let result = client.try_claim_incentives(&early, &1u32);
+    assert_eq!(result, Err(Ok(STRGRIDError::NothingToClaim.into())));
+}
+
+#[test]
+fn test_distribute_from_pool() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let pilot1 = Address::generate(&env);
+    let pilot2 = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&treasury, &1000u64);
+    client.mint_energy_tokens(&treasury, &500i128, &24u64, &None);
+    client.fund_distribution(&treasury, &300i128);
+    assert_eq!(client.distribution_pool(), 300);
+    assert_eq!(client.balance_of(&treasury), 200);
+    
+    client.distribute(&vec![
+        &env,
+        (pilot1.clone(), 100i128),
+        (pilot2.clone(), 150i128),
+    ]);
+    let emitted = env.events().all();
+    assert_eq!(client.balance_of(&pilot1), 100);
+    assert_eq!(client.balance_of(&pilot2), 150);
+    assert_eq!(client.distribution_pool(), 50);
+    
+    // One event per recipient
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(topics, (events::DISTRIBUTED, pilot2.clone()).into_val(&env));
+    let amount: i128 = data.into_val(&env);
+    assert_eq!(amount, 150);
+    
+    // The whole batch fails atomically if the pool cannot cover it
+    let result = client.try_distribute(&vec![
+        &env,
+        (pilot1.clone(), 40i128),
+        (pilot2.clone(), 20i128),
+    ]);
+    assert_eq!(result, Err(Ok(STRGRIDError::InsufficientBalance.into())));
+    assert_eq!(client.balance_of(&pilot1), 100);
+    assert_eq!(client.distribution_pool(), 50);
+}
+
+#[test]
+fn test_burn_energy_tokens_from_operator() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    let retailer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    let other_id = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    client.transfer(&generator, &consumer, &400i128);
+    
+    // Without an allowance the retailer cannot settle consumption
+    assert_eq!(
+        client.try_burn_energy_tokens_from(&retailer, &consumer, &token_id, &150i128),
+        Err(Ok(STRGRIDError::InsufficientAllowance.into()))
+    );
+    
+    client.approve(&consumer, &retailer, &200i128);
+    client.burn_energy_tokens_from(&retailer, &consumer, &token_id, &150i128);
+    assert!(client.get_energy_token(&token_id).is_consumed);
+    assert_eq!(client.balance_of(&consumer), 250);
+    assert_eq!(client.total_supply(), 450);
+    assert_eq!(client.allowance(&consumer, &retailer), 50);
+    
+    assert_eq!(
+        client.try_burn_energy_tokens_from(&retailer, &consumer, &other_id, &60i128),
+        Err(Ok(STRGRIDError::InsufficientAllowance.into()))
+    );
+}
+
+#[test]
+fn test_end_of_day_netting_settlement() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let trader_a = Address::generate(&env);
+    let trader_b = Address::generate(&env);
+    let trader_c = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&trader_a, &1000u64);
+    client.mint_energy_tokens(&trader_a, &100i128, &48u64, &None);
+    client.transfer(&trader_a, &trader_b, &50i128);
+    
+    // Intraday trades: only the net positions ever move
+    let period = client.current_netting_period();
+    client.record_obligation(&trader_a, &trader_b, &300u64);
+    client.record_obligation(&trader_b, &trader_c, &280u64);
+    client.record_obligation(&trader_c, &trader_a, &260u64);
+    client.record_obligation(&trader_b, &trader_a, &30u64);
+    assert_eq!(client.netting_position(&trader_a, &period), -10);
+    assert_eq!(client.netting_position(&trader_b, &period), -10);
+    assert_eq!(client.netting_position(&trader_c, &period), 20);
+    
+    let result = client.try_settle_netting(&period);
+    assert_eq!(result, Err(Ok(STRGRIDExtError::IntervalOpen.into())));
+    
+    env.ledger().with_mut(|li| li.timestamp += NETTING_PERIOD_SECONDS);
+    assert_eq!(client.settle_netting(&period), 20);
+    let emitted = env.events().all();
+    assert_eq!(client.balance_of(&trader_a), 40);
+    assert_eq!(client.balance_of(&trader_b), 40);
+    assert_eq!(client.balance_of(&trader_c), 20);
+    assert_eq!(client.balance_of(&contract_id), 0);
+    
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(topics, (events::NETTING_SETTLED, period).into_val(&env));
+    let (participants, gross, netted): (u32, u64, u64) = data.into_val(&env);
+    assert_eq!((participants, gross, netted), (3, 870, 20));
+    
+    let result = client.try_settle_netting(&period);
+    assert_eq!(result, Err(Ok(STRGRIDExtError::NettingSettled.into())));
+}
+
+#[test]
+fn test_supply_stats() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    client.set_dispute_window(&100u64);
+    
+    let consumed_id = client.mint_energy_tokens(&generator, &300i128, &24u64, &None);
+    client.mint_energy_tokens(&generator, &200i128, &1u64, &None);
+    let voided_id = client.mint_energy_tokens(&generator, &100i128, &48u64, &None);
+    client.mint_energy_tokens(&generator, &50i128, &48u64, &None);
+    client.challenge_mint(&auditor, &voided_id, &BytesN::from_array(&env, &[1u8; 32]));
+    
+    env.ledger().with_mut(|li| li.timestamp += 2 * 3600);
+    client.transfer(&generator, &consumer, &250i128);
+    client.burn_energy_tokens(&consumer, &consumed_id, &120i128);
+    client.burn(&generator, &30i128);
+    
+    let stats = client.get_stats();
+    assert_eq!(
+        stats,
+        SupplyStats {
+            total_supply: 400,
+            total_minted: 550,
+            total_burned: 150,
+            expired_unswept: 1,
+            active_tokens: 1,
+        }
+    );
+    assert_eq!(stats.total_supply, client.total_supply());
+}
+
+#[test]
+fn test_production_history_and_capacity_factor() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &2000u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    client.set_dispute_window(&100u64);
+    
+    // 2024-01-30 (day 19752) and the following two days, crossing into February
+    let day = 19_752u64;
+    env.ledger().with_mut(|li| li.timestamp = day * 86_400);
+    client.mint_energy_tokens(&generator, &600i128, &24u64, &None);
+    let voided_id = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    client.challenge_mint(&auditor, &voided_id, &BytesN::from_array(&env, &[1u8; 32]));
+    env.ledger().with_mut(|li| li.timestamp += 2 * 86_400);
+    client.mint_energy_tokens(&generator, &1_200i128, &24u64, &None);
+    
+    let history = client.production_history(&generator, &day, &(day + 2));
+    assert_eq!(
+        history,
+        vec![
+            &env,
+            DailyProduction { day, kwh: 600 },
+            DailyProduction { day: day + 2, kwh: 1_200 },
+        ]
+    );
+    assert_eq!(client.monthly_production(&generator, &2024u32, &1u32), 600);
+    assert_eq!(client.monthly_production(&generator, &2024u32, &2u32), 1_200);
+    
+    // 1,800 kWh over 3 days of a 2,000 kW plant = 1.25%
+    assert_eq!(client.capacity_factor(&generator, &day, &(day + 2)), 125);
+    
+    let result = client.try_production_history(&generator, &day, &(day + MAX_HISTORY_DAYS));
+    assert_eq!(result, Err(Ok(STRGRIDError::InvalidAmount.into())));
+}
+
+#[test]
+fn test_expired_tokens_ordered_by_expiry() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    
+    // Minted out of expiry order
+    client.mint_energy_tokens(&generator, &100i128, &48u64, &None);
+    let first_id = client.mint_energy_tokens(&generator, &100i128, &1u64, &None);
+    let consumed_id = client.mint_energy_tokens(&generator, &100i128, &2u64, &None);
+    let second_id = client.mint_energy_tokens(&generator, &100i128, &3u64, &None);
+    client.burn_energy_tokens(&generator, &consumed_id, &50i128);
+    assert_eq!(client.expired_tokens(&0u32, &10u32).len(), 0);
+    
+    env.ledger().with_mut(|li| li.timestamp += 4 * 3600);
+    let expired = client.expired_tokens(&0u32, &10u32);
+    assert_eq!(expired.len(), 2);
+    assert_eq!(expired.get(0).unwrap().id, first_id);
+    assert_eq!(expired.get(1).unwrap().id, second_id);
+    let page = client.expired_tokens(&1u32, &1u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, second_id);
+    
+    // Compacted lots are swept out of the index
+    client.set_archive_retention(&0u64);
+    client.compact_lots(&vec![&env, first_id]);
+    let expired = client.expired_tokens(&0u32, &10u32);
+    assert_eq!(expired.len(), 1);
+    assert_eq!(expired.get(0).unwrap().id, second_id);
+    assert_eq!(client.get_stats().active_tokens, 1);
+}
+
+#[test]
+fn test_holder_registry() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    assert_eq!(client.holder_count(), 0);
+    
+    client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    client.transfer(&generator, &user1, &100i128);
+    client.transfer(&generator, &user2, &100i128);
+    assert_eq!(client.holder_count(), 3);
+    assert_eq!(client.holders(&0u32, &10u32), vec![&env, generator.clone(), user1.clone(), user2.clone()]);
+    assert_eq!(client.holders(&1u32, &1u32), vec![&env, user1.clone()]);
+    assert_eq!(client.holders(&5u32, &10u32).len(), 0);
+    
+    // Emptied balances leave the registry; the last holder takes the freed slot
+    client.transfer(&user1, &user2, &100i128);
+    assert_eq!(client.holder_count(), 2);
+    assert_eq!(client.holders(&0u32, &10u32), vec![&env, generator.clone(), user2.clone()]);
+    client.burn(&user2, &200i128);
+    assert_eq!(client.holders(&0u32, &10u32), vec![&env, generator.clone()]);
+    
+    client.transfer(&generator, &user1, &50i128);
+    assert_eq!(client.holders(&0u32, &10u32), vec![&env, generator.clone(), user1.clone()]);
+}
+
+#[test]
+fn test_supply_by_source_and_zone() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let solar = Address::generate(&env);
+    let wind = Address::generate(&env);
+    
+    // Setup
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&solar, &1000u64);
+    client.register_generator(&wind, &1000u64);
+    client.set_generator_profile(&solar, &SourceType::Solar, &1u32);
+    client.set_generator_profile(&wind, &SourceType::Wind, &2u32);
+    
+    let consumed_id = client.mint_energy_tokens(&solar, &300i128, &24u64, &None);
+    client.mint_energy_tokens(&solar, &200i128, &1u64, &None);
+    client.mint_energy_tokens(&wind, &400i128, &24u64, &None);
+    assert_eq!(client.supply_by_source(&SourceType::Solar), 500);
+    assert_eq!(client.supply_by_source(&SourceType::Wind), 400);
+    assert_eq!(client.supply_by_zone(&1u32), 500);
+    assert_eq!(client.supply_by_zone(&2u32), 400);
+    
+    // Consuming a lot removes it from the breakdown
+    client.burn_energy_tokens(&solar, &consumed_id, &300i128);
+    assert_eq!(client.supply_by_source(&SourceType::Solar), 200);
+    
+    // Expired lots stay counted until a keeper sweeps them
+    env.ledger().with_mut(|li| li.timestamp += 2 * 3600);
+    assert_eq!(client.get_stats().expired_unswept, 1);
+    assert_eq!(client.sweep_expired(&10u32), 1);
+    assert_eq!(client.supply_by_source(&SourceType::Solar), 0);
+    assert_eq!(client.supply_by_zone(&1u32), 0);
+    assert_eq!(client.supply_by_zone(&2u32), 400);
+    assert_eq!(client.get_stats().expired_unswept, 0);
+    assert_eq!(client.expired_tokens(&0u32, &10u32).len(), 0);
+    assert_eq!(client.sweep_expired(&10u32), 0);
+}
+
+#[test]
+fn test_indexer_events() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let consumer = Address::generate(&env);
+    
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (events::GENERATOR_REGISTERED, generator.clone()).into_val(&env));
+    let capacity: u64 = data.into_val(&env);
+    assert_eq!(capacity, 1000);
+    
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &24u64, &None);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (events::MINT, generator.clone(), token_id).into_val(&env));
+    let (amount, expiry): (u64, u64) = data.into_val(&env);
+    assert_eq!(amount, 500);
+    assert_eq!(expiry, client.get_energy_token(&token_id).expiry_timestamp);
+    
+    client.transfer(&generator, &consumer, &200i128);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(topics, (events::TRANSFER, generator.clone(), consumer.clone()).into_val(&env));
+    let amount: i128 = data.into_val(&env);
+    assert_eq!(amount, 200);
+    
+    // Lot consumption is published right before the matching REC
+    client.burn_energy_tokens(&consumer, &token_id, &150i128);
+    let all = env.events().all();
+    let (_, topics, data) = all.get(all.len() - 2).unwrap();
+    assert_eq!(topics, (events::CONSUME, consumer.clone(), token_id).into_val(&env));
+    let amount: u64 = data.into_val(&env);
+    assert_eq!(amount, 150);
+}
+
+#[test]
+fn test_fixture_builder() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env)
+        .generators(2, 1000)
+        .users(3, 100)
+        .build();
+    
+    assert_eq!(fixture.generators.len(), 2);
+    assert_eq!(fixture.users.len(), 3);
+    // Users are funded round-robin, each generator minting for the users it serves
+    for user in fixture.users.iter() {
+        assert_eq!(fixture.client.balance_of(&user), 100);
+    }
+    assert_eq!(fixture.client.get_generator(&fixture.generators.get_unchecked(0)).current_production, 200);
+    assert_eq!(fixture.client.get_generator(&fixture.generators.get_unchecked(1)).current_production, 100);
+    assert_eq!(fixture.client.total_supply(), 300);
+    assert_eq!(fixture.client.holder_count(), 3);
+    crate::testutils::assert_invariants(&fixture.client);
+    
+    let user = fixture.users.get_unchecked(0);
+    fixture.client.burn(&user, &40i128);
+    crate::testutils::assert_invariants(&fixture.client);
+}
+
+#[test]
+#[should_panic(expected = "total supply differs from the sum of balances")]
+fn test_assert_invariants_detects_drift() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 50).build();
+    
+    // Corrupt a balance behind the contract's back
+    let user = fixture.users.get_unchecked(0);
+    env.as_contract(&fixture.contract_id, || {
+        env.storage().persistent().set(&(BALANCE, user), &51u64);
+    });
+    crate::testutils::assert_invariants(&fixture.client);
+}
+
+#[test]
+fn test_mint_oracle_gate() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).mock_oracle().build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let oracle = strgrid_mock_oracle::MockOracleContractClient::new(&env, fixture.oracle.as_ref().unwrap());
+    assert_eq!(client.get_mint_oracle(), fixture.oracle);
+    
+    // With an oracle configured, mints need an attested reading
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &100i128, &24u64, &None),
+        Err(Ok(STRGRIDExtError::OracleProofRequired.into()))
+    );
+    let proof = oracle.sign_reading(&generator, &100u64);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &150i128, &24u64, &Some(proof.clone())),
+        Err(Ok(STRGRIDExtError::InvalidOracleProof.into()))
+    );
+    let token_id = client.mint_energy_tokens(&generator, &100i128, &24u64, &Some(proof.clone()));
+    assert_eq!(client.get_energy_token(&token_id).amount_kwh, 100);
+    
+    // A reading can back a single mint
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &100i128, &24u64, &Some(proof)),
+        Err(Ok(STRGRIDExtError::OracleProofUsed.into()))
+    );
+    fixture.mint(&generator, 200);
+    assert_eq!(client.balance_of(&generator), 300);
+    crate::testutils::assert_invariants(client);
+    
+    // Removing the oracle makes the proof optional again
+    client.set_mint_oracle(&None);
+    client.mint_energy_tokens(&generator, &50i128, &24u64, &None);
+    assert_eq!(client.get_mint_oracle(), None);
+}
+
+#[test]
+fn test_classic_asset_wrap_unwrap() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(2, 100).build();
+    let client = &fixture.client;
+    let alice = fixture.users.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(1);
+    
+    assert_eq!(
+        client.try_unwrap(&alice, &10i128),
+        Err(Ok(STRGRIDExtError::ClassicAssetNotConfigured.into()))
+    );
+    
+    // The contract must administer the SAC before it can back the classic asset
+    let sac = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    assert_eq!(
+        client.try_set_classic_asset(&sac),
+        Err(Ok(STRGRIDExtError::ClassicAssetNotAdmin.into()))
+    );
+    token::StellarAssetClient::new(&env, &sac).set_admin(&fixture.contract_id);
+    client.set_classic_asset(&sac);
+    assert_eq!(client.get_classic_asset(), Some(sac.clone()));
+    let classic = token::Client::new(&env, &sac);
+    
+    // Unwrapping locks kWh in custody and issues the classic asset 1:1
+    client.unwrap(&alice, &40i128);
+    assert_eq!(client.balance_of(&alice), 60);
+    assert_eq!(classic.balance(&alice), 40);
+    assert_eq!(client.classic_locked(), 40);
+    assert_eq!(client.balance_of(&fixture.contract_id), 40);
+    assert_eq!(
+        client.try_unwrap(&alice, &61i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    
+    // The classic asset moves freely and any holder can redeem it
+    classic.transfer(&alice, &bob, &15i128);
+    client.wrap(&bob, &15i128);
+    assert_eq!(classic.balance(&bob), 0);
+    assert_eq!(client.balance_of(&bob), 115);
+    assert_eq!(client.classic_locked(), 25);
+    assert_eq!(
+        client.try_wrap(&alice, &26i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    
+    // Custody never changes the supply
+    assert_eq!(client.total_supply(), 200);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_interface_client_matches_contract() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(2, 100).build();
+    let generator = fixture.generators.get_unchecked(0);
+    let alice = fixture.users.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(1);
+    
+    // Callers that only depend on strgrid-interface see the same ABI as the implementation
+    let energy = strgrid_interface::STRGRIDClient::new(&env, &fixture.contract_id);
+    assert_eq!(energy.get_metadata().decimals, 7);
+    assert_eq!(energy.total_supply(), 200);
+    energy.approve(&alice, &bob, &30i128);
+    assert_eq!(energy.allowance(&alice, &bob), 30);
+    energy.transfer_from(&bob, &alice, &bob, &10i128);
+    energy.transfer(&bob, &alice, &5i128);
+    energy.burn(&alice, &5i128);
+    energy.burn_from(&bob, &alice, &10i128);
+    assert_eq!(energy.balance_of(&alice), 80);
+    
+    let token_id = energy.mint_energy_tokens(&generator, &50i128, &24u64, &None);
+    let lot: strgrid_interface::EnergyToken = energy.get_energy_token(&token_id);
+    assert_eq!(lot, fixture.client.get_energy_token(&token_id));
+    assert_eq!(energy.generator_of(&token_id), generator);
+    assert_eq!(energy.burn_energy_tokens(&generator, &token_id, &20i128), 1);
+    assert_eq!(energy.get_generator(&generator).capacity_kw, energy.capacity_of(&generator));
+    assert!(energy.is_eligible_consumer(&alice));
+    assert_eq!(energy.zone_of(&alice), None);
+    assert_eq!(energy.congestion_multiplier(&0u32), 10_000);
+    
+    energy.set_dispute_window(&3_600u64);
+    assert_eq!(fixture.client.get_dispute_window(), 3_600);
+    energy.set_emission_factor(&SourceType::Solar, &45u64);
+    energy.set_mint_caps(&strgrid_interface::MintCaps {
+        max_total_supply: Some(10_000),
+        max_daily_per_generator: None,
+        min_mint: None,
+        max_mint: None,
+    });
+    energy.set_transfer_fee(&0u32, &fixture.admin);
+}
+
+mod blocklist_hook {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+    
+    /// Minimal policy contract: rejects blocked accounts on either side
+    #[contract]
+    pub struct BlocklistHook;
+    
+    #[contractimpl]
+    impl BlocklistHook {
+        pub fn block(env: Env, account: Address) {
+            env.storage().instance().set(&account, &true);
+        }
+        
+        pub fn check_transfer(env: Env, from: Address, to: Address, _amount: i128) -> bool {
+            !env.storage().instance().has(&from) && !env.storage().instance().has(&to)
+        }
+    }
+}
+
+#[test]
+fn test_compliance_hook() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(3, 100).build();
+    let client = &fixture.client;
+    let alice = fixture.users.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(1);
+    let carol = fixture.users.get_unchecked(2);
+    
+    let hook_id = env.register(blocklist_hook::BlocklistHook, ());
+    let hook = blocklist_hook::BlocklistHookClient::new(&env, &hook_id);
+    client.set_compliance_hook(&Some(hook_id.clone()));
+    assert_eq!(client.get_compliance_hook(), Some(hook_id));
+    
+    // Allowed transfers go through, fee custody included
+    client.set_transfer_fee(&100u32, &fixture.admin);
+    client.transfer(&alice, &bob, &50i128);
+    assert_eq!(client.balance_of(&bob), 150);
+    
+    // The policy is consulted on every movement, whatever the entry point
+    hook.block(&bob);
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10i128),
+        Err(Ok(STRGRIDExtError::TransferRejected.into()))
+    );
+    assert_eq!(
+        client.try_transfer(&bob, &carol, &10i128),
+        Err(Ok(STRGRIDExtError::TransferRejected.into()))
+    );
+    client.approve(&alice, &carol, &20i128);
+    assert_eq!(
+        client.try_transfer_from(&carol, &alice, &bob, &10i128),
+        Err(Ok(STRGRIDExtError::TransferRejected.into()))
+    );
+    client.transfer_from(&carol, &alice, &carol, &10i128);
+    
+    // Removing the hook lifts the policy
+    client.set_compliance_hook(&None);
+    client.transfer(&alice, &bob, &10i128);
+    assert_eq!(client.get_compliance_hook(), None);
+    crate::testutils::assert_invariants(client);
+}
+
+mod vault_receiver {
+    use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, Symbol};
+    
+    /// Receiver that records the last notification and refuses `reject` payloads
+    #[contract]
+    pub struct VaultReceiver;
+    
+    #[contractimpl]
+    impl VaultReceiver {
+        pub fn on_token_received(env: Env, token: Address, from: Address, amount: i128, data: Bytes) -> bool {
+            if data == Bytes::from_slice(&env, b"reject") {
+                return false;
+            }
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "last"), &(token, from, amount, data));
+            true
+        }
+        
+        pub fn last(env: Env) -> Option<(Address, Address, i128, Bytes)> {
+            env.storage().instance().get(&Symbol::new(&env, "last"))
+        }
+    }
+}
+
+#[test]
+fn test_transfer_and_call() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 100).build();
+    let client = &fixture.client;
+    let alice = fixture.users.get_unchecked(0);
+    
+    let vault_id = env.register(vault_receiver::VaultReceiver, ());
+    let vault = vault_receiver::VaultReceiverClient::new(&env, &vault_id);
+    
+    // The receiver is told the credited amount, net of the transfer fee
+    client.set_transfer_fee(&1_000u32, &fixture.admin);
+    let data = Bytes::from_slice(&env, b"deposit:7");
+    client.transfer_and_call(&alice, &vault_id, &50i128, &data);
+    assert_eq!(client.balance_of(&vault_id), 45);
+    assert_eq!(vault.last(), Some((fixture.contract_id.clone(), alice.clone(), 45i128, data)));
+    
+    // A refusal reverts the whole transfer
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &vault_id, &10i128, &Bytes::from_slice(&env, b"reject")),
+        Err(Ok(STRGRIDExtError::ReceiverRejected.into()))
+    );
+    assert_eq!(client.balance_of(&alice), 50);
+    assert_eq!(client.balance_of(&vault_id), 45);
+    
+    // The interface client exposes the same entry point
+    strgrid_interface::STRGRIDClient::new(&env, &fixture.contract_id)
+        .transfer_and_call(&alice, &vault_id, &10i128, &Bytes::new(&env));
+    assert_eq!(client.balance_of(&vault_id), 54);
+}
+
+/// Account address whose master key is a deterministic Ed25519 key
+fn master_key_account(env: &Env, seed: u8) -> (ed25519_dalek::SigningKey, Address) {
+    use soroban_sdk::xdr::{AccountId, PublicKey, ScAddress, Uint256};
+    use soroban_sdk::TryFromVal;
+    
+    let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+    let account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(
+        key.verifying_key().to_bytes(),
+    ))));
+    (key, Address::try_from_val(env, &account).unwrap())
+}
+
+/// Registers a deterministic Ed25519 key as `owner`'s signing key, for signed-message tests
+fn register_signing_key(
+    env: &Env,
+    client: &STRGRIDContractClient,
+    owner: &Address,
+    seed: u8,
+) -> ed25519_dalek::SigningKey {
+    let key = ed25519_dalek::SigningKey::from_bytes(&[seed; 32]);
+    let public = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    client.set_signing_key(owner, &Some(public));
+    key
+}
+
+/// Address with a registered deterministic signing key, for signed-message tests
+fn signing_account(
+    env: &Env,
+    client: &STRGRIDContractClient,
+    seed: u8,
+) -> (ed25519_dalek::SigningKey, Address) {
+    let owner = Address::generate(env);
+    (register_signing_key(env, client, &owner, seed), owner)
+}
+
+fn sign_message<T: IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &ed25519_dalek::SigningKey, message: T) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+    
+    let digest = crate::signature::digest(env, message).to_array();
+    BytesN::from_array(env, &key.sign(&digest).to_bytes())
+}
+
+#[test]
+fn test_permit() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let spender = fixture.users.get_unchecked(0);
+    let (key, owner) = signing_account(&env, client, 7);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let message = |amount: i128, deadline: u64, nonce: u64| PermitMessage {
+        contract: fixture.contract_id.clone(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        live_until_ledger: 1_000,
+        deadline,
+        nonce,
+    };
+    
+    // Anyone can submit the owner's signature; the nonce advances
+    assert_eq!(client.nonce_of(&owner), 0);
+    let signature = sign_message(&env, &key, message(40, 2_000, 0));
+    env.set_auths(&[]);
+    client.permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &0u64, &signature);
+    assert_eq!(client.allowance(&owner, &spender), 40);
+    assert_eq!(client.nonce_of(&owner), 1);
+    
+    // Replaying the same signature is rejected by the nonce
+    assert_eq!(
+        client.try_permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &0u64, &signature),
+        Err(Ok(STRGRIDExtError::InvalidNonce.into()))
+    );
+    
+    // A signature over different terms does not verify
+    let signature = sign_message(&env, &key, message(40, 2_000, 1));
+    assert!(client
+        .try_permit(&owner, &spender, &90i128, &1_000u32, &2_000u64, &1u64, &signature)
+        .is_err());
+    assert_eq!(client.allowance(&owner, &spender), 40);
+    
+    // Past the deadline the permit is refused
+    let signature = sign_message(&env, &key, message(10, 999, 1));
+    assert_eq!(
+        client.try_permit(&owner, &spender, &10i128, &1_000u32, &999u64, &1u64, &signature),
+        Err(Ok(STRGRIDExtError::SignatureExpired.into()))
+    );
+    
+    // Addresses without a registered key cannot sign
+    assert_eq!(
+        client.try_permit(
+            &fixture.contract_id,
+            &spender,
+            &10i128,
+            &1_000u32,
+            &2_000u64,
+            &0u64,
+            &signature
+        ),
+        Err(Ok(STRGRIDExtError::InvalidSigner.into()))
+    );
+}
+
+#[test]
+fn test_permit_requires_registered_signing_key() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let spender = fixture.users.get_unchecked(0);
+    let (master, owner) = master_key_account(&env, 21);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let message = |nonce: u64| PermitMessage {
+        contract: fixture.contract_id.clone(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount: 40,
+        live_until_ledger: 1_000,
+        deadline: 2_000,
+        nonce,
+    };
+    
+    // The account's master key is not trusted on its own: it may be disabled or under-weighted
+    let signature = sign_message(&env, &master, message(0));
+    assert_eq!(
+        client.try_permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &0u64, &signature),
+        Err(Ok(STRGRIDExtError::InvalidSigner.into()))
+    );
+    
+    // Registering a key goes through the account's own authorization
+    let delegate = register_signing_key(&env, client, &owner, 22);
+    assert_eq!(env.auths()[0].0, owner);
+    assert!(client
+        .try_permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &0u64, &signature)
+        .is_err());
+    let signature = sign_message(&env, &delegate, message(0));
+    client.permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &0u64, &signature);
+    assert_eq!(client.allowance(&owner, &spender), 40);
+    
+    // Removing the key stops further permits
+    client.set_signing_key(&owner, &None);
+    assert_eq!(client.signing_key_of(&owner), None);
+    let signature = sign_message(&env, &delegate, message(1));
+    assert_eq!(
+        client.try_permit(&owner, &spender, &40i128, &1_000u32, &2_000u64, &1u64, &signature),
+        Err(Ok(STRGRIDExtError::InvalidSigner.into()))
+    );
+}
+
+#[test]
+fn test_permit_follows_approve_rules() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let spender = fixture.users.get_unchecked(0);
+    let (key, owner) = signing_account(&env, client, 13);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1_000;
+        li.sequence_number = 50;
+    });
+    
+    let message = |amount: i128, live_until_ledger: u32, nonce: u64| PermitMessage {
+        contract: fixture.contract_id.clone(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        live_until_ledger,
+        deadline: 2_000,
+        nonce,
+    };
+    
+    // In strict mode a zero permit cannot create an empty record
+    client.set_strict_mode(&true);
+    let signature = sign_message(&env, &key, message(0, 100, 0));
+    assert_eq!(
+        client.try_permit(&owner, &spender, &0i128, &100u32, &2_000u64, &0u64, &signature),
+        Err(Ok(STRGRIDError::ZeroApproval.into()))
+    );
+    
+    // The allowance expires where the owner signed it would
+    let signature = sign_message(&env, &key, message(40, 60, 0));
+    client.permit(&owner, &spender, &40i128, &60u32, &2_000u64, &0u64, &signature);
+    assert_eq!(client.allowance(&owner, &spender), 40);
+    env.ledger().with_mut(|li| li.sequence_number = 61);
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    
+    // An expiration already in the past is refused, as in approve_until
+    let signature = sign_message(&env, &key, message(40, 60, 1));
+    assert_eq!(
+        client.try_permit(&owner, &spender, &40i128, &60u32, &2_000u64, &1u64, &signature),
+        Err(Ok(STRGRIDError::AllowanceExpired.into()))
+    );
+    assert_eq!(client.nonce_of(&owner), 1);
+}
+
+#[test]
+fn test_relay_signed_actions() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).users(1, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(0);
+    let (key, household) = signing_account(&env, client, 9);
+    fixture.mint(&generator, 100);
+    client.transfer(&generator, &household, &100i128);
+    
+    let message = |action: RelayAction, nonce: u64| RelayMessage {
+        contract: fixture.contract_id.clone(),
+        signer: household.clone(),
+        action,
+        deadline: 500,
+        nonce,
+    };
+    
+    // No auth from the household: the signature is the authorization
+    env.set_auths(&[]);
+    let transfer = RelayAction::Transfer(bob.clone(), 30);
+    let signature = sign_message(&env, &key, message(transfer.clone(), 0));
+    client.relay(&household, &transfer, &500u64, &0u64, &signature);
+    assert_eq!(client.balance_of(&household), 70);
+    assert_eq!(client.balance_of(&bob), 30);
+    
+    // The same payload cannot be relayed twice
+    assert_eq!(
+        client.try_relay(&household, &transfer, &500u64, &0u64, &signature),
+        Err(Ok(STRGRIDExtError::InvalidNonce.into()))
+    );
+    
+    // A relayer cannot swap the action under the signature
+    let burn = RelayAction::Burn(20);
+    let signature = sign_message(&env, &key, message(burn.clone(), 1));
+    assert!(client.try_relay(&household, &RelayAction::Burn(70), &500u64, &1u64, &signature).is_err());
+    
+    client.relay(&household, &burn, &500u64, &1u64, &signature);
+    assert_eq!(client.balance_of(&household), 50);
+    assert_eq!(client.nonce_of(&household), 2);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_relay_uses_registered_signer_not_master_key() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).users(1, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(0);
+    let (master, household) = master_key_account(&env, 31);
+    fixture.mint(&generator, 100);
+    client.transfer(&generator, &household, &100i128);
+    let delegate = register_signing_key(&env, client, &household, 32);
+    
+    let transfer = RelayAction::Transfer(bob.clone(), 30);
+    let message = RelayMessage {
+        contract: fixture.contract_id.clone(),
+        signer: household.clone(),
+        action: transfer.clone(),
+        deadline: 500,
+        nonce: 0,
+    };
+    env.set_auths(&[]);
+    
+    // The master key cannot bypass the key the account registered
+    let signature = sign_message(&env, &master, message.clone());
+    assert!(client.try_relay(&household, &transfer, &500u64, &0u64, &signature).is_err());
+    assert_eq!(client.balance_of(&household), 100);
+    
+    // The registered signer authorizes the action
+    let signature = sign_message(&env, &delegate, message);
+    client.relay(&household, &transfer, &500u64, &0u64, &signature);
+    assert_eq!(client.balance_of(&bob), 30);
+    assert_eq!(client.nonce_of(&household), 1);
+}
+
+#[test]
+fn test_nonces_are_strict_and_kept_alive() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+    
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let spender = fixture.users.get_unchecked(0);
+    let (key, owner) = signing_account(&env, client, 11);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    
+    let sign = |nonce: u64| {
+        let message = PermitMessage {
+            contract: fixture.contract_id.clone(),
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount: 5,
+            live_until_ledger: 1_000,
+            deadline: 200,
+            nonce,
+        };
+        sign_message(&env, &key, message)
+    };
+    
+    // A correctly signed message with a future nonce is still refused
+    assert_eq!(
+        client.try_permit(&owner, &spender, &5i128, &1_000u32, &200u64, &1u64, &sign(1)),
+        Err(Ok(STRGRIDExtError::InvalidNonce.into()))
+    );
+    assert_eq!(client.nonce_of(&owner), 0);
+    
+    // Nonces advance one at a time, each signature usable once
+    for nonce in 0..3u64 {
+        client.permit(&owner, &spender, &5i128, &1_000u32, &200u64, &nonce, &sign(nonce));
+        assert_eq!(client.nonce_of(&owner), nonce + 1);
+    }
+    
+    // Each use pushes the counter's TTL out well beyond the current ledger
+    let ttl = env.as_contract(&fixture.contract_id, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&(strgrid_codes::StorageKey::Nonce.symbol(), owner.clone()))
+    });
+    assert!(ttl >= 2_000_000);
+}
+
+mod kyc_registry {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+    
+    /// External registry stub: approves whoever was verified through it
+    #[contract]
+    pub struct KycRegistryStub;
+    
+    #[contractimpl]
+    impl KycRegistryStub {
+        pub fn verify(env: Env, account: Address) {
+            env.storage().persistent().set(&account, &true);
+        }
+        
+        pub fn is_allowed(env: Env, account: Address) -> bool {
+            env.storage().persistent().has(&account)
+        }
+    }
+}
+
+#[test]
+fn test_kyc_allowlist() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(3, 100).build();
+    let client = &fixture.client;
+    let alice = fixture.users.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(1);
+    let carol = fixture.users.get_unchecked(2);
+    
+    // Allowlist entries have no effect until KYC mode is on
+    client.transfer(&alice, &bob, &10i128);
+    client.set_kyc_mode(&true);
+    assert!(client.is_kyc_mode());
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10i128),
+        Err(Ok(STRGRIDExtError::NotAllowlisted.into()))
+    );
+    
+    // Both ends must be allowed
+    client.add_to_allowlist(&alice);
+    assert!(client.is_allowed(&alice));
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10i128),
+        Err(Ok(STRGRIDExtError::NotAllowlisted.into()))
+    );
+    client.add_to_allowlist(&bob);
+    client.transfer(&alice, &bob, &10i128);
+    assert_eq!(client.balance_of(&bob), 120);
+    
+    // The external registry complements the local list
+    let registry_id = env.register(kyc_registry::KycRegistryStub, ());
+    let registry = kyc_registry::KycRegistryStubClient::new(&env, &registry_id);
+    client.set_kyc_registry(&Some(registry_id.clone()));
+    assert_eq!(client.get_kyc_registry(), Some(registry_id));
+    assert!(!client.is_allowed(&carol));
+    registry.verify(&carol);
+    assert!(client.is_allowed(&carol));
+    client.transfer(&bob, &carol, &20i128);
+    
+    // Removal blocks the account again
+    client.remove_from_allowlist(&alice);
+    assert!(!client.is_allowed(&alice));
+    assert_eq!(
+        client.try_transfer(&carol, &alice, &5i128),
+        Err(Ok(STRGRIDExtError::NotAllowlisted.into()))
+    );
+    
+    client.set_kyc_mode(&false);
+    client.transfer(&carol, &alice, &5i128);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_jurisdiction_matrix() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).users(2, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let (br, pt) = (fixture.users.get_unchecked(0), fixture.users.get_unchecked(1));
+    let untagged = Address::generate(&env);
+    fixture.mint(&generator, 300);
+    
+    // Only registered participants can be tagged
+    assert_eq!(
+        client.try_set_jurisdiction(&br, &Some(76u32)),
+        Err(Ok(STRGRIDExtError::ParticipantNotRegistered.into()))
+    );
+    let profile = ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: 1,
+        expected_monthly_kwh: 150,
+    };
+    client.register_consumer(&br, &profile);
+    client.register_consumer(&pt, &profile);
+    client.set_jurisdiction(&generator, &Some(76u32));
+    client.set_jurisdiction(&br, &Some(76u32));
+    client.set_jurisdiction(&pt, &Some(620u32));
+    assert_eq!(client.jurisdiction_of(&pt), Some(620));
+    
+    // Tags alone restrict nothing until the matrix is enforced
+    client.transfer(&generator, &pt, &10i128);
+    client.set_jurisdiction_restrictions(&true);
+    assert!(client.is_jurisdiction_restricted());
+    
+    // Same jurisdiction always passes; cross-border needs a permitted pair
+    client.transfer(&generator, &br, &50i128);
+    assert_eq!(
+        client.try_transfer(&generator, &pt, &10i128),
+        Err(Ok(STRGRIDExtError::JurisdictionRestricted.into()))
+    );
+    
+    // Pairs are directional
+    client.set_jurisdiction_pair(&76u32, &620u32, &true);
+    assert!(client.is_jurisdiction_pair_allowed(&76u32, &620u32));
+    assert!(!client.is_jurisdiction_pair_allowed(&620u32, &76u32));
+    client.transfer(&br, &pt, &10i128);
+    assert_eq!(
+        client.try_transfer(&pt, &br, &5i128),
+        Err(Ok(STRGRIDExtError::JurisdictionRestricted.into()))
+    );
+    
+    // Untagged accounts fall outside the matrix
+    client.transfer(&pt, &untagged, &5i128);
+    
+    client.set_jurisdiction_pair(&76u32, &620u32, &false);
+    assert_eq!(
+        client.try_transfer(&br, &pt, &10i128),
+        Err(Ok(STRGRIDExtError::JurisdictionRestricted.into()))
+    );
+    client.set_jurisdiction(&pt, &None);
+    client.transfer(&br, &pt, &10i128);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_anchor_documents() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    
+    let license = BytesN::from_array(&env, &[1u8; 32]);
+    let aneel = BytesN::from_array(&env, &[2u8; 32]);
+    let renewed = BytesN::from_array(&env, &[3u8; 32]);
+    
+    // Only registered generators can anchor credentials
+    assert_eq!(
+        client.try_anchor_document(&Address::generate(&env), &DocumentType::License, &license),
+        Err(Ok(STRGRIDError::GeneratorNotFound.into()))
+    );
+    
+    client.anchor_document(&generator, &DocumentType::License, &license);
+    client.anchor_document(&generator, &DocumentType::AneelAuthorization, &aneel);
+    assert_eq!(
+        client.get_document(&generator, &DocumentType::License),
+        Some(AnchoredDocument {
+            doc_type: DocumentType::License,
+            sha256: license.clone(),
+            anchored_at: 5_000,
+        })
+    );
+    assert_eq!(client.get_documents(&generator).len(), 2);
+    assert_eq!(client.get_document(&generator, &DocumentType::InterconnectionAgreement), None);
+    
+    // Auditors check the presented file against the current anchor
+    assert!(client.verify_document(&generator, &DocumentType::License, &license));
+    assert!(!client.verify_document(&generator, &DocumentType::License, &aneel));
+    
+    // A renewal supersedes the old hash, which stays traceable
+    client.anchor_document(&generator, &DocumentType::License, &renewed);
+    assert!(!client.verify_document(&generator, &DocumentType::License, &license));
+    assert!(client.verify_document(&generator, &DocumentType::License, &renewed));
+    assert_eq!(client.find_document(&license), Some((generator.clone(), DocumentType::License)));
+    assert_eq!(client.find_document(&BytesN::from_array(&env, &[9u8; 32])), None);
+}
+
+#[test]
+fn test_metadata_cids() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).users(1, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = fixture.users.get_unchecked(0);
+    let auditor = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let cid = String::from_str(&env, "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+    
+    // Generators describe themselves
+    client.set_generator_metadata(&generator, &generator, &Some(cid.clone()));
+    assert_eq!(client.get_generator(&generator).metadata_cid, Some(cid.clone()));
+    assert_eq!(
+        client.try_set_generator_metadata(&outsider, &generator, &Some(cid.clone())),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    assert_eq!(
+        client.try_set_generator_metadata(&generator, &generator, &Some(String::from_str(&env, ""))),
+        Err(Ok(STRGRIDExtError::InvalidCid.into()))
+    );
+    
+    // Lots start without a pointer; the issuing generator sets it
+    let lot = fixture.mint(&generator, 100);
+    assert_eq!(client.get_energy_token(&lot).metadata_cid, None);
+    client.set_token_metadata(&generator, &lot, &Some(cid.clone()));
+    assert_eq!(client.get_energy_token(&lot).metadata_cid, Some(cid.clone()));
+    
+    // Auditors may annotate certificates on the consumer's behalf
+    client.transfer(&generator, &consumer, &50i128);
+    let cert_id = client.burn_energy_tokens(&consumer, &lot, &20i128);
+    assert_eq!(
+        client.try_set_certificate_metadata(&auditor, &cert_id, &Some(cid.clone())),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.grant_role(&Role::Auditor, &auditor);
+    client.set_certificate_metadata(&auditor, &cert_id, &Some(cid.clone()));
+    assert_eq!(client.get_certificate(&cert_id).metadata_cid, Some(cid));
+    
+    client.set_certificate_metadata(&consumer, &cert_id, &None);
+    assert_eq!(client.get_certificate(&cert_id).metadata_cid, None);
+}
+
+#[test]
+fn test_generator_applications() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).build();
+    let client = &fixture.client;
+    let operator = Address::generate(&env);
+    let solar = Address::generate(&env);
+    let wind = Address::generate(&env);
+    let docs = BytesN::from_array(&env, &[4u8; 32]);
+    
+    client.apply_as_generator(&solar, &50u64, &SourceType::Solar, &3u32, &docs);
+    client.apply_as_generator(&wind, &900u64, &SourceType::Wind, &5u32, &docs);
+    assert_eq!(
+        client.try_apply_as_generator(&solar, &60u64, &SourceType::Solar, &3u32, &docs),
+        Err(Ok(STRGRIDExtError::AlreadyRegistered.into()))
+    );
+    assert_eq!(client.get_application(&solar).status, ApplicationStatus::Pending);
+    assert_eq!(
+        client.try_get_generator(&solar),
+        Err(Ok(STRGRIDError::GeneratorNotFound.into()))
+    );
+    
+    // Only grid operators review
+    assert_eq!(
+        client.try_approve_application(&operator, &solar, &GeneratorTier::Micro),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.grant_role(&Role::GridOperator, &operator);
+    
+    client.approve_application(&operator, &solar, &GeneratorTier::Micro);
+    assert_eq!(client.tier_of(&solar), Some(GeneratorTier::Micro));
+    let generator = client.get_generator(&solar);
+    assert_eq!((generator.capacity_kw, generator.source_type, generator.zone), (50, SourceType::Solar, 3));
+    let app = client.get_application(&solar);
+    assert_eq!(app.status, ApplicationStatus::Approved);
+    assert_eq!(app.reviewed_by, Some(operator.clone()));
+    assert_eq!(
+        client.try_reject_application(&operator, &solar),
+        Err(Ok(STRGRIDExtError::InvalidApplicationStatus.into()))
+    );
+    
+    // A rejected applicant may re-apply
+    client.reject_application(&operator, &wind);
+    assert_eq!(client.get_applications(&Some(ApplicationStatus::Rejected), &0u32, &10u32).len(), 1);
+    client.apply_as_generator(&wind, &800u64, &SourceType::Wind, &5u32, &docs);
+    assert_eq!(client.get_application(&wind).capacity_kw, 800);
+    
+    // Listing pages over the filtered set, in submission order
+    let pending = client.get_applications(&Some(ApplicationStatus::Pending), &0u32, &10u32);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending.get_unchecked(0).applicant, wind);
+    let all = client.get_applications(&None, &1u32, &10u32);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all.get_unchecked(0).applicant, wind);
+    
+    // Suspension stops minting until reinstated
+    client.suspend_generator(&operator, &solar);
+    assert!(!client.get_generator(&solar).is_active);
+    assert!(client.try_mint_energy_tokens(&solar, &10i128, &24u64, &None).is_err());
+    client.reinstate_generator(&operator, &solar);
+    client.mint_energy_tokens(&solar, &10i128, &24u64, &None);
+    assert_eq!(client.balance_of(&solar), 10);
+}
+
+#[test]
+fn test_generator_tiers() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).build();
+    let client = &fixture.client;
+    let operator = Address::generate(&env);
+    let rooftop = Address::generate(&env);
+    let plant = Address::generate(&env);
+    let docs = BytesN::from_array(&env, &[5u8; 32]);
+    client.grant_role(&Role::GridOperator, &operator);
+    
+    client.set_dispute_window(&600u64);
+    client.set_tier_config(&GeneratorTier::Micro, &TierConfig {
+        max_mint: Some(20),
+        max_daily_mint: Some(30),
+        required_bond: Some(0),
+        dispute_window: Some(60),
+    });
+    client.set_tier_config(&GeneratorTier::Utility, &TierConfig {
+        max_mint: None,
+        max_daily_mint: None,
+        required_bond: Some(5_000),
+        dispute_window: None,
+    });
+    assert_eq!(client.get_tier_config(&GeneratorTier::Small), None);
+    
+    // Bond applies only to the utility tier
+    let bond_token = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &bond_token).mint(&plant, &10_000i128);
+    client.set_bond_config(&bond_token, &1_000i128);
+    
+    client.apply_as_generator(&rooftop, &100u64, &SourceType::Solar, &1u32, &docs);
+    client.apply_as_generator(&plant, &100_000u64, &SourceType::Hydro, &1u32, &docs);
+    client.approve_application(&operator, &rooftop, &GeneratorTier::Micro);
+    client.approve_application(&operator, &plant, &GeneratorTier::Utility);
+    
+    // Micro: per-mint and daily caps, shorter dispute window, no bond
+    assert_eq!(
+        client.try_mint_energy_tokens(&rooftop, &25i128, &24u64, &None),
+        Err(Ok(STRGRIDError::MintAboveMaximum.into()))
+    );
+    let lot = client.mint_energy_tokens(&rooftop, &20i128, &24u64, &None);
+    assert_eq!(client.get_energy_token(&lot).dispute_until, env.ledger().timestamp() + 60);
+    assert_eq!(
+        client.try_mint_energy_tokens(&rooftop, &15i128, &24u64, &None),
+        Err(Ok(STRGRIDError::DailyMintCapExceeded.into()))
+    );
+    
+    // Utility: the tier bond replaces the global minimum; the global window applies
+    client.deposit_bond(&plant, &1_000i128);
+    assert_eq!(
+        client.try_mint_energy_tokens(&plant, &500i128, &24u64, &None),
+        Err(Ok(STRGRIDError::BondRequired.into()))
+    );
+    client.deposit_bond(&plant, &4_000i128);
+    let lot = client.mint_energy_tokens(&plant, &500i128, &24u64, &None);
+    assert_eq!(client.get_energy_token(&lot).dispute_until, env.ledger().timestamp() + 600);
+    
+    // Re-tiering is an operator decision
+    assert_eq!(
+        client.try_set_generator_tier(&rooftop, &rooftop, &GeneratorTier::Utility),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.set_generator_tier(&operator, &rooftop, &GeneratorTier::Small);
+    assert_eq!(client.tier_of(&rooftop), Some(GeneratorTier::Small));
+    
+    // Small has no rules of its own, so the global bond minimum now applies
+    assert_eq!(
+        client.try_mint_energy_tokens(&rooftop, &15i128, &24u64, &None),
+        Err(Ok(STRGRIDError::BondRequired.into()))
+    );
+}
+
+#[test]
+fn test_scheduled_maintenance() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    
+    let first = client.schedule_maintenance(&generator, &2_000u64, &3_000u64);
+    let second = client.schedule_maintenance(&generator, &5_000u64, &6_000u64);
+    assert_eq!(
+        client.try_schedule_maintenance(&generator, &2_500u64, &4_000u64),
+        Err(Ok(STRGRIDExtError::MaintenanceOverlap.into()))
+    );
+    assert_eq!(client.maintenance_calendar(&generator).len(), 2);
+    
+    // Mints are blocked only inside the window
+    fixture.mint(&generator, 10);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert!(client.is_under_maintenance(&generator));
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDExtError::GeneratorInMaintenance.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    assert!(!client.is_under_maintenance(&generator));
+    fixture.mint(&generator, 10);
+    
+    // Availability baselines discount the downtime
+    assert_eq!(client.available_seconds(&generator, &0u64, &10_000u64), 8_000);
+    assert_eq!(client.available_seconds(&generator, &2_500u64, &5_500u64), 2_000);
+    
+    // Ending a window early shortens it; cancelling a future one removes it
+    env.ledger().with_mut(|li| li.timestamp = 5_400);
+    client.cancel_maintenance(&generator, &second);
+    assert_eq!(client.get_maintenance(&second).ends_at, 5_400);
+    fixture.mint(&generator, 10);
+    let third = client.schedule_maintenance(&generator, &9_000u64, &9_500u64);
+    client.cancel_maintenance(&generator, &third);
+    assert!(client.get_maintenance(&third).is_cancelled);
+    assert_eq!(
+        client.try_cancel_maintenance(&generator, &first),
+        Err(Ok(STRGRIDError::NoOpUpdate.into()))
+    );
+    assert_eq!(client.available_seconds(&generator, &0u64, &10_000u64), 8_600);
+}
+
+#[test]
+fn test_forecast_deviation_settlement() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&generator, &1_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&fixture.contract_id, &1_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    client.set_forecast_config(&ForecastConfig {
+        payment_token: usdc.clone(),
+        tolerance_bps: 1_000,
+        penalty_per_kwh: 2,
+        reward_per_kwh: 1,
+    });
+    
+    // Forecasts are day-ahead: today's intervals are closed
+    let day = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+    assert_eq!(
+        client.try_submit_forecast(&generator, &(day - 1), &100u64),
+        Err(Ok(STRGRIDExtError::ScheduleClosed.into()))
+    );
+    client.submit_forecast(&generator, &day, &100u64);
+    client.submit_forecast(&generator, &(day + 1), &200u64);
+    client.submit_forecast(&generator, &(day + 2), &100u64);
+    
+    // Half of the third interval is scheduled downtime
+    let third_start = (day + 2) * SETTLEMENT_INTERVAL_SECONDS;
+    client.schedule_maintenance(&generator, &(third_start + 1_800), &(third_start + 3_600));
+    
+    env.ledger().with_mut(|li| li.timestamp = day * SETTLEMENT_INTERVAL_SECONDS);
+    fixture.mint(&generator, 95);
+    env.ledger().with_mut(|li| li.timestamp = (day + 1) * SETTLEMENT_INTERVAL_SECONDS);
+    fixture.mint(&generator, 100);
+    assert_eq!(
+        client.try_settle_forecast(&generator, &(day + 1)),
+        Err(Ok(STRGRIDExtError::IntervalOpen.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = third_start);
+    fixture.mint(&generator, 50);
+    env.ledger().with_mut(|li| li.timestamp = third_start + SETTLEMENT_INTERVAL_SECONDS);
+    
+    // Within the 10% tolerance: rewarded per expected kWh
+    let within = client.get_forecast_result(&generator, &day);
+    assert_eq!((within.actual_kwh, within.deviation_kwh, within.amount), (95, 5, 100));
+    assert_eq!(client.settle_forecast(&generator, &day), 100);
+    assert_eq!(
+        client.try_settle_forecast(&generator, &day),
+        Err(Ok(STRGRIDExtError::ForecastSettled.into()))
+    );
+    
+    // Short by 100 kWh with 20 tolerated: 80 kWh penalised at 2
+    assert_eq!(client.settle_forecast(&generator, &(day + 1)), -160);
+    
+    // Maintenance halves the expectation, so delivering 50 is on target
+    let adjusted = client.get_forecast_result(&generator, &(day + 2));
+    assert_eq!((adjusted.expected_kwh, adjusted.deviation_kwh), (50, 0));
+    assert_eq!(client.settle_forecast(&generator, &(day + 2)), 50);
+    
+    assert_eq!(usdc_client.balance(&generator), 1_000 + 100 - 160 + 50);
+    assert_eq!(
+        client.try_get_forecast_result(&generator, &(day + 3)),
+        Err(Ok(STRGRIDExtError::ForecastNotFound.into()))
+    );
+}
+
+#[test]
+fn test_day_ahead_commitments_roll_into_imbalance() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let (first_buyer, second_buyer) = (Address::generate(&env), Address::generate(&env));
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&first_buyer, &1_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&second_buyer, &1_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    // The day-ahead gate only accepts intervals from tomorrow on
+    let day = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+    assert_eq!(
+        client.try_offer_day_ahead(&generator, &(day - 1), &100u64, &usdc, &2i128),
+        Err(Ok(STRGRIDExtError::ScheduleClosed.into()))
+    );
+    let offer_id = client.offer_day_ahead(&generator, &day, &100u64, &usdc, &2i128);
+    let first = client.accept_day_ahead(&first_buyer, &offer_id, &60u64);
+    assert_eq!(
+        client.try_accept_day_ahead(&second_buyer, &offer_id, &50u64),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    let second = client.accept_day_ahead(&second_buyer, &offer_id, &40u64);
+    assert!(!client.get_day_ahead_offer(&offer_id).is_open);
+    assert_eq!(usdc_client.balance(&fixture.contract_id), 200);
+    
+    // Cancelled offers stop taking commitments
+    let spare = client.offer_day_ahead(&generator, &day, &10u64, &usdc, &3i128);
+    client.cancel_day_ahead_offer(&spare);
+    assert_eq!(
+        client.try_accept_day_ahead(&first_buyer, &spare, &5u64),
+        Err(Ok(STRGRIDExtError::OfferClosed.into()))
+    );
+    
+    // Only 70 of the 100 committed kWh are generated in the interval
+    env.ledger().with_mut(|li| li.timestamp = day * SETTLEMENT_INTERVAL_SECONDS);
+    assert_eq!(
+        client.try_accept_day_ahead(&first_buyer, &spare, &5u64),
+        Err(Ok(STRGRIDExtError::OfferClosed.into()))
+    );
+    fixture.mint(&generator, 70);
+    assert_eq!(
+        client.try_settle_day_ahead(&first),
+        Err(Ok(STRGRIDExtError::IntervalOpen.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = (day + 1) * SETTLEMENT_INTERVAL_SECONDS);
+    let oracle = Address::generate(&env);
+    client.grant_role(&Role::Oracle, &oracle);
+    client.publish_pld(&oracle, &day, &usdc, &1i128);
+    assert_eq!(
+        client.try_settle_imbalance(&generator, &day),
+        Err(Ok(STRGRIDExtError::DayAheadUnsettled.into()))
+    );
+    
+    // Commitments draw on the interval's generation in settlement order
+    assert_eq!(client.settle_day_ahead(&first), 60);
+    assert_eq!(client.settle_day_ahead(&second), 10);
+    assert_eq!(
+        client.try_settle_day_ahead(&second),
+        Err(Ok(STRGRIDExtError::CommitmentSettled.into()))
+    );
+    assert_eq!(client.balance_of(&first_buyer), 60);
+    assert_eq!(client.balance_of(&second_buyer), 10);
+    assert_eq!(usdc_client.balance(&generator), 140);
+    assert_eq!(usdc_client.balance(&second_buyer), 1_000 - 20);
+    assert_eq!(usdc_client.balance(&fixture.contract_id), 0);
+    
+    // The undelivered 30 kWh show up as a real-time shortfall
+    let position = client.get_imbalance(&generator, &day);
+    assert_eq!((position.scheduled_kwh, position.metered_kwh), (100, 70));
+    assert_eq!(position.imbalance_kwh, -30);
+    assert_eq!(client.settle_imbalance(&generator, &day), -30);
+    assert_eq!(usdc_client.balance(&generator), 110);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_forward_collateral_margin_call_and_liquidation() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let buyer = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&generator, &10_000i128);
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    let oracle_id = env.register(strgrid_mock_oracle::MockOracleContract, ());
+    let oracle = strgrid_mock_oracle::MockOracleContractClient::new(&env, &oracle_id);
+    oracle.set_price(&usdc, &10i128);
+    
+    let mut config = CollateralConfig {
+        price_oracle: oracle_id.clone(),
+        initial_margin_bps: 1_000,
+        maintenance_margin_bps: 2_000,
+        margin_call_period: 600,
+    };
+    assert_eq!(
+        client.try_set_collateral_config(&Some(config.clone())),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    config.initial_margin_bps = 2_000;
+    config.maintenance_margin_bps = 1_000;
+    client.set_collateral_config(&Some(config));
+    
+    // 100 kWh marked at 10 lock 20% of 1_000 as collateral
+    let liquidated = client.sell_forward(&generator, &buyer, &100u64, &10u64, &3_600u64, &usdc, &1_000i128, &0i128);
+    let delivered = client.sell_forward(&generator, &buyer, &100u64, &10u64, &3_600u64, &usdc, &1_000i128, &0i128);
+    assert_eq!(client.get_forward_margin(&liquidated).collateral, 200);
+    assert_eq!(usdc_client.balance(&generator), 10_000 - 400);
+    assert_eq!(
+        client.try_margin_call(&liquidated),
+        Err(Ok(STRGRIDExtError::MarginSufficient.into()))
+    );
+    
+    // A price rise to 25 requires 250 of maintenance margin
+    oracle.set_price(&usdc, &25i128);
+    assert_eq!(client.margin_call(&liquidated), 600);
+    assert_eq!(
+        client.try_liquidate_forward(&liquidated),
+        Err(Ok(STRGRIDExtError::MarginCallNotDue.into()))
+    );
+    client.add_collateral(&liquidated, &50i128);
+    assert_eq!(client.get_forward_margin(&liquidated).margin_call_deadline, None);
+    
+    // At 40 the seller does not top up again and is liquidated after the call period
+    oracle.set_price(&usdc, &40i128);
+    assert_eq!(client.margin_call(&liquidated), 600);
+    env.ledger().with_mut(|li| li.timestamp = 600);
+    assert_eq!(client.liquidate_forward(&liquidated), 1_250);
+    assert_eq!(client.get_forward(&liquidated).status, ForwardStatus::Liquidated);
+    assert_eq!(
+        client.try_add_collateral(&liquidated, &10i128),
+        Err(Ok(STRGRIDError::ForwardClosed.into()))
+    );
+    
+    // Delivery hands the collateral back to the seller
+    fixture.mint(&generator, 100);
+    client.deliver_forward(&delivered);
+    assert_eq!(client.get_forward_margin(&delivered).collateral, 0);
+    assert_eq!(usdc_client.balance(&generator), 10_000 - 400 - 50 + 1_000 + 200);
+    assert_eq!(usdc_client.balance(&buyer), 10_000 - 2_000 + 1_250);
+    assert_eq!(usdc_client.balance(&fixture.contract_id), 0);
+}
+
+#[test]
+fn test_consumers_rate_generators_once_per_billing_period() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(2, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let other = fixture.generators.get_unchecked(1);
+    let consumer = Address::generate(&env);
+    
+    // Holding kWh is not enough; the consumer must have consumed from the generator
+    let lot = fixture.mint(&generator, 100);
+    client.transfer(&generator, &consumer, &50i128);
+    assert_eq!(
+        client.try_rate_generator(&consumer, &generator, &4u32),
+        Err(Ok(STRGRIDExt2Error::NotACustomer.into()))
+    );
+    client.burn_energy_tokens(&consumer, &lot, &10i128);
+    assert_eq!(
+        client.try_rate_generator(&consumer, &generator, &6u32),
+        Err(Ok(STRGRIDExtError::InvalidRating.into()))
+    );
+    client.rate_generator(&consumer, &generator, &4u32);
+    assert_eq!(
+        client.try_rate_generator(&consumer, &generator, &5u32),
+        Err(Ok(STRGRIDExt2Error::AlreadyRated.into()))
+    );
+    assert_eq!(
+        client.try_rate_generator(&consumer, &other, &1u32),
+        Err(Ok(STRGRIDExt2Error::NotACustomer.into()))
+    );
+    
+    // A new billing period needs a new purchase before another rating
+    env.ledger().with_mut(|li| li.timestamp = BILLING_PERIOD_SECONDS);
+    assert_eq!(
+        client.try_rate_generator(&consumer, &generator, &2u32),
+        Err(Ok(STRGRIDExt2Error::NotACustomer.into()))
+    );
+    let fresh = fixture.mint(&generator, 100);
+    client.transfer(&generator, &consumer, &10i128);
+    client.burn_energy_tokens(&consumer, &fresh, &10i128);
+    client.rate_generator(&consumer, &generator, &2u32);
+    
+    let rating = client.get_generator_rating(&generator);
+    assert_eq!((rating.count, rating.sum), (2, 6));
+    assert_eq!(client.rating_of(&consumer, &generator, &0u64), Some(4));
+    assert_eq!(client.rating_of(&consumer, &generator, &1u64), Some(2));
+    assert_eq!(client.get_generator_rating(&other).count, 0);
+}
+
+#[test]
+fn test_guardians_recover_consumer_account_after_delay() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let new_key = Address::generate(&env);
+    let guardians = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    let stranger = Address::generate(&env);
+    
+    let profile = ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: 1,
+        expected_monthly_kwh: 300,
+    };
+    client.register_consumer(&consumer, &profile);
+    let lot = fixture.mint(&generator, 100);
+    client.transfer(&generator, &consumer, &60i128);
+    let cert_id = client.burn_energy_tokens(&consumer, &lot, &10i128);
+    
+    assert_eq!(
+        client.try_set_recovery(&consumer, &Some(RecoveryConfig {
+            guardians: guardians.clone(),
+            threshold: 4,
+            delay: 86_400,
+        })),
+        Err(Ok(STRGRIDExt2Error::InvalidRecoveryConfig.into()))
+    );
+    client.set_recovery(&consumer, &Some(RecoveryConfig {
+        guardians: guardians.clone(),
+        threshold: 2,
+        delay: 86_400,
+    }));
+    assert_eq!(
+        client.try_propose_recovery(&stranger, &consumer, &new_key),
+        Err(Ok(STRGRIDExt2Error::NotGuardian.into()))
+    );
+    
+    // The owner can still veto while they hold the key
+    client.propose_recovery(&guardians.get_unchecked(0), &consumer, &stranger);
+    client.cancel_recovery(&consumer);
+    assert_eq!(client.get_recovery_request(&consumer), None);
+    
+    // Two of three guardians start the delay
+    client.propose_recovery(&guardians.get_unchecked(0), &consumer, &new_key);
+    assert_eq!(
+        client.try_propose_recovery(&guardians.get_unchecked(1), &consumer, &stranger),
+        Err(Ok(STRGRIDExt2Error::RecoveryPending.into()))
+    );
+    assert_eq!(
+        client.try_execute_recovery(&consumer),
+        Err(Ok(STRGRIDExt2Error::RecoveryNotReady.into()))
+    );
+    client.approve_recovery(&guardians.get_unchecked(1), &consumer);
+    assert_eq!(client.get_recovery_request(&consumer).unwrap().executable_at, Some(86_400));
+    assert_eq!(
+        client.try_execute_recovery(&consumer),
+        Err(Ok(STRGRIDExt2Error::RecoveryNotReady.into()))
+    );
+    
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    assert_eq!(client.execute_recovery(&consumer), 50);
+    assert_eq!(client.balance_of(&consumer), 0);
+    assert_eq!(client.balance_of(&new_key), 50);
+    assert_eq!(client.get_certificate(&cert_id).consumer, new_key);
+    assert_eq!(client.certificates_of(&new_key).len(), 1);
+    assert_eq!(client.certificates_of(&consumer).len(), 0);
+    assert_eq!(client.get_consumer(&new_key), profile);
+    assert_eq!(client.get_recovery(&consumer), None);
+    assert_eq!(
+        client.try_execute_recovery(&consumer),
+        Err(Ok(STRGRIDExt2Error::RecoveryNotFound.into()))
+    );
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_generator_delegates_scoped_operator_keys() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let scada = Address::generate(&env);
+    let forecaster = Address::generate(&env);
+    
+    client.add_operator(&generator, &scada, &OperatorGrant {
+        permissions: vec![&env, OperatorPermission::Mint],
+        max_mint_kwh: Some(100),
+        expires_at: Some(86_400),
+    });
+    client.add_operator(&generator, &forecaster, &OperatorGrant {
+        permissions: vec![&env, OperatorPermission::Forecast],
+        max_mint_kwh: None,
+        expires_at: None,
+    });
+    assert_eq!(client.operators_of(&generator), vec![&env, scada.clone(), forecaster.clone()]);
+    
+    // Lots minted by the operator belong to the generator
+    let lot = client.operator_mint(&scada, &generator, &80i128, &24u64, &None);
+    assert_eq!(client.get_energy_token(&lot).generator_id, generator);
+    assert_eq!(client.balance_of(&generator), 80);
+    assert_eq!(client.balance_of(&scada), 0);
+    assert_eq!(
+        client.try_operator_mint(&scada, &generator, &101i128, &24u64, &None),
+        Err(Ok(STRGRIDExt2Error::OperatorLimitExceeded.into()))
+    );
+    
+    // Each key only holds the permissions it was granted
+    assert_eq!(
+        client.try_operator_mint(&forecaster, &generator, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    let day = 86_400 / SETTLEMENT_INTERVAL_SECONDS;
+    assert_eq!(
+        client.try_operator_submit_forecast(&scada, &generator, &day, &100u64),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    client.operator_submit_forecast(&forecaster, &generator, &day, &100u64);
+    assert_eq!(client.get_forecast_result(&generator, &day).forecast_kwh, 100);
+    
+    // Expired and revoked keys stop working
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    assert_eq!(
+        client.try_operator_mint(&scada, &generator, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    client.revoke_operator(&generator, &forecaster);
+    assert_eq!(client.get_operator(&generator, &forecaster), None);
+    assert_eq!(client.operators_of(&generator), vec![&env, scada.clone()]);
+    assert_eq!(
+        client.try_operator_submit_forecast(&forecaster, &generator, &(day + 1), &100u64),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_operator_key_rotation_overlaps_both_keys() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let old_key = Address::generate(&env);
+    let new_key = Address::generate(&env);
+    let grant = OperatorGrant {
+        permissions: vec![&env, OperatorPermission::Mint],
+        max_mint_kwh: Some(100),
+        expires_at: None,
+    };
+    client.add_operator(&generator, &old_key, &grant);
+    
+    assert_eq!(
+        client.try_rotate_operator_key(&generator, &new_key, &old_key),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    assert_eq!(
+        client.try_rotate_operator_key(&generator, &old_key, &old_key),
+        Err(Ok(STRGRIDExt2Error::OperatorExists.into()))
+    );
+    client.rotate_operator_key(&generator, &old_key, &new_key);
+    assert_eq!(client.get_operator(&generator, &new_key), Some(grant.clone()));
+    assert_eq!(
+        client.get_operator(&generator, &old_key).unwrap().expires_at,
+        Some(OPERATOR_ROTATION_GRACE_SECONDS)
+    );
+    assert_eq!(client.operators_of(&generator), vec![&env, old_key.clone(), new_key.clone()]);
+    
+    // Both keys mint during the grace period
+    client.operator_mint(&old_key, &generator, &10i128, &24u64, &None);
+    client.operator_mint(&new_key, &generator, &10i128, &24u64, &None);
+    
+    // Afterwards only the new key is accepted
+    env.ledger().with_mut(|li| li.timestamp = OPERATOR_ROTATION_GRACE_SECONDS);
+    assert_eq!(
+        client.try_operator_mint(&old_key, &generator, &10i128, &24u64, &None),
+        Err(Ok(STRGRIDExt2Error::NotOperator.into()))
+    );
+    client.operator_mint(&new_key, &generator, &10i128, &24u64, &None);
+    assert_eq!(client.balance_of(&generator), 30);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_spending_limit_resets_each_period() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let consumer = fixture.generators.get_unchecked(0);
+    let retailer = Address::generate(&env);
+    fixture.mint(&consumer, 500);
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+    
+    client.approve(&consumer, &retailer, &300i128);
+    assert_eq!(
+        client.try_set_spending_limit(&consumer, &retailer, &Some((100i128, 0u32))),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    client.set_spending_limit(&consumer, &retailer, &Some((100i128, 10u32)));
+    assert_eq!(client.spendable_allowance(&consumer, &retailer), 100);
+    
+    client.transfer_from(&retailer, &consumer, &retailer, &70i128);
+    assert_eq!(client.spendable_allowance(&consumer, &retailer), 30);
+    assert_eq!(
+        client.try_transfer_from(&retailer, &consumer, &retailer, &31i128),
+        Err(Ok(STRGRIDExt2Error::SpendingLimitExceeded.into()))
+    );
+    
+    // The window rolls over on period boundaries, even after skipped periods
+    env.ledger().with_mut(|li| li.sequence_number = 1_025);
+    let limit = client.get_spending_limit(&consumer, &retailer).unwrap();
+    assert_eq!((limit.period_start, limit.spent), (1_020, 0));
+    client.transfer_from(&retailer, &consumer, &retailer, &100i128);
+    
+    // The allowance still caps the total
+    env.ledger().with_mut(|li| li.sequence_number = 1_030);
+    assert_eq!(client.spendable_allowance(&consumer, &retailer), 100);
+    client.transfer_from(&retailer, &consumer, &retailer, &100i128);
+    env.ledger().with_mut(|li| li.sequence_number = 1_040);
+    assert_eq!(client.spendable_allowance(&consumer, &retailer), 30);
+    assert_eq!(
+        client.try_transfer_from(&retailer, &consumer, &retailer, &31i128),
+        Err(Ok(STRGRIDError::InsufficientAllowance.into()))
+    );
+    
+    client.set_spending_limit(&consumer, &retailer, &None);
+    assert_eq!(client.get_spending_limit(&consumer, &retailer), None);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_stream_accrues_per_second_and_cancels_pro_rata() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let supplier = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&consumer, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    
+    assert_eq!(
+        client.try_open_stream(&consumer, &supplier, &usdc, &2i128, &0u64),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    let id = client.open_stream(&consumer, &supplier, &usdc, &2i128, &1_000u64);
+    assert_eq!(usdc_client.balance(&consumer), 8_000);
+    
+    env.ledger().with_mut(|li| li.timestamp = 300);
+    assert_eq!(client.stream_withdrawable(&id), 600);
+    assert_eq!(client.withdraw_stream(&id), 600);
+    assert_eq!(client.withdraw_stream(&id), 0);
+    
+    // Cancelling pays the supplier up to now and refunds the rest
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_stream(&stranger, &id),
+        Err(Ok(STRGRIDExt2Error::NotStreamParty.into()))
+    );
+    assert_eq!(client.cancel_stream(&consumer, &id), 1_200);
+    assert_eq!(usdc_client.balance(&supplier), 800);
+    assert_eq!(usdc_client.balance(&consumer), 9_200);
+    assert_eq!(client.get_stream(&id).cancelled_at, Some(400));
+    assert_eq!(
+        client.try_cancel_stream(&supplier, &id),
+        Err(Ok(STRGRIDExt2Error::StreamEnded.into()))
+    );
+    
+    // Nothing accrues after cancellation
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(client.stream_withdrawable(&id), 0);
+    
+    // A stream that runs to the end pays out in full
+    let id = client.open_stream(&consumer, &supplier, &usdc, &1i128, &2_500u64);
+    env.ledger().with_mut(|li| li.timestamp = 3_000);
+    assert_eq!(client.withdraw_stream(&id), 500);
+    assert_eq!(usdc_client.balance(&fixture.contract_id), 0);
+    assert_eq!(
+        client.try_get_stream(&99u64),
+        Err(Ok(STRGRIDExt2Error::StreamNotFound.into()))
+    );
+}
+
+#[test]
+fn test_keeper_executes_scheduled_transfer_for_reward() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fixture.mint(&generator, 200);
+    
+    client.set_keeper_reward(&2i128);
+    assert_eq!(
+        client.try_schedule_transfer(&generator, &generator, &50i128, &1_000u64),
+        Err(Ok(STRGRIDError::SelfTransfer.into()))
+    );
+    let id = client.schedule_transfer(&generator, &consumer, &50i128, &1_000u64);
+    assert_eq!(client.get_scheduled_transfer(&id).keeper_reward, 2);
+    
+    // The reward is fixed when the order is placed
+    client.set_keeper_reward(&5i128);
+    assert_eq!(
+        client.try_execute_scheduled_transfer(&keeper, &id),
+        Err(Ok(STRGRIDExt2Error::ScheduledTransferNotDue.into()))
+    );
+    assert_eq!(client.balance_of(&generator), 200);
+    
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.execute_scheduled_transfer(&keeper, &id);
+    assert_eq!(client.balance_of(&consumer), 50);
+    assert_eq!(client.balance_of(&keeper), 2);
+    assert_eq!(client.balance_of(&generator), 148);
+    assert_eq!(
+        client.try_execute_scheduled_transfer(&keeper, &id),
+        Err(Ok(STRGRIDExt2Error::ScheduledTransferNotFound.into()))
+    );
+    
+    let id = client.schedule_transfer(&generator, &consumer, &50i128, &2_000u64);
+    client.cancel_scheduled_transfer(&id);
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(
+        client.try_execute_scheduled_transfer(&keeper, &id),
+        Err(Ok(STRGRIDExt2Error::ScheduledTransferNotFound.into()))
+    );
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_keeper_tasks_pay_creator_funded_bounties() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    let rival = Address::generate(&env);
+    fixture.mint(&generator, 200);
+    
+    // Keepers see the execution's priority classification when the task is posted
+    client.set_operation_priority(&Symbol::new(&env, "complete_task"), &Priority::GridCritical);
+    let sweep = client.create_task(&generator, &KeeperAction::SweepExpired(10), &5i128);
+    let (_, _, _, priority): (u64, KeeperAction, i128, Priority) =
+        env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(priority, Priority::GridCritical);
+    assert_eq!(client.balance_of(&generator), 195);
+    assert_eq!(client.claim_task(&keeper, &sweep), TASK_CLAIM_SECONDS);
+    assert_eq!(
+        client.try_claim_task(&rival, &sweep),
+        Err(Ok(STRGRIDExt2Error::TaskClaimed.into()))
+    );
+    assert_eq!(
+        client.try_cancel_task(&sweep),
+        Err(Ok(STRGRIDExt2Error::TaskClaimed.into()))
+    );
+    // Nothing has expired yet, so the keeper cannot collect
+    assert_eq!(
+        client.try_complete_task(&keeper, &sweep),
+        Err(Ok(STRGRIDExt2Error::TaskNotReady.into()))
+    );
+    
+    // Once the claim lapses another keeper can take over
+    env.ledger().with_mut(|li| li.timestamp = 86_401);
+    client.claim_task(&rival, &sweep);
+    assert_eq!(
+        client.try_complete_task(&keeper, &sweep),
+        Err(Ok(STRGRIDExt2Error::NotTaskClaimant.into()))
+    );
+    assert_eq!(client.complete_task(&rival, &sweep), 5);
+    let (_, _, priority): (u64, i128, Priority) =
+        env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(priority, Priority::GridCritical);
+    assert_eq!(client.balance_of(&rival), 5);
+    assert_eq!(client.get_stats().expired_unswept, 0);
+    
+    // Scheduled transfers can be wrapped in a task
+    let order = client.schedule_transfer(&generator, &consumer, &50i128, &90_000u64);
+    let task = client.create_task(&generator, &KeeperAction::ScheduledTransfer(order), &3i128);
+    env.ledger().with_mut(|li| li.timestamp = 90_000);
+    client.claim_task(&keeper, &task);
+    client.complete_task(&keeper, &task);
+    assert_eq!(client.balance_of(&consumer), 50);
+    assert_eq!(client.balance_of(&keeper), 3);
+    assert_eq!(
+        client.try_get_task(&task),
+        Err(Ok(STRGRIDExt2Error::TaskNotFound.into()))
+    );
+    
+    // Unclaimed tasks refund the bounty on cancellation
+    let task = client.create_task(&generator, &KeeperAction::SettleAuction(1), &4i128);
+    assert_eq!(client.balance_of(&generator), 138);
+    client.cancel_task(&task);
+    assert_eq!(client.balance_of(&generator), 142);
+    assert_eq!(client.balance_of(&fixture.contract_id), 0);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_batch_mint_of_interval_readings() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env)
+        .generators(1, 1_000)
+        .mock_oracle()
+        .build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    client.set_mint_cooldown(&3_600u64);
+    
+    let readings = vec![
+        &env,
+        (10i128, 24u64, fixture.oracle_proof(&generator, 10)),
+        (12i128, 24u64, fixture.oracle_proof(&generator, 12)),
+        (9i128, 48u64, fixture.oracle_proof(&generator, 9)),
+    ];
+    let token_ids = client.mint_energy_tokens_batch(&generator, &readings);
+    assert_eq!(token_ids, vec![&env, 1u64, 2u64, 3u64]);
+    assert_eq!(client.balance_of(&generator), 31);
+    assert_eq!(client.get_energy_token(&3u64).amount_kwh, 9);
+    assert_eq!(client.get_energy_token(&3u64).expiry_timestamp, 48 * 3_600);
+    
+    // The cooldown applies to the invocation as a whole
+    let next = vec![&env, (5i128, 24u64, fixture.oracle_proof(&generator, 5))];
+    assert_eq!(
+        client.try_mint_energy_tokens_batch(&generator, &next),
+        Err(Ok(STRGRIDError::MintTooFrequent.into()))
+    );
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    
+    // Every reading still needs its own proof
+    let unsigned = vec![&env, (5i128, 24u64, fixture.oracle_proof(&generator, 5)), (5i128, 24u64, None)];
+    assert_eq!(
+        client.try_mint_energy_tokens_batch(&generator, &unsigned),
+        Err(Ok(STRGRIDExtError::OracleProofRequired.into()))
+    );
+    assert_eq!(
+        client.try_mint_energy_tokens_batch(&generator, &Vec::new(&env)),
+        Err(Ok(STRGRIDError::BatchTooLarge.into()))
+    );
+    client.mint_energy_tokens_batch(&generator, &next);
+    assert_eq!(client.balance_of(&generator), 36);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_batch_burn_debits_once_and_issues_certificates() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(2, 1_000).build();
+    let client = &fixture.client;
+    let solar = fixture.generators.get_unchecked(0);
+    let wind = fixture.generators.get_unchecked(1);
+    let retailer = Address::generate(&env);
+    let first = fixture.mint(&solar, 100);
+    let second = fixture.mint(&solar, 100);
+    let third = fixture.mint(&wind, 50);
+    client.transfer(&solar, &retailer, &120i128);
+    client.transfer(&wind, &retailer, &50i128);
+    let supply = client.total_supply();
+    
+    assert_eq!(
+        client.try_burn_energy_tokens_batch(&retailer, &vec![&env, (first, 10i128), (first, 10i128)]),
+        Err(Ok(STRGRIDError::AlreadyBurned.into()))
+    );
+    assert_eq!(
+        client.try_burn_energy_tokens_batch(&retailer, &vec![&env, (first, 100i128), (third, 80i128)]),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    assert_eq!(client.balance_of(&retailer), 170);
+    
+    let certificates = client.burn_energy_tokens_batch(
+        &retailer,
+        &vec![&env, (first, 60i128), (second, 40i128), (third, 30i128)],
+    );
+    assert_eq!(certificates.len(), 3);
+    assert_eq!(client.get_certificate(&certificates.get_unchecked(2)).amount_kwh, 30);
+    assert_eq!(client.balance_of(&retailer), 40);
+    assert_eq!(client.total_supply(), supply - 130);
+    assert!(client.get_energy_token(&second).is_consumed);
+    // Lots from the same generator both reduce its production
+    assert_eq!(client.get_generator(&solar).current_production, 100);
+    assert_eq!(client.get_generator(&wind).current_production, 20);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_preview_transfer_matches_transfer() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let zone = client.get_generator(&generator).zone;
+    client.register_consumer(&consumer, &ConsumerProfile {
+        class: ConsumerClass::Residential,
+        zone: zone + 1,
+        expected_monthly_kwh: 250,
+    });
+    fixture.mint(&generator, 500);
+    client.set_transfer_fee(&100u32, &treasury);
+    client.set_loss_factor(&zone, &(zone + 1), &500u32);
+    
+    let preview = client.preview_transfer(&generator, &consumer, &200i128);
+    assert!(preview.ok);
+    assert_eq!((preview.fee, preview.loss, preview.received), (2, 9, 189));
+    assert_eq!((preview.from_balance_before, preview.from_balance_after), (500, 300));
+    assert_eq!(preview.to_balance_after, 189);
+    // Nothing moved yet
+    assert_eq!(client.balance_of(&consumer), 0);
+    
+    client.transfer(&generator, &consumer, &200i128);
+    assert_eq!(client.balance_of(&generator), preview.from_balance_after);
+    assert_eq!(client.balance_of(&consumer), preview.to_balance_after);
+    
+    // Failures surface the error the transfer would raise without aborting
+    let failed = client.preview_transfer(&consumer, &generator, &190i128);
+    assert!(!failed.ok);
+    assert_eq!(failed.error, Some(STRGRIDError::InsufficientBalance as u32));
+    assert_eq!((failed.received, failed.to_balance_after), (0, 0));
+    assert_eq!(
+        client.try_transfer(&consumer, &generator, &190i128),
+        Err(Ok(STRGRIDError::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        client.preview_transfer(&consumer, &generator, &0i128).error,
+        Some(STRGRIDError::InvalidAmount as u32)
+    );
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_config_partial_updates_emit_diffs() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(1, 1_000).build();
+    let client = &fixture.client;
+    let manager = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    let config = client.get_config();
+    assert_eq!((config.transfer_fee_bps, config.treasury), (0, None));
+    assert_eq!(config.mint_caps.max_mint, None);
+    assert_eq!((config.mint_cooldown, config.dispute_window, config.strict_mode), (0, 0, false));
+    
+    let changes = vec![&env, ConfigChange::MintCooldown(60)];
+    assert_eq!(
+        client.try_set_config(&manager, &changes),
+        Err(Ok(STRGRIDError::NotAuthorized.into()))
+    );
+    client.grant_role(&Role::ConfigManager, &manager);
+    
+    // Only the listed parameters change
+    let fee = TransferFee { fee_bps: 50, treasury: treasury.clone() };
+    let config = client.set_config(
+        &manager,
+        &vec![&env, ConfigChange::TransferFee(fee.clone()), ConfigChange::DisputeWindow(3_600)],
+    );
+    let emitted = env.events().all();
+    assert_eq!((config.transfer_fee_bps, config.treasury), (50, Some(treasury.clone())));
+    assert_eq!(client.get_transfer_fee(), Some(fee));
+    assert_eq!(config.dispute_window, 3_600);
+    assert_eq!(config.mint_cooldown, 0);
+    assert_eq!(client.get_dispute_window(), 3_600);
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(
+        topics,
+        (events::CONFIG_CHANGED, manager.clone(), Symbol::new(&env, "dispute_window")).into_val(&env)
+    );
+    let diff: (u64, u64) = data.into_val(&env);
+    assert_eq!(diff, (0, 3_600));
+    
+    // The admin keeps access and strict mode rejects no-op changes
+    client.set_config(&fixture.admin, &vec![&env, ConfigChange::StrictMode(true)]);
+    assert_eq!(
+        client.try_set_config(&manager, &vec![&env, ConfigChange::DisputeWindow(3_600)]),
+        Err(Ok(STRGRIDError::NoOpUpdate.into()))
+    );
+    let oracle = Address::generate(&env);
+    let config = client.set_config(&manager, &vec![&env, ConfigChange::MintOracle(Some(oracle.clone()))]);
+    assert_eq!(config.mint_oracle, Some(oracle));
+    assert!(config.strict_mode);
+}
+
+#[test]
+fn test_constructor_applies_config_at_deploy() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    
+    // Admin, metadata and parameters land in the deploy itself: no initialize call to front-run
+    let config = Config {
+        transfer_fee_bps: 25,
+        treasury: Some(treasury.clone()),
+        mint_caps: MintCaps { max_mint: Some(5_000), ..Default::default() },
+        mint_cooldown: 300,
+        dispute_window: 3_600,
+        strict_mode: true,
+        mint_oracle: Some(oracle.clone()),
+    };
+    let contract_id = env.register(
+        STRGRIDContract,
+        (
+            admin.clone(),
+            String::from_str(&env, "StellarGrid Energy Token"),
+            String::from_str(&env, "STRGRID"),
+            7u32,
+            true,
+            config.clone(),
+        ),
+    );
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    
+    assert_eq!(client.get_config(), config);
+    assert_eq!(client.get_transfer_fee(), Some(TransferFee { fee_bps: 25, treasury }));
+    assert_eq!(client.get_mint_oracle(), Some(oracle));
+    assert!(client.is_strict_mode());
+    assert!(client.is_clawback_enabled());
+    assert_eq!(client.get_metadata().symbol, String::from_str(&env, "STRGRID"));
+    
+    let entry = client.get_audit_log(&0u64, &1u32).get(0).unwrap();
+    assert_eq!(entry.actor, admin);
+    assert_eq!(entry.action, Symbol::new(&env, "__constructor"));
+    
+    // Defaults leave every optional parameter unset
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    assert_eq!(client.get_config(), Config::default());
+    assert_eq!(client.get_mint_caps(), None);
+    assert_eq!(client.get_transfer_fee(), None);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_constructor_rejects_fee_without_treasury() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let config = Config { transfer_fee_bps: 25, ..Default::default() };
+    env.register(
+        STRGRIDContract,
+        (
+            admin,
+            String::from_str(&env, "STRGRID"),
+            String::from_str(&env, "STRGRID"),
+            7u32,
+            false,
+            config,
+        ),
+    );
+}
+
+#[test]
+fn test_legacy_u64_amounts_are_migrated() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 50).build();
+    let client = &fixture.client;
+    let user = fixture.users.get_unchecked(0);
+    let spender = Address::generate(&env);
+    let supply = client.total_supply();
+    let ledger = env.ledger().sequence();
+    let balance_checkpoints = strgrid_codes::StorageKey::BalanceCheckpoints.symbol();
+    
+    // Rewrite the state as it was stored before amounts moved to i128
+    env.as_contract(&fixture.contract_id, || {
+        let persistent = env.storage().persistent();
+        persistent.set(&(BALANCE, user.clone()), &50u64);
+        persistent.set(&(balance_checkpoints.clone(), user.clone()), &vec![&env, (ledger, 50u64)]);
+        persistent.set(
+            &(ALLOWANCE, user.clone(), spender.clone()),
+            &migration::LegacyAllowanceValue { amount: 30, live_until_ledger: None },
+        );
+        let instance = env.storage().instance();
+        instance.set(&TOTAL_SUPPLY, &(supply as u64));
+        instance.set(
+            &METADATA,
+            &migration::LegacyTokenMetadata {
+                name: String::from_str(&env, "STRGRID"),
+                symbol: String::from_str(&env, "STRGRID"),
+                decimals: 7,
+                total_supply: 0,
+            },
+        );
+    });
+    
+    // Legacy entries are read as they are
+    assert_eq!(client.balance_of(&user), 50);
+    assert_eq!(client.total_supply(), supply);
+    assert_eq!(client.allowance(&user, &spender), 30);
+    assert_eq!(client.balance_at(&user, &ledger), 50);
+    assert_eq!(client.get_metadata().decimals, 7);
+    assert_eq!(client.get_metadata().total_supply, supply);
+    
+    // ...and written back as i128 by the next operation
+    client.transfer_from(&spender, &user, &spender, &10i128);
+    assert_eq!(client.balance_of(&user), 40);
+    assert_eq!(client.allowance(&user, &spender), 20);
+    assert_eq!(client.balance_at(&user, &ledger), 40);
+    crate::testutils::assert_invariants(client);
+    
+    // The admin can migrate the remaining entries eagerly
+    let empty = Address::generate(&env);
+    client.migrate_amounts(&vec![&env, user.clone(), spender.clone(), empty.clone()]);
+    let emitted = env.events().all();
+    let (_, topics, data) = emitted.last().unwrap();
+    assert_eq!(topics, (events::AMOUNTS_MIGRATED,).into_val(&env));
+    let migrated: u32 = data.into_val(&env);
+    assert_eq!(migrated, 3);
+    let supply = client.total_supply();
+    env.as_contract(&fixture.contract_id, || {
+        let balance: Option<i128> = env.storage().persistent().get(&(BALANCE, user.clone()));
+        assert_eq!(balance, Some(40));
+        let stored: Option<i128> = env.storage().instance().get(&TOTAL_SUPPLY);
+        assert_eq!(stored, Some(supply));
+        let metadata: TokenMetadata = env.storage().instance().get(&METADATA).unwrap();
+        assert_eq!((metadata.decimals, metadata.total_supply), (7, supply));
+        // Accounts without a balance do not get a zero entry
+        assert!(!env.storage().persistent().has(&(BALANCE, empty.clone())));
+    });
+    
+    let mut accounts = Vec::new(&env);
+    for _ in 0..=MAX_BATCH_SIZE {
+        accounts.push_back(user.clone());
+    }
+    assert_eq!(
+        client.try_migrate_amounts(&accounts),
+        Err(Ok(STRGRIDError::BatchTooLarge.into()))
+    );
+}
+
+#[test]
+fn test_supply_beyond_u64() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).generators(2, u64::MAX).build();
+    let client = &fixture.client;
+    let (first, second) = (fixture.generators.get_unchecked(0), fixture.generators.get_unchecked(1));
+    
+    // Each mint fits in u64; their sum no longer does
+    let beyond = 2 * i128::from(u64::MAX);
+    fixture.mint(&first, u64::MAX);
+    fixture.mint(&second, u64::MAX);
+    assert_eq!(client.total_supply(), beyond);
+    assert_eq!(client.get_stats().total_minted, beyond);
+    assert_eq!(client.get_metadata().total_supply, beyond);
+    
+    client.transfer(&first, &second, &i128::from(u64::MAX));
+    assert_eq!(client.balance_of(&first), 0);
+    assert_eq!(client.balance_of(&second), beyond);
+    
+    // Amounts past u64 move in a single call and a single journal entry
+    client.transfer(&second, &first, &beyond);
+    assert_eq!(client.balance_of(&first), beyond);
+    let entry = client.journal_entries(&client.journal_length(), &1u32).get_unchecked(0);
+    assert_eq!(entry.amount, beyond);
+    // Two mints and two transfers: u64::MAX * (1 + 1 + 1 + 2)
+    assert_eq!(client.journal_rollup(&0u64).unwrap().total_debits, 5 * i128::from(u64::MAX));
+    crate::testutils::assert_invariants(client);
+    
+    let new_key = Address::generate(&env);
+    let guardians = vec![&env, Address::generate(&env), Address::generate(&env)];
+    client.set_recovery(&first, &Some(RecoveryConfig {
+        guardians: guardians.clone(),
+        threshold: 2,
+        delay: 0,
+    }));
+    client.propose_recovery(&guardians.get_unchecked(0), &first, &new_key);
+    client.approve_recovery(&guardians.get_unchecked(1), &first);
+    let length = client.journal_length();
+    assert_eq!(client.execute_recovery(&first), beyond);
+    assert_eq!(client.balance_of(&new_key), beyond);
+    assert_eq!(client.journal_length(), length + 1);
+    
+    // Pool entrypoints take the same i128 amounts
+    client.fund_distribution(&new_key, &beyond);
+    assert_eq!(client.distribution_pool(), beyond);
+    client.distribute(&vec![&env, (second.clone(), beyond)]);
+    assert_eq!(client.balance_of(&second), beyond);
+    crate::testutils::assert_invariants(client);
+    
+    client.approve(&second, &first, &i128::MAX);
+    assert_eq!(client.allowance(&second, &first), i128::MAX);
+    assert_eq!(
+        client.try_approve(&second, &first, &-1i128),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn test_lot_cannot_be_burned_before_it_can_no_longer_be_challenged() {
+    let env = Env::default();
+    
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    
+    env.mock_all_auths();
+    let contract_id = testutils::deploy(&env, &admin, false);
+    let client = STRGRIDContractClient::new(&env, &contract_id);
+    client.register_generator(&generator, &1000u64);
+    client.grant_role(&Role::Auditor, &auditor);
+    
+    // An older lot outside any window gives the generator unlocked balance to burn with
+    let honest_id = client.mint_energy_tokens(&generator, &300i128, &24u64, &None);
+    client.set_dispute_window(&3600u64);
+    let fraud_id = client.mint_energy_tokens(&generator, &200i128, &24u64, &None);
+    
+    // Consuming the disputed lot would put it out of the auditor's reach
+    assert_eq!(
+        client.try_burn_energy_tokens(&generator, &fraud_id, &100i128),
+        Err(Ok(STRGRIDError::TokensLocked.into()))
+    );
+    client.challenge_mint(&auditor, &fraud_id, &BytesN::from_array(&env, &[3u8; 32]));
+    assert!(client.get_energy_token(&fraud_id).is_voided);
+    assert_eq!(client.balance_of(&generator), 300);
+    
+    client.burn_energy_tokens(&generator, &honest_id, &100i128);
+    assert_eq!(client.balance_of(&generator), 200);
+    
+    // Once the window is over the lot is consumable and no longer challengeable
+    let late_id = client.mint_energy_tokens(&generator, &100i128, &24u64, &None);
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    client.burn_energy_tokens(&generator, &late_id, &100i128);
+    assert_eq!(
+        client.try_challenge_mint(&auditor, &late_id, &BytesN::from_array(&env, &[4u8; 32])),
+        Err(Ok(STRGRIDError::DisputeWindowClosed.into()))
+    );
+}
+
+#[test]
+fn test_lock_queries_leave_storage_untouched() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let consumer = Address::generate(&env);
+    let locked_key = (strgrid_codes::StorageKey::Locked.symbol(), generator.clone());
+    let has_locks = || {
+        env.as_contract(&fixture.contract_id, || env.storage().persistent().has(&locked_key))
+    };
+    
+    client.set_dispute_window(&3600u64);
+    let token_id = fixture.mint(&generator, 200);
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    
+    // Queries and previews see the lot as released without pruning the lock list
+    assert_eq!(client.locked_balance_of(&generator), 0);
+    assert_eq!(client.preview_burn(&generator, &token_id, &50i128).balance_after, 150);
+    assert!(client.preview_transfer(&generator, &consumer, &50i128).ok);
+    assert!(has_locks());
+    
+    // The next write drops it
+    client.transfer(&generator, &consumer, &50i128);
+    assert!(!has_locks());
+}
+
+#[test]
+fn test_compliance_hook_sees_auction_seller_and_buyer() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let buyer = fixture.users.get_unchecked(0);
+    let token_id = client.mint_energy_tokens(&generator, &500i128, &10u64, &None);
+    
+    let usdc = env.register_stellar_asset_contract_v2(fixture.admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &10_000i128);
+    
+    let hook_id = env.register(blocklist_hook::BlocklistHook, ());
+    let hook = blocklist_hook::BlocklistHookClient::new(&env, &hook_id);
+    client.set_compliance_hook(&Some(hook_id));
+    
+    // Custody hides the pair, but a bid is still checked as seller -> buyer
+    let auction_id = client.start_auction(&generator, &token_id, &200u64, &usdc, &10i128, &10i128);
+    hook.block(&buyer);
+    assert_eq!(
+        client.try_bid(&buyer, &auction_id, &50u64),
+        Err(Ok(STRGRIDExtError::TransferRejected.into()))
+    );
+    assert_eq!(client.balance_of(&buyer), 0);
+    
+    // Unsold kWh still go back to the seller once the lot expires
+    env.ledger().with_mut(|li| li.timestamp += 10 * 3600);
+    client.settle(&auction_id);
+    assert_eq!(client.balance_of(&generator), 500);
+    crate::testutils::assert_invariants(client);
+}
+
+#[test]
+fn test_previews_report_failures_without_calling_out() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(2, 100).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let alice = fixture.users.get_unchecked(0);
+    let bob = fixture.users.get_unchecked(1);
+    
+    // Locked kWh come back as a structured failure, not an abort
+    client.set_dispute_window(&3600u64);
+    let token_id = fixture.mint(&generator, 200);
+    let preview = client.preview_transfer(&generator, &alice, &150i128);
+    assert_eq!((preview.ok, preview.error), (false, Some(STRGRIDError::TokensLocked as u32)));
+    let preview = client.preview_burn(&generator, &token_id, &50i128);
+    assert_eq!((preview.ok, preview.error), (false, Some(STRGRIDError::TokensLocked as u32)));
+    assert_eq!(preview.generator, generator);
+    
+    // The external compliance hook is never invoked by a preview
+    let hook_id = env.register(blocklist_hook::BlocklistHook, ());
+    let hook = blocklist_hook::BlocklistHookClient::new(&env, &hook_id);
+    client.set_compliance_hook(&Some(hook_id));
+    hook.block(&bob);
+    assert!(client.preview_transfer(&alice, &bob, &10i128).ok);
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &10i128),
+        Err(Ok(STRGRIDExtError::TransferRejected.into()))
+    );
+    
+    // Frozen senders fail the preview with the transfer's own error
+    client.freeze_account(&alice);
+    assert_eq!(
+        client.preview_transfer(&alice, &generator, &10i128).error,
+        Some(STRGRIDError::AccountFrozen as u32)
+    );
+}
+
+#[test]
+fn test_amounts_reject_negatives() {
+    let env = Env::default();
+    let fixture = crate::testutils::FixtureBuilder::new(&env).users(1, 0).build();
+    let client = &fixture.client;
+    let generator = fixture.generators.get_unchecked(0);
+    let user = fixture.users.get_unchecked(0);
+    let lot = fixture.mint(&generator, 100);
+    client.approve(&generator, &user, &50i128);
+    let invalid = Err(Ok(STRGRIDError::InvalidAmount.into()));
+    
+    assert_eq!(client.try_transfer(&generator, &user, &-1i128), invalid);
+    assert_eq!(client.try_transfer_from(&user, &generator, &user, &-1i128), invalid);
+    assert_eq!(client.try_transfer_batch(&generator, &vec![&env, (user.clone(), -1i128)]), invalid);
+    assert_eq!(client.try_burn(&generator, &-1i128), invalid);
+    assert_eq!(client.try_burn_from(&user, &generator, &-1i128), invalid);
+    assert_eq!(client.try_set_spending_limit(&generator, &user, &Some((-1i128, 10u32))), invalid);
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &-1i128, &24u64, &None),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    assert_eq!(
+        client.preview_transfer(&generator, &user, &-1i128).error,
+        Some(STRGRIDError::InvalidAmount as u32)
+    );
+    assert_eq!(client.try_fund_distribution(&generator, &-1i128), invalid);
+    assert_eq!(client.try_distribute(&vec![&env, (user.clone(), -1i128)]), invalid);
+    assert_eq!(client.try_fund_incentives(&generator, &-1i128), invalid);
+    assert_eq!(client.try_unwrap(&generator, &-1i128), invalid);
+    assert_eq!(client.try_wrap(&generator, &-1i128), invalid);
+    assert_eq!(client.try_set_keeper_reward(&-1i128), invalid);
+    let invalid_id = Err(Ok(STRGRIDError::InvalidAmount.into()));
+    assert_eq!(client.try_burn_energy_tokens(&generator, &lot, &-1i128), invalid_id);
+    assert_eq!(client.try_burn_energy_tokens(&generator, &lot, &0i128), invalid_id);
+    assert_eq!(client.try_burn_energy_tokens_from(&user, &generator, &lot, &-1i128), invalid_id);
+    assert_eq!(client.try_schedule_transfer(&generator, &user, &-1i128, &0u64), invalid_id);
+    assert_eq!(
+        client.try_create_task(&generator, &KeeperAction::SweepExpired(1), &-1i128),
+        invalid_id
+    );
+    assert_eq!(
+        client.try_burn_energy_tokens_batch(&generator, &vec![&env, (lot, -1i128)]),
+        Err(Ok(STRGRIDError::InvalidAmount.into()))
+    );
+    
+    // Lots are bounded by the generator's u64 capacity
+    assert_eq!(
+        client.try_mint_energy_tokens(&generator, &(i128::from(u64::MAX) + 1), &24u64, &None),
+        Err(Ok(STRGRIDError::InsufficientCapacity.into()))
+    );
+    assert_eq!(
+        client.try_burn_energy_tokens(&generator, &lot, &(i128::from(u64::MAX) + 1)),
+        Err(Ok(STRGRIDError::InsufficientCapacity.into()))
+    );
+    assert_eq!(client.balance_of(&generator), 100);
+    assert_eq!(client.allowance(&generator, &user), 50);
 }
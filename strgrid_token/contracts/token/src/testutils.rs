@@ -0,0 +1,230 @@
+//! Utilitários de teste para quem integra com o STRGRID (feature `testutils`)
+//!
+//! `FixtureBuilder` monta um contrato inicializado com geradores registrados e usuários com
+//! saldo, opcionalmente com o `strgrid-mock-oracle` como oracle de mint; `assert_invariants`
+//! confere a contabilidade do token a qualquer momento do teste.
+//!
+//! ```ignore
+//! let env = Env::default();
+//! let fixture = FixtureBuilder::new(&env).generators(2, 1_000).users(3, 100).build();
+//! fixture.client.transfer(&fixture.users.get_unchecked(0), &fixture.users.get_unchecked(1), &40);
+//! assert_invariants(&fixture.client);
+//! ```
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+use strgrid_mock_oracle::{MockOracleContract, MockOracleContractClient};
+
+use crate::query::MAX_PAGE_SIZE;
+use crate::{Config, STRGRIDContract, STRGRIDContractClient, TokenFilter};
+
+/// Contrato pronto para uso com os participantes criados pelo `FixtureBuilder`
+pub struct Fixture<'a> {
+    pub env: Env,
+    pub contract_id: Address,
+    pub client: STRGRIDContractClient<'a>,
+    pub admin: Address,
+    pub generators: Vec<Address>,
+    pub users: Vec<Address>,
+    /// Oracle simulado configurado como oracle de mint, se pedido ao builder
+    pub oracle: Option<Address>,
+}
+
+impl Fixture<'_> {
+    /// Prova do oracle simulado para um mint do gerador; `None` se o fixture não tem oracle
+    pub fn oracle_proof(&self, generator: &Address, amount_kwh: u64) -> Option<BytesN<32>> {
+        self.oracle.as_ref().map(|oracle| {
+            MockOracleContractClient::new(&self.env, oracle).sign_reading(generator, &amount_kwh)
+        })
+    }
+
+    /// Emite um lote do gerador, assinando a leitura quando há oracle de mint
+    pub fn mint(&self, generator: &Address, amount_kwh: u64) -> u64 {
+        let proof = self.oracle_proof(generator, amount_kwh);
+        self.client
+            .mint_energy_tokens(generator, &i128::from(amount_kwh), &24u64, &proof)
+    }
+}
+
+/// Registra o contrato com os metadados do STRGRID e a configuração padrão
+pub fn deploy(env: &Env, admin: &Address, clawback_enabled: bool) -> Address {
+    env.register(
+        STRGRIDContract,
+        (
+            admin.clone(),
+            String::from_str(env, "STRGRID"),
+            String::from_str(env, "STRGRID"),
+            7u32,
+            clawback_enabled,
+            Config::default(),
+        ),
+    )
+}
+
+/// Monta um `Fixture`; por padrão, um gerador de 1000 kW e nenhum usuário
+pub struct FixtureBuilder {
+    env: Env,
+    generators: u32,
+    capacity_kw: u64,
+    users: u32,
+    funding_kwh: u64,
+    mock_oracle: bool,
+}
+
+impl FixtureBuilder {
+    pub fn new(env: &Env) -> Self {
+        FixtureBuilder {
+            env: env.clone(),
+            generators: 1,
+            capacity_kw: 1_000,
+            users: 0,
+            funding_kwh: 0,
+            mock_oracle: false,
+        }
+    }
+
+    /// Quantidade de geradores registrados e a capacidade de cada um
+    pub fn generators(mut self, count: u32, capacity_kw: u64) -> Self {
+        self.generators = count;
+        self.capacity_kw = capacity_kw;
+        self
+    }
+
+    /// Quantidade de usuários e o saldo inicial de cada um
+    ///
+    /// O saldo vem de um lote emitido pelo gerador responsável pelo usuário (rodízio entre os
+    /// geradores), então a capacidade precisa cobrir os usuários atendidos por cada gerador.
+    pub fn users(mut self, count: u32, funding_kwh: u64) -> Self {
+        self.users = count;
+        self.funding_kwh = funding_kwh;
+        self
+    }
+
+    /// Registra um `strgrid-mock-oracle` e o configura como oracle de mint
+    ///
+    /// Os mints passam a exigir prova; use `Fixture::mint` ou `Fixture::oracle_proof`.
+    pub fn mock_oracle(mut self) -> Self {
+        self.mock_oracle = true;
+        self
+    }
+
+    /// Registra o contrato e prepara os participantes; habilita `mock_all_auths` no ambiente
+    pub fn build<'a>(self) -> Fixture<'a> {
+        let env = self.env;
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let contract_id = deploy(&env, &admin, false);
+        let client = STRGRIDContractClient::new(&env, &contract_id);
+
+        let oracle = self.mock_oracle.then(|| {
+            let oracle = env.register(MockOracleContract, ());
+            client.set_mint_oracle(&Some(oracle.clone()));
+            oracle
+        });
+
+        let mut generators = Vec::new(&env);
+        for _ in 0..self.generators {
+            let generator = Address::generate(&env);
+            client.register_generator(&generator, &self.capacity_kw);
+            generators.push_back(generator);
+        }
+
+        let mut users = Vec::new(&env);
+        for _ in 0..self.users {
+            users.push_back(Address::generate(&env));
+        }
+
+        let fixture = Fixture {
+            env,
+            contract_id,
+            client,
+            admin,
+            generators,
+            users,
+            oracle,
+        };
+
+        if self.funding_kwh > 0 && !fixture.users.is_empty() {
+            assert!(!fixture.generators.is_empty(), "funded users need at least one generator");
+            for (index, generator) in fixture.generators.iter().enumerate() {
+                let served = (index as u32..self.users).step_by(self.generators as usize);
+                let funded = served.clone().count() as u64;
+                if funded == 0 {
+                    continue;
+                }
+                fixture.mint(&generator, self.funding_kwh * funded);
+                for user in served {
+                    fixture.client.transfer(
+                        &generator,
+                        &fixture.users.get_unchecked(user),
+                        &i128::from(self.funding_kwh),
+                    );
+                }
+            }
+        }
+        fixture
+    }
+}
+
+/// Confere as invariantes contábeis do token e entra em pânico na primeira violação
+///
+/// - o supply é a soma dos saldos do registro de titulares, e todo titular tem saldo;
+/// - as estatísticas batem com o supply (`total_minted - total_burned`);
+/// - nenhum gerador com lotes registrados produz acima da capacidade.
+pub fn assert_invariants(client: &STRGRIDContractClient) {
+    let env = &client.env;
+    let total_supply = client.total_supply();
+
+    let holder_count = client.holder_count();
+    let mut sum = 0i128;
+    let mut offset = 0u32;
+    while offset < holder_count {
+        for holder in client.holders(&offset, &MAX_PAGE_SIZE).iter() {
+            let balance = client.balance_of(&holder);
+            assert!(balance > 0, "holder registry lists an empty balance");
+            sum += balance;
+        }
+        offset += MAX_PAGE_SIZE;
+    }
+    assert_eq!(sum, total_supply, "total supply differs from the sum of balances");
+
+    let stats = client.get_stats();
+    assert_eq!(stats.total_supply, total_supply, "stats total supply is stale");
+    assert_eq!(
+        stats.total_minted - stats.total_burned,
+        total_supply,
+        "minted - burned differs from total supply"
+    );
+
+    let filter = TokenFilter {
+        generator: None,
+        source_types: Vec::new(env),
+        zone: None,
+        expiry_from: None,
+        expiry_to: None,
+        is_consumed: None,
+        vintage_year: None,
+        vintage_month: None,
+    };
+    let mut checked: Vec<Address> = Vec::new(env);
+    let mut offset = 0u32;
+    loop {
+        let page = client.find_tokens(&filter, &offset, &MAX_PAGE_SIZE);
+        for token in page.iter() {
+            if checked.contains(&token.generator_id) {
+                continue;
+            }
+            let generator = client.get_generator(&token.generator_id);
+            assert!(
+                generator.current_production <= generator.capacity_kw,
+                "generator production exceeds capacity"
+            );
+            checked.push_back(token.generator_id);
+        }
+        if page.len() < MAX_PAGE_SIZE {
+            break;
+        }
+        offset += MAX_PAGE_SIZE;
+    }
+}
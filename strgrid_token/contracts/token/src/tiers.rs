@@ -0,0 +1,122 @@
+use soroban_sdk::{contractimpl, contracttype, panic_with_error, Address, Env, Error, Symbol};
+use strgrid_codes::StorageKey;
+
+use crate::bond::BondConfig;
+use crate::roles::{require_role, Role};
+use crate::{
+    audit, caps, dispute, events, require_admin, STRGRIDContract, STRGRIDContractArgs,
+    STRGRIDContractClient, STRGRIDError, GENERATOR,
+};
+
+const TIER_CONFIG: Symbol = StorageKey::TierConfig.symbol();
+const GENERATOR_TIER: Symbol = StorageKey::GeneratorTier.symbol();
+
+/// Porte do gerador, atribuído na aprovação da candidatura
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GeneratorTier {
+    /// Microgeração distribuída (telhados residenciais)
+    Micro,
+    /// Minigeração (comércio, condomínios, fazendas solares pequenas)
+    Small,
+    /// Usinas de grande porte
+    Utility,
+}
+
+/// Regras próprias da classe; campos `None` seguem a configuração global
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierConfig {
+    /// Teto de kWh por mint
+    pub max_mint: Option<u64>,
+    /// Teto de kWh emitidos por gerador no dia
+    pub max_daily_mint: Option<u64>,
+    /// Caução mínima, no ativo de `BondConfig`, no lugar de `required_amount`
+    pub required_bond: Option<i128>,
+    /// Janela de contestação dos lotes emitidos, em segundos
+    pub dispute_window: Option<u64>,
+}
+
+pub(crate) fn tier_of(env: &Env, generator: &Address) -> Option<GeneratorTier> {
+    env.storage()
+        .persistent()
+        .get(&(GENERATOR_TIER, generator.clone()))
+}
+
+fn config_of(env: &Env, tier: GeneratorTier) -> Option<TierConfig> {
+    env.storage().instance().get(&(TIER_CONFIG, tier))
+}
+
+/// Configuração da classe do gerador; geradores sem classe seguem só as regras globais
+fn generator_config(env: &Env, generator: &Address) -> Option<TierConfig> {
+    tier_of(env, generator).and_then(|tier| config_of(env, tier))
+}
+
+pub(crate) fn assign(env: &Env, generator: &Address, tier: GeneratorTier) {
+    env.storage()
+        .persistent()
+        .set(&(GENERATOR_TIER, generator.clone()), &tier);
+    events::tier_assigned(env, generator.clone(), tier);
+}
+
+/// Rejeita mints acima dos tetos da classe, somados aos limites globais de `caps`
+pub(crate) fn check_mint(env: &Env, generator: &Address, amount: u64) -> Result<(), Error> {
+    let Some(config) = generator_config(env, generator) else {
+        return Ok(());
+    };
+    if config.max_mint.is_some_and(|max| amount > max) {
+        return Err(STRGRIDError::MintAboveMaximum.into());
+    }
+    if config
+        .max_daily_mint
+        .is_some_and(|max| caps::minted_today(env, generator) + amount > max)
+    {
+        return Err(STRGRIDError::DailyMintCapExceeded.into());
+    }
+    Ok(())
+}
+
+pub(crate) fn required_bond(env: &Env, generator: &Address, bond: &BondConfig) -> i128 {
+    generator_config(env, generator)
+        .and_then(|config| config.required_bond)
+        .unwrap_or(bond.required_amount)
+}
+
+pub(crate) fn dispute_window(env: &Env, generator: &Address) -> u64 {
+    generator_config(env, generator)
+        .and_then(|config| config.dispute_window)
+        .unwrap_or_else(|| dispute::dispute_window(env))
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Define as regras de uma classe de gerador (apenas admin/governança)
+    pub fn set_tier_config(env: Env, tier: GeneratorTier, config: TierConfig) {
+        let admin = require_admin(&env);
+        audit::record(&env, &admin, "set_tier_config", (tier, config.clone()));
+        if config.required_bond.is_some_and(|bond| bond < 0) {
+            panic_with_error!(&env, STRGRIDError::InvalidAmount);
+        }
+        env.storage().instance().set(&(TIER_CONFIG, tier), &config);
+        events::tier_config_set(&env, tier, config);
+    }
+
+    /// Regras configuradas para a classe, se houver
+    pub fn get_tier_config(env: Env, tier: GeneratorTier) -> Option<TierConfig> {
+        config_of(&env, tier)
+    }
+
+    /// Reclassifica um gerador já cadastrado (operador da rede)
+    pub fn set_generator_tier(env: Env, operator: Address, generator: Address, tier: GeneratorTier) {
+        require_role(&env, &operator, Role::GridOperator);
+        if !env.storage().persistent().has(&(GENERATOR, generator.clone())) {
+            panic_with_error!(&env, STRGRIDError::GeneratorNotFound);
+        }
+        assign(&env, &generator, tier);
+    }
+
+    /// Classe do gerador, se atribuída
+    pub fn tier_of(env: Env, generator: Address) -> Option<GeneratorTier> {
+        tier_of(&env, &generator)
+    }
+}
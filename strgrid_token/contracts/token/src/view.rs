@@ -0,0 +1,61 @@
+use soroban_sdk::{contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use strgrid_codes::StorageKey;
+
+use crate::roles::{has_role, Role};
+use crate::{events, STRGRIDContract, STRGRIDContractArgs, STRGRIDContractClient, STRGRIDError};
+
+const VIEW_GRANTS: Symbol = StorageKey::ViewGrants.symbol();
+
+fn grants_of(env: &Env, owner: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(VIEW_GRANTS, owner.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Exige que `viewer` possa ler os dados restritos de `owner`: o próprio
+/// titular, uma conta com papel de compliance ou um terceiro autorizado
+pub(crate) fn require_viewer(env: &Env, owner: &Address, viewer: &Address) {
+    viewer.require_auth();
+    if viewer == owner
+        || has_role(env, viewer, Role::Compliance)
+        || grants_of(env, owner).contains(viewer)
+    {
+        return;
+    }
+    panic_with_error!(env, STRGRIDError::NotAuthorized);
+}
+
+#[contractimpl]
+impl STRGRIDContract {
+    /// Concede a um terceiro (ex.: auditor externo) leitura dos dados restritos do titular
+    pub fn grant_view_access(env: Env, owner: Address, viewer: Address) {
+        owner.require_auth();
+        let mut grants = grants_of(&env, &owner);
+        if !grants.contains(&viewer) {
+            grants.push_back(viewer.clone());
+            env.storage().persistent().set(&(VIEW_GRANTS, owner.clone()), &grants);
+        }
+        events::view_access_granted(&env, owner, viewer);
+    }
+
+    /// Revoga o acesso de leitura concedido a um terceiro
+    pub fn revoke_view_access(env: Env, owner: Address, viewer: Address) {
+        owner.require_auth();
+        let mut grants = grants_of(&env, &owner);
+        if let Some(index) = grants.first_index_of(&viewer) {
+            grants.remove(index);
+            if grants.is_empty() {
+                env.storage().persistent().remove(&(VIEW_GRANTS, owner.clone()));
+            } else {
+                env.storage().persistent().set(&(VIEW_GRANTS, owner.clone()), &grants);
+            }
+        }
+        events::view_access_revoked(&env, owner, viewer);
+    }
+
+    /// Lista os terceiros com acesso de leitura aos dados do titular
+    pub fn view_grants_of(env: Env, owner: Address) -> Vec<Address> {
+        grants_of(&env, &owner)
+    }
+}
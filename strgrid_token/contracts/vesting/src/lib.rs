@@ -0,0 +1,225 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, Address, Env, Symbol,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const ADMIN: Symbol = symbol_short!("ADMIN");
+const GRANT: Symbol = symbol_short!("GRANT");
+
+// Tópicos dos eventos publicados pelo vesting
+const GRANTED: Symbol = symbol_short!("granted");
+const CLAIMED: Symbol = symbol_short!("claimed");
+const REVOKED: Symbol = symbol_short!("revoked");
+
+/// Concessão de STRGRID liberada linearmente entre o fim do cliff e o fim da duração
+///
+/// Com `cliff_seconds == duration_seconds` o valor inteiro é liberado de uma vez no cliff.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub beneficiary: Address,
+    pub total: u64,
+    pub claimed: u64,
+    pub start: u64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+    pub revocable: bool,
+    /// Momento da revogação; a liberação para de crescer a partir dele
+    pub revoked_at: Option<u64>,
+}
+
+impl Grant {
+    /// Valor liberado até `now`
+    pub fn vested(&self, now: u64) -> u64 {
+        let now = match self.revoked_at {
+            Some(revoked_at) => now.min(revoked_at),
+            None => now,
+        };
+        if now < self.start + self.cliff_seconds {
+            0
+        } else if now >= self.start + self.duration_seconds {
+            self.total
+        } else {
+            (self.total as u128 * (now - self.start) as u128 / self.duration_seconds as u128) as u64
+        }
+    }
+
+    /// Se não resta nada a liberar nem a sacar: tudo sacado, ou sacado até a revogação
+    pub fn is_settled(&self) -> bool {
+        let owed = match self.revoked_at {
+            Some(revoked_at) => self.vested(revoked_at),
+            None => self.total,
+        };
+        self.claimed >= owed
+    }
+}
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VestingError {
+    NotInitialized = 1,
+    InvalidSchedule = 3,
+    GrantExists = 4,
+    GrantNotFound = 5,
+    NotRevocable = 6,
+    AlreadyRevoked = 7,
+    NothingToClaim = 8,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, VestingError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&ADMIN)
+        .unwrap_or_else(|| panic_with_error!(env, VestingError::NotInitialized))
+}
+
+fn load(env: &Env, beneficiary: &Address) -> Grant {
+    env.storage()
+        .persistent()
+        .get(&(GRANT, beneficiary.clone()))
+        .unwrap_or_else(|| panic_with_error!(env, VestingError::GrantNotFound))
+}
+
+fn save(env: &Env, grant: &Grant) {
+    env.storage()
+        .persistent()
+        .set(&(GRANT, grant.beneficiary.clone()), grant);
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    /// Configura no deploy o vesting com o token de energia e o admin que concede e revoga
+    pub fn __constructor(env: Env, energy_token: Address, admin: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&ADMIN, &admin);
+    }
+
+    /// Concede `total` ao beneficiário, custodiado a partir do saldo do admin (apenas admin)
+    ///
+    /// Só substitui a concessão anterior do beneficiário depois que ela foi inteiramente sacada.
+    pub fn create_grant(
+        env: Env,
+        beneficiary: Address,
+        total: u64,
+        start: u64,
+        cliff_seconds: u64,
+        duration_seconds: u64,
+        revocable: bool,
+    ) {
+        let admin = admin(&env);
+        admin.require_auth();
+        if total == 0 || duration_seconds == 0 || cliff_seconds > duration_seconds {
+            panic_with_error!(&env, VestingError::InvalidSchedule);
+        }
+        // Uma concessão encerrada pode ser substituída; uma com saldo pendente, não
+        let existing: Option<Grant> = env
+            .storage()
+            .persistent()
+            .get(&(GRANT, beneficiary.clone()));
+        if existing.is_some_and(|grant| !grant.is_settled()) {
+            panic_with_error!(&env, VestingError::GrantExists);
+        }
+
+        // O cronograma libera só o que chegou à custódia, já descontada a taxa de transferência
+        let energy = energy_token(&env);
+        let contract = env.current_contract_address();
+        let before = energy.balance_of(&contract);
+        energy.transfer(&admin, &contract, &i128::from(total));
+        let total = (energy.balance_of(&contract) - before) as u64;
+        if total == 0 {
+            panic_with_error!(&env, VestingError::InvalidSchedule);
+        }
+        let grant = Grant {
+            beneficiary: beneficiary.clone(),
+            total,
+            claimed: 0,
+            start,
+            cliff_seconds,
+            duration_seconds,
+            revocable,
+            revoked_at: None,
+        };
+        save(&env, &grant);
+
+        env.events()
+            .publish((GRANTED, beneficiary), (total, start, cliff_seconds, duration_seconds));
+    }
+
+    /// Valor liberado e ainda não sacado pelo beneficiário
+    pub fn claimable(env: Env, beneficiary: Address) -> u64 {
+        let grant = load(&env, &beneficiary);
+        grant.vested(env.ledger().timestamp()) - grant.claimed
+    }
+
+    /// Saca o valor liberado
+    pub fn claim(env: Env, beneficiary: Address) -> u64 {
+        beneficiary.require_auth();
+        let mut grant = load(&env, &beneficiary);
+        let amount = grant.vested(env.ledger().timestamp()) - grant.claimed;
+        if amount == 0 {
+            panic_with_error!(&env, VestingError::NothingToClaim);
+        }
+        grant.claimed += amount;
+        save(&env, &grant);
+        energy_token(&env).transfer(
+            &env.current_contract_address(),
+            &beneficiary,
+            &i128::from(amount),
+        );
+
+        env.events().publish((CLAIMED, beneficiary), amount);
+        amount
+    }
+
+    /// Revoga uma concessão revogável: o já liberado continua sacável e o restante volta ao admin
+    pub fn revoke(env: Env, beneficiary: Address) -> u64 {
+        let admin = admin(&env);
+        admin.require_auth();
+        let mut grant = load(&env, &beneficiary);
+        if !grant.revocable {
+            panic_with_error!(&env, VestingError::NotRevocable);
+        }
+        if grant.revoked_at.is_some() {
+            panic_with_error!(&env, VestingError::AlreadyRevoked);
+        }
+        let now = env.ledger().timestamp();
+        let unvested = grant.total - grant.vested(now);
+        grant.revoked_at = Some(now);
+        save(&env, &grant);
+        if unvested > 0 {
+            energy_token(&env).transfer(
+                &env.current_contract_address(),
+                &admin,
+                &i128::from(unvested),
+            );
+        }
+
+        env.events().publish((REVOKED, beneficiary), unvested);
+        unvested
+    }
+
+    /// Consulta a concessão de um beneficiário
+    pub fn get_grant(env: Env, beneficiary: Address) -> Grant {
+        load(&env, &beneficiary)
+    }
+}
+
+mod test;
@@ -0,0 +1,108 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_linear_cliff_and_revocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let founder = Address::generate(&env);
+    let partner = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &admin, &1000i128);
+
+    let vesting_id = env.register(VestingContract, (energy_id.clone(), admin.clone()));
+    let vesting = VestingContractClient::new(&env, &vesting_id);
+
+    // Founder: 400 linearly over 400s with a 100s cliff; partner: 200 all at once at 300s
+    vesting.create_grant(&founder, &400u64, &0u64, &100u64, &400u64, &true);
+    vesting.create_grant(&partner, &200u64, &0u64, &300u64, &300u64, &false);
+    assert_eq!(
+        vesting.try_create_grant(&partner, &1u64, &0u64, &0u64, &1u64, &false),
+        Err(Ok(VestingError::GrantExists.into()))
+    );
+    assert_eq!(
+        vesting.try_create_grant(&admin, &1u64, &0u64, &10u64, &5u64, &false),
+        Err(Ok(VestingError::InvalidSchedule.into()))
+    );
+    assert_eq!(energy.balance_of(&vesting_id), 600);
+
+    env.ledger().with_mut(|li| li.timestamp = 99);
+    assert_eq!(vesting.claimable(&founder), 0);
+    assert_eq!(vesting.try_claim(&founder), Err(Ok(VestingError::NothingToClaim.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(vesting.claim(&founder), 100);
+    assert_eq!(vesting.claimable(&partner), 0);
+
+    // Revoking at 200s keeps what vested and returns the rest
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(vesting.revoke(&founder), 200);
+    assert_eq!(energy.balance_of(&admin), 600);
+    assert_eq!(vesting.try_revoke(&partner), Err(Ok(VestingError::NotRevocable.into())));
+
+    env.ledger().with_mut(|li| li.timestamp = 400);
+    assert_eq!(vesting.claimable(&founder), 100);
+    // A grant with kWh still owed cannot be replaced
+    assert_eq!(
+        vesting.try_create_grant(&founder, &100u64, &400u64, &0u64, &100u64, &false),
+        Err(Ok(VestingError::GrantExists.into()))
+    );
+    assert_eq!(vesting.claim(&founder), 100);
+    assert_eq!(energy.balance_of(&founder), 200);
+    assert_eq!(vesting.claim(&partner), 200);
+    assert_eq!(energy.balance_of(&vesting_id), 0);
+
+    // Once fully claimed, the beneficiary can receive a new grant
+    vesting.create_grant(&founder, &100u64, &400u64, &0u64, &100u64, &false);
+    let grant = vesting.get_grant(&founder);
+    assert_eq!((grant.total, grant.claimed, grant.revoked_at), (100, 0, None));
+    assert_eq!(energy.balance_of(&vesting_id), 100);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    assert_eq!(vesting.claim(&founder), 100);
+}
+
+#[test]
+fn test_grant_vests_kwh_received_after_transfer_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let generator = Address::generate(&env);
+    let founder = Address::generate(&env);
+    let partner = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&generator, &1000u64);
+    energy.mint_energy_tokens(&generator, &1000i128, &24u64, &None);
+    energy.transfer(&generator, &admin, &1000i128);
+    energy.set_transfer_fee(&100u32, &Address::generate(&env));
+
+    let vesting_id = env.register(VestingContract, (energy_id.clone(), admin.clone()));
+    let vesting = VestingContractClient::new(&env, &vesting_id);
+
+    // A 1% fee leaves 396 of the 400 kWh in custody, and that is what vests
+    vesting.create_grant(&founder, &400u64, &0u64, &0u64, &100u64, &false);
+    vesting.create_grant(&partner, &200u64, &0u64, &0u64, &100u64, &false);
+    assert_eq!(vesting.get_grant(&founder).total, 396);
+    assert_eq!(energy.balance_of(&vesting_id), 594);
+
+    // Claiming a full grant does not eat into another beneficiary's custody
+    env.ledger().set_timestamp(100);
+    assert_eq!(vesting.claim(&founder), 396);
+    assert_eq!(vesting.claim(&partner), 198);
+    assert_eq!(energy.balance_of(&vesting_id), 0);
+}
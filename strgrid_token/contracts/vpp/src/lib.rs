@@ -0,0 +1,190 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contracterror, contractimpl, panic_with_error, symbol_short, token,
+    Address, Env, Map, Symbol,
+};
+use strgrid_interface::STRGRIDClient;
+
+// Símbolos para armazenamento de dados
+const ENERGY_TOKEN: Symbol = symbol_short!("ENERGY");
+const OPERATOR: Symbol = symbol_short!("OPERATOR");
+const MEMBERS: Symbol = symbol_short!("MEMBERS");
+
+// Tópicos dos eventos publicados pela usina virtual
+const JOINED: Symbol = symbol_short!("joined");
+const LEFT: Symbol = symbol_short!("left");
+const REBALANCED: Symbol = symbol_short!("rebalance");
+const DISPATCHED: Symbol = symbol_short!("dispatch");
+const DISTRIBUTED: Symbol = symbol_short!("revenue");
+
+// Erros customizados
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VppError {
+    NotInitialized = 1,
+    InvalidAmount = 3,
+    AlreadyMember = 4,
+    NotMember = 5,
+    ExceedsCapacity = 6,
+    NoMembers = 7,
+}
+
+fn energy_token(env: &Env) -> STRGRIDClient<'_> {
+    let address: Address = env
+        .storage()
+        .instance()
+        .get(&ENERGY_TOKEN)
+        .unwrap_or_else(|| panic_with_error!(env, VppError::NotInitialized));
+    STRGRIDClient::new(env, &address)
+}
+
+fn operator(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&OPERATOR)
+        .unwrap_or_else(|| panic_with_error!(env, VppError::NotInitialized))
+}
+
+/// Capacidade (kW) comprometida por membro; é também o peso de cada um na divisão
+fn members(env: &Env) -> Map<Address, u64> {
+    env.storage()
+        .instance()
+        .get(&MEMBERS)
+        .unwrap_or(Map::new(env))
+}
+
+fn total_capacity(members: &Map<Address, u64>) -> u64 {
+    members.values().iter().sum()
+}
+
+/// Valida o compromisso contra a capacidade registrada do gerador no token de energia
+fn check_pledge(env: &Env, member: &Address, pledged_kw: u64) {
+    if pledged_kw == 0 {
+        panic_with_error!(env, VppError::InvalidAmount);
+    }
+    if pledged_kw > energy_token(env).capacity_of(member) {
+        panic_with_error!(env, VppError::ExceedsCapacity);
+    }
+}
+
+/// Divide `amount` proporcionalmente à capacidade comprometida; o resíduo do arredondamento fica de fora
+fn split(env: &Env, members: &Map<Address, u64>, amount: i128) -> Map<Address, i128> {
+    let total = total_capacity(members);
+    if total == 0 {
+        panic_with_error!(env, VppError::NoMembers);
+    }
+    let mut shares = Map::new(env);
+    for (member, pledged_kw) in members.iter() {
+        let share = amount
+            .checked_mul(pledged_kw as i128)
+            .unwrap_or_else(|| panic_with_error!(env, VppError::InvalidAmount))
+            / total as i128;
+        shares.set(member, share);
+    }
+    shares
+}
+
+#[contract]
+pub struct VppContract;
+
+#[contractimpl]
+impl VppContract {
+    /// Configura no deploy a usina virtual com o token de energia e o operador que a despacha
+    pub fn __constructor(env: Env, energy_token: Address, operator: Address) {
+        env.storage().instance().set(&ENERGY_TOKEN, &energy_token);
+        env.storage().instance().set(&OPERATOR, &operator);
+    }
+
+    /// Adere um gerador registrado, comprometendo até sua capacidade registrada
+    pub fn join(env: Env, member: Address, pledged_kw: u64) {
+        member.require_auth();
+        let mut members = members(&env);
+        if members.contains_key(member.clone()) {
+            panic_with_error!(&env, VppError::AlreadyMember);
+        }
+        check_pledge(&env, &member, pledged_kw);
+
+        members.set(member.clone(), pledged_kw);
+        env.storage().instance().set(&MEMBERS, &members);
+        env.events().publish((JOINED, member), pledged_kw);
+    }
+
+    /// Retira o membro e sua capacidade da usina virtual
+    pub fn leave(env: Env, member: Address) {
+        member.require_auth();
+        let mut members = members(&env);
+        let pledged_kw = members
+            .get(member.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, VppError::NotMember));
+
+        members.remove(member.clone());
+        env.storage().instance().set(&MEMBERS, &members);
+        env.events().publish((LEFT, member), pledged_kw);
+    }
+
+    /// Altera a capacidade comprometida por um membro, revalidando contra a capacidade registrada
+    pub fn rebalance(env: Env, member: Address, pledged_kw: u64) {
+        member.require_auth();
+        let mut members = members(&env);
+        if !members.contains_key(member.clone()) {
+            panic_with_error!(&env, VppError::NotMember);
+        }
+        check_pledge(&env, &member, pledged_kw);
+
+        members.set(member.clone(), pledged_kw);
+        env.storage().instance().set(&MEMBERS, &members);
+        env.events().publish((REBALANCED, member), pledged_kw);
+    }
+
+    /// Capacidade agregada que a usina virtual apresenta ao mercado
+    pub fn aggregate_capacity(env: Env) -> u64 {
+        total_capacity(&members(&env))
+    }
+
+    /// Membros e a capacidade comprometida por cada um
+    pub fn members(env: Env) -> Map<Address, u64> {
+        members(&env)
+    }
+
+    /// Reparte uma ordem de despacho de `amount_kwh` em direitos de mint por membro (apenas operador)
+    pub fn dispatch(env: Env, amount_kwh: u64) -> Map<Address, u64> {
+        operator(&env).require_auth();
+        if amount_kwh == 0 {
+            panic_with_error!(&env, VppError::InvalidAmount);
+        }
+        let members = members(&env);
+        if amount_kwh > total_capacity(&members) {
+            panic_with_error!(&env, VppError::ExceedsCapacity);
+        }
+
+        let mut rights = Map::new(&env);
+        for (member, share) in split(&env, &members, amount_kwh as i128).iter() {
+            rights.set(member, share as u64);
+        }
+        env.events().publish((DISPATCHED,), (amount_kwh, rights.clone()));
+        rights
+    }
+
+    /// Reparte receita de `from` entre os membros na proporção da capacidade comprometida
+    pub fn distribute_revenue(env: Env, from: Address, payment_token: Address, amount: i128) -> i128 {
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, VppError::InvalidAmount);
+        }
+
+        let payment = token::Client::new(&env, &payment_token);
+        let mut distributed = 0i128;
+        for (member, share) in split(&env, &members(&env), amount).iter() {
+            if share > 0 {
+                payment.transfer(&from, &member, &share);
+                distributed += share;
+            }
+        }
+        env.events()
+            .publish((DISTRIBUTED, from, payment_token), distributed);
+        distributed
+    }
+}
+
+mod test;
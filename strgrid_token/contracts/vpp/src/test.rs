@@ -0,0 +1,60 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, token, Address, Env};
+use strgrid_token::{testutils, STRGRIDContractClient};
+
+#[test]
+fn test_vpp_pools_capacity_and_splits_revenue() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let solar = Address::generate(&env);
+    let wind = Address::generate(&env);
+    let offtaker = Address::generate(&env);
+
+    let energy_id = testutils::deploy(&env, &admin, false);
+    let energy = STRGRIDContractClient::new(&env, &energy_id);
+    energy.register_generator(&solar, &100u64);
+    energy.register_generator(&wind, &300u64);
+
+    let vpp_id = env.register(VppContract, (energy_id.clone(), operator.clone()));
+    let vpp = VppContractClient::new(&env, &vpp_id);
+
+    // Pledges are capped by each generator's registered capacity
+    assert_eq!(
+        vpp.try_join(&solar, &150u64),
+        Err(Ok(VppError::ExceedsCapacity.into()))
+    );
+    vpp.join(&solar, &100u64);
+    vpp.join(&wind, &100u64);
+    assert_eq!(vpp.aggregate_capacity(), 200);
+    assert_eq!(
+        vpp.try_join(&wind, &50u64),
+        Err(Ok(VppError::AlreadyMember.into()))
+    );
+
+    // Rebalancing shifts the split to 1:3
+    vpp.rebalance(&wind, &300u64);
+    assert_eq!(vpp.aggregate_capacity(), 400);
+    let rights = vpp.dispatch(&200u64);
+    assert_eq!(rights.get(solar.clone()), Some(50));
+    assert_eq!(rights.get(wind.clone()), Some(150));
+    assert_eq!(
+        vpp.try_dispatch(&401u64),
+        Err(Ok(VppError::ExceedsCapacity.into()))
+    );
+
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    token::StellarAssetClient::new(&env, &usdc).mint(&offtaker, &10_000i128);
+    let usdc_client = token::Client::new(&env, &usdc);
+    assert_eq!(vpp.distribute_revenue(&offtaker, &usdc, &4_000i128), 4_000);
+    assert_eq!(usdc_client.balance(&solar), 1_000);
+    assert_eq!(usdc_client.balance(&wind), 3_000);
+
+    vpp.leave(&solar);
+    assert_eq!(vpp.aggregate_capacity(), 300);
+    assert_eq!(vpp.try_leave(&solar), Err(Ok(VppError::NotMember.into())));
+}
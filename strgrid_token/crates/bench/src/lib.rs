@@ -0,0 +1,175 @@
+//! Benchmarks de orçamento (instruções de CPU e memória) das operações quentes do STRGRID
+//!
+//! Cada cenário monta o token e o marketplace com `size` lotes, titulares e ofertas já
+//! existentes e mede uma única invocação com `env.cost_estimate().budget()`. Os limites em
+//! `THRESHOLDS` falham o `cargo test` quando uma mudança encarece a operação além da folga, antes
+//! do deploy.
+//!
+//! Para ver os números: `make bench` (ou `cargo test -p strgrid-bench -- --nocapture`).
+
+use soroban_sdk::testutils::{Address as _, EnvTestConfig};
+use soroban_sdk::{token, Address, Env};
+use strgrid_marketplace::{MarketplaceContract, MarketplaceContractClient};
+use strgrid_token::testutils::FixtureBuilder;
+use strgrid_token::STRGRIDContractClient;
+
+/// Quantidades de lotes, titulares e ofertas pré-existentes medidas
+pub const SIZES: [u32; 3] = [0, 50, 200];
+
+/// Custo de uma invocação no orçamento do Soroban
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cost {
+    pub cpu_insns: u64,
+    pub mem_bytes: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    Mint,
+    Transfer,
+    Burn,
+    Consume,
+    Fill,
+}
+
+impl Op {
+    pub const ALL: [Op; 5] = [Op::Mint, Op::Transfer, Op::Burn, Op::Consume, Op::Fill];
+}
+
+/// Teto aceito para a operação com `size` itens pré-existentes
+#[derive(Clone, Copy, Debug)]
+pub struct Threshold {
+    pub op: Op,
+    pub size: u32,
+    pub cost: Cost,
+}
+
+const fn limit(op: Op, size: u32, cpu_insns: u64, mem_bytes: u64) -> Threshold {
+    Threshold {
+        op,
+        size,
+        cost: Cost {
+            cpu_insns,
+            mem_bytes,
+        },
+    }
+}
+
+/// Limites com ~25% de folga sobre as medições de referência; ajuste junto com a mudança
+/// que justificar o aumento
+pub const THRESHOLDS: &[Threshold] = &[
+    limit(Op::Mint, 0, 980_000, 190_000),
+    limit(Op::Transfer, 0, 610_000, 150_000),
+    limit(Op::Burn, 0, 380_000, 89_000),
+    limit(Op::Consume, 0, 1_100_000, 210_000),
+    limit(Op::Fill, 0, 1_400_000, 330_000),
+    limit(Op::Mint, 50, 4_900_000, 1_800_000),
+    limit(Op::Transfer, 50, 4_300_000, 1_400_000),
+    limit(Op::Burn, 50, 2_100_000, 730_000),
+    limit(Op::Consume, 50, 5_100_000, 1_700_000),
+    limit(Op::Fill, 50, 6_900_000, 2_500_000),
+    limit(Op::Mint, 200, 17_000_000, 6_300_000),
+    limit(Op::Transfer, 200, 15_000_000, 5_200_000),
+    limit(Op::Burn, 200, 6_900_000, 2_700_000),
+    limit(Op::Consume, 200, 17_000_000, 6_100_000),
+    limit(Op::Fill, 200, 24_000_000, 9_000_000),
+];
+
+pub fn threshold(op: Op, size: u32) -> Option<Cost> {
+    THRESHOLDS
+        .iter()
+        .find(|t| t.op == op && t.size == size)
+        .map(|t| t.cost)
+}
+
+/// Mede o custo de `f`, zerando o orçamento (com os limites padrão da rede) antes da chamada
+pub fn measure<T>(env: &Env, f: impl FnOnce() -> T) -> (T, Cost) {
+    env.cost_estimate().budget().reset_default();
+    let result = f();
+    let cost = Cost {
+        cpu_insns: env.cost_estimate().budget().cpu_instruction_cost(),
+        mem_bytes: env.cost_estimate().budget().memory_bytes_cost(),
+    };
+    env.cost_estimate().budget().reset_unlimited();
+    (result, cost)
+}
+
+/// Token e marketplace com `size` lotes do gerador, titulares e ofertas ativas
+pub struct Bench<'a> {
+    pub env: Env,
+    pub token: STRGRIDContractClient<'a>,
+    pub market: MarketplaceContractClient<'a>,
+    pub generator: Address,
+    pub buyer: Address,
+    pub payment_token: Address,
+    pub lot: u64,
+    pub listing: u64,
+}
+
+impl Bench<'_> {
+    pub fn new(size: u32) -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.cost_estimate().budget().reset_unlimited();
+        let fixture = FixtureBuilder::new(&env)
+            .generators(1, 1_000 * (size as u64 + 10))
+            .build();
+        let token = fixture.client;
+        let generator = fixture.generators.get_unchecked(0);
+
+        let market_id = env.register(MarketplaceContract, (fixture.contract_id.clone(),));
+        let market = MarketplaceContractClient::new(&env, &market_id);
+        let payment_token = env
+            .register_stellar_asset_contract_v2(fixture.admin.clone())
+            .address();
+        let buyer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &payment_token).mint(&buyer, &1_000_000i128);
+
+        // Estado pré-existente: lotes do gerador, titulares distintos e ofertas ativas
+        for _ in 0..size {
+            let lot = token.mint_energy_tokens(&generator, &10i128, &24u64, &None);
+            token.transfer(&generator, &Address::generate(&env), &1i128);
+            market.list(&generator, &lot, &1u64, &payment_token, &5i128);
+        }
+        let lot = token.mint_energy_tokens(&generator, &1_000i128, &24u64, &None);
+        let listing = market.list(&generator, &lot, &100u64, &payment_token, &5i128);
+
+        Bench {
+            env,
+            token,
+            market,
+            generator,
+            buyer,
+            payment_token,
+            lot,
+            listing,
+        }
+    }
+
+    /// Executa uma invocação da operação e devolve o custo medido
+    pub fn run(&self, op: Op) -> Cost {
+        let env = &self.env;
+        let (_, cost) = match op {
+            Op::Mint => measure(env, || {
+                self.token.mint_energy_tokens(&self.generator, &10i128, &24u64, &None);
+            }),
+            // Destinatário novo: inclui a entrada no registro de titulares
+            Op::Transfer => {
+                let to = Address::generate(env);
+                measure(env, || self.token.transfer(&self.generator, &to, &5i128))
+            }
+            Op::Burn => measure(env, || self.token.burn(&self.generator, &5i128)),
+            Op::Consume => measure(env, || {
+                self.token.burn_energy_tokens(&self.generator, &self.lot, &5i128);
+            }),
+            Op::Fill => measure(env, || {
+                self.market.buy(&self.buyer, &self.listing, &10u64);
+            }),
+        };
+        cost
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,24 @@
+use crate::{threshold, Bench, Op, SIZES};
+
+#[test]
+fn budget_within_thresholds() {
+    let mut failures = Vec::new();
+    for size in SIZES {
+        let bench = Bench::new(size);
+        for op in Op::ALL {
+            let cost = bench.run(op);
+            println!(
+                "{:<8?} size={:<4} cpu_insns={:<10} mem_bytes={}",
+                op, size, cost.cpu_insns, cost.mem_bytes
+            );
+            match threshold(op, size) {
+                Some(limit) if cost.cpu_insns > limit.cpu_insns || cost.mem_bytes > limit.mem_bytes => {
+                    failures.push(format!("{:?} at size {}: {:?} > {:?}", op, size, cost, limit))
+                }
+                Some(_) => {}
+                None => failures.push(format!("{:?} at size {}: no threshold", op, size)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "budget regressions:\n{}", failures.join("\n"));
+}
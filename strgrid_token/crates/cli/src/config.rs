@@ -0,0 +1,145 @@
+use std::fs;
+use std::process::Command;
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use crate::invoke::{self, Invocation};
+
+/// Conexão e credenciais; cada opção também pode vir do ambiente, como na API
+#[derive(Args, Debug)]
+pub struct Config {
+    /// Endereço do contrato STRGRID
+    #[arg(long, env = "CONTRACT_ID", global = true)]
+    pub contract_id: Option<String>,
+
+    /// Rede Stellar: testnet, mainnet, futurenet ou standalone
+    #[arg(long, env = "STELLAR_NETWORK", default_value = "testnet", global = true)]
+    pub network: String,
+
+    /// URL do Soroban RPC; sem ela, usa a configuração de rede do próprio soroban
+    #[arg(long, env = "SOROBAN_RPC_URL", global = true)]
+    pub rpc_url: Option<String>,
+
+    /// Identidade do soroban ou chave secreta que assina as transações
+    #[arg(long, env = "STRGRID_SOURCE", global = true, hide_env_values = true)]
+    pub source: Option<String>,
+
+    /// Arquivo contendo a identidade ou chave secreta; tem precedência sobre `--source`
+    #[arg(long, env = "STRGRID_SOURCE_FILE", global = true)]
+    pub source_file: Option<String>,
+
+    /// Executável do soroban CLI
+    #[arg(long, env = "SOROBAN_BIN", default_value = "soroban", global = true)]
+    pub soroban_bin: String,
+}
+
+/// Passphrase das redes conhecidas, necessária quando o RPC é informado diretamente
+pub fn network_passphrase(network: &str) -> Option<&'static str> {
+    match network {
+        "testnet" => Some("Test SDF Network ; September 2015"),
+        "mainnet" | "public" => Some("Public Global Stellar Network ; September 2015"),
+        "futurenet" => Some("Test SDF Future Network ; October 2022"),
+        "standalone" | "local" => Some("Standalone Network ; February 2017"),
+        _ => None,
+    }
+}
+
+fn config_error(message: impl Into<String>) -> Value {
+    json!({ "error": "config", "message": message.into() })
+}
+
+impl Config {
+    fn contract_id(&self) -> Result<&str, Value> {
+        self.contract_id
+            .as_deref()
+            .ok_or_else(|| config_error("contract id missing: use --contract-id or CONTRACT_ID"))
+    }
+
+    /// Resolve a credencial de assinatura, lendo o arquivo quando informado
+    pub fn source(&self) -> Result<String, Value> {
+        if let Some(path) = &self.source_file {
+            let content = fs::read_to_string(path)
+                .map_err(|e| config_error(format!("cannot read {}: {}", path, e)))?;
+            let source = content.trim();
+            if source.is_empty() {
+                return Err(config_error(format!("{} is empty", path)));
+            }
+            return Ok(source.to_string());
+        }
+        self.source.clone().ok_or_else(|| {
+            config_error("source missing: use --source, --source-file, STRGRID_SOURCE or STRGRID_SOURCE_FILE")
+        })
+    }
+
+    fn network_args(&self) -> Result<Vec<String>, Value> {
+        match &self.rpc_url {
+            Some(rpc_url) => {
+                let passphrase = network_passphrase(&self.network).ok_or_else(|| {
+                    config_error(format!("unknown network {} for --rpc-url", self.network))
+                })?;
+                Ok(vec![
+                    "--rpc-url".to_string(),
+                    rpc_url.clone(),
+                    "--network-passphrase".to_string(),
+                    passphrase.to_string(),
+                ])
+            }
+            None => Ok(vec!["--network".to_string(), self.network.clone()]),
+        }
+    }
+
+    /// Argumentos completos do `soroban`; a credencial vai pelo ambiente, nunca pela linha de comando
+    pub fn command_args(&self, invocation: &Invocation) -> Result<Vec<String>, Value> {
+        let mut args = vec![
+            "contract".to_string(),
+            "invoke".to_string(),
+            "--id".to_string(),
+            self.contract_id()?.to_string(),
+        ];
+        args.extend(self.network_args()?);
+        if !invocation.send {
+            args.push("--send=no".to_string());
+        }
+        args.extend(invocation.contract_args());
+        Ok(args)
+    }
+
+    fn envelope(&self, invocation: &Invocation, result: Value) -> Value {
+        json!({
+            "network": self.network,
+            "contract_id": self.contract_id,
+            "function": invocation.function,
+            "result": result,
+        })
+    }
+
+    /// Descreve a chamada sem executá-la (`--dry-run`)
+    pub fn plan(&self, invocation: &Invocation) -> Result<Value, Value> {
+        let mut command = vec![self.soroban_bin.clone()];
+        command.extend(self.command_args(invocation)?);
+        Ok(self.envelope(
+            invocation,
+            json!({ "command": command, "send": invocation.send }),
+        ))
+    }
+
+    /// Executa a chamada e devolve o resultado decodificado
+    pub fn execute(&self, invocation: &Invocation) -> Result<Value, Value> {
+        let source = self.source()?;
+        let output = Command::new(&self.soroban_bin)
+            .args(self.command_args(invocation)?)
+            .env("SOROBAN_ACCOUNT", &source)
+            .env("STELLAR_ACCOUNT", &source)
+            .output()
+            .map_err(|e| {
+                json!({ "error": "spawn", "message": format!("{}: {}", self.soroban_bin, e) })
+            })?;
+
+        if !output.status.success() {
+            return Err(invoke::decode_error(&String::from_utf8_lossy(&output.stderr)));
+        }
+        let result = invoke::parse_result(&String::from_utf8_lossy(&output.stdout));
+        Ok(self.envelope(invocation, result))
+    }
+}
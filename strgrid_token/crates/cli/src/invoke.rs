@@ -0,0 +1,102 @@
+use serde_json::{json, Value};
+use strgrid_codes::{STRGRIDError, STRGRIDExt2Error, STRGRIDExtError};
+
+/// Argumento de uma função do contrato, no formato aceito pelo `soroban contract invoke`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Arg {
+    Value(&'static str, String),
+    /// Parâmetros `bool` viram flags: presentes quando verdadeiros
+    Flag(&'static str, bool),
+}
+
+impl Arg {
+    pub fn value(name: &'static str, value: impl ToString) -> Arg {
+        Arg::Value(name, value.to_string())
+    }
+
+    pub fn flag(name: &'static str, enabled: bool) -> Arg {
+        Arg::Flag(name, enabled)
+    }
+}
+
+/// Chamada a uma função do contrato; consultas são apenas simuladas, sem enviar transação
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invocation {
+    pub function: &'static str,
+    pub args: Vec<Arg>,
+    pub send: bool,
+}
+
+impl Invocation {
+    pub fn send(function: &'static str, args: Vec<Arg>) -> Invocation {
+        Invocation {
+            function,
+            args,
+            send: true,
+        }
+    }
+
+    pub fn query(function: &'static str, args: Vec<Arg>) -> Invocation {
+        Invocation {
+            function,
+            args,
+            send: false,
+        }
+    }
+
+    /// Argumentos após o `--`: nome da função seguido dos parâmetros
+    pub fn contract_args(&self) -> Vec<String> {
+        let mut out = vec!["--".to_string(), self.function.to_string()];
+        for arg in &self.args {
+            match arg {
+                Arg::Value(name, value) => {
+                    out.push(format!("--{}", name));
+                    out.push(value.clone());
+                }
+                Arg::Flag(name, true) => out.push(format!("--{}", name)),
+                Arg::Flag(_, false) => {}
+            }
+        }
+        out
+    }
+}
+
+/// Interpreta o valor de retorno impresso pelo invoke; sem JSON válido, devolve o texto
+pub fn parse_result(stdout: &str) -> Value {
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_str(trimmed).unwrap_or_else(|_| Value::String(trimmed.to_string()))
+}
+
+/// Converte a falha do invoke em JSON, traduzindo `Error(Contract, #N)` pelo crate de códigos
+pub fn decode_error(stderr: &str) -> Value {
+    let message = stderr
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or("")
+        .to_string();
+
+    match contract_error_code(stderr) {
+        Some(code) => {
+            let name = STRGRIDError::from_code(code)
+                .map(STRGRIDError::name)
+                .or_else(|| STRGRIDExtError::from_code(code).map(STRGRIDExtError::name))
+                .or_else(|| STRGRIDExt2Error::from_code(code).map(STRGRIDExt2Error::name));
+            json!({ "error": "contract", "code": code, "name": name, "message": message })
+        }
+        None => json!({ "error": "invoke", "message": message }),
+    }
+}
+
+fn contract_error_code(text: &str) -> Option<u32> {
+    const MARKER: &str = "Error(Contract, #";
+    let start = text.find(MARKER)? + MARKER.len();
+    let digits: String = text[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
@@ -0,0 +1,162 @@
+//! CLI de operação do contrato STRGRID
+//!
+//! Monta as chamadas ao contrato e as executa pelo `soroban contract invoke`, que cuida de
+//! RPC, assinatura e XDR. A saída é sempre JSON: o resultado em stdout ou o erro em stderr.
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+mod config;
+mod invoke;
+
+use config::Config;
+use invoke::{Arg, Invocation};
+
+#[derive(Parser, Debug)]
+#[command(name = "strgrid-cli", version, about = "Operação do contrato STRGRID em testnet e mainnet")]
+struct Cli {
+    #[command(flatten)]
+    config: Config,
+
+    /// Imprime a chamada que seria feita, sem executá-la
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Registra um gerador com sua capacidade em kW
+    RegisterGenerator { generator: String, capacity_kw: u64 },
+    /// Emite um lote de energia para o gerador
+    Mint {
+        generator: String,
+        amount_kwh: i128,
+        #[arg(long, default_value_t = 24)]
+        expiry_hours: u64,
+        /// Hash hex de 32 bytes da leitura atestada pelo oracle
+        #[arg(long)]
+        oracle_proof: Option<String>,
+    },
+    /// Consome energia de um lote do consumidor
+    Burn { consumer: String, token_id: u64, amount: i128 },
+    /// Transfere STRGRID entre endereços
+    Transfer { from: String, to: String, amount: i128 },
+    /// Ativa ou desativa um gerador
+    SetStatus {
+        generator: String,
+        #[arg(long, action = clap::ArgAction::Set)]
+        active: bool,
+    },
+    /// Saldo de um endereço
+    Balance { address: String },
+    /// Supply total em circulação
+    TotalSupply,
+    /// Dados de um lote de energia
+    Token { token_id: u64 },
+    /// Dados de um gerador
+    Generator { address: String },
+    /// Estatísticas de emissão, queima e lotes em aberto
+    Stats,
+    /// Nome, símbolo, decimais e supply
+    Metadata,
+}
+
+impl Command {
+    fn invocation(self) -> Invocation {
+        match self {
+            Command::RegisterGenerator {
+                generator,
+                capacity_kw,
+            } => Invocation::send(
+                "register_generator",
+                vec![
+                    Arg::value("generator", generator),
+                    Arg::value("capacity_kw", capacity_kw),
+                ],
+            ),
+            Command::Mint {
+                generator,
+                amount_kwh,
+                expiry_hours,
+                oracle_proof,
+            } => {
+                let mut args = vec![
+                    Arg::value("generator", generator),
+                    Arg::value("energy_amount_kwh", amount_kwh),
+                    Arg::value("expiry_hours", expiry_hours),
+                ];
+                if let Some(proof) = oracle_proof {
+                    args.push(Arg::value("oracle_proof", proof));
+                }
+                Invocation::send("mint_energy_tokens", args)
+            }
+            Command::Burn {
+                consumer,
+                token_id,
+                amount,
+            } => Invocation::send(
+                "burn_energy_tokens",
+                vec![
+                    Arg::value("consumer", consumer),
+                    Arg::value("token_id", token_id),
+                    Arg::value("amount", amount),
+                ],
+            ),
+            Command::Transfer { from, to, amount } => Invocation::send(
+                "transfer",
+                vec![
+                    Arg::value("from", from),
+                    Arg::value("to", to),
+                    Arg::value("amount", amount),
+                ],
+            ),
+            Command::SetStatus { generator, active } => Invocation::send(
+                "set_generator_status",
+                vec![
+                    Arg::value("generator", generator),
+                    Arg::flag("is_active", active),
+                ],
+            ),
+            Command::Balance { address } => {
+                Invocation::query("balance_of", vec![Arg::value("address", address)])
+            }
+            Command::TotalSupply => Invocation::query("total_supply", vec![]),
+            Command::Token { token_id } => {
+                Invocation::query("get_energy_token", vec![Arg::value("token_id", token_id)])
+            }
+            Command::Generator { address } => {
+                Invocation::query("get_generator", vec![Arg::value("generator", address)])
+            }
+            Command::Stats => Invocation::query("get_stats", vec![]),
+            Command::Metadata => Invocation::query("get_metadata", vec![]),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let invocation = cli.command.invocation();
+
+    let outcome = if cli.dry_run {
+        cli.config.plan(&invocation)
+    } else {
+        cli.config.execute(&invocation)
+    };
+    match outcome {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,80 @@
+use clap::Parser;
+use serde_json::json;
+
+use super::*;
+use crate::invoke::{decode_error, parse_result};
+
+fn parse(args: &[&str]) -> Cli {
+    let mut argv = vec!["strgrid-cli", "--contract-id", "CSTRGRID", "--source", "ops"];
+    argv.extend_from_slice(args);
+    Cli::try_parse_from(argv).unwrap()
+}
+
+#[test]
+fn test_subcommands_map_to_contract_calls() {
+    let cli = parse(&["mint", "GGEN", "500", "--expiry-hours", "48"]);
+    let invocation = cli.command.invocation();
+    assert!(invocation.send);
+    assert_eq!(
+        invocation.contract_args(),
+        vec![
+            "--", "mint_energy_tokens", "--generator", "GGEN", "--energy_amount_kwh", "500",
+            "--expiry_hours", "48",
+        ]
+    );
+
+    // Parâmetros bool só aparecem quando verdadeiros
+    let cli = parse(&["set-status", "GGEN", "--active", "false"]);
+    assert_eq!(
+        cli.command.invocation().contract_args(),
+        vec!["--", "set_generator_status", "--generator", "GGEN"]
+    );
+
+    let cli = parse(&["--network", "mainnet", "--rpc-url", "https://rpc.example", "balance", "GUSER"]);
+    let invocation = cli.command.invocation();
+    assert!(!invocation.send);
+    let args = cli.config.command_args(&invocation).unwrap();
+    assert_eq!(
+        args,
+        vec![
+            "contract", "invoke", "--id", "CSTRGRID", "--rpc-url", "https://rpc.example",
+            "--network-passphrase", "Public Global Stellar Network ; September 2015", "--send=no",
+            "--", "balance_of", "--address", "GUSER",
+        ]
+    );
+    // A credencial nunca vai para a linha de comando
+    assert!(!args.iter().any(|arg| arg == "ops"));
+}
+
+#[test]
+fn test_source_file_takes_precedence() {
+    let path = std::env::temp_dir().join("strgrid-cli-test-source");
+    std::fs::write(&path, "SSECRET\n").unwrap();
+    let cli = parse(&["--source-file", path.to_str().unwrap(), "stats"]);
+    assert_eq!(cli.config.source().unwrap(), "SSECRET");
+    std::fs::remove_file(&path).unwrap();
+
+    let cli = Cli::try_parse_from(["strgrid-cli", "stats"]).unwrap();
+    let missing = Config {
+        source: None,
+        source_file: None,
+        ..cli.config
+    };
+    assert_eq!(missing.source().unwrap_err()["error"], "config");
+}
+
+#[test]
+fn test_outputs_are_decoded_to_json() {
+    assert_eq!(parse_result("\"1000\"\n"), json!("1000"));
+    assert_eq!(parse_result("{\"total_supply\":10}"), json!({ "total_supply": 10 }));
+    assert_eq!(parse_result(""), json!(null));
+
+    let stderr = "error: transaction simulation failed\nHostError: Error(Contract, #3)\n";
+    let error = decode_error(stderr);
+    assert_eq!(error["code"], 3);
+    assert_eq!(error["name"], "InsufficientBalance");
+
+    // Códigos da extensão também são traduzidos
+    assert_eq!(decode_error("Error(Contract, #68)")["name"], "NettingSettled");
+    assert_eq!(decode_error("connection refused")["error"], "invoke");
+}
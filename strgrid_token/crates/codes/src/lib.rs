@@ -0,0 +1,557 @@
+#![no_std]
+//! Códigos de erro, tópicos de eventos e chaves de storage do StellarGrid
+//!
+//! Fonte única compartilhada por contrato, SDK cliente, CLI e indexador. Com a feature
+//! `soroban`, os tipos também ganham as representações usadas dentro do contrato.
+//! `codes.json` espelha estas tabelas para os componentes em JavaScript.
+
+macro_rules! errors {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident = $code:tt),* $(,)? }) => {
+        $(#[$meta])*
+        #[cfg_attr(feature = "soroban", soroban_sdk::contracterror)]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum $name {
+            $($variant = $code,)*
+        }
+
+        impl $name {
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            pub const fn code(self) -> u32 {
+                self as u32
+            }
+
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant),)*
+                }
+            }
+
+            pub const fn from_code(code: u32) -> Option<$name> {
+                match code {
+                    $($code => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! topics {
+    ($($name:ident = $topic:tt),* $(,)?) => {
+        /// Tópicos dos eventos publicados pelo contrato
+        pub mod topics {
+            $(pub const $name: &str = $topic;)*
+
+            /// Pares (constante, tópico) na ordem de declaração
+            pub const ALL: &[(&str, &str)] = &[$((stringify!($name), $topic)),*];
+        }
+
+        /// Tópicos como `Symbol`, para publicação dentro do contrato
+        #[cfg(feature = "soroban")]
+        pub mod topic_symbols {
+            use soroban_sdk::{symbol_short, Symbol};
+
+            $(pub const $name: Symbol = symbol_short!($topic);)*
+        }
+    };
+}
+
+macro_rules! storage_keys {
+    ($($variant:ident = $key:tt),* $(,)?) => {
+        /// Prefixos das chaves de storage do contrato
+        #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+        pub enum StorageKey {
+            $($variant,)*
+        }
+
+        impl StorageKey {
+            pub const ALL: &'static [StorageKey] = &[$(StorageKey::$variant),*];
+
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(StorageKey::$variant => $key,)*
+                }
+            }
+
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(StorageKey::$variant => stringify!($variant),)*
+                }
+            }
+
+            #[cfg(feature = "soroban")]
+            pub const fn symbol(self) -> soroban_sdk::Symbol {
+                match self {
+                    $(StorageKey::$variant => soroban_sdk::symbol_short!($key),)*
+                }
+            }
+        }
+    };
+}
+
+errors! {
+    /// Erros do contrato; os códigos são estáveis e nunca devem ser reutilizados
+    STRGRIDError {
+        NotAuthorized = 1,
+        InvalidAmount = 2,
+        InsufficientBalance = 3,
+        GeneratorNotFound = 4,
+        GeneratorInactive = 5,
+        InsufficientCapacity = 6,
+        TokenNotFound = 7,
+        InsufficientAllowance = 8,
+        AlreadyBurned = 9,
+        DisputeWindowClosed = 10,
+        TokensLocked = 11,
+        TokenVoided = 12,
+        BondRequired = 13,
+        BondNotConfigured = 14,
+        InsufficientBond = 15,
+        ChallengeNotFound = 16,
+        AlreadySlashed = 17,
+        SelfTransfer = 18,
+        ZeroApproval = 19,
+        NoOpUpdate = 20,
+        ArithmeticOverflow = 21,
+        AllowanceExpired = 22,
+        BatchTooLarge = 23,
+        AccountFrozen = 24,
+        LotNotArchivable = 25,
+        ClawbackDisabled = 26,
+        FeeNotConfigured = 27,
+        SupplyCapExceeded = 28,
+        DailyMintCapExceeded = 29,
+        MintBelowMinimum = 30,
+        MintAboveMaximum = 31,
+        MintTooFrequent = 32,
+        CertificateNotFound = 33,
+        CertificateRetired = 34,
+        AuctionNotFound = 35,
+        AuctionClosed = 36,
+        AuctionRunning = 37,
+        ForwardNotFound = 38,
+        ForwardClosed = 39,
+        OutsideDeliveryWindow = 40,
+        DeliveryNotMinted = 41,
+        DrEventNotFound = 42,
+        DrEventRunning = 43,
+        DrEventClosed = 44,
+        EnrollmentClosed = 45,
+        AlreadyEnrolled = 46,
+        NotEnrolled = 47,
+        AlreadyVerified = 48,
+        NothingToClaim = 49,
+        ConsumerNotRegistered = 50,
+    }
+}
+
+errors! {
+    /// Continuação de `STRGRIDError` a partir do código 51; a spec do contrato limita cada enum de erro a 50 casos
+    STRGRIDExtError {
+        AggregatorNotFound = 51,
+        AlreadyAggregated = 52,
+        NotAggregatorMember = 53,
+        AlreadyRegistered = 54,
+        CurtailmentNotFound = 55,
+        CapacityCurtailed = 56,
+        ScheduleClosed = 57,
+        IntervalOpen = 58,
+        PldNotPublished = 59,
+        PldAlreadyPublished = 60,
+        ImbalanceSettled = 61,
+        ReservationNotFound = 62,
+        ReservationSettled = 63,
+        CapacityReserved = 64,
+        IncentivesNotConfigured = 65,
+        IncentivesStarted = 66,
+        EpochNotFinal = 67,
+        NettingSettled = 68,
+        OracleProofRequired = 69,
+        InvalidOracleProof = 70,
+        OracleProofUsed = 71,
+        ClassicAssetNotConfigured = 72,
+        ClassicAssetNotAdmin = 73,
+        TransferRejected = 74,
+        ReceiverRejected = 75,
+        SignatureExpired = 76,
+        InvalidSigner = 77,
+        InvalidNonce = 78,
+        NotAllowlisted = 79,
+        JurisdictionRestricted = 80,
+        ParticipantNotRegistered = 81,
+        InvalidCid = 82,
+        ApplicationNotFound = 83,
+        InvalidApplicationStatus = 84,
+        GeneratorInMaintenance = 85,
+        MaintenanceNotFound = 86,
+        MaintenanceOverlap = 87,
+        ForecastNotFound = 88,
+        ForecastSettled = 89,
+        ForecastNotConfigured = 90,
+        OfferNotFound = 91,
+        OfferClosed = 92,
+        CommitmentNotFound = 93,
+        CommitmentSettled = 94,
+        DayAheadUnsettled = 95,
+        CollateralNotConfigured = 96,
+        PriceUnavailable = 97,
+        MarginSufficient = 98,
+        MarginCallNotDue = 99,
+        InvalidRating = 100,
+    }
+}
+
+errors! {
+    /// Continuação de `STRGRIDExtError` a partir do código 101
+    STRGRIDExt2Error {
+        NotACustomer = 101,
+        AlreadyRated = 102,
+        NotGuardian = 103,
+        InvalidRecoveryConfig = 104,
+        RecoveryNotFound = 105,
+        RecoveryPending = 106,
+        RecoveryNotReady = 107,
+        NotOperator = 108,
+        OperatorLimitExceeded = 109,
+        OperatorExists = 110,
+        SpendingLimitExceeded = 111,
+        StreamNotFound = 112,
+        StreamEnded = 113,
+        NotStreamParty = 114,
+        ScheduledTransferNotFound = 115,
+        ScheduledTransferNotDue = 116,
+        TaskNotFound = 117,
+        TaskClaimed = 118,
+        NotTaskClaimant = 119,
+        TaskNotReady = 120,
+        DuplicateLot = 121,
+    }
+}
+
+topics! {
+    ROLE_GRANTED = "role_add",
+    ROLE_REVOKED = "role_rm",
+    MINT_CHALLENGED = "challenge",
+    BOND_DEPOSITED = "bond_dep",
+    BOND_WITHDRAWN = "bond_wd",
+    BOND_SLASHED = "slash",
+    STRICT_MODE = "strict",
+    VIEW_GRANTED = "view_add",
+    VIEW_REVOKED = "view_rm",
+    BURN = "burn",
+    PRIORITY_SET = "priority",
+    GENERATOR_STATUS = "gen_stat",
+    TRANSFER_MEMO = "xfer_memo",
+    ACCOUNT_FROZEN = "frozen",
+    LOTS_ARCHIVED = "archived",
+    CLAWBACK = "clawback",
+    TRANSFER_FEE = "xfer_fee",
+    TREASURY_WITHDRAWN = "treasury",
+    CERTIFICATE_ISSUED = "rec_issue",
+    CERTIFICATE_RETIRED = "rec_claim",
+    SWAP = "swap",
+    AUCTION_STARTED = "auc_start",
+    AUCTION_BID = "auc_bid",
+    AUCTION_SETTLED = "auc_end",
+    FORWARD_SOLD = "fwd_sold",
+    FORWARD_DELIVERED = "fwd_dlvr",
+    FORWARD_DEFAULTED = "fwd_dflt",
+    DR_EVENT_POSTED = "dr_event",
+    DR_ENROLLED = "dr_enroll",
+    DR_VERIFIED = "dr_verify",
+    DR_REWARD_CLAIMED = "dr_claim",
+    DR_EVENT_CLOSED = "dr_close",
+    NET_METERING = "net_meter",
+    CONSUMER_REGISTERED = "cons_reg",
+    CONSUMER_GATE = "cons_gate",
+    AGGREGATOR_REGISTERED = "agg_reg",
+    AGGREGATOR_JOINED = "agg_join",
+    AGGREGATOR_LEFT = "agg_leave",
+    PROCEEDS_DISTRIBUTED = "agg_dist",
+    CURTAILMENT_ISSUED = "curtail",
+    CURTAILMENT_CANCELLED = "curt_cncl",
+    TRANSMISSION_LOSS = "xfer_loss",
+    CONGESTION_SET = "congest",
+    CONGESTION_CLEARED = "cong_clr",
+    ENERGY_SCHEDULED = "schedule",
+    PLD_PUBLISHED = "pld",
+    IMBALANCE_SETTLED = "imb_settl",
+    CAPACITY_RESERVED = "reserve",
+    RESERVATION_DELIVERED = "res_dlvr",
+    RESERVATION_SETTLED = "res_settl",
+    INCENTIVES_FUNDED = "inc_fund",
+    INCENTIVE_CLAIMED = "inc_claim",
+    DISTRIBUTION_FUNDED = "dist_fund",
+    DISTRIBUTED = "distrib",
+    OBLIGATION_RECORDED = "oblig",
+    NETTING_SETTLED = "net_settl",
+    LOTS_SWEPT = "swept",
+    GENERATOR_REGISTERED = "gen_reg",
+    MINT = "mint",
+    TRANSFER = "transfer",
+    CONSUME = "consume",
+    MINT_ORACLE_SET = "mint_orcl",
+    CLASSIC_ASSET_SET = "classic",
+    WRAP = "wrap",
+    UNWRAP = "unwrap",
+    COMPLIANCE_HOOK_SET = "comp_hook",
+    PERMIT = "permit",
+    RELAYED = "relayed",
+    ALLOWLISTED = "allowlist",
+    KYC_MODE_SET = "kyc_mode",
+    KYC_REGISTRY_SET = "kyc_reg",
+    JURISDICTION_SET = "jur_set",
+    JURISDICTION_PAIR_SET = "jur_pair",
+    JURISDICTION_MODE_SET = "jur_mode",
+    DOCUMENT_ANCHORED = "doc_anchr",
+    GENERATOR_METADATA = "gen_meta",
+    TOKEN_METADATA = "lot_meta",
+    CERTIFICATE_METADATA = "cert_meta",
+    GENERATOR_APPLIED = "gen_apply",
+    APPLICATION_STATUS = "app_state",
+    TIER_CONFIG_SET = "tier_cfg",
+    TIER_ASSIGNED = "tier_set",
+    MAINTENANCE_SCHEDULED = "maint_set",
+    MAINTENANCE_CANCELLED = "maint_del",
+    FORECAST_SUBMITTED = "forecast",
+    FORECAST_SETTLED = "fcst_done",
+    DA_OFFERED = "da_offer",
+    DA_OFFER_CANCELLED = "da_cancel",
+    DA_COMMITTED = "da_commit",
+    DA_SETTLED = "da_settle",
+    INSURANCE_SET = "ins_set",
+    INSURANCE_PREMIUM = "ins_prem",
+    COLLATERAL_CONFIG_SET = "coll_set",
+    COLLATERAL_ADDED = "coll_add",
+    MARGIN_CALLED = "mrg_call",
+    FORWARD_LIQUIDATED = "fwd_liq",
+    GENERATOR_RATED = "rated",
+    RECOVERY_SET = "recov_set",
+    RECOVERY_PROPOSED = "recov_req",
+    RECOVERY_APPROVED = "recov_ok",
+    RECOVERY_CANCELLED = "recov_cnl",
+    ACCOUNT_RECOVERED = "recovered",
+    OPERATOR_ADDED = "op_add",
+    OPERATOR_REVOKED = "op_revoke",
+    OPERATOR_ROTATED = "op_rotate",
+    SPENDING_LIMIT_SET = "spend_lim",
+    STREAM_OPENED = "strm_open",
+    STREAM_WITHDRAWN = "strm_wdr",
+    STREAM_CANCELLED = "strm_cncl",
+    TRANSFER_SCHEDULED = "xfer_sch",
+    SCHEDULED_EXECUTED = "xfer_exec",
+    SCHEDULED_CANCELLED = "xfer_cncl",
+    TASK_CREATED = "task_new",
+    TASK_CLAIMED = "task_clm",
+    TASK_COMPLETED = "task_done",
+    TASK_CANCELLED = "task_cncl",
+    CONFIG_CHANGED = "cfg_set",
+    AMOUNTS_MIGRATED = "amt_migr",
+    SIGNING_KEY_SET = "sign_key",
+}
+
+storage_keys! {
+    Balance = "BALANCE",
+    Allowance = "ALLOW",
+    Admin = "ADMIN",
+    Generator = "GEN",
+    TotalSupply = "TOTAL",
+    Metadata = "META",
+    EnergyData = "ENERGY",
+    TokenSeq = "TOKSEQ",
+    ArchiveRetention = "ARCRETEN",
+    ArchiveAggregate = "ARCAGG",
+    BondConfig = "BONDCFG",
+    Bond = "BOND",
+    ClawbackEnabled = "CLAWBACK",
+    Consumption = "CONSUME",
+    DisputeWindow = "DISPWIN",
+    Locked = "LOCKED",
+    Challenge = "CHALLENGE",
+    Frozen = "FROZEN",
+    Journal = "JOURNAL",
+    JournalSeq = "JRNSEQ",
+    Rollup = "ROLLUP",
+    Priority = "PRIORITY",
+    IndexGenerator = "IDX_GEN",
+    IndexSource = "IDX_SRC",
+    IndexZone = "IDX_ZONE",
+    IndexVintage = "IDX_VINT",
+    IndexExpiry = "IDX_EXP",
+    Role = "ROLE",
+    StrictMode = "STRICT",
+    ViewGrants = "VIEWKEYS",
+    TransferFee = "XFERFEE",
+    Treasury = "TREASURY",
+    Auction = "AUCTION",
+    AuctionSeq = "AUCTSEQ",
+    MintCaps = "MINTCAPS",
+    DailyMinted = "MINTDAY",
+    MintCooldown = "MINTCOOL",
+    Certificate = "CERT",
+    CertificateSeq = "CERTSEQ",
+    CertificatesOf = "CERTS_OF",
+    Retirement = "RETIRE",
+    EmissionFactor = "EMISSION",
+    CarbonOffset = "CARBON",
+    CarbonTotal = "CARBONTOT",
+    Forward = "FORWARD",
+    ForwardSeq = "FWDSEQ",
+    DrEvent = "DREVENT",
+    DrEventSeq = "DRSEQ",
+    DrEnrollment = "DRENROLL",
+    NetMetering = "NETMETER",
+    NetUsage = "NETUSAGE",
+    Consumer = "CONSUMER",
+    ConsumerGate = "CONSGATE",
+    Aggregator = "AGGREG",
+    AggregatorOf = "AGG_OF",
+    Curtailment = "CURTAIL",
+    CurtailmentSeq = "CURTSEQ",
+    CurtailmentsOf = "CURTS_OF",
+    LossFactor = "LOSSFACT",
+    Congestion = "CONGEST",
+    Schedule = "SCHEDULE",
+    Metered = "METERED",
+    Pld = "PLD",
+    ImbalanceSettled = "IMBSETTL",
+    Reservation = "RESERVE",
+    ReservationSeq = "RESSEQ",
+    ReservationsOf = "RES_OF",
+    BalanceCheckpoints = "BAL_CKPT",
+    SupplyCheckpoints = "SUP_CKPT",
+    AuditLog = "AUDIT",
+    AuditSeq = "AUDITSEQ",
+    IncentiveSchedule = "INCSCHED",
+    IncentivePool = "INCPOOL",
+    IncentiveProduction = "INCPROD",
+    IncentiveTotal = "INCTOTAL",
+    IncentiveClaimed = "INCCLAIM",
+    DistributionPool = "DISTPOOL",
+    NettingPosition = "NET_POS",
+    NettingParties = "NET_PTY",
+    NettingGross = "NET_GRS",
+    NettingSettled = "NET_DONE",
+    TotalMinted = "MINTED",
+    TotalBurned = "BURNED",
+    ProductionDaily = "PROD_DAY",
+    ProductionMonthly = "PROD_MON",
+    Holders = "HOLDERS",
+    HolderPosition = "HOLDERPOS",
+    SupplyBySource = "SUP_SRC",
+    SupplyByZone = "SUP_ZONE",
+    MintOracle = "MINTORCL",
+    UsedOracleProof = "ORCLPROOF",
+    ClassicAsset = "CLASSIC",
+    ClassicLocked = "CLSLOCKED",
+    ComplianceHook = "COMPHOOK",
+    Nonce = "NONCE",
+    KycMode = "KYCMODE",
+    KycAllowed = "KYCALLOW",
+    KycRegistry = "KYCREG",
+    Jurisdiction = "JURISDICT",
+    JurisdictionPair = "JURPAIR",
+    JurisdictionMode = "JURMODE",
+    Document = "DOCUMENT",
+    DocumentHash = "DOCHASH",
+    GeneratorApplication = "GENAPP",
+    GeneratorApplicants = "GENAPPS",
+    TierConfig = "TIERCFG",
+    GeneratorTier = "GENTIER",
+    Maintenance = "MAINT",
+    MaintenanceSeq = "MAINTSEQ",
+    MaintenanceOf = "MAINTOF",
+    Forecast = "FORECAST",
+    ForecastConfig = "FCSTCFG",
+    ForecastSettled = "FCSTDONE",
+    DaOffer = "DAOFFER",
+    DaOfferSeq = "DAOFFSEQ",
+    DaCommitment = "DACOMMIT",
+    DaCommitmentSeq = "DACOMSEQ",
+    DaOpen = "DAOPEN",
+    DaAllocated = "DAALLOC",
+    Insurance = "INSURE",
+    CollateralConfig = "COLLCFG",
+    ForwardMargin = "FWDMARGIN",
+    RatingEligible = "RATEELIG",
+    Rating = "RATING",
+    RatingTotal = "RATINGTOT",
+    Recovery = "RECOVERY",
+    RecoveryRequest = "RECOVREQ",
+    Operator = "OPERATOR",
+    OperatorsOf = "OPERATORS",
+    SpendingLimit = "SPENDLIM",
+    Stream = "STREAM",
+    StreamSeq = "STREAMSEQ",
+    ScheduledTransfer = "SCHEDXFER",
+    ScheduledTransferSeq = "SCHEDSEQ",
+    KeeperReward = "KEEPRWD",
+    KeeperTask = "KTASK",
+    KeeperTaskSeq = "KTASKSEQ",
+    SigningKey = "SIGNKEY",
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::{format, string::String, vec::Vec};
+
+    use super::*;
+
+    /// Gera o conteúdo esperado de `codes.json`
+    fn render_json() -> String {
+        let errors: Vec<String> = STRGRIDError::ALL
+            .iter()
+            .map(|e| (e.code(), e.name()))
+            .chain(STRGRIDExtError::ALL.iter().map(|e| (e.code(), e.name())))
+            .chain(STRGRIDExt2Error::ALL.iter().map(|e| (e.code(), e.name())))
+            .map(|(code, name)| format!("    \"{}\": \"{}\"", code, name))
+            .collect();
+        let topics: Vec<String> = topics::ALL
+            .iter()
+            .map(|(name, topic)| format!("    \"{}\": \"{}\"", name, topic))
+            .collect();
+        let storage: Vec<String> = StorageKey::ALL
+            .iter()
+            .map(|k| format!("    \"{}\": \"{}\"", k.name(), k.as_str()))
+            .collect();
+        format!(
+            "{{\n  \"errors\": {{\n{}\n  }},\n  \"topics\": {{\n{}\n  }},\n  \"storageKeys\": {{\n{}\n  }}\n}}\n",
+            errors.join(",\n"),
+            topics.join(",\n"),
+            storage.join(",\n"),
+        )
+    }
+
+    #[test]
+    fn codes_json_in_sync() {
+        let expected = render_json();
+        if std::env::var_os("UPDATE_CODES").is_some() {
+            std::fs::write(concat!(env!("CARGO_MANIFEST_DIR"), "/codes.json"), &expected).unwrap();
+        }
+        assert_eq!(
+            include_str!("../codes.json"),
+            expected,
+            "codes.json desatualizado; rode com UPDATE_CODES=1"
+        );
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for error in STRGRIDError::ALL {
+            assert_eq!(STRGRIDError::from_code(error.code()), Some(*error));
+        }
+        assert_eq!(STRGRIDError::from_code(0), None);
+        for error in STRGRIDExtError::ALL {
+            assert_eq!(STRGRIDExtError::from_code(error.code()), Some(*error));
+            assert_eq!(STRGRIDError::from_code(error.code()), None);
+        }
+        for error in STRGRIDExt2Error::ALL {
+            assert_eq!(STRGRIDExt2Error::from_code(error.code()), Some(*error));
+            assert_eq!(STRGRIDExtError::from_code(error.code()), None);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Leitura das tabelas do indexador em SQLite ou Postgres
+//!
+//! Cada consulta devolve as linhas como objetos JSON com os nomes das colunas, de modo que
+//! as rotas são apenas SQL. Placeholders `$N` devem aparecer em ordem crescente (o SQLite os
+//! numera pela primeira ocorrência), como no indexador.
+
+use serde_json::{Map, Value};
+
+use crate::ApiError;
+
+/// Colunas booleanas; o SQLite as devolve como inteiros
+const BOOL_COLUMNS: &[&str] = &["is_active", "voided"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Param {
+    Text(String),
+    Int(i64),
+    Bool(bool),
+}
+
+pub trait Db {
+    fn rows(&mut self, sql: &str, params: &[Param]) -> Result<Vec<Value>, ApiError>;
+}
+
+/// Abre o banco pela URL, nos mesmos formatos aceitos pelo indexador
+pub fn open(url: &str) -> Result<Box<dyn Db>, ApiError> {
+    if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+        return open_postgres(url);
+    }
+    let path = url.strip_prefix("sqlite://").unwrap_or(url);
+    Ok(Box::new(SqliteDb::open(path)?))
+}
+
+#[cfg(feature = "postgres")]
+fn open_postgres(url: &str) -> Result<Box<dyn Db>, ApiError> {
+    Ok(Box::new(postgres_db::PostgresDb::connect(url)?))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open_postgres(_url: &str) -> Result<Box<dyn Db>, ApiError> {
+    Err(ApiError::Store(
+        "postgres support not compiled in: build with --features postgres".to_string(),
+    ))
+}
+
+fn store_error(e: impl std::fmt::Display) -> ApiError {
+    ApiError::Store(e.to_string())
+}
+
+pub struct SqliteDb {
+    conn: rusqlite::Connection,
+}
+
+impl SqliteDb {
+    /// Abre somente para leitura: quem escreve é o indexador
+    pub fn open(path: &str) -> Result<SqliteDb, ApiError> {
+        let conn = rusqlite::Connection::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_URI,
+        )
+        .map_err(store_error)?;
+        Ok(SqliteDb { conn })
+    }
+}
+
+impl rusqlite::ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            Param::Text(value) => value.to_sql(),
+            Param::Int(value) => value.to_sql(),
+            Param::Bool(value) => value.to_sql(),
+        }
+    }
+}
+
+impl Db for SqliteDb {
+    fn rows(&mut self, sql: &str, params: &[Param]) -> Result<Vec<Value>, ApiError> {
+        use rusqlite::types::ValueRef;
+
+        let mut statement = self.conn.prepare(sql).map_err(store_error)?;
+        let columns: Vec<String> = statement
+            .column_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let mut rows = statement
+            .query(rusqlite::params_from_iter(params))
+            .map_err(store_error)?;
+
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().map_err(store_error)? {
+            let mut object = Map::new();
+            for (index, column) in columns.iter().enumerate() {
+                let value = match row.get_ref(index).map_err(store_error)? {
+                    ValueRef::Null => Value::Null,
+                    ValueRef::Integer(n) if BOOL_COLUMNS.contains(&column.as_str()) => {
+                        Value::Bool(n != 0)
+                    }
+                    ValueRef::Integer(n) => Value::from(n),
+                    ValueRef::Real(n) => Value::from(n),
+                    ValueRef::Text(text) => Value::from(String::from_utf8_lossy(text).into_owned()),
+                    ValueRef::Blob(_) => Value::Null,
+                };
+                object.insert(column.clone(), value);
+            }
+            out.push(Value::Object(object));
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres_db {
+    use postgres::types::{ToSql, Type};
+    use postgres::{Client, NoTls};
+    use serde_json::{Map, Value};
+
+    use super::{store_error, Db, Param};
+    use crate::ApiError;
+
+    pub struct PostgresDb {
+        client: Client,
+    }
+
+    impl PostgresDb {
+        pub fn connect(url: &str) -> Result<PostgresDb, ApiError> {
+            let client = Client::connect(url, NoTls).map_err(store_error)?;
+            Ok(PostgresDb { client })
+        }
+    }
+
+    impl Db for PostgresDb {
+        fn rows(&mut self, sql: &str, params: &[Param]) -> Result<Vec<Value>, ApiError> {
+            let params: Vec<&(dyn ToSql + Sync)> = params
+                .iter()
+                .map(|param| match param {
+                    Param::Text(value) => value as &(dyn ToSql + Sync),
+                    Param::Int(value) => value,
+                    Param::Bool(value) => value,
+                })
+                .collect();
+            let rows = self.client.query(sql, &params).map_err(store_error)?;
+
+            let mut out = Vec::new();
+            for row in rows {
+                let mut object = Map::new();
+                for (index, column) in row.columns().iter().enumerate() {
+                    let value = match *column.type_() {
+                        Type::BOOL => row.get::<_, Option<bool>>(index).map(Value::from),
+                        Type::INT8 => row.get::<_, Option<i64>>(index).map(Value::from),
+                        Type::INT4 => row.get::<_, Option<i32>>(index).map(Value::from),
+                        _ => row.get::<_, Option<String>>(index).map(Value::from),
+                    };
+                    object.insert(column.name().to_string(), value.unwrap_or(Value::Null));
+                }
+                out.push(Value::Object(object));
+            }
+            Ok(out)
+        }
+    }
+}
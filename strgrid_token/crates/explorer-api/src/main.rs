@@ -0,0 +1,110 @@
+//! API HTTP de consulta sobre os dados do `strgrid-indexer`
+//!
+//! Expõe saldos, estatísticas de geradores, histórico de lotes, transferências, certificados
+//! e ofertas do marketplace em JSON, para que os frontends não precisem falar com o RPC nem
+//! decodificar XDR. Só leitura: o banco é mantido pelo indexador.
+//!
+//! Rotas (GET; listas aceitam `offset` e `limit`):
+//! `/health`, `/balances`, `/balances/{address}`, `/generators`, `/generators/{address}`,
+//! `/tokens/{id}`, `/accounts/{address}/transfers`, `/accounts/{address}/certificates`,
+//! `/listings?active=true|false`, `/listings/{id}`
+
+use std::fmt;
+use std::process::ExitCode;
+
+use clap::Parser;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Response, Server};
+
+mod db;
+mod routes;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ApiError {
+    BadRequest(String),
+    NotFound(String),
+    Store(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::BadRequest(_) => 400,
+            ApiError::NotFound(_) => 404,
+            ApiError::Store(_) => 500,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let (kind, message) = match self {
+            ApiError::BadRequest(message) => ("bad_request", message),
+            ApiError::NotFound(message) => ("not_found", message),
+            ApiError::Store(message) => ("store", message),
+        };
+        json!({ "error": kind, "message": message })
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_json())
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "strgrid-explorer-api", version, about = "API de consulta dos eventos indexados do STRGRID")]
+struct Args {
+    /// Banco do indexador: caminho/`sqlite://` para SQLite ou `postgres://` para Postgres
+    #[arg(long, env = "DATABASE_URL", default_value = "sqlite://strgrid-index.db")]
+    database_url: String,
+
+    /// Endereço e porta do servidor HTTP
+    #[arg(long, env = "EXPLORER_API_BIND", default_value = "0.0.0.0:3002")]
+    bind: String,
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut db = match db::open(&args.database_url) {
+        Ok(db) => db,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match Server::http(&args.bind) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("{}", json!({ "error": "bind", "message": error.to_string() }));
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("{}", json!({ "listening": args.bind }));
+
+    for request in server.incoming_requests() {
+        let (status, body) = if *request.method() == Method::Get {
+            match routes::handle(db.as_mut(), request.url()) {
+                Ok(body) => (200, body),
+                Err(error) => (error.status(), error.to_json()),
+            }
+        } else {
+            (405, json!({ "error": "method_not_allowed", "message": request.method().to_string() }))
+        };
+
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header("Content-Type", "application/json"))
+            .with_header(header("Access-Control-Allow-Origin", "*"));
+        if let Err(error) = request.respond(response) {
+            eprintln!("{}", json!({ "error": "respond", "message": error.to_string() }));
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod test;
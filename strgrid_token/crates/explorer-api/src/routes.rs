@@ -0,0 +1,249 @@
+//! Rotas da API: cada uma traduz o caminho numa consulta às tabelas do indexador
+
+use serde_json::{json, Value};
+
+use crate::db::{Db, Param};
+use crate::ApiError;
+
+pub const DEFAULT_LIMIT: i64 = 20;
+pub const MAX_LIMIT: i64 = 100;
+
+const GENERATOR_STATS: &str = "
+SELECT g.address, g.capacity_kw, g.is_active, g.registered_ledger,
+       CAST(COALESCE(SUM(CASE WHEN m.voided THEN 0 ELSE m.amount_kwh END), 0) AS BIGINT)
+           AS minted_kwh,
+       COUNT(m.token_id) AS mint_count
+FROM generators g
+LEFT JOIN mints m ON m.generator = g.address";
+
+const GENERATOR_GROUP: &str = "GROUP BY g.address, g.capacity_kw, g.is_active, g.registered_ledger";
+
+const LISTING_COLUMNS: &str = "SELECT listing_id, seller, amount_kwh, remaining_kwh, price_per_kwh,
+       is_active, listed_ledger, listed_at
+FROM listings";
+
+/// Paginação `offset`/`limit` da query string
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Page {
+    pub offset: i64,
+    pub limit: i64,
+}
+
+/// Caminho já separado em segmentos e parâmetros da query string
+pub struct Request<'a> {
+    pub segments: Vec<&'a str>,
+    query: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Request<'a> {
+    pub fn parse(url: &'a str) -> Request<'a> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        Request {
+            segments: path.split('/').filter(|segment| !segment.is_empty()).collect(),
+            query: query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+                .collect(),
+        }
+    }
+
+    fn param(&self, name: &str) -> Option<&'a str> {
+        self.query
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn int_param(&self, name: &str, default: i64) -> Result<i64, ApiError> {
+        match self.param(name) {
+            Some(value) => value
+                .parse::<i64>()
+                .ok()
+                .filter(|n| *n >= 0)
+                .ok_or_else(|| ApiError::BadRequest(format!("invalid {}: {}", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    pub fn page(&self) -> Result<Page, ApiError> {
+        Ok(Page {
+            offset: self.int_param("offset", 0)?,
+            limit: self.int_param("limit", DEFAULT_LIMIT)?.clamp(1, MAX_LIMIT),
+        })
+    }
+}
+
+fn id(segment: &str) -> Result<i64, ApiError> {
+    segment
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid id: {}", segment)))
+}
+
+fn one(rows: Vec<Value>, what: &str) -> Result<Value, ApiError> {
+    rows.into_iter()
+        .next()
+        .ok_or_else(|| ApiError::NotFound(what.to_string()))
+}
+
+fn paged(items: Vec<Value>, page: Page) -> Value {
+    json!({ "items": items, "offset": page.offset, "limit": page.limit })
+}
+
+/// Resolve a rota; caminhos desconhecidos viram `NotFound`
+pub fn handle(db: &mut dyn Db, url: &str) -> Result<Value, ApiError> {
+    let request = Request::parse(url);
+    match request.segments.as_slice() {
+        ["health"] => health(db),
+        ["balances"] => top_balances(db, request.page()?),
+        ["balances", address] => balance(db, address),
+        ["generators"] => generators(db, request.page()?),
+        ["generators", address] => generator(db, address),
+        ["tokens", token_id] => token(db, id(token_id)?),
+        ["accounts", address, "transfers"] => transfers(db, address, request.page()?),
+        ["accounts", address, "certificates"] => certificates(db, address, request.page()?),
+        ["listings"] => {
+            let active = match request.param("active") {
+                None => None,
+                Some("true") => Some(true),
+                Some("false") => Some(false),
+                Some(other) => return Err(ApiError::BadRequest(format!("invalid active: {}", other))),
+            };
+            listings(db, active, request.page()?)
+        }
+        ["listings", listing_id] => listing(db, id(listing_id)?),
+        _ => Err(ApiError::NotFound(format!("route {}", url))),
+    }
+}
+
+fn health(db: &mut dyn Db) -> Result<Value, ApiError> {
+    let cursors = db.rows("SELECT contract_id, cursor, ledger FROM cursors", &[])?;
+    Ok(json!({ "status": "ok", "cursors": cursors }))
+}
+
+fn top_balances(db: &mut dyn Db, page: Page) -> Result<Value, ApiError> {
+    let rows = db.rows(
+        "SELECT address, balance FROM balances WHERE balance > 0
+         ORDER BY balance DESC, address LIMIT $1 OFFSET $2",
+        &[Param::Int(page.limit), Param::Int(page.offset)],
+    )?;
+    Ok(paged(rows, page))
+}
+
+fn balance(db: &mut dyn Db, address: &str) -> Result<Value, ApiError> {
+    let rows = db.rows(
+        "SELECT address, balance FROM balances WHERE address = $1",
+        &[Param::Text(address.to_string())],
+    )?;
+    // Endereços sem movimentação indexada têm saldo zero
+    Ok(rows
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| json!({ "address": address, "balance": 0 })))
+}
+
+fn generators(db: &mut dyn Db, page: Page) -> Result<Value, ApiError> {
+    let sql = format!(
+        "{} {} ORDER BY g.address LIMIT $1 OFFSET $2",
+        GENERATOR_STATS, GENERATOR_GROUP
+    );
+    let rows = db.rows(&sql, &[Param::Int(page.limit), Param::Int(page.offset)])?;
+    Ok(paged(rows, page))
+}
+
+fn generator(db: &mut dyn Db, address: &str) -> Result<Value, ApiError> {
+    let address = Param::Text(address.to_string());
+    let sql = format!("{} WHERE g.address = $1 {}", GENERATOR_STATS, GENERATOR_GROUP);
+    let mut generator = one(db.rows(&sql, std::slice::from_ref(&address))?, "generator")?;
+
+    let consumed = one(
+        db.rows(
+            "SELECT CAST(COALESCE(SUM(b.amount), 0) AS BIGINT) AS consumed_kwh
+             FROM burns b JOIN mints m ON m.token_id = b.token_id
+             WHERE m.generator = $1",
+            &[address],
+        )?,
+        "generator",
+    )?;
+    generator["consumed_kwh"] = consumed["consumed_kwh"].clone();
+    Ok(generator)
+}
+
+fn token(db: &mut dyn Db, token_id: i64) -> Result<Value, ApiError> {
+    let mut token = one(
+        db.rows(
+            "SELECT token_id, generator, amount_kwh, expiry, voided, ledger, closed_at, event_id
+             FROM mints WHERE token_id = $1",
+            &[Param::Int(token_id)],
+        )?,
+        "token",
+    )?;
+    let history = db.rows(
+        "SELECT event_id, ledger, closed_at, address AS consumer, amount
+         FROM burns WHERE token_id = $1 ORDER BY ledger, event_id",
+        &[Param::Int(token_id)],
+    )?;
+    token["consumptions"] = Value::from(history);
+    Ok(token)
+}
+
+fn transfers(db: &mut dyn Db, address: &str, page: Page) -> Result<Value, ApiError> {
+    let rows = db.rows(
+        "SELECT event_id, ledger, closed_at, from_address, to_address, amount FROM transfers
+         WHERE from_address = $1 OR to_address = $1
+         ORDER BY ledger DESC, event_id DESC LIMIT $2 OFFSET $3",
+        &[
+            Param::Text(address.to_string()),
+            Param::Int(page.limit),
+            Param::Int(page.offset),
+        ],
+    )?;
+    Ok(paged(rows, page))
+}
+
+fn certificates(db: &mut dyn Db, address: &str, page: Page) -> Result<Value, ApiError> {
+    let rows = db.rows(
+        "SELECT cert_id, amount_kwh, issued_ledger, beneficiary, retired_ledger FROM certificates
+         WHERE consumer = $1 ORDER BY cert_id LIMIT $2 OFFSET $3",
+        &[
+            Param::Text(address.to_string()),
+            Param::Int(page.limit),
+            Param::Int(page.offset),
+        ],
+    )?;
+    Ok(paged(rows, page))
+}
+
+fn listings(db: &mut dyn Db, active: Option<bool>, page: Page) -> Result<Value, ApiError> {
+    let rows = match active {
+        Some(active) => db.rows(
+            &format!(
+                "{} WHERE is_active = $1 ORDER BY listing_id DESC LIMIT $2 OFFSET $3",
+                LISTING_COLUMNS
+            ),
+            &[Param::Bool(active), Param::Int(page.limit), Param::Int(page.offset)],
+        )?,
+        None => db.rows(
+            &format!("{} ORDER BY listing_id DESC LIMIT $1 OFFSET $2", LISTING_COLUMNS),
+            &[Param::Int(page.limit), Param::Int(page.offset)],
+        )?,
+    };
+    Ok(paged(rows, page))
+}
+
+fn listing(db: &mut dyn Db, listing_id: i64) -> Result<Value, ApiError> {
+    let mut listing = one(
+        db.rows(
+            &format!("{} WHERE listing_id = $1", LISTING_COLUMNS),
+            &[Param::Int(listing_id)],
+        )?,
+        "listing",
+    )?;
+    let purchases = db.rows(
+        "SELECT event_id, ledger, closed_at, buyer, amount_kwh, payment FROM purchases
+         WHERE listing_id = $1 ORDER BY ledger, event_id",
+        &[Param::Int(listing_id)],
+    )?;
+    listing["purchases"] = Value::from(purchases);
+    Ok(listing)
+}
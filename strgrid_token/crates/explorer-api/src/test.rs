@@ -0,0 +1,201 @@
+use serde_json::json;
+use stellar_xdr::curr::{
+    AccountId, Int128Parts, Limits, PublicKey, ScAddress, ScSymbol, ScVal, ScVec, Uint256,
+    WriteXdr,
+};
+use strgrid_codes::topics;
+use strgrid_indexer::decode::{self, DecodedEvent};
+use strgrid_indexer::rpc::RawEvent;
+use strgrid_indexer::store::{Cursor, SqliteStore, Store};
+
+use crate::db::SqliteDb;
+use crate::routes::{self, Request};
+use crate::ApiError;
+
+fn address(seed: u8) -> ScVal {
+    ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(
+        Uint256([seed; 32]),
+    ))))
+}
+
+fn strkey(val: &ScVal) -> String {
+    decode::to_json(val).as_str().unwrap().to_string()
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn pair(first: ScVal, second: ScVal) -> ScVal {
+    ScVal::Vec(Some(ScVec(vec![first, second].try_into().unwrap())))
+}
+
+fn event(id: &str, ledger: u32, topic: Vec<ScVal>, value: ScVal) -> DecodedEvent {
+    decode::decode(RawEvent {
+        id: id.to_string(),
+        ledger,
+        closed_at: "2026-10-17T12:00:00Z".to_string(),
+        tx_hash: "ab".repeat(32),
+        contract_id: "CSTRGRID".to_string(),
+        topic: topic
+            .iter()
+            .map(|val| val.to_xdr_base64(Limits::none()).unwrap())
+            .collect(),
+        value: value.to_xdr_base64(Limits::none()).unwrap(),
+    })
+    .unwrap()
+}
+
+/// Banco populado pelo próprio indexador, aberto pela API somente para leitura
+fn indexed_db(name: &str) -> (SqliteDb, std::path::PathBuf) {
+    let generator = address(1);
+    let buyer = address(2);
+    let market = address(3);
+    let events = vec![
+        event(
+            "0010-1",
+            10,
+            vec![symbol(topics::GENERATOR_REGISTERED), generator.clone()],
+            ScVal::U64(1000),
+        ),
+        event(
+            "0011-1",
+            11,
+            vec![symbol(topics::MINT), generator.clone(), ScVal::U64(1)],
+            pair(ScVal::U64(500), ScVal::U64(86_400)),
+        ),
+        event(
+            "0012-1",
+            12,
+            vec![symbol(topics::TRANSFER), generator.clone(), market.clone()],
+            ScVal::U64(300),
+        ),
+        event(
+            "0012-2",
+            12,
+            vec![symbol("listed"), ScVal::U64(1), generator.clone()],
+            pair(ScVal::U64(300), ScVal::I128(Int128Parts { hi: 0, lo: 25 })),
+        ),
+        event(
+            "0013-1",
+            13,
+            vec![symbol(topics::TRANSFER), market.clone(), buyer.clone()],
+            ScVal::U64(100),
+        ),
+        event(
+            "0013-2",
+            13,
+            vec![symbol("purchased"), ScVal::U64(1), buyer.clone()],
+            pair(ScVal::U64(100), ScVal::I128(Int128Parts { hi: 0, lo: 2500 })),
+        ),
+        event(
+            "0014-1",
+            14,
+            vec![symbol(topics::CONSUME), buyer.clone(), ScVal::U64(1)],
+            ScVal::U64(40),
+        ),
+        event(
+            "0014-2",
+            14,
+            vec![symbol(topics::CERTIFICATE_ISSUED), buyer.clone()],
+            pair(ScVal::U64(1), ScVal::U64(40)),
+        ),
+    ];
+
+    let path = std::env::temp_dir().join(format!("strgrid-explorer-{}.db", name));
+    let _ = std::fs::remove_file(&path);
+    let mut store = SqliteStore::open(path.to_str().unwrap()).unwrap();
+    let cursor = Cursor {
+        cursor: "0014-2".to_string(),
+        ledger: 14,
+    };
+    store.write_page("CSTRGRID", &events, &cursor).unwrap();
+    drop(store);
+
+    (SqliteDb::open(path.to_str().unwrap()).unwrap(), path)
+}
+
+#[test]
+fn test_request_parsing_and_pagination() {
+    let request = Request::parse("/accounts/GABC/transfers?offset=20&limit=500");
+    assert_eq!(request.segments, vec!["accounts", "GABC", "transfers"]);
+    let page = request.page().unwrap();
+    // O limite é limitado ao máximo da API
+    assert_eq!((page.offset, page.limit), (20, routes::MAX_LIMIT));
+
+    let page = Request::parse("/balances").page().unwrap();
+    assert_eq!((page.offset, page.limit), (0, routes::DEFAULT_LIMIT));
+
+    let error = Request::parse("/balances?limit=abc").page().unwrap_err();
+    assert_eq!(error.status(), 400);
+}
+
+#[test]
+fn test_routes_serve_indexed_data() {
+    let (mut db, path) = indexed_db("routes");
+    let generator = strkey(&address(1));
+    let buyer = strkey(&address(2));
+
+    let balances = routes::handle(&mut db, "/balances?limit=2").unwrap();
+    assert_eq!(balances["limit"], 2);
+    assert_eq!(
+        balances["items"],
+        json!([
+            // Empates são ordenados pelo endereço
+            { "address": generator, "balance": 200 },
+            { "address": strkey(&address(3)), "balance": 200 },
+        ])
+    );
+    let balance = routes::handle(&mut db, &format!("/balances/{}", buyer)).unwrap();
+    assert_eq!(balance["balance"], 60);
+    let unknown = routes::handle(&mut db, "/balances/GUNKNOWN").unwrap();
+    assert_eq!(unknown["balance"], 0);
+
+    let stats = routes::handle(&mut db, &format!("/generators/{}", generator)).unwrap();
+    assert_eq!(stats["capacity_kw"], 1000);
+    assert_eq!(stats["is_active"], true);
+    assert_eq!(stats["minted_kwh"], 500);
+    assert_eq!(stats["mint_count"], 1);
+    assert_eq!(stats["consumed_kwh"], 40);
+    let all = routes::handle(&mut db, "/generators").unwrap();
+    assert_eq!(all["items"].as_array().unwrap().len(), 1);
+
+    let token = routes::handle(&mut db, "/tokens/1").unwrap();
+    assert_eq!(token["generator"], generator);
+    assert_eq!(token["voided"], false);
+    assert_eq!(token["consumptions"][0]["consumer"], buyer);
+    assert_eq!(token["consumptions"][0]["amount"], 40);
+
+    let transfers = routes::handle(&mut db, &format!("/accounts/{}/transfers", buyer)).unwrap();
+    assert_eq!(transfers["items"][0]["amount"], 100);
+    let certificates =
+        routes::handle(&mut db, &format!("/accounts/{}/certificates", buyer)).unwrap();
+    assert_eq!(certificates["items"][0]["cert_id"], 1);
+
+    let listings = routes::handle(&mut db, "/listings?active=true").unwrap();
+    assert_eq!(listings["items"][0]["remaining_kwh"], 200);
+    assert_eq!(listings["items"][0]["price_per_kwh"], "25");
+    let listing = routes::handle(&mut db, "/listings/1").unwrap();
+    assert_eq!(listing["purchases"][0]["payment"], "2500");
+    let closed = routes::handle(&mut db, "/listings?active=false").unwrap();
+    assert_eq!(closed["items"], json!([]));
+
+    let health = routes::handle(&mut db, "/health").unwrap();
+    assert_eq!(health["cursors"][0]["ledger"], 14);
+
+    assert!(matches!(
+        routes::handle(&mut db, "/tokens/99"),
+        Err(ApiError::NotFound(_))
+    ));
+    assert!(matches!(
+        routes::handle(&mut db, "/tokens/abc"),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        routes::handle(&mut db, "/nope"),
+        Err(ApiError::NotFound(_))
+    ));
+
+    drop(db);
+    std::fs::remove_file(path).unwrap();
+}
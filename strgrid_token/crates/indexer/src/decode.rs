@@ -0,0 +1,214 @@
+use serde_json::{json, Map, Value};
+use stellar_xdr::curr::{Limits, ReadXdr, ScVal};
+use strgrid_codes::topics;
+
+use crate::rpc::RawEvent;
+use crate::IndexerError;
+
+// Tópicos publicados pelo contrato de marketplace
+const LISTED: &str = "listed";
+const CANCELLED: &str = "cancelled";
+const PURCHASED: &str = "purchased";
+
+/// Evento decodificado: tópicos e dados em JSON, prontos para a tabela `events`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedEvent {
+    pub raw: RawEvent,
+    /// Primeiro tópico (o `Symbol` do evento)
+    pub name: String,
+    pub topics: Vec<Value>,
+    pub data: Value,
+}
+
+/// Linhas derivadas para as tabelas de domínio
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Record {
+    Transfer { from: String, to: String, amount: i64 },
+    Mint { generator: String, token_id: i64, amount_kwh: i64, expiry: i64 },
+    /// `token_id` só existe no consumo de lote; burns de saldo, perdas e clawbacks não têm lote
+    Burn { address: String, token_id: Option<i64>, amount: i64 },
+    /// Lote anulado por contestação; o saldo sai do gerador que o emitiu
+    Void { token_id: i64, amount: i64 },
+    GeneratorRegistered { address: String, capacity_kw: i64 },
+    GeneratorStatus { address: String, is_active: bool },
+    CertificateIssued { cert_id: i64, consumer: String, amount_kwh: i64 },
+    CertificateRetired { cert_id: i64, beneficiary: String },
+    /// Preços em i128 seguem como texto decimal
+    Listed { listing_id: i64, seller: String, amount_kwh: i64, price_per_kwh: String },
+    ListingCancelled { listing_id: i64 },
+    Purchased { listing_id: i64, buyer: String, amount_kwh: i64, payment: String },
+}
+
+fn decode_val(xdr: &str) -> Result<ScVal, IndexerError> {
+    ScVal::from_xdr_base64(xdr, Limits::none())
+        .map_err(|e| IndexerError::Decode(format!("invalid ScVal: {}", e)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Converte um `ScVal` em JSON: endereços em strkey, inteiros de 128 bits como string e
+/// structs (mapas com chaves `Symbol`) como objetos
+pub fn to_json(val: &ScVal) -> Value {
+    match val {
+        ScVal::Bool(b) => json!(b),
+        ScVal::Void => Value::Null,
+        ScVal::U32(n) => json!(n),
+        ScVal::I32(n) => json!(n),
+        ScVal::U64(n) => json!(n),
+        ScVal::I64(n) => json!(n),
+        ScVal::Timepoint(t) => json!(t.0),
+        ScVal::Duration(d) => json!(d.0),
+        ScVal::U128(parts) => json!((((parts.hi as u128) << 64) | parts.lo as u128).to_string()),
+        ScVal::I128(parts) => json!((((parts.hi as i128) << 64) | parts.lo as i128).to_string()),
+        ScVal::Bytes(bytes) => json!(hex(bytes.as_slice())),
+        ScVal::String(s) => json!(s.0.to_utf8_string_lossy()),
+        ScVal::Symbol(s) => json!(s.0.to_utf8_string_lossy()),
+        ScVal::Address(address) => json!(address.to_string()),
+        ScVal::Vec(Some(items)) => Value::Array(items.iter().map(to_json).collect()),
+        ScVal::Vec(None) => Value::Array(Vec::new()),
+        ScVal::Map(Some(entries)) => {
+            if entries.iter().all(|entry| matches!(entry.key, ScVal::Symbol(_))) {
+                let mut object = Map::new();
+                for entry in entries.iter() {
+                    if let ScVal::Symbol(key) = &entry.key {
+                        object.insert(key.0.to_utf8_string_lossy(), to_json(&entry.val));
+                    }
+                }
+                Value::Object(object)
+            } else {
+                Value::Array(
+                    entries
+                        .iter()
+                        .map(|entry| json!([to_json(&entry.key), to_json(&entry.val)]))
+                        .collect(),
+                )
+            }
+        }
+        ScVal::Map(None) => Value::Object(Map::new()),
+        other => json!(format!("{:?}", other)),
+    }
+}
+
+pub fn decode(raw: RawEvent) -> Result<DecodedEvent, IndexerError> {
+    let topics = raw
+        .topic
+        .iter()
+        .map(|topic| decode_val(topic).map(|val| to_json(&val)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let data = to_json(&decode_val(&raw.value)?);
+    let name = topics
+        .first()
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+    Ok(DecodedEvent {
+        raw,
+        name,
+        topics,
+        data,
+    })
+}
+
+fn text(value: &Value) -> Result<String, IndexerError> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| IndexerError::Decode(format!("expected string, got {}", value)))
+}
+
+fn int(value: &Value) -> Result<i64, IndexerError> {
+    value
+        .as_u64()
+        .and_then(|n| i64::try_from(n).ok())
+        .ok_or_else(|| IndexerError::Decode(format!("expected amount, got {}", value)))
+}
+
+/// Quantidade de STRGRID: eventos em i128 chegam como texto decimal, os anteriores à migração
+/// como número
+fn amount(value: &Value) -> Result<i64, IndexerError> {
+    match value.as_str() {
+        Some(text) => text.parse().ok(),
+        None => value.as_u64().and_then(|n| i64::try_from(n).ok()),
+    }
+    .ok_or_else(|| IndexerError::Decode(format!("expected amount, got {}", value)))
+}
+
+/// Extrai a linha de domínio do evento; eventos de outros tópicos ficam só na tabela `events`
+pub fn record(event: &DecodedEvent) -> Result<Option<Record>, IndexerError> {
+    let topic = |index: usize| event.topics.get(index).unwrap_or(&Value::Null);
+    let data = &event.data;
+
+    let record = match event.name.as_str() {
+        topics::TRANSFER => Record::Transfer {
+            from: text(topic(1))?,
+            to: text(topic(2))?,
+            amount: amount(data)?,
+        },
+        topics::MINT => Record::Mint {
+            generator: text(topic(1))?,
+            token_id: int(topic(2))?,
+            amount_kwh: int(&data[0])?,
+            expiry: int(&data[1])?,
+        },
+        topics::BURN => Record::Burn {
+            address: text(topic(1))?,
+            token_id: None,
+            amount: amount(data)?,
+        },
+        topics::CONSUME => Record::Burn {
+            address: text(topic(1))?,
+            token_id: Some(int(topic(2))?),
+            amount: int(data)?,
+        },
+        topics::CLAWBACK => Record::Burn {
+            address: text(topic(2))?,
+            token_id: None,
+            amount: amount(data)?,
+        },
+        topics::TRANSMISSION_LOSS => Record::Burn {
+            address: text(topic(1))?,
+            token_id: None,
+            amount: amount(&data[2])?,
+        },
+        topics::MINT_CHALLENGED => Record::Void {
+            token_id: int(topic(1))?,
+            amount: int(&data[1])?,
+        },
+        topics::GENERATOR_REGISTERED => Record::GeneratorRegistered {
+            address: text(topic(1))?,
+            capacity_kw: int(data)?,
+        },
+        topics::GENERATOR_STATUS => Record::GeneratorStatus {
+            address: text(topic(1))?,
+            is_active: data[0].as_bool().unwrap_or(false),
+        },
+        topics::CERTIFICATE_ISSUED => Record::CertificateIssued {
+            cert_id: int(&data[0])?,
+            consumer: text(topic(1))?,
+            amount_kwh: int(&data[1])?,
+        },
+        topics::CERTIFICATE_RETIRED => Record::CertificateRetired {
+            cert_id: int(topic(1))?,
+            beneficiary: text(topic(2))?,
+        },
+        LISTED => Record::Listed {
+            listing_id: int(topic(1))?,
+            seller: text(topic(2))?,
+            amount_kwh: int(&data[0])?,
+            price_per_kwh: text(&data[1])?,
+        },
+        CANCELLED => Record::ListingCancelled {
+            listing_id: int(topic(1))?,
+        },
+        PURCHASED => Record::Purchased {
+            listing_id: int(topic(1))?,
+            buyer: text(topic(2))?,
+            amount_kwh: int(&data[0])?,
+            payment: text(&data[1])?,
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(record))
+}
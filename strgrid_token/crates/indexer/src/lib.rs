@@ -0,0 +1,32 @@
+//! Leitura, decodificação e persistência dos eventos do contrato STRGRID
+//!
+//! Usado pelo binário `strgrid-indexer` e, para o schema e as gravações, pela API de consulta.
+
+use std::fmt;
+
+use serde_json::json;
+
+pub mod decode;
+pub mod rpc;
+pub mod store;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexerError {
+    Rpc(String),
+    Decode(String),
+    Store(String),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, message) = match self {
+            IndexerError::Rpc(message) => ("rpc", message),
+            IndexerError::Decode(message) => ("decode", message),
+            IndexerError::Store(message) => ("store", message),
+        };
+        write!(f, "{}", json!({ "error": kind, "message": message }))
+    }
+}
+
+#[cfg(test)]
+mod test;
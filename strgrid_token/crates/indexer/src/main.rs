@@ -0,0 +1,128 @@
+//! Indexador de eventos do contrato STRGRID
+//!
+//! Lê os eventos do contrato pelo `getEvents` do Soroban RPC, decodifica o XDR e grava
+//! em SQLite ou Postgres: a tabela `events` guarda tudo em JSON e as tabelas de domínio
+//! (transfers, mints, burns, generators, certificates, balances e, com o marketplace, listings
+//! e purchases) alimentam exploradores e a API.
+//! O cursor é salvo junto com cada página, então o processo pode ser reiniciado a qualquer
+//! momento sem perder nem duplicar eventos.
+
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use clap::Parser;
+use serde_json::json;
+
+use strgrid_indexer::rpc::{RpcClient, Start};
+use strgrid_indexer::store::{self, Cursor, Store};
+use strgrid_indexer::{decode, IndexerError};
+
+#[derive(Parser, Debug)]
+#[command(name = "strgrid-indexer", version, about = "Indexador de eventos do contrato STRGRID")]
+struct Args {
+    /// URL do Soroban RPC
+    #[arg(long, env = "SOROBAN_RPC_URL", default_value = "https://soroban-testnet.stellar.org")]
+    rpc_url: String,
+
+    /// Endereço do contrato STRGRID
+    #[arg(long, env = "CONTRACT_ID")]
+    contract_id: String,
+
+    /// Endereço do contrato de marketplace, para indexar também as ofertas
+    #[arg(long, env = "MARKETPLACE_ID")]
+    marketplace_id: Option<String>,
+
+    /// Banco de destino: caminho/`sqlite://` para SQLite ou `postgres://` para Postgres
+    #[arg(long, env = "DATABASE_URL", default_value = "sqlite://strgrid-index.db")]
+    database_url: String,
+
+    /// Ledger inicial na primeira execução; sem ele, começa no ledger mais recente
+    #[arg(long)]
+    start_ledger: Option<u32>,
+
+    /// Eventos por página do `getEvents`
+    #[arg(long, default_value_t = 200)]
+    page_size: u32,
+
+    /// Intervalo entre consultas quando não há eventos novos, em segundos
+    #[arg(long, default_value_t = 5)]
+    poll_seconds: u64,
+
+    /// Processa o que houver até o topo da cadeia e encerra
+    #[arg(long)]
+    once: bool,
+}
+
+/// Busca, decodifica e grava uma página; devolve quantos eventos foram indexados e o novo cursor
+fn sync_page(
+    rpc: &RpcClient,
+    store: &mut dyn Store,
+    contract_id: &str,
+    start: &Start,
+    page_size: u32,
+) -> Result<(usize, Option<Cursor>), IndexerError> {
+    let page = rpc.events(start, page_size)?;
+    let events = page
+        .events
+        .into_iter()
+        .map(decode::decode)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let ledger = events.last().map(|event| event.raw.ledger).unwrap_or(page.latest_ledger);
+    let cursor = match page.cursor {
+        Some(cursor) => Cursor { cursor, ledger },
+        // Sem cursor e sem eventos não há o que avançar
+        None => return Ok((0, None)),
+    };
+    store.write_page(contract_id, &events, &cursor)?;
+    Ok((events.len(), Some(cursor)))
+}
+
+fn run(args: &Args) -> Result<(), IndexerError> {
+    let mut contract_ids = vec![args.contract_id.clone()];
+    contract_ids.extend(args.marketplace_id.clone());
+    let rpc = RpcClient::new(&args.rpc_url, contract_ids);
+    let mut store = store::open(&args.database_url)?;
+
+    let mut start = match store.cursor(&args.contract_id)? {
+        Some(saved) => Start::Cursor(saved.cursor),
+        None => match args.start_ledger {
+            Some(ledger) => Start::Ledger(ledger),
+            None => Start::Ledger(rpc.latest_ledger()?),
+        },
+    };
+
+    loop {
+        let (indexed, cursor) =
+            sync_page(&rpc, store.as_mut(), &args.contract_id, &start, args.page_size)?;
+        if let Some(cursor) = cursor {
+            if indexed > 0 {
+                eprintln!(
+                    "{}",
+                    json!({ "indexed": indexed, "ledger": cursor.ledger, "cursor": cursor.cursor })
+                );
+            }
+            start = Start::Cursor(cursor.cursor);
+        }
+
+        // Página cheia indica que ainda há eventos pendentes
+        if indexed < args.page_size as usize {
+            if args.once {
+                return Ok(());
+            }
+            thread::sleep(Duration::from_secs(args.poll_seconds));
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use serde_json::{json, Value};
+
+use crate::IndexerError;
+
+/// Evento como devolvido pelo `getEvents`, com tópicos e dados ainda em XDR base64
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub closed_at: String,
+    pub tx_hash: String,
+    pub contract_id: String,
+    pub topic: Vec<String>,
+    pub value: String,
+}
+
+/// Página de eventos e o cursor para continuar a leitura
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventPage {
+    pub events: Vec<RawEvent>,
+    pub cursor: Option<String>,
+    pub latest_ledger: u32,
+}
+
+/// Ponto de partida da leitura: um ledger (primeira execução) ou o cursor salvo
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Start {
+    Ledger(u32),
+    Cursor(String),
+}
+
+pub struct RpcClient {
+    url: String,
+    contract_ids: Vec<String>,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>, contract_ids: Vec<String>) -> RpcClient {
+        RpcClient {
+            url: url.into(),
+            contract_ids,
+        }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, IndexerError> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = ureq::post(&self.url)
+            .send_json(request)
+            .map_err(|e| IndexerError::Rpc(format!("{}: {}", method, e)))?
+            .into_json()
+            .map_err(|e| IndexerError::Rpc(format!("{}: {}", method, e)))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(IndexerError::Rpc(format!("{}: {}", method, error)));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| IndexerError::Rpc(format!("{}: missing result", method)))
+    }
+
+    /// Sequência do ledger mais recente, usada quando não há cursor nem ledger inicial
+    pub fn latest_ledger(&self) -> Result<u32, IndexerError> {
+        let result = self.call("getLatestLedger", json!({}))?;
+        result["sequence"]
+            .as_u64()
+            .map(|sequence| sequence as u32)
+            .ok_or_else(|| IndexerError::Rpc("getLatestLedger: missing sequence".to_string()))
+    }
+
+    pub fn events(&self, start: &Start, limit: u32) -> Result<EventPage, IndexerError> {
+        let result = self.call("getEvents", events_params(&self.contract_ids, start, limit))?;
+        parse_page(&result)
+    }
+}
+
+/// Parâmetros do `getEvents` filtrados pelos contratos indexados
+pub fn events_params(contract_ids: &[String], start: &Start, limit: u32) -> Value {
+    let filters = json!([{ "type": "contract", "contractIds": contract_ids }]);
+    match start {
+        Start::Ledger(ledger) => json!({
+            "startLedger": ledger,
+            "filters": filters,
+            "pagination": { "limit": limit },
+        }),
+        Start::Cursor(cursor) => json!({
+            "filters": filters,
+            "pagination": { "cursor": cursor, "limit": limit },
+        }),
+    }
+}
+
+fn field(event: &Value, name: &str) -> Result<String, IndexerError> {
+    event[name]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| IndexerError::Rpc(format!("getEvents: event without {}", name)))
+}
+
+/// Interpreta o resultado do `getEvents`; RPCs antigos não devolvem `cursor`, e aí vale o
+/// `pagingToken` do último evento
+pub fn parse_page(result: &Value) -> Result<EventPage, IndexerError> {
+    let mut events = Vec::new();
+    for event in result["events"].as_array().into_iter().flatten() {
+        let topic = event["topic"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|topic| topic.as_str().map(str::to_string))
+            .collect();
+        events.push(RawEvent {
+            id: field(event, "id")?,
+            ledger: event["ledger"].as_u64().unwrap_or(0) as u32,
+            closed_at: field(event, "ledgerClosedAt")?,
+            tx_hash: event["txHash"].as_str().unwrap_or("").to_string(),
+            contract_id: field(event, "contractId")?,
+            topic,
+            value: field(event, "value")?,
+        });
+    }
+
+    let cursor = result["cursor"]
+        .as_str()
+        .filter(|cursor| !cursor.is_empty())
+        .map(str::to_string)
+        .or_else(|| {
+            let last = result["events"].as_array()?.last()?;
+            last["pagingToken"].as_str().or(last["id"].as_str()).map(str::to_string)
+        });
+
+    Ok(EventPage {
+        events,
+        cursor,
+        latest_ledger: result["latestLedger"].as_u64().unwrap_or(0) as u32,
+    })
+}
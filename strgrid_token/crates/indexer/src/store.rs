@@ -0,0 +1,502 @@
+//! Persistência em SQLite ou Postgres
+//!
+//! Os dois backends compartilham o schema e as instruções. Placeholders `$N` valem nos dois,
+//! desde que apareçam em ordem crescente: o SQLite os numera pela primeira ocorrência. Cada página é gravada numa única transação junto com o cursor, então uma
+//! interrupção retoma exatamente de onde parou; inserções repetidas são ignoradas pelo id
+//! do evento.
+
+use crate::decode::{DecodedEvent, Record};
+use crate::IndexerError;
+
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS cursors (
+    contract_id TEXT PRIMARY KEY,
+    cursor TEXT NOT NULL,
+    ledger BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    id TEXT PRIMARY KEY,
+    ledger BIGINT NOT NULL,
+    closed_at TEXT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    topics TEXT NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS events_name ON events (name, ledger);
+CREATE TABLE IF NOT EXISTS transfers (
+    event_id TEXT PRIMARY KEY,
+    ledger BIGINT NOT NULL,
+    closed_at TEXT NOT NULL,
+    from_address TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    amount BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transfers_from ON transfers (from_address, ledger);
+CREATE INDEX IF NOT EXISTS transfers_to ON transfers (to_address, ledger);
+CREATE TABLE IF NOT EXISTS mints (
+    event_id TEXT PRIMARY KEY,
+    ledger BIGINT NOT NULL,
+    closed_at TEXT NOT NULL,
+    generator TEXT NOT NULL,
+    token_id BIGINT NOT NULL,
+    amount_kwh BIGINT NOT NULL,
+    expiry BIGINT NOT NULL,
+    voided BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE INDEX IF NOT EXISTS mints_token ON mints (token_id);
+CREATE INDEX IF NOT EXISTS mints_generator ON mints (generator, ledger);
+CREATE TABLE IF NOT EXISTS burns (
+    event_id TEXT PRIMARY KEY,
+    ledger BIGINT NOT NULL,
+    closed_at TEXT NOT NULL,
+    address TEXT NOT NULL,
+    token_id BIGINT,
+    amount BIGINT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS burns_address ON burns (address, ledger);
+CREATE TABLE IF NOT EXISTS generators (
+    address TEXT PRIMARY KEY,
+    capacity_kw BIGINT,
+    is_active BOOLEAN NOT NULL,
+    registered_ledger BIGINT,
+    updated_ledger BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS certificates (
+    cert_id BIGINT PRIMARY KEY,
+    consumer TEXT NOT NULL,
+    amount_kwh BIGINT NOT NULL,
+    issued_ledger BIGINT NOT NULL,
+    beneficiary TEXT,
+    retired_ledger BIGINT
+);
+CREATE INDEX IF NOT EXISTS certificates_consumer ON certificates (consumer);
+CREATE TABLE IF NOT EXISTS balances (
+    address TEXT PRIMARY KEY,
+    balance BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS listings (
+    listing_id BIGINT PRIMARY KEY,
+    seller TEXT NOT NULL,
+    amount_kwh BIGINT NOT NULL,
+    remaining_kwh BIGINT NOT NULL,
+    price_per_kwh TEXT NOT NULL,
+    is_active BOOLEAN NOT NULL,
+    listed_ledger BIGINT NOT NULL,
+    listed_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS purchases (
+    event_id TEXT PRIMARY KEY,
+    ledger BIGINT NOT NULL,
+    closed_at TEXT NOT NULL,
+    listing_id BIGINT NOT NULL,
+    buyer TEXT NOT NULL,
+    amount_kwh BIGINT NOT NULL,
+    payment TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS purchases_listing ON purchases (listing_id);
+";
+
+/// Parâmetro de uma instrução, convertido para o tipo nativo de cada backend
+#[derive(Clone, Debug, PartialEq)]
+pub enum Param {
+    Text(String),
+    Int(i64),
+    OptInt(Option<i64>),
+    Bool(bool),
+}
+
+/// Posição salva da leitura
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cursor {
+    pub cursor: String,
+    pub ledger: u32,
+}
+
+/// Execução de SQL dentro de uma transação aberta
+trait Sql {
+    /// Executa a instrução e devolve o número de linhas afetadas
+    fn exec(&mut self, sql: &str, params: &[Param]) -> Result<usize, IndexerError>;
+}
+
+pub trait Store {
+    fn cursor(&mut self, contract_id: &str) -> Result<Option<Cursor>, IndexerError>;
+
+    /// Grava os eventos da página e avança o cursor atomicamente
+    fn write_page(
+        &mut self,
+        contract_id: &str,
+        events: &[DecodedEvent],
+        cursor: &Cursor,
+    ) -> Result<(), IndexerError>;
+}
+
+/// Abre o backend pela URL: `postgres://...` (feature `postgres`), `sqlite://arquivo` ou
+/// apenas o caminho do arquivo SQLite
+pub fn open(url: &str) -> Result<Box<dyn Store>, IndexerError> {
+    if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+        return open_postgres(url);
+    }
+    let path = url.strip_prefix("sqlite://").unwrap_or(url);
+    Ok(Box::new(SqliteStore::open(path)?))
+}
+
+#[cfg(feature = "postgres")]
+fn open_postgres(url: &str) -> Result<Box<dyn Store>, IndexerError> {
+    Ok(Box::new(postgres_store::PostgresStore::connect(url)?))
+}
+
+#[cfg(not(feature = "postgres"))]
+fn open_postgres(_url: &str) -> Result<Box<dyn Store>, IndexerError> {
+    Err(IndexerError::Store(
+        "postgres support not compiled in: build with --features postgres".to_string(),
+    ))
+}
+
+fn write_page(
+    sql: &mut dyn Sql,
+    contract_id: &str,
+    events: &[DecodedEvent],
+    cursor: &Cursor,
+) -> Result<(), IndexerError> {
+    for event in events {
+        write_event(sql, event)?;
+    }
+    sql.exec(
+        "INSERT INTO cursors (contract_id, cursor, ledger) VALUES ($1, $2, $3)
+         ON CONFLICT (contract_id) DO UPDATE SET cursor = excluded.cursor, ledger = excluded.ledger",
+        &[
+            Param::Text(contract_id.to_string()),
+            Param::Text(cursor.cursor.clone()),
+            Param::Int(cursor.ledger as i64),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Soma `delta` ao saldo derivado do endereço
+fn adjust_balance(sql: &mut dyn Sql, address: &str, delta: i64) -> Result<usize, IndexerError> {
+    sql.exec(
+        "INSERT INTO balances (address, balance) VALUES ($1, $2)
+         ON CONFLICT (address) DO UPDATE SET balance = balances.balance + excluded.balance",
+        &[Param::Text(address.to_string()), Param::Int(delta)],
+    )
+}
+
+fn write_event(sql: &mut dyn Sql, event: &DecodedEvent) -> Result<(), IndexerError> {
+    let raw = &event.raw;
+    let id = Param::Text(raw.id.clone());
+    let ledger = Param::Int(raw.ledger as i64);
+    let closed_at = Param::Text(raw.closed_at.clone());
+
+    let inserted = sql.exec(
+        "INSERT INTO events (id, ledger, closed_at, tx_hash, contract_id, name, topics, data)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (id) DO NOTHING",
+        &[
+            id.clone(),
+            ledger.clone(),
+            closed_at.clone(),
+            Param::Text(raw.tx_hash.clone()),
+            Param::Text(raw.contract_id.clone()),
+            Param::Text(event.name.clone()),
+            Param::Text(serde_json::Value::from(event.topics.clone()).to_string()),
+            Param::Text(event.data.to_string()),
+        ],
+    )?;
+    // Evento já indexado: os saldos derivados não podem ser aplicados duas vezes
+    if inserted == 0 {
+        return Ok(());
+    }
+
+    let record = match crate::decode::record(event)? {
+        Some(record) => record,
+        None => return Ok(()),
+    };
+    match record {
+        Record::Transfer { from, to, amount } => {
+            adjust_balance(sql, &from, -amount)?;
+            adjust_balance(sql, &to, amount)?;
+            sql.exec(
+                "INSERT INTO transfers (event_id, ledger, closed_at, from_address, to_address, amount)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[id, ledger, closed_at, Param::Text(from), Param::Text(to), Param::Int(amount)],
+            )
+        }
+        Record::Mint {
+            generator,
+            token_id,
+            amount_kwh,
+            expiry,
+        } => {
+            adjust_balance(sql, &generator, amount_kwh)?;
+            sql.exec(
+                "INSERT INTO mints (event_id, ledger, closed_at, generator, token_id, amount_kwh, expiry)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                &[
+                    id,
+                    ledger,
+                    closed_at,
+                    Param::Text(generator),
+                    Param::Int(token_id),
+                    Param::Int(amount_kwh),
+                    Param::Int(expiry),
+                ],
+            )
+        }
+        Record::Burn {
+            address,
+            token_id,
+            amount,
+        } => {
+            adjust_balance(sql, &address, -amount)?;
+            sql.exec(
+                "INSERT INTO burns (event_id, ledger, closed_at, address, token_id, amount)
+                 VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    id,
+                    ledger,
+                    closed_at,
+                    Param::Text(address),
+                    Param::OptInt(token_id),
+                    Param::Int(amount),
+                ],
+            )
+        }
+        Record::Void { token_id, amount } => {
+            sql.exec(
+                "UPDATE balances SET balance = balance - $1
+                 WHERE address = (SELECT generator FROM mints WHERE token_id = $2)",
+                &[Param::Int(amount), Param::Int(token_id)],
+            )?;
+            sql.exec(
+                "UPDATE mints SET voided = $1 WHERE token_id = $2",
+                &[Param::Bool(true), Param::Int(token_id)],
+            )
+        }
+        Record::GeneratorRegistered {
+            address,
+            capacity_kw,
+        } => sql.exec(
+            "INSERT INTO generators (address, capacity_kw, is_active, registered_ledger, updated_ledger)
+             VALUES ($1, $2, $3, $4, $4)
+             ON CONFLICT (address) DO UPDATE SET capacity_kw = excluded.capacity_kw,
+                 is_active = excluded.is_active, registered_ledger = excluded.registered_ledger,
+                 updated_ledger = excluded.updated_ledger",
+            &[Param::Text(address), Param::Int(capacity_kw), Param::Bool(true), ledger],
+        ),
+        // O status pode chegar antes do registro quando a indexação começa no meio do histórico
+        Record::GeneratorStatus { address, is_active } => sql.exec(
+            "INSERT INTO generators (address, is_active, updated_ledger) VALUES ($1, $2, $3)
+             ON CONFLICT (address) DO UPDATE SET is_active = excluded.is_active,
+                 updated_ledger = excluded.updated_ledger",
+            &[Param::Text(address), Param::Bool(is_active), ledger],
+        ),
+        Record::CertificateIssued {
+            cert_id,
+            consumer,
+            amount_kwh,
+        } => sql.exec(
+            "INSERT INTO certificates (cert_id, consumer, amount_kwh, issued_ledger)
+             VALUES ($1, $2, $3, $4) ON CONFLICT (cert_id) DO NOTHING",
+            &[Param::Int(cert_id), Param::Text(consumer), Param::Int(amount_kwh), ledger],
+        ),
+        Record::CertificateRetired {
+            cert_id,
+            beneficiary,
+        } => sql.exec(
+            "UPDATE certificates SET beneficiary = $1, retired_ledger = $2 WHERE cert_id = $3",
+            &[Param::Text(beneficiary), ledger, Param::Int(cert_id)],
+        ),
+        Record::Listed {
+            listing_id,
+            seller,
+            amount_kwh,
+            price_per_kwh,
+        } => sql.exec(
+            "INSERT INTO listings
+                 (listing_id, seller, amount_kwh, remaining_kwh, price_per_kwh, is_active,
+                  listed_ledger, listed_at)
+             VALUES ($1, $2, $3, $3, $4, $5, $6, $7) ON CONFLICT (listing_id) DO NOTHING",
+            &[
+                Param::Int(listing_id),
+                Param::Text(seller),
+                Param::Int(amount_kwh),
+                Param::Text(price_per_kwh),
+                Param::Bool(true),
+                ledger,
+                closed_at,
+            ],
+        ),
+        Record::ListingCancelled { listing_id } => sql.exec(
+            "UPDATE listings SET remaining_kwh = 0, is_active = $1 WHERE listing_id = $2",
+            &[Param::Bool(false), Param::Int(listing_id)],
+        ),
+        Record::Purchased {
+            listing_id,
+            buyer,
+            amount_kwh,
+            payment,
+        } => {
+            sql.exec(
+                "UPDATE listings SET remaining_kwh = remaining_kwh - $1,
+                     is_active = remaining_kwh - $1 > 0
+                 WHERE listing_id = $2",
+                &[Param::Int(amount_kwh), Param::Int(listing_id)],
+            )?;
+            sql.exec(
+                "INSERT INTO purchases (event_id, ledger, closed_at, listing_id, buyer, amount_kwh, payment)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+                &[
+                    id,
+                    ledger,
+                    closed_at,
+                    Param::Int(listing_id),
+                    Param::Text(buyer),
+                    Param::Int(amount_kwh),
+                    Param::Text(payment),
+                ],
+            )
+        }
+    }
+    .map(|_| ())
+}
+
+fn store_error(e: impl std::fmt::Display) -> IndexerError {
+    IndexerError::Store(e.to_string())
+}
+
+pub struct SqliteStore {
+    conn: rusqlite::Connection,
+}
+
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<SqliteStore, IndexerError> {
+        let conn = if path == ":memory:" {
+            rusqlite::Connection::open_in_memory()
+        } else {
+            rusqlite::Connection::open(path)
+        }
+        .map_err(store_error)?;
+        conn.execute_batch(SCHEMA).map_err(store_error)?;
+        Ok(SqliteStore { conn })
+    }
+
+    /// Conexão para consultas diretas às tabelas indexadas
+    #[cfg(test)]
+    pub fn connection(&self) -> &rusqlite::Connection {
+        &self.conn
+    }
+}
+
+impl rusqlite::ToSql for Param {
+    fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput<'_>> {
+        match self {
+            Param::Text(value) => value.to_sql(),
+            Param::Int(value) => value.to_sql(),
+            Param::OptInt(value) => value.to_sql(),
+            Param::Bool(value) => value.to_sql(),
+        }
+    }
+}
+
+impl Sql for rusqlite::Transaction<'_> {
+    fn exec(&mut self, sql: &str, params: &[Param]) -> Result<usize, IndexerError> {
+        self.execute(sql, rusqlite::params_from_iter(params))
+            .map_err(store_error)
+    }
+}
+
+impl Store for SqliteStore {
+    fn cursor(&mut self, contract_id: &str) -> Result<Option<Cursor>, IndexerError> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT cursor, ledger FROM cursors WHERE contract_id = $1")
+            .map_err(store_error)?;
+        let mut rows = statement.query([contract_id]).map_err(store_error)?;
+        match rows.next().map_err(store_error)? {
+            Some(row) => Ok(Some(Cursor {
+                cursor: row.get(0).map_err(store_error)?,
+                ledger: row.get::<_, i64>(1).map_err(store_error)? as u32,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn write_page(
+        &mut self,
+        contract_id: &str,
+        events: &[DecodedEvent],
+        cursor: &Cursor,
+    ) -> Result<(), IndexerError> {
+        let mut tx = self.conn.transaction().map_err(store_error)?;
+        write_page(&mut tx, contract_id, events, cursor)?;
+        tx.commit().map_err(store_error)
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres_store {
+    use postgres::types::ToSql;
+    use postgres::{Client, NoTls};
+
+    use super::{store_error, write_page, Cursor, Param, Sql, Store, SCHEMA};
+    use crate::decode::DecodedEvent;
+    use crate::IndexerError;
+
+    pub struct PostgresStore {
+        client: Client,
+    }
+
+    impl PostgresStore {
+        pub fn connect(url: &str) -> Result<PostgresStore, IndexerError> {
+            let mut client = Client::connect(url, NoTls).map_err(store_error)?;
+            client.batch_execute(SCHEMA).map_err(store_error)?;
+            Ok(PostgresStore { client })
+        }
+    }
+
+    impl Sql for postgres::Transaction<'_> {
+        fn exec(&mut self, sql: &str, params: &[Param]) -> Result<usize, IndexerError> {
+            let params: Vec<&(dyn ToSql + Sync)> = params
+                .iter()
+                .map(|param| match param {
+                    Param::Text(value) => value as &(dyn ToSql + Sync),
+                    Param::Int(value) => value,
+                    Param::OptInt(value) => value,
+                    Param::Bool(value) => value,
+                })
+                .collect();
+            self.execute(sql, &params)
+                .map(|rows| rows as usize)
+                .map_err(store_error)
+        }
+    }
+
+    impl Store for PostgresStore {
+        fn cursor(&mut self, contract_id: &str) -> Result<Option<Cursor>, IndexerError> {
+            let row = self
+                .client
+                .query_opt(
+                    "SELECT cursor, ledger FROM cursors WHERE contract_id = $1",
+                    &[&contract_id],
+                )
+                .map_err(store_error)?;
+            Ok(row.map(|row| Cursor {
+                cursor: row.get(0),
+                ledger: row.get::<_, i64>(1) as u32,
+            }))
+        }
+
+        fn write_page(
+            &mut self,
+            contract_id: &str,
+            events: &[DecodedEvent],
+            cursor: &Cursor,
+        ) -> Result<(), IndexerError> {
+            let mut tx = self.client.transaction().map_err(store_error)?;
+            write_page(&mut tx, contract_id, events, cursor)?;
+            tx.commit().map_err(store_error)
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use serde_json::json;
+use stellar_xdr::curr::{
+    AccountId, Int128Parts, Limits, PublicKey, ScAddress, ScMap, ScMapEntry, ScString, ScSymbol, ScVal, ScVec,
+    Uint256, WriteXdr,
+};
+use strgrid_codes::topics;
+
+use crate::decode::{self, Record};
+use crate::rpc::{events_params, parse_page, RawEvent, Start};
+use crate::store::{Cursor, SqliteStore, Store};
+
+fn address(seed: u8) -> ScVal {
+    ScVal::Address(ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(
+        Uint256([seed; 32]),
+    ))))
+}
+
+fn symbol(name: &str) -> ScVal {
+    ScVal::Symbol(ScSymbol(name.try_into().unwrap()))
+}
+
+fn vec(items: Vec<ScVal>) -> ScVal {
+    ScVal::Vec(Some(ScVec(items.try_into().unwrap())))
+}
+
+fn strkey(val: &ScVal) -> String {
+    decode::to_json(val).as_str().unwrap().to_string()
+}
+
+fn raw(id: &str, ledger: u32, topic: Vec<ScVal>, value: ScVal) -> RawEvent {
+    RawEvent {
+        id: id.to_string(),
+        ledger,
+        closed_at: "2026-10-17T12:00:00Z".to_string(),
+        tx_hash: "ab".repeat(32),
+        contract_id: "CSTRGRID".to_string(),
+        topic: topic
+            .iter()
+            .map(|val| val.to_xdr_base64(Limits::none()).unwrap())
+            .collect(),
+        value: value.to_xdr_base64(Limits::none()).unwrap(),
+    }
+}
+
+fn record(event: RawEvent) -> Record {
+    decode::record(&decode::decode(event).unwrap()).unwrap().unwrap()
+}
+
+#[test]
+fn test_events_decode_to_records() {
+    let generator = address(1);
+    let consumer = address(2);
+
+    let mint = raw(
+        "0001-1",
+        10,
+        vec![symbol(topics::MINT), generator.clone(), ScVal::U64(7)],
+        vec(vec![ScVal::U64(500), ScVal::U64(86_400)]),
+    );
+    assert_eq!(
+        record(mint),
+        Record::Mint {
+            generator: strkey(&generator),
+            token_id: 7,
+            amount_kwh: 500,
+            expiry: 86_400,
+        }
+    );
+
+    let consume = raw(
+        "0001-2",
+        10,
+        vec![symbol(topics::CONSUME), consumer.clone(), ScVal::U64(7)],
+        ScVal::U64(120),
+    );
+    assert_eq!(
+        record(consume),
+        Record::Burn {
+            address: strkey(&consumer),
+            token_id: Some(7),
+            amount: 120,
+        }
+    );
+
+    // Structs chegam como mapas de `Symbol` e viram objetos JSON
+    let retirement = ScVal::Map(Some(ScMap(
+        vec![
+            ScMapEntry { key: symbol("cert_id"), val: ScVal::U64(3) },
+            ScMapEntry {
+                key: symbol("purpose"),
+                val: ScVal::String(ScString("ESG 2026".try_into().unwrap())),
+            },
+        ]
+        .try_into()
+        .unwrap(),
+    )));
+    let retired = decode::decode(raw(
+        "0001-3",
+        11,
+        vec![symbol(topics::CERTIFICATE_RETIRED), ScVal::U64(3), consumer.clone()],
+        retirement,
+    ))
+    .unwrap();
+    assert_eq!(retired.data, json!({ "cert_id": 3, "purpose": "ESG 2026" }));
+    assert_eq!(
+        decode::record(&retired).unwrap(),
+        Some(Record::CertificateRetired {
+            cert_id: 3,
+            beneficiary: strkey(&consumer),
+        })
+    );
+
+    // Tópicos sem tabela de domínio ficam apenas em `events`
+    let frozen = decode::decode(raw(
+        "0001-4",
+        11,
+        vec![symbol(topics::ACCOUNT_FROZEN), consumer],
+        ScVal::Bool(true),
+    ))
+    .unwrap();
+    assert_eq!(frozen.name, "frozen");
+    assert_eq!(decode::record(&frozen).unwrap(), None);
+}
+
+#[test]
+fn test_rpc_pages_and_cursors() {
+    let contracts = vec!["CSTRGRID".to_string(), "CMARKET".to_string()];
+    let params = events_params(&contracts, &Start::Ledger(100), 50);
+    assert_eq!(params["startLedger"], 100);
+    assert_eq!(params["filters"][0]["contractIds"], json!(["CSTRGRID", "CMARKET"]));
+
+    let params = events_params(&contracts, &Start::Cursor("0042-1".to_string()), 50);
+    assert!(params.get("startLedger").is_none());
+    assert_eq!(params["pagination"], json!({ "cursor": "0042-1", "limit": 50 }));
+
+    let page = parse_page(&json!({
+        "latestLedger": 120,
+        "events": [{
+            "id": "0042-1",
+            "pagingToken": "0042-1",
+            "ledger": 42,
+            "ledgerClosedAt": "2026-10-17T12:00:00Z",
+            "contractId": "CSTRGRID",
+            "txHash": "ff",
+            "topic": ["AAAADwAAAARtaW50"],
+            "value": "AAAAAQ==",
+        }],
+    }))
+    .unwrap();
+    assert_eq!(page.events.len(), 1);
+    assert_eq!(page.latest_ledger, 120);
+    // Sem `cursor` na resposta, vale o pagingToken do último evento
+    assert_eq!(page.cursor.as_deref(), Some("0042-1"));
+
+    let empty = parse_page(&json!({ "latestLedger": 121, "events": [], "cursor": "0121-0" }))
+        .unwrap();
+    assert_eq!(empty.cursor.as_deref(), Some("0121-0"));
+}
+
+#[test]
+fn test_sqlite_store_is_resumable_and_idempotent() {
+    let generator = address(1);
+    let consumer = address(2);
+    let events: Vec<_> = vec![
+        // Status antes do registro: indexação iniciada no meio do histórico
+        raw(
+            "0009-1",
+            9,
+            vec![symbol(topics::GENERATOR_STATUS), generator.clone()],
+            vec(vec![ScVal::Bool(false), symbol("Normal")]),
+        ),
+        raw(
+            "0010-1",
+            10,
+            vec![symbol(topics::GENERATOR_REGISTERED), generator.clone()],
+            ScVal::U64(1000),
+        ),
+        raw(
+            "0010-2",
+            10,
+            vec![symbol(topics::TRANSFER), generator.clone(), consumer.clone()],
+            ScVal::I128(Int128Parts { hi: 0, lo: 250 }),
+        ),
+        raw(
+            "0011-1",
+            11,
+            vec![symbol(topics::CERTIFICATE_ISSUED), consumer.clone()],
+            vec(vec![ScVal::U64(1), ScVal::U64(100)]),
+        ),
+        raw(
+            "0012-1",
+            12,
+            vec![symbol(topics::CERTIFICATE_RETIRED), ScVal::U64(1), generator.clone()],
+            ScVal::Void,
+        ),
+    ]
+    .into_iter()
+    .map(|event| decode::decode(event).unwrap())
+    .collect();
+
+    let mut store = SqliteStore::open(":memory:").unwrap();
+    assert_eq!(store.cursor("CSTRGRID").unwrap(), None);
+
+    let cursor = Cursor {
+        cursor: "0012-1".to_string(),
+        ledger: 12,
+    };
+    store.write_page("CSTRGRID", &events, &cursor).unwrap();
+    // Reprocessar a mesma página não duplica linhas
+    store.write_page("CSTRGRID", &events, &cursor).unwrap();
+    assert_eq!(store.cursor("CSTRGRID").unwrap(), Some(cursor));
+
+    let conn = store.connection();
+    let count = |table: &str| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    };
+    assert_eq!(count("events"), 5);
+    assert_eq!(count("transfers"), 1);
+
+    let (capacity, active, registered): (i64, bool, i64) = conn
+        .query_row(
+            "SELECT capacity_kw, is_active, registered_ledger FROM generators WHERE address = $1",
+            [strkey(&generator)],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!((capacity, active, registered), (1000, true, 10));
+
+    let (owner, beneficiary): (String, String) = conn
+        .query_row(
+            "SELECT consumer, beneficiary FROM certificates WHERE cert_id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(owner, strkey(&consumer));
+    assert_eq!(beneficiary, strkey(&generator));
+}
+
+#[test]
+fn test_balances_and_listings_are_derived() {
+    let generator = address(1);
+    let buyer = address(2);
+    let market = address(3);
+    let events: Vec<_> = vec![
+        raw(
+            "0020-1",
+            20,
+            vec![symbol(topics::MINT), generator.clone(), ScVal::U64(1)],
+            vec(vec![ScVal::U64(500), ScVal::U64(86_400)]),
+        ),
+        raw(
+            "0020-2",
+            20,
+            vec![symbol(topics::MINT), generator.clone(), ScVal::U64(2)],
+            vec(vec![ScVal::U64(80), ScVal::U64(86_400)]),
+        ),
+        // Lote 2 anulado por contestação: sai do saldo do gerador
+        raw(
+            "0021-1",
+            21,
+            vec![symbol(topics::MINT_CHALLENGED), ScVal::U64(2), market.clone()],
+            vec(vec![ScVal::Bytes(vec![0u8; 32].try_into().unwrap()), ScVal::U64(80)]),
+        ),
+        raw(
+            "0022-1",
+            22,
+            vec![symbol(topics::TRANSFER), generator.clone(), market.clone()],
+            ScVal::I128(Int128Parts { hi: 0, lo: 300 }),
+        ),
+        raw(
+            "0022-2",
+            22,
+            vec![symbol("listed"), ScVal::U64(1), generator.clone()],
+            vec(vec![ScVal::U64(300), ScVal::I128(Int128Parts { hi: 0, lo: 25 })]),
+        ),
+        raw(
+            "0023-1",
+            23,
+            vec![symbol(topics::TRANSFER), market.clone(), buyer.clone()],
+            ScVal::I128(Int128Parts { hi: 0, lo: 120 }),
+        ),
+        raw(
+            "0023-2",
+            23,
+            vec![symbol("purchased"), ScVal::U64(1), buyer.clone()],
+            vec(vec![ScVal::U64(120), ScVal::I128(Int128Parts { hi: 0, lo: 3000 })]),
+        ),
+        raw(
+            "0024-1",
+            24,
+            vec![symbol(topics::CONSUME), buyer.clone(), ScVal::U64(1)],
+            ScVal::U64(20),
+        ),
+    ]
+    .into_iter()
+    .map(|event| decode::decode(event).unwrap())
+    .collect();
+
+    let mut store = SqliteStore::open(":memory:").unwrap();
+    let cursor = Cursor {
+        cursor: "0024-1".to_string(),
+        ledger: 24,
+    };
+    store.write_page("CSTRGRID", &events, &cursor).unwrap();
+    // Reaplicar a página não altera os saldos derivados
+    store.write_page("CSTRGRID", &events, &cursor).unwrap();
+
+    let conn = store.connection();
+    let balance = |account: &ScVal| -> i64 {
+        conn.query_row(
+            "SELECT balance FROM balances WHERE address = $1",
+            [strkey(account)],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    assert_eq!(balance(&generator), 200);
+    assert_eq!(balance(&market), 180);
+    assert_eq!(balance(&buyer), 100);
+
+    let (remaining, active, price): (i64, bool, String) = conn
+        .query_row(
+            "SELECT remaining_kwh, is_active, price_per_kwh FROM listings WHERE listing_id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .unwrap();
+    assert_eq!((remaining, active, price.as_str()), (180, true, "25"));
+}
@@ -0,0 +1,233 @@
+#![no_std]
+//! Interface pública do contrato STRGRID para chamadas entre contratos
+//!
+//! Contém os tipos compartilhados (`EnergyToken`, `EnergyGenerator`, ...) e o trait
+//! `STRGRIDInterface`, cujo `STRGRIDClient` permite chamar o token com tipos verificados sem
+//! depender do crate de implementação. O próprio contrato usa estes tipos, então o XDR é o mesmo.
+//!
+//! ```ignore
+//! let energy = strgrid_interface::STRGRIDClient::new(&env, &energy_token);
+//! energy.transfer(&from, &to, &amount_kwh);
+//! ```
+
+// `#[contractclient]` gera `STRGRIDClient::permit` sem o allow do método
+#![allow(clippy::too_many_arguments)]
+
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, BytesN, Env, String};
+
+pub use strgrid_codes::{STRGRIDError, STRGRIDExt2Error, STRGRIDExtError};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SourceType {
+    Unspecified,
+    Solar,
+    Wind,
+    Hydro,
+    Biomass,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnergyGenerator {
+    pub address: Address,
+    pub capacity_kw: u64,
+    pub current_production: u64,
+    pub is_active: bool,
+    pub registration_date: u64,
+    pub source_type: SourceType,
+    pub zone: u32,
+    pub last_mint_timestamp: Option<u64>,
+    /// CID (IPFS) dos documentos ricos do gerador, mantidos off-chain
+    pub metadata_cid: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnergyToken {
+    pub id: u64,
+    pub generator_id: Address,
+    pub amount_kwh: u64,
+    pub creation_timestamp: u64,
+    pub expiry_timestamp: u64,
+    pub is_consumed: bool,
+    pub dispute_until: u64,
+    pub is_voided: bool,
+    pub source_type: SourceType,
+    pub zone: u32,
+    /// Safra (ano, mês UTC) do período de geração
+    pub vintage: (u32, u32),
+    /// CID (IPFS) dos documentos do lote, mantidos off-chain
+    pub metadata_cid: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    /// Mesmo valor de `total_supply()`
+    pub total_supply: i128,
+}
+
+/// Limites de emissão; campos `None` não restringem
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MintCaps {
+    pub max_total_supply: Option<u64>,
+    pub max_daily_per_generator: Option<u64>,
+    pub min_mint: Option<u64>,
+    pub max_mint: Option<u64>,
+}
+
+/// Papéis operacionais concedidos pelo admin
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Auditor,
+    Compliance,
+    GridOperator,
+    Oracle,
+    /// Ajusta os parâmetros agrupados em `set_config`
+    ConfigManager,
+}
+
+/// Duração do intervalo de liquidação das diferenças (1 hora)
+pub const SETTLEMENT_INTERVAL_SECONDS: u64 = 3_600;
+
+/// Preço de liquidação das diferenças (PLD) publicado pelo oracle para um intervalo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PldPrice {
+    pub payment_token: Address,
+    pub price_per_kwh: i128,
+}
+
+/// Situação de um contrato a termo
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ForwardStatus {
+    Open,
+    Delivered,
+    Defaulted,
+    /// Encerrado por margem insuficiente; o colateral foi ao comprador
+    Liquidated,
+}
+
+/// Obrigação de entrega futura: o comprador paga adiantado e o gerador cauciona a multa por inadimplência
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Forward {
+    pub id: u64,
+    pub generator: Address,
+    pub buyer: Address,
+    pub amount_kwh: u64,
+    pub window_start: u64,
+    pub window_end: u64,
+    pub payment_token: Address,
+    pub price: i128,
+    pub penalty: i128,
+    pub status: ForwardStatus,
+}
+
+/// Compromisso day-ahead aceito por um comprador, que custodia o pagamento até a liquidação
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DayAheadCommitment {
+    pub id: u64,
+    pub offer_id: u64,
+    pub generator: Address,
+    pub buyer: Address,
+    pub interval: u64,
+    pub payment_token: Address,
+    pub price_per_kwh: i128,
+    pub amount_kwh: u64,
+    /// kWh efetivamente entregues, conhecidos na liquidação
+    pub delivered_kwh: u64,
+    pub settled: bool,
+}
+
+/// Avaliações recebidas por um gerador; a média é `sum / count`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneratorRating {
+    pub count: u32,
+    pub sum: u64,
+}
+
+/// Funções do STRGRID estáveis para outros contratos; quantidades em kWh
+#[contractclient(name = "STRGRIDClient")]
+pub trait STRGRIDInterface {
+    // Token
+    fn get_metadata(env: Env) -> TokenMetadata;
+    fn total_supply(env: Env) -> i128;
+    fn balance_of(env: Env, address: Address) -> i128;
+    fn allowance(env: Env, owner: Address, spender: Address) -> i128;
+    fn approve(env: Env, owner: Address, spender: Address, amount: i128);
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+        deadline: u64,
+        nonce: u64,
+        signature: BytesN<64>,
+    );
+    fn nonce_of(env: Env, address: Address) -> u64;
+    fn set_signing_key(env: Env, owner: Address, key: Option<BytesN<32>>);
+    fn signing_key_of(env: Env, address: Address) -> Option<BytesN<32>>;
+    fn transfer(env: Env, from: Address, to: Address, amount: i128);
+    fn transfer_and_call(env: Env, from: Address, to_contract: Address, amount: i128, data: Bytes);
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+    fn burn(env: Env, from: Address, amount: i128);
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128);
+
+    // Lotes de energia e geradores
+    fn mint_energy_tokens(
+        env: Env,
+        generator: Address,
+        energy_amount_kwh: i128,
+        expiry_hours: u64,
+        oracle_proof: Option<BytesN<32>>,
+    ) -> u64;
+    fn burn_energy_tokens(env: Env, consumer: Address, token_id: u64, amount: i128) -> u64;
+    fn get_energy_token(env: Env, token_id: u64) -> EnergyToken;
+    fn generator_of(env: Env, token_id: u64) -> Address;
+    fn get_generator(env: Env, generator: Address) -> EnergyGenerator;
+    fn capacity_of(env: Env, generator: Address) -> u64;
+    fn get_generator_rating(env: Env, generator: Address) -> GeneratorRating;
+
+    // Consumidores e rede
+    fn is_eligible_consumer(env: Env, account: Address) -> bool;
+    fn zone_of(env: Env, account: Address) -> Option<u32>;
+    fn congestion_multiplier(env: Env, zone: u32) -> u32;
+    fn has_role(env: Env, role: Role, account: Address) -> bool;
+
+    // Mercado
+    fn get_pld(env: Env, interval: u64) -> PldPrice;
+    fn get_forward(env: Env, forward_id: u64) -> Forward;
+    fn get_day_ahead_commitment(env: Env, commitment_id: u64) -> DayAheadCommitment;
+
+    // Contestação de mints (papel Auditor)
+    fn challenge_mint(env: Env, auditor: Address, token_id: u64, evidence_hash: BytesN<32>);
+
+    // Parâmetros do protocolo (admin)
+    fn set_transfer_fee(env: Env, fee_bps: u32, treasury: Address);
+    fn set_mint_caps(env: Env, caps: MintCaps);
+    fn set_dispute_window(env: Env, seconds: u64);
+    fn set_emission_factor(env: Env, source_type: SourceType, grams_per_kwh: u64);
+}
+
+/// Interface padrão dos contratos notificados por `transfer_and_call`
+///
+/// Chamada depois que os kWh já estão no saldo do contrato; devolver `false` reverte a
+/// transferência. `token` é informado por quem chama, então o receptor deve conferir se é o
+/// token esperado e, se precisar de garantia, o próprio saldo. O callback não pode chamar o
+/// token de volta (o Soroban rejeita reentrância).
+#[contractclient(name = "TokenReceiverClient")]
+pub trait TokenReceiver {
+    fn on_token_received(env: Env, token: Address, from: Address, amount: i128, data: Bytes)
+        -> bool;
+}